@@ -2,6 +2,8 @@ pub mod black_box_functions;
 pub mod brillig;
 pub mod directives;
 pub mod opcodes;
+pub mod serialization;
+pub mod text;
 
 use crate::native_types::{Expression, Witness};
 use acir_field::AcirField;
@@ -16,7 +18,11 @@ use serde::{de::Error as DeserializationError, Deserialize, Deserializer, Serial
 
 use std::collections::BTreeSet;
 
-use self::{brillig::BrilligBytecode, opcodes::BlockId};
+use self::{
+    brillig::BrilligBytecode,
+    opcodes::BlockId,
+    serialization::{SerializationError, SerializationFormat},
+};
 
 /// Specifies the maximum width of the expressions which will be constrained.
 ///
@@ -250,20 +256,34 @@ impl<F> Circuit<F> {
 }
 
 impl<F: Serialize> Program<F> {
-    fn write<W: Write>(&self, writer: W) -> std::io::Result<()> {
-        let buf = bincode::serialize(self).unwrap();
+    fn write<W: Write>(buf: &[u8], writer: W) -> std::io::Result<()> {
         let mut encoder = flate2::write::GzEncoder::new(writer, Compression::default());
-        encoder.write_all(&buf)?;
+        encoder.write_all(buf)?;
         encoder.finish()?;
         Ok(())
     }
 
+    /// Serializes the program as bincode without a versioned envelope, as read by backends.
     pub fn serialize_program(program: &Self) -> Vec<u8> {
+        let buf = bincode::serialize(program).expect("expected circuit to be serializable");
         let mut program_bytes: Vec<u8> = Vec::new();
-        program.write(&mut program_bytes).expect("expected circuit to be serializable");
+        Program::<F>::write(&buf, &mut program_bytes)
+            .expect("expected writing to memory to succeed");
         program_bytes
     }
 
+    /// Serializes the program in the given `format`, prefixed by a versioned envelope.
+    pub fn serialize_program_with_format(
+        program: &Self,
+        format: SerializationFormat,
+    ) -> Result<Vec<u8>, SerializationError> {
+        let buf = serialization::serialize(program, format)?;
+        let mut program_bytes: Vec<u8> = Vec::new();
+        Program::<F>::write(&buf, &mut program_bytes)
+            .expect("expected writing to memory to succeed");
+        Ok(program_bytes)
+    }
+
     // Serialize and base64 encode program
     pub fn serialize_program_base64<S>(program: &Self, s: S) -> Result<S::Ok, S::Error>
     where
@@ -280,10 +300,11 @@ impl<F: for<'a> Deserialize<'a>> Program<F> {
         let mut gz_decoder = flate2::read::GzDecoder::new(reader);
         let mut buf_d = Vec::new();
         gz_decoder.read_to_end(&mut buf_d)?;
-        bincode::deserialize(&buf_d)
+        serialization::deserialize(&buf_d)
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))
     }
 
+    /// Deserializes a program in any [serialization format][SerializationFormat], detecting its format and version.
     pub fn deserialize_program(serialized_circuit: &[u8]) -> std::io::Result<Self> {
         Program::read(serialized_circuit)
     }
@@ -0,0 +1,265 @@
+//! Versioned serialization of ACIR [programs][super::Program].
+//!
+//! Programs are serialized into an envelope made up of the `ACIR` magic bytes, the little-endian [FORMAT_VERSION]
+//! the program was written with and a byte identifying the [SerializationFormat] of the payload which follows.
+//!
+//! [Program::serialize_program][super::Program::serialize_program] writes plain bincode without an envelope, as
+//! expected by backends. The envelope is only written when a format is chosen explicitly with
+//! [Program::serialize_program_with_format][super::Program::serialize_program_with_format].
+//! Both are read by [Program::deserialize_program][super::Program::deserialize_program].
+//!
+//! The MessagePack and JSON encodings require the `msgpack` and `json` features respectively.
+
+use std::{fmt::Display, str::FromStr};
+
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+
+/// The version of the ACIR serialization format written by this crate.
+///
+/// This must be bumped whenever a change to the ACIR types changes their serialized representation.
+pub const FORMAT_VERSION: u16 = 1;
+
+const MAGIC: &[u8; 4] = b"ACIR";
+const HEADER_LENGTH: usize = MAGIC.len() + 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerializationFormat {
+    Bincode,
+    /// MessagePack with named struct fields.
+    Msgpack,
+    Json,
+}
+
+impl SerializationFormat {
+    fn tag(self) -> u8 {
+        match self {
+            SerializationFormat::Bincode => 1,
+            SerializationFormat::Msgpack => 2,
+            SerializationFormat::Json => 3,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            1 => Some(SerializationFormat::Bincode),
+            2 => Some(SerializationFormat::Msgpack),
+            3 => Some(SerializationFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SerializationFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SerializationFormat::Bincode => write!(f, "bincode"),
+            SerializationFormat::Msgpack => write!(f, "msgpack"),
+            SerializationFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for SerializationFormat {
+    type Err = SerializationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bincode" => Ok(SerializationFormat::Bincode),
+            "msgpack" => Ok(SerializationFormat::Msgpack),
+            "json" => Ok(SerializationFormat::Json),
+            _ => Err(SerializationError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SerializationError {
+    #[error("Unknown serialization format `{0}`, expected one of bincode, msgpack or json")]
+    UnknownFormat(String),
+    #[error("Unknown serialization format tag {0}")]
+    UnknownFormatTag(u8),
+    #[error("Support for the {0} serialization format is not enabled, it requires the `{0}` feature of `acir`")]
+    FormatNotEnabled(SerializationFormat),
+    #[error("Program was serialized with format version {found} but only versions up to {supported} are supported, a newer version of Noir is required to read it")]
+    UnsupportedVersion { found: u16, supported: u16 },
+    #[error("Failed to serialize program as {format}: {message}")]
+    Serialization { format: SerializationFormat, message: String },
+    #[error("Failed to deserialize {format} program with format version {version}: {message}")]
+    Deserialization { format: SerializationFormat, version: u16, message: String },
+    #[error("Failed to deserialize unversioned bincode program, it may have been compiled with an incompatible version of Noir: {0}")]
+    LegacyDeserialization(String),
+}
+
+/// Serializes `value` in the given `format`, prefixed by the versioned envelope.
+pub(crate) fn serialize<T: Serialize>(
+    value: &T,
+    format: SerializationFormat,
+) -> Result<Vec<u8>, SerializationError> {
+    let error = |message: String| SerializationError::Serialization { format, message };
+
+    let payload = match format {
+        SerializationFormat::Bincode => {
+            bincode::serialize(value).map_err(|err| error(err.to_string()))?
+        }
+        #[cfg(feature = "msgpack")]
+        SerializationFormat::Msgpack => {
+            rmp_serde::to_vec_named(value).map_err(|err| error(err.to_string()))?
+        }
+        #[cfg(feature = "json")]
+        SerializationFormat::Json => {
+            serde_json::to_vec(value).map_err(|err| error(err.to_string()))?
+        }
+        #[allow(unreachable_patterns)]
+        _ => return Err(SerializationError::FormatNotEnabled(format)),
+    };
+
+    let mut buf = Vec::with_capacity(HEADER_LENGTH + payload.len());
+    buf.extend_from_slice(MAGIC);
+    buf.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    buf.push(format.tag());
+    buf.extend(payload);
+    Ok(buf)
+}
+
+/// Deserializes a value written by [serialize], detecting its format and version from the envelope.
+/// Values without an envelope are read as unversioned bincode, as written by older versions of this crate.
+pub(crate) fn deserialize<T: DeserializeOwned>(buf: &[u8]) -> Result<T, SerializationError> {
+    // An unversioned bincode program starts with the number of functions as a u64,
+    // which can never be large enough to be mistaken for the magic bytes.
+    if !buf.starts_with(MAGIC) || buf.len() < HEADER_LENGTH {
+        return bincode::deserialize(buf)
+            .map_err(|err| SerializationError::LegacyDeserialization(err.to_string()));
+    }
+
+    let version = u16::from_le_bytes([buf[MAGIC.len()], buf[MAGIC.len() + 1]]);
+    let tag = buf[MAGIC.len() + 2];
+    let payload = &buf[HEADER_LENGTH..];
+
+    if version > FORMAT_VERSION {
+        return Err(SerializationError::UnsupportedVersion {
+            found: version,
+            supported: FORMAT_VERSION,
+        });
+    }
+
+    let format =
+        SerializationFormat::from_tag(tag).ok_or(SerializationError::UnknownFormatTag(tag))?;
+    let error = |message: String| SerializationError::Deserialization { format, version, message };
+    match format {
+        SerializationFormat::Bincode => {
+            bincode::deserialize(payload).map_err(|err| error(err.to_string()))
+        }
+        #[cfg(feature = "msgpack")]
+        SerializationFormat::Msgpack => {
+            rmp_serde::from_slice(payload).map_err(|err| error(err.to_string()))
+        }
+        #[cfg(feature = "json")]
+        SerializationFormat::Json => {
+            serde_json::from_slice(payload).map_err(|err| error(err.to_string()))
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err(SerializationError::FormatNotEnabled(format)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, io::Read};
+
+    use acir_field::{AcirField, FieldElement};
+
+    use super::{deserialize, serialize, SerializationError, SerializationFormat, FORMAT_VERSION};
+    use crate::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            Circuit, Opcode, Program,
+        },
+        native_types::{Expression, Witness},
+    };
+
+    fn program() -> Program<FieldElement> {
+        let keccakf1600 = BlackBoxFuncCall::Keccakf1600 {
+            inputs: Box::new(std::array::from_fn(|i| {
+                FunctionInput::witness(Witness(i as u32), 64)
+            })),
+            outputs: Box::new(std::array::from_fn(|i| Witness(i as u32 + 25))),
+        };
+        let circuit = Circuit {
+            current_witness_index: 50,
+            opcodes: vec![
+                Opcode::AssertZero(Expression {
+                    mul_terms: Vec::new(),
+                    linear_combinations: vec![(-FieldElement::one(), Witness(1))],
+                    q_c: FieldElement::from(5u128),
+                }),
+                Opcode::BlackBoxFuncCall(keccakf1600),
+            ],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            ..Circuit::default()
+        };
+        Program { functions: vec![circuit], unconstrained_functions: Vec::new() }
+    }
+
+    #[test]
+    fn roundtrips_every_enabled_format() {
+        let program = program();
+        let formats = [
+            SerializationFormat::Bincode,
+            #[cfg(feature = "msgpack")]
+            SerializationFormat::Msgpack,
+            #[cfg(feature = "json")]
+            SerializationFormat::Json,
+        ];
+        for format in formats {
+            let buf = serialize(&program, format).unwrap();
+            let deserialized: Program<FieldElement> = deserialize(&buf).unwrap();
+            assert_eq!(deserialized, program, "{format} should roundtrip");
+            assert_eq!(format.to_string().parse::<SerializationFormat>().unwrap(), format);
+        }
+    }
+
+    #[test]
+    fn reads_unversioned_bincode() {
+        let buf = bincode::serialize(&program()).unwrap();
+        let deserialized: Program<FieldElement> = deserialize(&buf).unwrap();
+        assert_eq!(deserialized, program());
+    }
+
+    #[test]
+    fn writes_unversioned_bincode_by_default() {
+        let bytes = Program::serialize_program(&program());
+        let mut buf = Vec::new();
+        flate2::read::GzDecoder::new(bytes.as_slice()).read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, bincode::serialize(&program()).unwrap());
+    }
+
+    #[test]
+    fn writes_the_envelope_for_an_explicit_format() {
+        let buf = serialize(&program(), SerializationFormat::Bincode).unwrap();
+        assert!(buf.starts_with(b"ACIR"));
+        assert_eq!(buf[4..6], FORMAT_VERSION.to_le_bytes());
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let mut buf = serialize(&program(), SerializationFormat::Bincode).unwrap();
+        buf[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+
+        let error = deserialize::<Program<FieldElement>>(&buf).unwrap_err();
+        assert!(matches!(
+            error,
+            SerializationError::UnsupportedVersion { found, supported }
+                if found == FORMAT_VERSION + 1 && supported == FORMAT_VERSION
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_format_tags() {
+        let mut buf = serialize(&program(), SerializationFormat::Bincode).unwrap();
+        buf[6] = 255;
+
+        let error = deserialize::<Program<FieldElement>>(&buf).unwrap_err();
+        assert!(matches!(error, SerializationError::UnknownFormatTag(255)));
+    }
+}
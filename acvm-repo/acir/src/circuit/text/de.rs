@@ -0,0 +1,468 @@
+use std::marker::PhantomData;
+
+use acir_field::AcirField;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+
+use super::{Error, NEWTYPE_PREFIXES};
+
+/// Deserializes a single value of the textual format, requiring that all of `input` is consumed.
+pub(super) fn from_str<F: AcirField, T: DeserializeOwned>(input: &str) -> Result<T, Error> {
+    let mut deserializer = TextDeserializer::<F> { input, position: 0, _field: PhantomData };
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.skip_whitespace();
+    if deserializer.position != input.len() {
+        return Err(deserializer.error("unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+struct TextDeserializer<'de, F> {
+    input: &'de str,
+    position: usize,
+    _field: PhantomData<F>,
+}
+
+impl<'de, F: AcirField> TextDeserializer<'de, F> {
+    fn error(&self, message: &str) -> Error {
+        Error::new(format!("{message} at column {}", self.position + 1))
+    }
+
+    fn rest(&self) -> &'de str {
+        &self.input[self.position..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    fn consume_if(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        if self.consume_if(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{expected}`")))
+        }
+    }
+
+    /// Consumes characters while `predicate` holds, returning them.
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'de str {
+        let rest = self.rest();
+        let len = rest.find(|char| !predicate(char)).unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    fn parse_ident(&mut self) -> Result<&'de str, Error> {
+        self.skip_whitespace();
+        let ident = self.take_while(|char| char.is_ascii_alphanumeric() || char == '_');
+        if ident.is_empty() {
+            return Err(self.error("expected an identifier"));
+        }
+        Ok(ident)
+    }
+
+    fn expect_ident(&mut self, expected: &str) -> Result<(), Error> {
+        let start = self.position;
+        let ident = self.parse_ident()?;
+        if ident != expected {
+            self.position = start;
+            return Err(self.error(&format!("expected `{expected}`, found `{ident}`")));
+        }
+        Ok(())
+    }
+
+    /// Parses an optionally negated decimal or `0x` prefixed hexadecimal literal.
+    fn parse_number(&mut self) -> Result<(bool, &'de str, u32), Error> {
+        self.skip_whitespace();
+        let negative = self.consume_if('-');
+        let radix = if self.rest().starts_with("0x") {
+            self.position += 2;
+            16
+        } else {
+            10
+        };
+        let digits = self.take_while(|char| char.is_digit(radix));
+        if digits.is_empty() {
+            return Err(self.error("expected a number"));
+        }
+        Ok((negative, digits, radix))
+    }
+
+    fn parse_integer(&mut self) -> Result<i128, Error> {
+        let (negative, digits, radix) = self.parse_number()?;
+        let value = u128::from_str_radix(digits, radix)
+            .ok()
+            .and_then(|value| i128::try_from(value).ok())
+            .ok_or_else(|| self.error("integer literal is out of range"))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    fn parse_field(&mut self) -> Result<F, Error> {
+        let (negative, digits, radix) = self.parse_number()?;
+        let field = if radix == 16 {
+            F::from_hex(digits)
+        } else {
+            digits.parse::<u128>().ok().map(F::from)
+        }
+        .ok_or_else(|| self.error("invalid field element literal"))?;
+        Ok(if negative { -field } else { field })
+    }
+
+    fn parse_string(&mut self) -> Result<String, Error> {
+        self.expect('"')?;
+        let mut string = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((index, char)) = chars.next() {
+            match char {
+                '"' => {
+                    self.position += index + 1;
+                    return Ok(string);
+                }
+                '\\' => match chars.next() {
+                    Some((_, '"')) => string.push('"'),
+                    Some((_, '\\')) => string.push('\\'),
+                    Some((_, 'n')) => string.push('\n'),
+                    Some((_, 'r')) => string.push('\r'),
+                    Some((_, 't')) => string.push('\t'),
+                    _ => {
+                        self.position += index;
+                        return Err(self.error("invalid escape sequence"));
+                    }
+                },
+                _ => string.push(char),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn parse_seq<V: Visitor<'de>>(
+        &mut self,
+        open: char,
+        close: char,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.expect(open)?;
+        let value = visitor.visit_seq(Separated { de: self, close, first: true })?;
+        self.expect(close)?;
+        Ok(value)
+    }
+
+    fn parse_map<V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value, Error> {
+        self.expect('{')?;
+        let value = visitor.visit_map(Separated { de: self, close: '}', first: true })?;
+        self.expect('}')?;
+        Ok(value)
+    }
+}
+
+macro_rules! deserialize_integer {
+    ($($method:ident => $visit:ident($ty:ty),)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                let value = self.parse_integer()?;
+                let value = <$ty>::try_from(value)
+                    .map_err(|_| self.error("integer literal is out of range"))?;
+                visitor.$visit(value)
+            }
+        )*
+    };
+}
+
+impl<'de, F: AcirField> de::Deserializer<'de> for &mut TextDeserializer<'de, F> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(self.error("the textual ACIR format is not self-describing"))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let start = self.position;
+        match self.parse_ident()? {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            _ => {
+                self.position = start;
+                Err(self.error("expected a boolean"))
+            }
+        }
+    }
+
+    deserialize_integer! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let (negative, digits, radix) = self.parse_number()?;
+        let value = u128::from_str_radix(digits, radix)
+            .ok()
+            .filter(|_| !negative)
+            .ok_or_else(|| self.error("integer literal is out of range"))?;
+        visitor.visit_u128(value)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(self.error("floating point values are not supported"))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(self.error("floating point values are not supported"))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let string = self.parse_string()?;
+        let mut chars = string.chars();
+        match (chars.next(), chars.next()) {
+            (Some(char), None) => visitor.visit_char(char),
+            _ => Err(self.error("expected a single character")),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    /// Strings are either quoted, or field element literals which are converted back into their hex representation.
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.peek() == Some('"') {
+            visitor.visit_string(self.parse_string()?)
+        } else {
+            visitor.visit_string(self.parse_field()?.to_hex())
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let start = self.position;
+        match self.parse_ident()? {
+            "None" => visitor.visit_none(),
+            "Some" => {
+                self.expect('(')?;
+                let value = visitor.visit_some(&mut *self)?;
+                self.expect(')')?;
+                Ok(value)
+            }
+            _ => {
+                self.position = start;
+                Err(self.error("expected `None` or `Some`"))
+            }
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.expect('(')?;
+        self.expect(')')?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.expect_ident(name)?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if let Some((_, prefix)) = NEWTYPE_PREFIXES.iter().find(|(newtype, _)| *newtype == name) {
+            self.expect(*prefix)?;
+            return visitor.visit_newtype_struct(self);
+        }
+
+        self.expect_ident(name)?;
+        self.expect('(')?;
+        let value = visitor.visit_newtype_struct(&mut *self)?;
+        self.expect(')')?;
+        Ok(value)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.parse_seq('[', ']', visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.parse_seq('(', ')', visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.expect_ident(name)?;
+        self.parse_seq('(', ')', visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.parse_map(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.expect_ident(name)?;
+        self.parse_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_str(self.parse_ident()?)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(self.error("unexpected value"))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+}
+
+/// Accesses the comma separated elements of a sequence, or the `key: value` entries of a map,
+/// up to (but not including) the `close` character.
+struct Separated<'a, 'de, F> {
+    de: &'a mut TextDeserializer<'de, F>,
+    close: char,
+    first: bool,
+}
+
+impl<'a, 'de, F: AcirField> Separated<'a, 'de, F> {
+    /// Consumes the separator before the next element, returning whether there is one.
+    fn has_next(&mut self) -> Result<bool, Error> {
+        if self.de.peek() == Some(self.close) {
+            return Ok(false);
+        }
+        if !self.first {
+            self.de.expect(',')?;
+            // Allow a trailing comma.
+            if self.de.peek() == Some(self.close) {
+                return Ok(false);
+            }
+        }
+        self.first = false;
+        Ok(true)
+    }
+}
+
+impl<'a, 'de, F: AcirField> SeqAccess<'de> for Separated<'a, 'de, F> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if !self.has_next()? {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+impl<'a, 'de, F: AcirField> MapAccess<'de> for Separated<'a, 'de, F> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if !self.has_next()? {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        self.de.expect(':')?;
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+impl<'de, F: AcirField> EnumAccess<'de> for &mut TextDeserializer<'de, F> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let variant = self.parse_ident()?;
+        let value = seed.deserialize(variant.into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, F: AcirField> VariantAccess<'de> for &mut TextDeserializer<'de, F> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        self.expect('(')?;
+        let value = seed.deserialize(&mut *self)?;
+        self.expect(')')?;
+        Ok(value)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        self.parse_seq('(', ')', visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.parse_map(visitor)
+    }
+}
@@ -0,0 +1,471 @@
+//! A human-readable textual format for ACIR [programs][Program] which round-trips through [Program::to_text]
+//! and [Program::from_text].
+//!
+//! A program is written as a sequence of function sections, each introduced by an unindented header.
+//! ACIR functions are headed by `func <index>` and list their fields followed by their opcodes and assert messages,
+//! while Brillig functions are headed by `unconstrained func <index>` and list one opcode per line.
+//! Indices must start at zero and increase by one. Lines starting with `//` and blank lines are ignored.
+//!
+//! ```text
+//! func 0
+//!   current_witness_index: 2
+//!   expression_width: Bounded { width: 4 }
+//!   private_parameters: [w0, w1]
+//!   public_parameters: []
+//!   return_values: [w2]
+//!   recursive: false
+//!   opcodes:
+//!     AssertZero(Expression { mul_terms: [], linear_combinations: [(1, w0), (1, w1), (-1, w2)], q_c: 0 })
+//!     BrilligCall { id: 0, inputs: [Single(Expression { mul_terms: [], linear_combinations: [(1, w2)], q_c: 0 })], outputs: [], predicate: None }
+//!   assert_messages:
+//!     (Acir(1), StaticString("sum is too large"))
+//! unconstrained func 0
+//!   CalldataCopy { destination_address: @0, size: 1, offset: 0 }
+//!   Stop { return_data_offset: 0, return_data_size: 0 }
+//! ```
+//!
+//! Omitted circuit fields take their default values.
+//!
+//! Each field and opcode is written in a Rust-like notation: enum variants and structs are written by name
+//! followed by their fields in parentheses or braces, sequences in square brackets, tuples in parentheses and
+//! options as `None` or `Some(value)`. [Witnesses][crate::native_types::Witness] are written as `w<index>`,
+//! [memory blocks][crate::circuit::opcodes::BlockId] as `b<index>` and Brillig
+//! [memory addresses][brillig::MemoryAddress] as `@<index>`. Field elements are written as decimal literals,
+//! negated decimal literals for elements close to the modulus, or `0x` prefixed hexadecimal literals otherwise.
+
+mod de;
+mod ser;
+
+use std::fmt::Write;
+
+use acir_field::AcirField;
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+
+use super::{brillig::BrilligBytecode, Circuit, Program, PublicInputs};
+
+/// Newtypes which are written as a prefix character followed by their index.
+const NEWTYPE_PREFIXES: [(&str, char); 3] =
+    [("Witness", 'w'), ("BlockId", 'b'), ("MemoryAddress", '@')];
+
+/// The circuit fields which are written on their own line.
+const CIRCUIT_FIELDS: [&str; 6] = [
+    "current_witness_index",
+    "expression_width",
+    "private_parameters",
+    "public_parameters",
+    "return_values",
+    "recursive",
+];
+
+/// An error encountered while reading or writing a single value of the textual format.
+#[derive(Debug)]
+struct Error(String);
+
+impl Error {
+    fn new(message: impl Into<String>) -> Self {
+        Error(message.into())
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl serde::ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("line {line}: {message}")]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Field elements are serialized as strings of their hex representation.
+/// Returns the field element if `value` is the canonical hex representation of one.
+fn is_field_hex<F: AcirField>(value: &str) -> Option<F> {
+    if value.len() != 64 || !value.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f')) {
+        return None;
+    }
+    F::from_hex(value).filter(|field| field.to_hex() == value)
+}
+
+impl<F: AcirField + Serialize> Program<F> {
+    /// Writes the program in the [textual ACIR format][self].
+    pub fn to_text(&self) -> String {
+        fn value<F: AcirField, T: Serialize + ?Sized>(value: &T) -> String {
+            ser::to_string::<F, _>(value).expect("ACIR should be serializable to text")
+        }
+
+        let mut output = String::new();
+        for (index, circuit) in self.functions.iter().enumerate() {
+            writeln!(output, "func {index}").unwrap();
+            writeln!(output, "  current_witness_index: {}", circuit.current_witness_index).unwrap();
+            writeln!(output, "  expression_width: {}", value::<F, _>(&circuit.expression_width))
+                .unwrap();
+            writeln!(
+                output,
+                "  private_parameters: {}",
+                value::<F, _>(&circuit.private_parameters)
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "  public_parameters: {}",
+                value::<F, _>(&circuit.public_parameters.0)
+            )
+            .unwrap();
+            writeln!(output, "  return_values: {}", value::<F, _>(&circuit.return_values.0))
+                .unwrap();
+            writeln!(output, "  recursive: {}", circuit.recursive).unwrap();
+            writeln!(output, "  opcodes:").unwrap();
+            for opcode in &circuit.opcodes {
+                writeln!(output, "    {}", value::<F, _>(opcode)).unwrap();
+            }
+            if !circuit.assert_messages.is_empty() {
+                writeln!(output, "  assert_messages:").unwrap();
+                for assert_message in &circuit.assert_messages {
+                    writeln!(output, "    {}", value::<F, _>(assert_message)).unwrap();
+                }
+            }
+        }
+        for (index, function) in self.unconstrained_functions.iter().enumerate() {
+            writeln!(output, "unconstrained func {index}").unwrap();
+            for opcode in &function.bytecode {
+                writeln!(output, "  {}", value::<F, _>(opcode)).unwrap();
+            }
+        }
+        output
+    }
+}
+
+/// The section of the document which subsequent indented lines belong to.
+enum Section {
+    Circuit { list: Option<CircuitList> },
+    Unconstrained,
+}
+
+enum CircuitList {
+    Opcodes,
+    AssertMessages,
+}
+
+impl<F: AcirField + DeserializeOwned> Program<F> {
+    /// Reads a program written in the [textual ACIR format][self].
+    pub fn from_text(text: &str) -> Result<Self, ParseError> {
+        fn value<F: AcirField, T: DeserializeOwned>(
+            text: &str,
+            line: usize,
+        ) -> Result<T, ParseError> {
+            de::from_str::<F, T>(text).map_err(|err| ParseError { line, message: err.to_string() })
+        }
+
+        let mut program = Program::default();
+        let mut section = None;
+
+        for (line_index, line) in text.lines().enumerate() {
+            let line_number = line_index + 1;
+            let error = |message: String| ParseError { line: line_number, message };
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with("//") {
+                continue;
+            }
+
+            if !line.starts_with(char::is_whitespace) {
+                let (index, expected_index) = if let Some(index) =
+                    trimmed.strip_prefix("unconstrained func ")
+                {
+                    program.unconstrained_functions.push(BrilligBytecode::default());
+                    section = Some(Section::Unconstrained);
+                    (index, program.unconstrained_functions.len() - 1)
+                } else if let Some(index) = trimmed.strip_prefix("func ") {
+                    program.functions.push(Circuit::default());
+                    section = Some(Section::Circuit { list: None });
+                    (index, program.functions.len() - 1)
+                } else {
+                    return Err(error(format!("expected a function header, found `{trimmed}`")));
+                };
+                if index.trim().parse::<usize>() != Ok(expected_index) {
+                    return Err(error(format!("expected function index {expected_index}")));
+                }
+                continue;
+            }
+
+            match &mut section {
+                None => return Err(error("expected a function header".to_string())),
+                Some(Section::Unconstrained) => {
+                    let function = program.unconstrained_functions.last_mut().unwrap();
+                    function.bytecode.push(value::<F, _>(trimmed, line_number)?);
+                }
+                Some(Section::Circuit { list }) => {
+                    let circuit = program.functions.last_mut().unwrap();
+                    match trimmed.split_once(':') {
+                        Some(("opcodes", "")) => *list = Some(CircuitList::Opcodes),
+                        Some(("assert_messages", "")) => *list = Some(CircuitList::AssertMessages),
+                        Some((field, text)) if CIRCUIT_FIELDS.contains(&field) => {
+                            *list = None;
+                            match field {
+                                "current_witness_index" => {
+                                    circuit.current_witness_index =
+                                        value::<F, _>(text, line_number)?;
+                                }
+                                "expression_width" => {
+                                    circuit.expression_width = value::<F, _>(text, line_number)?;
+                                }
+                                "private_parameters" => {
+                                    circuit.private_parameters = value::<F, _>(text, line_number)?;
+                                }
+                                "public_parameters" => {
+                                    circuit.public_parameters =
+                                        PublicInputs(value::<F, _>(text, line_number)?);
+                                }
+                                "return_values" => {
+                                    circuit.return_values =
+                                        PublicInputs(value::<F, _>(text, line_number)?);
+                                }
+                                "recursive" => {
+                                    circuit.recursive = value::<F, _>(text, line_number)?;
+                                }
+                                _ => unreachable!("all circuit fields are handled"),
+                            }
+                        }
+                        _ => match list {
+                            Some(CircuitList::Opcodes) => {
+                                circuit.opcodes.push(value::<F, _>(trimmed, line_number)?);
+                            }
+                            Some(CircuitList::AssertMessages) => {
+                                circuit.assert_messages.push(value::<F, _>(trimmed, line_number)?);
+                            }
+                            None => {
+                                return Err(error(format!(
+                                    "expected a circuit field, found `{trimmed}`"
+                                )))
+                            }
+                        },
+                    }
+                }
+            }
+        }
+
+        Ok(program)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::{AcirField, FieldElement};
+    use brillig::{
+        BinaryFieldOp, BitSize, HeapArray, HeapValueType, IntegerBitSize, MemoryAddress,
+        Opcode as BrilligOpcode, ValueOrArray,
+    };
+
+    use super::ParseError;
+    use crate::{
+        circuit::{
+            brillig::{BrilligBytecode, BrilligFunctionId, BrilligInputs, BrilligOutputs},
+            opcodes::{AcirFunctionId, BlackBoxFuncCall, BlockId, BlockType, FunctionInput, MemOp},
+            AssertionPayload, Circuit, ExpressionOrMemory, ExpressionWidth, Opcode, OpcodeLocation,
+            Program, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+    };
+
+    fn linear_combination(terms: &[(i128, u32)]) -> Expression<FieldElement> {
+        let linear_combinations = terms
+            .iter()
+            .map(|(coefficient, witness)| {
+                let magnitude = FieldElement::from(coefficient.unsigned_abs());
+                let coefficient = if *coefficient < 0 { -magnitude } else { magnitude };
+                (coefficient, Witness(*witness))
+            })
+            .collect();
+        Expression { linear_combinations, ..Expression::default() }
+    }
+
+    fn program() -> Program<FieldElement> {
+        let sum = Expression {
+            mul_terms: vec![(FieldElement::from(2u128), Witness(0), Witness(1))],
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(0)),
+                (-FieldElement::one(), Witness(2)),
+            ],
+            q_c: FieldElement::from_hex("0x1234567890abcdef1234567890abcdef1234567890abcdef")
+                .unwrap(),
+        };
+        let sha256_compression = BlackBoxFuncCall::Sha256Compression {
+            inputs: Box::new(std::array::from_fn(|i| {
+                FunctionInput::witness(Witness(i as u32), 32)
+            })),
+            hash_values: Box::new(std::array::from_fn(|i| {
+                FunctionInput::constant(FieldElement::from(i as u128), 32)
+            })),
+            outputs: Box::new(std::array::from_fn(|i| Witness(i as u32 + 16))),
+        };
+
+        let main = Circuit {
+            current_witness_index: 24,
+            expression_width: ExpressionWidth::Bounded { width: 4 },
+            opcodes: vec![
+                Opcode::AssertZero(sum),
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                    input: FunctionInput::witness(Witness(2), 8),
+                }),
+                Opcode::BlackBoxFuncCall(sha256_compression),
+                Opcode::MemoryInit {
+                    block_id: BlockId(0),
+                    init: vec![Witness(0), Witness(1)],
+                    block_type: BlockType::CallData(1),
+                },
+                Opcode::MemoryOp {
+                    block_id: BlockId(0),
+                    op: MemOp::write_to_mem_index(Expression::one(), Witness(2).into()),
+                    predicate: Some(Expression::one()),
+                },
+                Opcode::BrilligCall {
+                    id: BrilligFunctionId(0),
+                    inputs: vec![
+                        BrilligInputs::Single(Witness(0).into()),
+                        BrilligInputs::MemoryArray(BlockId(0)),
+                    ],
+                    outputs: vec![BrilligOutputs::Array(vec![Witness(23)])],
+                    predicate: None,
+                },
+                Opcode::Call {
+                    id: AcirFunctionId(1),
+                    inputs: vec![Witness(23)],
+                    outputs: vec![Witness(24)],
+                    predicate: None,
+                },
+            ],
+            private_parameters: BTreeSet::from([Witness(0), Witness(1)]),
+            public_parameters: PublicInputs(BTreeSet::from([Witness(2)])),
+            return_values: PublicInputs(BTreeSet::from([Witness(24)])),
+            assert_messages: vec![
+                (OpcodeLocation::Acir(0), AssertionPayload::StaticString("\"sum\"\n".to_string())),
+                (
+                    OpcodeLocation::Brillig { acir_index: 5, brillig_index: 2 },
+                    AssertionPayload::Dynamic(
+                        12345,
+                        vec![
+                            ExpressionOrMemory::Expression(Witness(1).into()),
+                            ExpressionOrMemory::Memory(BlockId(0)),
+                        ],
+                    ),
+                ),
+            ],
+            recursive: true,
+        };
+        let callee = Circuit {
+            current_witness_index: 1,
+            opcodes: vec![Opcode::AssertZero(linear_combination(&[(1, 0), (-1, 1)]))],
+            private_parameters: BTreeSet::from([Witness(0)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(1)])),
+            ..Circuit::default()
+        };
+
+        let brillig = BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::Const {
+                    destination: MemoryAddress(0),
+                    bit_size: BitSize::Integer(IntegerBitSize::U32),
+                    value: FieldElement::from(3u128),
+                },
+                BrilligOpcode::Const {
+                    destination: MemoryAddress(1),
+                    bit_size: BitSize::Field,
+                    value: -FieldElement::from(7u128),
+                },
+                BrilligOpcode::BinaryFieldOp {
+                    destination: MemoryAddress(2),
+                    op: BinaryFieldOp::Mul,
+                    lhs: MemoryAddress(0),
+                    rhs: MemoryAddress(1),
+                },
+                BrilligOpcode::ForeignCall {
+                    function: "print".to_string(),
+                    destinations: Vec::new(),
+                    destination_value_types: Vec::new(),
+                    inputs: vec![ValueOrArray::HeapArray(HeapArray {
+                        pointer: MemoryAddress(2),
+                        size: 1,
+                    })],
+                    input_value_types: vec![HeapValueType::Array {
+                        value_types: vec![HeapValueType::field()],
+                        size: 1,
+                    }],
+                },
+                BrilligOpcode::Stop { return_data_offset: 2, return_data_size: 1 },
+            ],
+        };
+
+        Program { functions: vec![main, callee], unconstrained_functions: vec![brillig] }
+    }
+
+    #[test]
+    fn text_roundtrip() {
+        let program = program();
+        let text = program.to_text();
+        assert_eq!(Program::from_text(&text), Ok(program));
+    }
+
+    #[test]
+    fn writes_compact_literals() {
+        let text = program().to_text();
+        assert!(text.contains("private_parameters: [w0, w1]"));
+        assert!(
+            text.contains("MemoryInit { block_id: b0, init: [w0, w1], block_type: CallData(1) }")
+        );
+        assert!(text.contains("Const { destination: @1, bit_size: Field, value: -7 }"));
+        assert!(text.contains("q_c: 0x1234567890abcdef1234567890abcdef1234567890abcdef"));
+    }
+
+    #[test]
+    fn parses_handwritten_program() {
+        let text = r#"
+// Asserts that w0 + w1 = w2
+func 0
+  private_parameters: [w0, w1]
+  return_values: [w2]
+  opcodes:
+    AssertZero(Expression { mul_terms: [], linear_combinations: [(1, w0), (1, w1), (-1, w2)], q_c: 0, })
+"#;
+        let program = Program::<FieldElement>::from_text(text).unwrap();
+        let expected = Circuit {
+            opcodes: vec![Opcode::AssertZero(linear_combination(&[(1, 0), (1, 1), (-1, 2)]))],
+            private_parameters: BTreeSet::from([Witness(0), Witness(1)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(2)])),
+            ..Circuit::default()
+        };
+        assert_eq!(program, Program { functions: vec![expected], ..Program::default() });
+    }
+
+    #[test]
+    fn reports_line_of_errors() {
+        let text = "func 0\n  opcodes:\n    AssertZero(Expression { mul_terms: [] })\n";
+        let error = Program::<FieldElement>::from_text(text).unwrap_err();
+        assert_eq!(error.line, 3);
+
+        let text = "func 1\n";
+        assert_eq!(
+            Program::<FieldElement>::from_text(text),
+            Err(ParseError { line: 1, message: "expected function index 0".to_string() })
+        );
+    }
+}
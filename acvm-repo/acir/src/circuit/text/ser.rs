@@ -0,0 +1,387 @@
+use std::marker::PhantomData;
+
+use acir_field::AcirField;
+use serde::ser::{self, Serialize};
+
+use super::{is_field_hex, Error, NEWTYPE_PREFIXES};
+
+/// Serializes `value` into a single line of the textual format.
+pub(super) fn to_string<F: AcirField, T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    let mut serializer = TextSerializer::<F> { output: String::new(), _field: PhantomData };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
+struct TextSerializer<F> {
+    output: String,
+    _field: PhantomData<F>,
+}
+
+impl<F: AcirField> TextSerializer<F> {
+    fn write_str_literal(&mut self, value: &str) {
+        if let Some(field) = is_field_hex::<F>(value) {
+            self.write_field(field);
+            return;
+        }
+
+        self.output.push('"');
+        for char in value.chars() {
+            match char {
+                '"' => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                _ => self.output.push(char),
+            }
+        }
+        self.output.push('"');
+    }
+
+    /// Field elements are written as the shortest of a decimal, negated decimal or hexadecimal literal.
+    fn write_field(&mut self, field: F) {
+        if let Some(value) = field.try_into_u128() {
+            self.output += &value.to_string();
+        } else if let Some(value) = (-field).try_into_u128() {
+            self.output += &format!("-{value}");
+        } else {
+            let hex = field.to_hex();
+            self.output += &format!("0x{}", hex.trim_start_matches('0'));
+        }
+    }
+
+    fn compound(&mut self, open: &'static str, close: &'static str) -> Compound<'_, F> {
+        self.output.push_str(open);
+        Compound { serializer: self, first: true, close }
+    }
+}
+
+/// Serializes the elements of a sequence, tuple, map or struct separated by commas.
+struct Compound<'a, F> {
+    serializer: &'a mut TextSerializer<F>,
+    first: bool,
+    close: &'static str,
+}
+
+impl<'a, F: AcirField> Compound<'a, F> {
+    fn separator(&mut self) {
+        if !self.first {
+            self.serializer.output.push_str(", ");
+        }
+        self.first = false;
+    }
+
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.separator();
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        self.separator();
+        self.serializer.output += &format!("{key}: ");
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.serializer.output.push_str(self.close);
+        Ok(())
+    }
+}
+
+impl<'a, F: AcirField> ser::Serializer for &'a mut TextSerializer<F> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'a, F>;
+    type SerializeTuple = Compound<'a, F>;
+    type SerializeTupleStruct = Compound<'a, F>;
+    type SerializeTupleVariant = Compound<'a, F>;
+    type SerializeMap = Compound<'a, F>;
+    type SerializeStruct = Compound<'a, F>;
+    type SerializeStructVariant = Compound<'a, F>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.output += if v { "true" } else { "false" };
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.output += &v.to_string();
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.output += &v.to_string();
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.output += &v.to_string();
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.output += &v.to_string();
+        Ok(())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), Error> {
+        Err(Error::new("floating point values are not supported"))
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), Error> {
+        Err(Error::new("floating point values are not supported"))
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.serialize_str(&v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.write_str_literal(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        v.serialize(self)
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.output += "None";
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        self.output += "Some(";
+        value.serialize(&mut *self)?;
+        self.output += ")";
+        Ok(())
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.output += "()";
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), Error> {
+        self.output += name;
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.output += variant;
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        if let Some((_, prefix)) = NEWTYPE_PREFIXES.iter().find(|(newtype, _)| *newtype == name) {
+            self.output.push(*prefix);
+            return value.serialize(self);
+        }
+
+        self.output += name;
+        self.output += "(";
+        value.serialize(&mut *self)?;
+        self.output += ")";
+        Ok(())
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.output += variant;
+        self.output += "(";
+        value.serialize(&mut *self)?;
+        self.output += ")";
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a, F>, Error> {
+        Ok(self.compound("[", "]"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a, F>, Error> {
+        Ok(self.compound("(", ")"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, F>, Error> {
+        self.output += name;
+        Ok(self.compound("(", ")"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, F>, Error> {
+        self.output += variant;
+        Ok(self.compound("(", ")"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a, F>, Error> {
+        Ok(self.compound("{", "}"))
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Compound<'a, F>, Error> {
+        self.output += name;
+        Ok(self.compound(" { ", " }"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, F>, Error> {
+        self.output += variant;
+        Ok(self.compound(" { ", " }"))
+    }
+}
+
+impl<'a, F: AcirField> ser::SerializeSeq for Compound<'a, F> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a, F: AcirField> ser::SerializeTuple for Compound<'a, F> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a, F: AcirField> ser::SerializeTupleStruct for Compound<'a, F> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a, F: AcirField> ser::SerializeTupleVariant for Compound<'a, F> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a, F: AcirField> ser::SerializeMap for Compound<'a, F> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.element(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.serializer.output += ": ";
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a, F: AcirField> ser::SerializeStruct for Compound<'a, F> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a, F: AcirField> ser::SerializeStructVariant for Compound<'a, F> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
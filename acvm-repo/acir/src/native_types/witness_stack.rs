@@ -12,6 +12,9 @@ use super::WitnessMap;
 enum SerializationError {
     #[error(transparent)]
     Deflate(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Bincode(#[from] bincode::Error),
 }
 
 #[derive(Debug, Error)]
@@ -76,7 +79,8 @@ impl<F: for<'a> Deserialize<'a>> TryFrom<&[u8]> for WitnessStack<F> {
         let mut deflater = GzDecoder::new(bytes);
         let mut buf_d = Vec::new();
         deflater.read_to_end(&mut buf_d).map_err(|err| WitnessStackError(err.into()))?;
-        let witness_stack = bincode::deserialize(&buf_d).unwrap();
+        let witness_stack =
+            bincode::deserialize(&buf_d).map_err(|err| WitnessStackError(err.into()))?;
         Ok(witness_stack)
     }
 }
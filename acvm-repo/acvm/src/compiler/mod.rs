@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use acir::{
-    circuit::{AssertionPayload, Circuit, ExpressionWidth, OpcodeLocation},
+    circuit::{
+        brillig::BrilligBytecode, AssertionPayload, Circuit, ExpressionWidth, OpcodeLocation,
+    },
     AcirField,
 };
 
@@ -9,10 +11,12 @@ use acir::{
 mod optimizers;
 mod transformers;
 
-pub use optimizers::optimize;
 use optimizers::optimize_internal;
-pub use transformers::transform;
-use transformers::transform_internal;
+pub use optimizers::{optimize, optimize_with_brillig};
+pub use transformers::{
+    transform, transform_to_gate_shape, BoundedGateShape, CostReport, GateShape,
+};
+use transformers::{transform_internal, transform_to_gate_shape_internal};
 
 /// This module moves and decomposes acir opcodes. The transformation map allows consumers of this module to map
 /// metadata they had about the opcodes to the new opcode structure generated after the transformation.
@@ -70,11 +74,25 @@ fn transform_assert_messages<F: Clone>(
 }
 
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`].
+///
+/// Brillig calls are never evaluated at compile time. Use [`compile_with_brillig`] to allow this.
 pub fn compile<F: AcirField>(
     acir: Circuit<F>,
     expression_width: ExpressionWidth,
 ) -> (Circuit<F>, AcirTransformationMap) {
-    let (acir, acir_opcode_positions) = optimize_internal(acir);
+    compile_with_brillig(acir, expression_width, &[])
+}
+
+/// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`].
+///
+/// `brillig_functions` are the unconstrained functions of the program which `acir` belongs to. These are used
+/// to evaluate any Brillig calls whose inputs are known at compile time.
+pub fn compile_with_brillig<F: AcirField>(
+    acir: Circuit<F>,
+    expression_width: ExpressionWidth,
+    brillig_functions: &[BrilligBytecode<F>],
+) -> (Circuit<F>, AcirTransformationMap) {
+    let (acir, acir_opcode_positions) = optimize_internal(acir, brillig_functions);
 
     let (mut acir, acir_opcode_positions) =
         transform_internal(acir, expression_width, acir_opcode_positions);
@@ -85,3 +103,26 @@ pub fn compile<F: AcirField>(
 
     (acir, transformation_map)
 }
+
+/// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`],
+/// splitting [`Opcode::AssertZero`][acir::circuit::Opcode::AssertZero] opcodes to fit the given [`GateShape`].
+///
+/// Returns a [`CostReport`] estimating the cost of proving the resulting circuit.
+pub fn compile_to_gate_shape<F: AcirField>(
+    acir: Circuit<F>,
+    gate_shape: &dyn GateShape,
+    brillig_functions: &[BrilligBytecode<F>],
+) -> (Circuit<F>, AcirTransformationMap, CostReport) {
+    let (acir, acir_opcode_positions) = optimize_internal(acir, brillig_functions);
+
+    let (mut acir, acir_opcode_positions) =
+        transform_to_gate_shape_internal(acir, gate_shape, acir_opcode_positions);
+
+    let transformation_map = AcirTransformationMap::new(acir_opcode_positions);
+
+    acir.assert_messages = transform_assert_messages(acir.assert_messages, &transformation_map);
+
+    let cost_report = CostReport::new(&acir, gate_shape);
+
+    (acir, transformation_map, cost_report)
+}
@@ -3,104 +3,77 @@ use std::collections::{BTreeMap, BTreeSet, HashMap};
 use crate::{
     compiler::optimizers::GeneralOptimizer,
     pwg::{
-        arithmetic::ExpressionSolver, blackbox::solve_range_opcode, directives::solve_directives,
+        arithmetic::ExpressionSolver,
+        blackbox::{self, bigint::AcvmBigIntSolver, solve_range_opcode},
+        directives::solve_directives,
+        insert_value, is_predicate_false,
+        memory_op::MemoryOpSolver,
         BrilligSolver, BrilligSolverStatus,
     },
 };
 use acir::{
     circuit::{
-        brillig::{Brillig, BrilligInputs, BrilligOutputs},
+        brillig::{BrilligBytecode, BrilligInputs, BrilligOutputs},
         directives::Directive,
-        opcodes::BlackBoxFuncCall,
-        Circuit, Opcode,
+        opcodes::{BlackBoxFuncCall, BlockId, ConstantOrWitnessEnum},
+        AssertionPayload, Circuit, ExpressionOrMemory, Opcode,
     },
     native_types::{Expression, Witness, WitnessMap},
+    AcirField,
 };
 use acvm_blackbox_solver::StubbedBlackBoxSolver;
 
+/// The maximum number of Brillig opcodes we are willing to execute when attempting to evaluate
+/// a single Brillig call at compile time.
+///
+/// Any Brillig call which takes longer than this to execute is left in the circuit.
+const BRILLIG_EXECUTION_LIMIT: usize = 100_000;
+
 /// `ConstantBackpropagationOptimizer` will attempt to determine any constant witnesses within the program.
 /// It does this by attempting to solve the program without any inputs (i.e. using an empty witness map),
 /// any values which it can determine are then enforced to be constant values.
 ///
+/// Witnesses are determined by:
+/// - `AssertZero` opcodes which have a single unknown witness after substituting known values.
+/// - Brillig calls, blackbox function calls and directives for which all inputs are known.
+/// - Memory reads from a block whose contents are fully known at that point in the circuit.
+///
 /// The optimizer will then replace any witnesses wherever they appear within the circuit with these constant values.
+/// Brillig calls, blackbox function calls and directives whose outputs have all been replaced are removed.
 /// This is repeated until the circuit stabilizes.
-pub(crate) struct ConstantBackpropagationOptimizer {
-    circuit: Circuit,
+///
+/// Witnesses which form part of the circuit's interface or which are used by opcodes which can only take witnesses
+/// (e.g. memory opcodes, blackbox function inputs and ACIR calls) are never replaced, although their values can still
+/// be used to determine other witnesses.
+pub(crate) struct ConstantBackpropagationOptimizer<'a, F> {
+    circuit: Circuit<F>,
+    brillig_functions: &'a [BrilligBytecode<F>],
 }
 
-impl ConstantBackpropagationOptimizer {
+impl<'a, F: AcirField> ConstantBackpropagationOptimizer<'a, F> {
     /// Creates a new `ConstantBackpropagationOptimizer`
-    pub(crate) fn new(circuit: Circuit) -> Self {
-        Self { circuit }
-    }
-
-    fn gather_known_witnesses(&self) -> (WitnessMap, BTreeSet<Witness>) {
-        // We do not want to affect the circuit's interface so avoid optimizing away these witnesses.
-        let mut required_witnesses: BTreeSet<Witness> = self
-            .circuit
-            .private_parameters
-            .union(&self.circuit.public_parameters.0)
-            .chain(&self.circuit.return_values.0)
-            .copied()
-            .collect();
-
-        for opcode in &self.circuit.opcodes {
-            match &opcode {
-                Opcode::BlackBoxFuncCall(func_call) => {
-                    required_witnesses.extend(
-                        func_call.get_inputs_vec().into_iter().map(|func_input| func_input.witness),
-                    );
-                    required_witnesses.extend(func_call.get_outputs_vec());
-                }
-
-                Opcode::MemoryInit { init, .. } => {
-                    required_witnesses.extend(init);
-                }
-
-                Opcode::MemoryOp { op, .. } => {
-                    required_witnesses.insert(op.index.to_witness().unwrap());
-                    required_witnesses.insert(op.value.to_witness().unwrap());
-                }
-
-                _ => (),
-            };
-        }
-
-        let mut known_witnesses = WitnessMap::new();
-        for opcode in self.circuit.opcodes.iter().rev() {
-            if let Opcode::AssertZero(expr) = opcode {
-                let solve_result = ExpressionSolver::solve(&mut known_witnesses, expr);
-                // It doesn't matter what the result is. We expect most opcodes to not be solved successfully so we discard errors.
-                // At the same time, if the expression can be solved then we track this by the updates to `known_witnesses`
-                drop(solve_result);
-            }
-        }
-
-        // We want to retain any references to required witnesses so we "forget" these assignments.
-        let known_witnesses: BTreeMap<_, _> = known_witnesses
-            .into_iter()
-            .filter(|(witness, _)| !required_witnesses.contains(witness))
-            .collect();
-
-        (known_witnesses.into(), required_witnesses)
+    pub(crate) fn new(circuit: Circuit<F>, brillig_functions: &'a [BrilligBytecode<F>]) -> Self {
+        Self { circuit, brillig_functions }
     }
 
     /// Returns a `Circuit` where with any constant witnesses replaced with the constant they resolve to.
     #[tracing::instrument(level = "trace", skip_all)]
     pub(crate) fn backpropagate_constants(
-        circuit: Circuit,
+        circuit: Circuit<F>,
+        brillig_functions: &[BrilligBytecode<F>],
         order_list: Vec<usize>,
-    ) -> (Circuit, Vec<usize>) {
-        let old_circuit_size = circuit.opcodes.len();
+    ) -> (Circuit<F>, Vec<usize>) {
+        let mut circuit = circuit;
+        let mut order_list = order_list;
+        loop {
+            let old_circuit_size = circuit.opcodes.len();
 
-        let optimizer = Self::new(circuit);
-        let (circuit, order_list) = optimizer.backpropagate_constants_iteration(order_list);
+            let optimizer = ConstantBackpropagationOptimizer::new(circuit, brillig_functions);
+            (circuit, order_list) = optimizer.backpropagate_constants_iteration(order_list);
 
-        let new_circuit_size = circuit.opcodes.len();
-        if new_circuit_size < old_circuit_size {
-            Self::backpropagate_constants(circuit, order_list)
-        } else {
-            (circuit, order_list)
+            if circuit.opcodes.len() >= old_circuit_size {
+                return (circuit, order_list);
+            }
         }
     }
 
@@ -108,146 +81,112 @@ impl ConstantBackpropagationOptimizer {
     pub(crate) fn backpropagate_constants_iteration(
         mut self,
         order_list: Vec<usize>,
-    ) -> (Circuit, Vec<usize>) {
-        let (mut known_witnesses, required_witnesses) = self.gather_known_witnesses();
-
+    ) -> (Circuit<F>, Vec<usize>) {
         let opcodes = std::mem::take(&mut self.circuit.opcodes);
 
-        fn remap_expression(known_witnesses: &WitnessMap, expression: Expression) -> Expression {
+        let (known_witnesses, mut removable_opcodes, hint_dependencies) =
+            self.partially_execute(&opcodes);
+
+        // Determine which witnesses can't be replaced with their constant values.
+        let mut required_witnesses = self.interface_witnesses();
+        for (opcode, removable) in opcodes.iter().zip(&removable_opcodes) {
+            required_witnesses.extend(used_witnesses(opcode));
+            if !removable {
+                required_witnesses.extend(defined_witnesses(opcode));
+            }
+        }
+
+        // If an opcode defines a witness which we can't replace then we need to keep this opcode
+        // so that the witness remains constrained. Keeping this opcode may in turn require other witnesses
+        // so we iterate until we reach a fixed point.
+        //
+        // Blackbox function calls are only known to be satisfied for the values found when partially executing the circuit.
+        // If any of these values came from a hint (a Brillig call or directive) which we keep then the inputs are
+        // unconstrained in the optimized circuit, so the blackbox function call must be kept to constrain them.
+        loop {
+            let mut changed = false;
+            for (idx, opcode) in opcodes.iter().enumerate() {
+                if !removable_opcodes[idx] {
+                    continue;
+                }
+                let outputs = defined_witnesses(opcode);
+                let depends_on_kept_hint = matches!(opcode, Opcode::BlackBoxFuncCall(_))
+                    && hint_dependencies[idx].iter().any(|hint| !removable_opcodes[*hint]);
+                if depends_on_kept_hint
+                    || outputs.iter().any(|witness| required_witnesses.contains(witness))
+                {
+                    removable_opcodes[idx] = false;
+                    required_witnesses.extend(outputs);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let known_witnesses: WitnessMap<F> = known_witnesses
+            .into_iter()
+            .filter(|(witness, _)| !required_witnesses.contains(witness))
+            .collect::<BTreeMap<_, _>>()
+            .into();
+
+        fn remap_expression<F: AcirField>(
+            known_witnesses: &WitnessMap<F>,
+            expression: Expression<F>,
+        ) -> Expression<F> {
             GeneralOptimizer::optimize(ExpressionSolver::evaluate(&expression, known_witnesses))
         }
 
         let mut new_order_list = Vec::with_capacity(order_list.len());
         let mut new_opcodes = Vec::with_capacity(opcodes.len());
-        for (idx, opcode) in opcodes.into_iter().enumerate() {
+        for (idx, (opcode, removable)) in opcodes.into_iter().zip(removable_opcodes).enumerate() {
+            if removable {
+                continue;
+            }
+
             let new_opcode = match opcode {
                 Opcode::AssertZero(expression) => {
                     let new_expr = remap_expression(&known_witnesses, expression);
                     if new_expr.is_zero() {
                         continue;
                     }
-
-                    // Attempt to solve the opcode to see if we can determine the value of any witnesses in the expression.
-                    // We only do this _after_ we apply any simplifications to create the new opcode as we want to
-                    // keep the constraint on the witness which we are solving for here.
-                    let solve_result = ExpressionSolver::solve(&mut known_witnesses, &new_expr);
-                    // It doesn't matter what the result is. We expect most opcodes to not be solved successfully so we discard errors.
-                    // At the same time, if the expression can be solved then we track this by the updates to `known_witnesses`
-                    drop(solve_result);
-
                     Opcode::AssertZero(new_expr)
                 }
-                Opcode::Brillig(brillig) => {
-                    let remapped_inputs = brillig
-                        .inputs
+                Opcode::BrilligCall { id, inputs, outputs, predicate } => {
+                    let inputs = inputs
                         .into_iter()
                         .map(|input| match input {
                             BrilligInputs::Single(expr) => {
                                 BrilligInputs::Single(remap_expression(&known_witnesses, expr))
                             }
-                            BrilligInputs::Array(expr_array) => {
-                                let new_input: Vec<_> = expr_array
+                            BrilligInputs::Array(expr_array) => BrilligInputs::Array(
+                                expr_array
                                     .into_iter()
                                     .map(|expr| remap_expression(&known_witnesses, expr))
-                                    .collect();
-
-                                BrilligInputs::Array(new_input)
-                            }
+                                    .collect(),
+                            ),
                             input @ BrilligInputs::MemoryArray(_) => input,
                         })
                         .collect();
+                    let predicate =
+                        predicate.map(|predicate| remap_expression(&known_witnesses, predicate));
 
-                    let remapped_predicate = brillig
-                        .predicate
-                        .map(|predicate| remap_expression(&known_witnesses, predicate));
-
-                    let new_brillig = Brillig {
-                        inputs: remapped_inputs,
-                        predicate: remapped_predicate,
-                        ..brillig
-                    };
-
-                    let brillig_output_is_required_witness =
-                        new_brillig.outputs.iter().any(|output| match output {
-                            BrilligOutputs::Simple(witness) => required_witnesses.contains(witness),
-                            BrilligOutputs::Array(witness_array) => witness_array
-                                .iter()
-                                .any(|witness| required_witnesses.contains(witness)),
-                        });
-
-                    if brillig_output_is_required_witness {
-                        // If one of the brillig opcode's outputs is a required witness then we can't remove the opcode. In this case we can't replace
-                        // all of the uses of this witness with the calculated constant so we'll be attempting to use an uninitialized witness.
-                        //
-                        // We then do not attempt execution of this opcode and just simplify the inputs.
-                        Opcode::Brillig(new_brillig)
-                    } else if let Ok(mut solver) = BrilligSolver::new(
-                        &known_witnesses,
-                        &HashMap::new(),
-                        &new_brillig,
-                        &StubbedBlackBoxSolver,
-                        idx,
-                    ) {
-                        match solver.solve() {
-                            Ok(BrilligSolverStatus::Finished) => {
-                                // Write execution outputs
-                                match solver.finalize(&mut known_witnesses, &new_brillig) {
-                                    Ok(()) => {
-                                        // If we've managed to execute the brillig opcode at compile time, we can now just write in the
-                                        // results as constants for the rest of the circuit.
-                                        continue;
-                                    }
-                                    _ => Opcode::Brillig(new_brillig),
-                                }
-                            }
-                            Ok(BrilligSolverStatus::InProgress) => unreachable!(
-                                "Solver should either finish, block on foreign call, or error."
-                            ),
-                            Ok(BrilligSolverStatus::ForeignCallWait(_)) | Err(_) => {
-                                Opcode::Brillig(new_brillig)
-                            }
-                        }
-                    } else {
-                        Opcode::Brillig(new_brillig)
-                    }
+                    Opcode::BrilligCall { id, inputs, outputs, predicate }
                 }
-
                 Opcode::Directive(Directive::ToLeRadix { a, b, radix }) => {
-                    if b.iter().all(|output| known_witnesses.contains_key(output)) {
-                        continue;
-                    } else if b.iter().any(|witness| required_witnesses.contains(witness)) {
-                        // If one of the brillig opcode's outputs is a required witness then we can't remove the opcode. In this case we can't replace
-                        // all of the uses of this witness with the calculated constant so we'll be attempting to use an uninitialized witness.
-                        //
-                        // We then do not attempt execution of this opcode and just simplify the inputs.
-                        Opcode::Directive(Directive::ToLeRadix {
-                            a: remap_expression(&known_witnesses, a),
-                            b,
-                            radix,
-                        })
-                    } else {
-                        let directive = Directive::ToLeRadix {
-                            a: remap_expression(&known_witnesses, a),
-                            b,
-                            radix,
-                        };
-                        let result = solve_directives(&mut known_witnesses, &directive);
-
-                        match result {
-                            Ok(()) => continue,
-                            Err(_) => Opcode::Directive(directive),
-                        }
-                    }
+                    Opcode::Directive(Directive::ToLeRadix {
+                        a: remap_expression(&known_witnesses, a),
+                        b,
+                        radix,
+                    })
                 }
+                Opcode::Call { id, inputs, outputs, predicate } => {
+                    let predicate =
+                        predicate.map(|predicate| remap_expression(&known_witnesses, predicate));
 
-                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }) => {
-                    if solve_range_opcode(&known_witnesses, &input).is_ok() {
-                        continue;
-                    } else {
-                        opcode
-                    }
+                    Opcode::Call { id, inputs, outputs, predicate }
                 }
-
                 Opcode::BlackBoxFuncCall(_)
                 | Opcode::MemoryOp { .. }
                 | Opcode::MemoryInit { .. } => opcode,
@@ -261,6 +200,359 @@ impl ConstantBackpropagationOptimizer {
 
         (self.circuit, new_order_list)
     }
+
+    /// Executes the circuit as far as possible without any inputs.
+    ///
+    /// Returns the values of all witnesses which could be determined along with whether each opcode
+    /// has had all of its outputs determined and so can be removed from the circuit.
+    ///
+    /// For each opcode we also return the hints (Brillig calls and directives) which the known values of its
+    /// inputs were derived from. Hint outputs are unconstrained, so these values only hold in the optimized
+    /// circuit if the hints they came from are removed and their outputs replaced with constants.
+    fn partially_execute(
+        &self,
+        opcodes: &[Opcode<F>],
+    ) -> (WitnessMap<F>, Vec<bool>, Vec<BTreeSet<usize>>) {
+        let mut known_witnesses = WitnessMap::new();
+
+        // Constraints may fix a witness to a constant value before it's used by an earlier opcode
+        // so we first look for any `AssertZero` opcodes which can be solved in isolation.
+        for opcode in opcodes.iter().rev() {
+            if let Opcode::AssertZero(expr) = opcode {
+                let solve_result = ExpressionSolver::solve(&mut known_witnesses, expr);
+                // It doesn't matter what the result is. We expect most opcodes to not be solved successfully so we discard errors.
+                // At the same time, if the expression can be solved then we track this by the updates to `known_witnesses`
+                drop(solve_result);
+            }
+        }
+
+        let mut memory: HashMap<BlockId, MemoryOpSolver<F>> = HashMap::new();
+        let mut bigint_solver = AcvmBigIntSolver::default();
+
+        // The hints which each known witness and the contents of each memory block were derived from.
+        // Witnesses solved above only depend on constants so they have no entry.
+        let mut witness_hints: HashMap<Witness, BTreeSet<usize>> = HashMap::new();
+        let mut block_hints: HashMap<BlockId, BTreeSet<usize>> = HashMap::new();
+
+        let mut removable_opcodes = Vec::with_capacity(opcodes.len());
+        let mut hint_dependencies = Vec::with_capacity(opcodes.len());
+        for (idx, opcode) in opcodes.iter().enumerate() {
+            let witnesses = opcode_witnesses(opcode);
+            let mut hints: BTreeSet<usize> = witnesses
+                .iter()
+                .filter_map(|witness| witness_hints.get(witness))
+                .flatten()
+                .copied()
+                .collect();
+            for block_id in read_blocks(opcode) {
+                hints.extend(block_hints.get(&block_id).into_iter().flatten());
+            }
+            let unknown_witnesses: Vec<Witness> = witnesses
+                .into_iter()
+                .filter(|witness| !known_witnesses.contains_key(witness))
+                .collect();
+
+            let removable = self.solve_opcode(
+                idx,
+                opcode,
+                &mut known_witnesses,
+                &mut memory,
+                &mut bigint_solver,
+            );
+
+            let mut output_hints = hints.clone();
+            if matches!(opcode, Opcode::BrilligCall { .. } | Opcode::Directive(_)) {
+                output_hints.insert(idx);
+            }
+            for witness in unknown_witnesses {
+                if known_witnesses.contains_key(&witness) && !output_hints.is_empty() {
+                    witness_hints.insert(witness, output_hints.clone());
+                }
+            }
+            match opcode {
+                Opcode::MemoryInit { block_id, .. } => {
+                    block_hints.insert(*block_id, hints.clone());
+                }
+                Opcode::MemoryOp { block_id, .. } => {
+                    block_hints.entry(*block_id).or_default().extend(hints.iter().copied());
+                }
+                _ => (),
+            }
+
+            removable_opcodes.push(removable);
+            hint_dependencies.push(hints);
+        }
+
+        (known_witnesses, removable_opcodes, hint_dependencies)
+    }
+
+    /// Attempts to solve `opcode` using the witnesses known so far, returning whether all of its outputs
+    /// have been determined so it can be removed from the circuit.
+    fn solve_opcode(
+        &self,
+        idx: usize,
+        opcode: &Opcode<F>,
+        known_witnesses: &mut WitnessMap<F>,
+        memory: &mut HashMap<BlockId, MemoryOpSolver<F>>,
+        bigint_solver: &mut AcvmBigIntSolver,
+    ) -> bool {
+        match opcode {
+            Opcode::AssertZero(expr) => {
+                let solve_result = ExpressionSolver::solve(known_witnesses, expr);
+                drop(solve_result);
+                false
+            }
+
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }) => {
+                solve_range_opcode(known_witnesses, input).is_ok()
+            }
+
+            // These opcodes either can't be evaluated at compile time or rely on state
+            // which is shared with other opcodes so we never remove them.
+            Opcode::BlackBoxFuncCall(
+                BlackBoxFuncCall::RecursiveAggregation { .. }
+                | BlackBoxFuncCall::BigIntAdd { .. }
+                | BlackBoxFuncCall::BigIntSub { .. }
+                | BlackBoxFuncCall::BigIntMul { .. }
+                | BlackBoxFuncCall::BigIntDiv { .. }
+                | BlackBoxFuncCall::BigIntFromLeBytes { .. }
+                | BlackBoxFuncCall::BigIntToLeBytes { .. },
+            ) => false,
+
+            Opcode::BlackBoxFuncCall(func_call) => {
+                let outputs = func_call.get_outputs_vec();
+                if outputs.is_empty() || is_any_known(known_witnesses, &outputs) {
+                    return false;
+                }
+                let solved = blackbox::solve(
+                    &StubbedBlackBoxSolver,
+                    known_witnesses,
+                    func_call,
+                    bigint_solver,
+                )
+                .is_ok();
+
+                // Blackbox functions implicitly constrain their inputs to fit within their bit sizes
+                // so we can only remove the opcode if this holds for the known input values.
+                solved
+                    && func_call
+                        .get_inputs_vec()
+                        .iter()
+                        .all(|input| solve_range_opcode(known_witnesses, input).is_ok())
+            }
+
+            Opcode::Directive(directive @ Directive::ToLeRadix { b, .. }) => {
+                if is_any_known(known_witnesses, b) {
+                    return false;
+                }
+                solve_directives(known_witnesses, directive).is_ok()
+            }
+
+            Opcode::MemoryInit { block_id, init, .. } => {
+                let mut solver = MemoryOpSolver::default();
+                if solver.init(init, known_witnesses).is_ok() {
+                    memory.insert(*block_id, solver);
+                } else {
+                    memory.remove(block_id);
+                }
+                false
+            }
+
+            Opcode::MemoryOp { block_id, op, predicate } => {
+                if let Some(solver) = memory.get_mut(block_id) {
+                    let is_read = op.operation.to_const().map_or(false, |op| op.is_zero());
+                    let result = solver.solve_memory_op(op, known_witnesses, predicate);
+                    // If we can't tell whether (or where) a write happened then we no longer know the contents of the block.
+                    if result.is_err() && !is_read {
+                        memory.remove(block_id);
+                    }
+                }
+                false
+            }
+
+            Opcode::BrilligCall { id, inputs, outputs, predicate } => {
+                let output_witnesses = flatten_brillig_outputs(outputs);
+                if is_any_known(known_witnesses, &output_witnesses) {
+                    return false;
+                }
+
+                match is_predicate_false(known_witnesses, predicate) {
+                    // The ACVM sets all outputs to zero when the predicate is false.
+                    Ok(true) => output_witnesses
+                        .iter()
+                        .all(|witness| insert_value(witness, F::zero(), known_witnesses).is_ok()),
+                    Ok(false) => {
+                        let Some(brillig_function) = self.brillig_functions.get(id.as_usize())
+                        else {
+                            return false;
+                        };
+                        let Ok(mut solver) = BrilligSolver::new_call(
+                            known_witnesses,
+                            memory,
+                            inputs,
+                            &brillig_function.bytecode,
+                            &StubbedBlackBoxSolver,
+                            idx,
+                            *id,
+                        ) else {
+                            return false;
+                        };
+
+                        for _ in 0..BRILLIG_EXECUTION_LIMIT {
+                            match solver.step() {
+                                Ok(BrilligSolverStatus::InProgress) => continue,
+                                Ok(BrilligSolverStatus::Finished) => {
+                                    // If we've managed to execute the brillig opcode at compile time, we can now just write in the
+                                    // results as constants for the rest of the circuit.
+                                    return solver.finalize(known_witnesses, outputs).is_ok();
+                                }
+                                // Foreign calls can't be resolved at compile time.
+                                Ok(BrilligSolverStatus::ForeignCallWait(_)) | Err(_) => {
+                                    return false
+                                }
+                            }
+                        }
+                        false
+                    }
+                    Err(_) => false,
+                }
+            }
+
+            // ACIR calls are solved by a separate ACVM instance so we treat their outputs as unknown.
+            Opcode::Call { .. } => false,
+        }
+    }
+
+    /// Returns the witnesses which make up the circuit's interface. These cannot be replaced by constants.
+    fn interface_witnesses(&self) -> BTreeSet<Witness> {
+        let mut witnesses: BTreeSet<Witness> = self
+            .circuit
+            .private_parameters
+            .union(&self.circuit.public_parameters.0)
+            .chain(&self.circuit.return_values.0)
+            .copied()
+            .collect();
+
+        // Assertion payloads are evaluated using the witness map so we must keep any witnesses they reference.
+        for (_, payload) in &self.circuit.assert_messages {
+            if let AssertionPayload::Dynamic(_, payload) = payload {
+                for item in payload {
+                    if let ExpressionOrMemory::Expression(expr) = item {
+                        witnesses.extend(expression_witnesses(expr));
+                    }
+                }
+            }
+        }
+
+        witnesses
+    }
+}
+
+/// Returns the witnesses which `opcode` reads from positions which we can't replace with a constant.
+fn used_witnesses<F: AcirField>(opcode: &Opcode<F>) -> Vec<Witness> {
+    match opcode {
+        Opcode::BlackBoxFuncCall(func_call) => func_call
+            .get_inputs_vec()
+            .into_iter()
+            .filter_map(|input| match input.input {
+                ConstantOrWitnessEnum::Witness(witness) => Some(witness),
+                ConstantOrWitnessEnum::Constant(_) => None,
+            })
+            .collect(),
+        Opcode::MemoryInit { init, .. } => init.clone(),
+        Opcode::MemoryOp { op, predicate, .. } => {
+            let mut witnesses = expression_witnesses(&op.operation);
+            witnesses.extend(expression_witnesses(&op.index));
+            witnesses.extend(expression_witnesses(&op.value));
+            if let Some(predicate) = predicate {
+                witnesses.extend(expression_witnesses(predicate));
+            }
+            witnesses
+        }
+        Opcode::Call { inputs, outputs, .. } => [inputs.clone(), outputs.clone()].concat(),
+        Opcode::AssertZero(_) | Opcode::Directive(_) | Opcode::BrilligCall { .. } => Vec::new(),
+    }
+}
+
+/// Returns the witnesses which are assigned by `opcode`.
+fn defined_witnesses<F: AcirField>(opcode: &Opcode<F>) -> Vec<Witness> {
+    match opcode {
+        Opcode::BlackBoxFuncCall(func_call) => func_call.get_outputs_vec(),
+        Opcode::Directive(Directive::ToLeRadix { b, .. }) => b.clone(),
+        Opcode::BrilligCall { outputs, .. } => flatten_brillig_outputs(outputs),
+        Opcode::AssertZero(_)
+        | Opcode::MemoryInit { .. }
+        | Opcode::MemoryOp { .. }
+        | Opcode::Call { .. } => Vec::new(),
+    }
+}
+
+/// Returns every witness which `opcode` reads or assigns.
+fn opcode_witnesses<F: AcirField>(opcode: &Opcode<F>) -> Vec<Witness> {
+    let mut witnesses = match opcode {
+        Opcode::AssertZero(expr) => expression_witnesses(expr),
+        Opcode::Directive(Directive::ToLeRadix { a, .. }) => expression_witnesses(a),
+        Opcode::BrilligCall { inputs, predicate, .. } => {
+            let mut witnesses: Vec<Witness> = inputs
+                .iter()
+                .flat_map(|input| match input {
+                    BrilligInputs::Single(expr) => expression_witnesses(expr),
+                    BrilligInputs::Array(exprs) => {
+                        exprs.iter().flat_map(expression_witnesses).collect()
+                    }
+                    BrilligInputs::MemoryArray(_) => Vec::new(),
+                })
+                .collect();
+            if let Some(predicate) = predicate {
+                witnesses.extend(expression_witnesses(predicate));
+            }
+            witnesses
+        }
+        Opcode::BlackBoxFuncCall(_)
+        | Opcode::MemoryInit { .. }
+        | Opcode::MemoryOp { .. }
+        | Opcode::Call { .. } => Vec::new(),
+    };
+    witnesses.extend(used_witnesses(opcode));
+    witnesses.extend(defined_witnesses(opcode));
+    witnesses
+}
+
+/// Returns the memory blocks whose contents `opcode` reads.
+fn read_blocks<F>(opcode: &Opcode<F>) -> Vec<BlockId> {
+    match opcode {
+        Opcode::MemoryOp { block_id, .. } => vec![*block_id],
+        Opcode::BrilligCall { inputs, .. } => inputs
+            .iter()
+            .filter_map(|input| match input {
+                BrilligInputs::MemoryArray(block_id) => Some(*block_id),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn flatten_brillig_outputs(outputs: &[BrilligOutputs]) -> Vec<Witness> {
+    outputs
+        .iter()
+        .flat_map(|output| match output {
+            BrilligOutputs::Simple(witness) => vec![*witness],
+            BrilligOutputs::Array(witness_array) => witness_array.clone(),
+        })
+        .collect()
+}
+
+fn expression_witnesses<F>(expr: &Expression<F>) -> Vec<Witness> {
+    expr.linear_combinations
+        .iter()
+        .map(|(_, witness)| *witness)
+        .chain(expr.mul_terms.iter().flat_map(|(_, lhs, rhs)| [*lhs, *rhs]))
+        .collect()
+}
+
+fn is_any_known<F>(known_witnesses: &WitnessMap<F>, witnesses: &[Witness]) -> bool {
+    witnesses.iter().any(|witness| known_witnesses.contains_key(witness))
 }
 
 #[cfg(test)]
@@ -269,19 +561,19 @@ mod tests {
 
     use crate::compiler::optimizers::constant_backpropagation::ConstantBackpropagationOptimizer;
     use acir::{
-        brillig::MemoryAddress,
+        brillig::{BitSize, MemoryAddress, Opcode as BrilligOpcode},
         circuit::{
-            brillig::{Brillig, BrilligOutputs},
+            brillig::{BrilligBytecode, BrilligFunctionId, BrilligOutputs},
             opcodes::{BlackBoxFuncCall, FunctionInput},
             Circuit, ExpressionWidth, Opcode, PublicInputs,
         },
-        native_types::Witness,
+        native_types::{Expression, Witness},
+        AcirField, FieldElement,
     };
-    use brillig_vm::brillig::Opcode as BrilligOpcode;
 
-    fn test_circuit(opcodes: Vec<Opcode>) -> Circuit {
+    fn test_circuit(opcodes: Vec<Opcode<FieldElement>>) -> Circuit<FieldElement> {
         Circuit {
-            current_witness_index: 1,
+            current_witness_index: 3,
             expression_width: ExpressionWidth::Bounded { width: 4 },
             opcodes,
             private_parameters: BTreeSet::new(),
@@ -292,32 +584,38 @@ mod tests {
         }
     }
 
-    #[test]
-    fn retain_brillig_with_required_witness_outputs() {
-        let brillig_opcode = Opcode::Brillig(Brillig {
-            inputs: Vec::new(),
-            outputs: vec![BrilligOutputs::Simple(Witness(1))],
+    fn constant_brillig_function() -> BrilligBytecode<FieldElement> {
+        BrilligBytecode {
             bytecode: vec![
                 BrilligOpcode::Const {
                     destination: MemoryAddress(0),
-                    bit_size: 32,
-                    value: 1u128.into(),
+                    bit_size: BitSize::Field,
+                    value: FieldElement::from(1u128),
                 },
                 BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 1 },
             ],
+        }
+    }
+
+    #[test]
+    fn retain_brillig_with_required_witness_outputs() {
+        let brillig_opcode = Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: Vec::new(),
+            outputs: vec![BrilligOutputs::Simple(Witness(1))],
             predicate: None,
-        });
+        };
         let blackbox_opcode = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
-            lhs: FunctionInput { witness: Witness(1), num_bits: 64 },
-            rhs: FunctionInput { witness: Witness(2), num_bits: 64 },
+            lhs: FunctionInput::witness(Witness(1), 64),
+            rhs: FunctionInput::witness(Witness(2), 64),
             output: Witness(3),
         });
 
         let opcodes = vec![brillig_opcode, blackbox_opcode];
-        // The optimizer should keep the lowest bit size range constraint
         let circuit = test_circuit(opcodes);
         let acir_opcode_positions = circuit.opcodes.iter().enumerate().map(|(i, _)| i).collect();
-        let optimizer = ConstantBackpropagationOptimizer::new(circuit);
+        let brillig_functions = [constant_brillig_function()];
+        let optimizer = ConstantBackpropagationOptimizer::new(circuit, &brillig_functions);
 
         let (optimized_circuit, _) =
             optimizer.backpropagate_constants_iteration(acir_opcode_positions);
@@ -328,4 +626,184 @@ mod tests {
             "The brillig opcode should not be removed as the output is needed as a witness"
         );
     }
+
+    #[test]
+    fn removes_brillig_with_constant_outputs() {
+        // w1 = brillig()
+        // w2 - w1 - 1 = 0
+        let brillig_opcode = Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: Vec::new(),
+            outputs: vec![BrilligOutputs::Simple(Witness(1))],
+            predicate: None,
+        };
+        let assert_zero = Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(2)),
+                (-FieldElement::one(), Witness(1)),
+            ],
+            q_c: -FieldElement::one(),
+        });
+
+        let mut circuit = test_circuit(vec![brillig_opcode, assert_zero]);
+        circuit.return_values = PublicInputs(BTreeSet::from([Witness(2)]));
+        let acir_opcode_positions = vec![0, 1];
+        let brillig_functions = [constant_brillig_function()];
+
+        let (optimized_circuit, new_positions) =
+            ConstantBackpropagationOptimizer::backpropagate_constants(
+                circuit,
+                &brillig_functions,
+                acir_opcode_positions,
+            );
+
+        // The return value must remain constrained to be equal to 2.
+        let expected = Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(FieldElement::one(), Witness(2))],
+            q_c: -FieldElement::from(2u128),
+        });
+        assert_eq!(optimized_circuit.opcodes, vec![expected]);
+        assert_eq!(new_positions, vec![1]);
+    }
+
+    #[test]
+    fn retain_brillig_with_unknown_predicate() {
+        let brillig_opcode = Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: Vec::new(),
+            outputs: vec![BrilligOutputs::Simple(Witness(1))],
+            predicate: Some(Witness(3).into()),
+        };
+        let assert_zero = Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(2)),
+                (-FieldElement::one(), Witness(1)),
+            ],
+            q_c: FieldElement::zero(),
+        });
+
+        let mut circuit = test_circuit(vec![brillig_opcode, assert_zero]);
+        circuit.private_parameters = BTreeSet::from([Witness(3)]);
+        circuit.return_values = PublicInputs(BTreeSet::from([Witness(2)]));
+        let brillig_functions = [constant_brillig_function()];
+
+        let (optimized_circuit, _) = ConstantBackpropagationOptimizer::backpropagate_constants(
+            circuit.clone(),
+            &brillig_functions,
+            vec![0, 1],
+        );
+
+        assert_eq!(optimized_circuit.opcodes, circuit.opcodes);
+    }
+
+    #[test]
+    fn retain_range_on_retained_brillig_output() {
+        // w1 = brillig()
+        // RANGE(w1, 8)
+        let brillig_opcode = Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: Vec::new(),
+            outputs: vec![BrilligOutputs::Simple(Witness(1))],
+            predicate: None,
+        };
+        let range_opcode = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput::witness(Witness(1), 8),
+        });
+
+        let mut circuit = test_circuit(vec![brillig_opcode, range_opcode]);
+        circuit.return_values = PublicInputs(BTreeSet::from([Witness(1)]));
+        let brillig_functions = [constant_brillig_function()];
+
+        let (optimized_circuit, _) = ConstantBackpropagationOptimizer::backpropagate_constants(
+            circuit.clone(),
+            &brillig_functions,
+            vec![0, 1],
+        );
+
+        // The brillig output is unconstrained in the optimized circuit so the range constraint must be kept.
+        assert_eq!(optimized_circuit.opcodes, circuit.opcodes);
+    }
+
+    #[test]
+    fn retain_range_derived_from_retained_brillig_output() {
+        // w1 = brillig()
+        // w2 - w1 - 1 = 0
+        // RANGE(w2, 8)
+        let brillig_opcode = Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: Vec::new(),
+            outputs: vec![BrilligOutputs::Simple(Witness(1))],
+            predicate: None,
+        };
+        let assert_zero = Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(2)),
+                (-FieldElement::one(), Witness(1)),
+            ],
+            q_c: -FieldElement::one(),
+        });
+        let range_opcode = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput::witness(Witness(2), 8),
+        });
+
+        let mut circuit = test_circuit(vec![brillig_opcode, assert_zero, range_opcode]);
+        circuit.return_values = PublicInputs(BTreeSet::from([Witness(1)]));
+        let brillig_functions = [constant_brillig_function()];
+
+        let (optimized_circuit, _) = ConstantBackpropagationOptimizer::backpropagate_constants(
+            circuit.clone(),
+            &brillig_functions,
+            vec![0, 1, 2],
+        );
+
+        assert_eq!(optimized_circuit.opcodes, circuit.opcodes);
+    }
+
+    #[test]
+    fn removes_range_on_removed_brillig_output() {
+        // w1 = brillig()
+        // w2 - w1 - 1 = 0
+        // RANGE(w2, 8)
+        let brillig_opcode = Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: Vec::new(),
+            outputs: vec![BrilligOutputs::Simple(Witness(1))],
+            predicate: None,
+        };
+        let assert_zero = Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(2)),
+                (-FieldElement::one(), Witness(1)),
+            ],
+            q_c: -FieldElement::one(),
+        });
+        let range_opcode = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput::witness(Witness(2), 8),
+        });
+
+        let mut circuit = test_circuit(vec![brillig_opcode, assert_zero, range_opcode]);
+        circuit.return_values = PublicInputs(BTreeSet::from([Witness(2)]));
+        let brillig_functions = [constant_brillig_function()];
+
+        let (optimized_circuit, new_positions) =
+            ConstantBackpropagationOptimizer::backpropagate_constants(
+                circuit,
+                &brillig_functions,
+                vec![0, 1, 2],
+            );
+
+        // The brillig call is replaced by a constant so `w2` is constrained to be 2, which fits in 8 bits.
+        let expected = Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(FieldElement::one(), Witness(2))],
+            q_c: -FieldElement::from(2u128),
+        });
+        assert_eq!(optimized_circuit.opcodes, vec![expected]);
+        assert_eq!(new_positions, vec![1]);
+    }
 }
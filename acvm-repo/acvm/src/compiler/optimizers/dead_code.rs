@@ -0,0 +1,409 @@
+use std::collections::{BTreeSet, HashMap};
+
+use acir::{
+    brillig::{BinaryFieldOp, BinaryIntOp, Opcode as BrilligOpcode},
+    circuit::{
+        brillig::{BrilligBytecode, BrilligInputs, BrilligOutputs},
+        directives::Directive,
+        opcodes::{BlackBoxFuncCall, ConstantOrWitnessEnum},
+        AssertionPayload, Circuit, ExpressionOrMemory, Opcode,
+    },
+    native_types::{Expression, Witness},
+    AcirField,
+};
+
+/// `DeadCodeOptimizer` removes opcodes which have no effect on the witnesses which make up the circuit's interface
+/// (parameters, return values and witnesses referenced in assertion messages).
+///
+/// Starting from these witnesses, we trace backwards through the circuit removing:
+/// - Brillig calls and directives for which none of the outputs are used by any other opcode.
+/// - Blackbox function calls (including range checks) for which none of the input or output witnesses are
+///   used by any other opcode.
+/// - `AssertZero` opcodes which contain a witness, appearing only linearly, which isn't used by any other opcode.
+///   Such a constraint can always be satisfied by choosing a value for this witness.
+///
+/// Removing an opcode may cause the witnesses it uses to become dead so we repeat this until the circuit stabilizes.
+///
+/// Brillig calls which have no outputs, perform foreign calls or can fail (e.g. by hitting a `Trap` opcode)
+/// are executed for their side effects and so are always kept. Memory opcodes are handled by [`UnusedMemoryOptimizer`][super::UnusedMemoryOptimizer]
+/// and ACIR calls are never removed.
+pub(crate) struct DeadCodeOptimizer<'a, F> {
+    circuit: Circuit<F>,
+    brillig_functions: &'a [BrilligBytecode<F>],
+}
+
+impl<'a, F: AcirField> DeadCodeOptimizer<'a, F> {
+    /// Creates a new `DeadCodeOptimizer`
+    pub(crate) fn new(circuit: Circuit<F>, brillig_functions: &'a [BrilligBytecode<F>]) -> Self {
+        Self { circuit, brillig_functions }
+    }
+
+    /// Returns a `Circuit` where all dead opcodes have been removed.
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub(crate) fn eliminate_dead_code(self, order_list: Vec<usize>) -> (Circuit<F>, Vec<usize>) {
+        let interface_witnesses = self.interface_witnesses();
+
+        // Tracks how many of the remaining opcodes use each witness.
+        // Outputs of Brillig calls and directives don't count as uses as these opcodes don't constrain them.
+        let mut use_counts: HashMap<Witness, usize> = HashMap::new();
+        for opcode in &self.circuit.opcodes {
+            for witness in used_witnesses(opcode) {
+                *use_counts.entry(witness).or_default() += 1;
+            }
+        }
+
+        let is_unused_elsewhere = |use_counts: &HashMap<Witness, usize>, witness: &Witness| {
+            !interface_witnesses.contains(witness)
+                && use_counts.get(witness).copied().unwrap_or_default() <= 1
+        };
+
+        let mut removed = vec![false; self.circuit.opcodes.len()];
+        loop {
+            let mut changed = false;
+            // Opcodes are visited in reverse order as dead witnesses tend to be defined before they're used.
+            for (opcode, is_removed) in self.circuit.opcodes.iter().zip(removed.iter_mut()).rev() {
+                if *is_removed {
+                    continue;
+                }
+
+                let is_dead = match opcode {
+                    Opcode::AssertZero(expr) => {
+                        expr.linear_combinations.iter().any(|(scale, witness)| {
+                            !scale.is_zero()
+                                && !expr
+                                    .mul_terms
+                                    .iter()
+                                    .any(|(_, lhs, rhs)| lhs == witness || rhs == witness)
+                                && is_unused_elsewhere(&use_counts, witness)
+                        })
+                    }
+                    Opcode::BlackBoxFuncCall(func_call) => {
+                        !has_side_effects(func_call)
+                            && used_witnesses(opcode)
+                                .iter()
+                                .all(|witness| is_unused_elsewhere(&use_counts, witness))
+                    }
+                    Opcode::Directive(Directive::ToLeRadix { b, .. }) => b.iter().all(|witness| {
+                        !interface_witnesses.contains(witness) && !use_counts.contains_key(witness)
+                    }),
+                    Opcode::BrilligCall { id, outputs, .. } => {
+                        let has_side_effects = self
+                            .brillig_functions
+                            .get(id.as_usize())
+                            .map_or(true, brillig_has_side_effects);
+                        let output_witnesses = brillig_output_witnesses(outputs);
+
+                        !has_side_effects
+                            && !output_witnesses.is_empty()
+                            && output_witnesses.iter().all(|witness| {
+                                !interface_witnesses.contains(witness)
+                                    && !use_counts.contains_key(witness)
+                            })
+                    }
+                    Opcode::MemoryInit { .. } | Opcode::MemoryOp { .. } | Opcode::Call { .. } => {
+                        false
+                    }
+                };
+
+                if is_dead {
+                    for witness in used_witnesses(opcode) {
+                        if let Some(count) = use_counts.get_mut(&witness) {
+                            *count -= 1;
+                            if *count == 0 {
+                                use_counts.remove(&witness);
+                            }
+                        }
+                    }
+                    *is_removed = true;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let mut new_order_list = Vec::with_capacity(order_list.len());
+        let mut new_opcodes = Vec::with_capacity(self.circuit.opcodes.len());
+        for (idx, (opcode, is_removed)) in self.circuit.opcodes.into_iter().zip(removed).enumerate()
+        {
+            if !is_removed {
+                new_order_list.push(order_list[idx]);
+                new_opcodes.push(opcode);
+            }
+        }
+
+        (Circuit { opcodes: new_opcodes, ..self.circuit }, new_order_list)
+    }
+
+    /// Returns the witnesses which make up the circuit's interface. These are always live.
+    fn interface_witnesses(&self) -> BTreeSet<Witness> {
+        let mut witnesses: BTreeSet<Witness> = self
+            .circuit
+            .private_parameters
+            .union(&self.circuit.public_parameters.0)
+            .chain(&self.circuit.return_values.0)
+            .copied()
+            .collect();
+
+        for (_, payload) in &self.circuit.assert_messages {
+            if let AssertionPayload::Dynamic(_, payload) = payload {
+                for item in payload {
+                    if let ExpressionOrMemory::Expression(expr) = item {
+                        witnesses.extend(expression_witnesses(expr));
+                    }
+                }
+            }
+        }
+
+        witnesses
+    }
+}
+
+/// Returns whether `func_call` affects state outside of its own inputs and outputs.
+fn has_side_effects<F>(func_call: &BlackBoxFuncCall<F>) -> bool {
+    matches!(
+        func_call,
+        BlackBoxFuncCall::RecursiveAggregation { .. }
+            | BlackBoxFuncCall::BigIntAdd { .. }
+            | BlackBoxFuncCall::BigIntSub { .. }
+            | BlackBoxFuncCall::BigIntMul { .. }
+            | BlackBoxFuncCall::BigIntDiv { .. }
+            | BlackBoxFuncCall::BigIntFromLeBytes { .. }
+            | BlackBoxFuncCall::BigIntToLeBytes { .. }
+    )
+}
+
+/// Returns whether executing `function` can have an effect other than assigning its outputs, either by making
+/// a foreign call or by failing. Removing a call which can fail would allow execution to succeed where it
+/// previously failed, e.g. when an `assert` in an unconstrained function is violated.
+fn brillig_has_side_effects<F>(function: &BrilligBytecode<F>) -> bool {
+    function.bytecode.iter().any(|opcode| match opcode {
+        BrilligOpcode::ForeignCall { .. }
+        | BrilligOpcode::Trap { .. }
+        | BrilligOpcode::BlackBox(_) => true,
+        BrilligOpcode::BinaryFieldOp { op, .. } => *op == BinaryFieldOp::IntegerDiv,
+        BrilligOpcode::BinaryIntOp { op, .. } => *op == BinaryIntOp::Div,
+        _ => false,
+    })
+}
+
+/// Returns the set of witnesses which `opcode` uses.
+///
+/// The outputs of Brillig calls and directives are not included as these opcodes only assign them a value.
+fn used_witnesses<F: AcirField>(opcode: &Opcode<F>) -> BTreeSet<Witness> {
+    let mut witnesses = BTreeSet::new();
+    match opcode {
+        Opcode::AssertZero(expr) => witnesses.extend(expression_witnesses(expr)),
+        Opcode::BlackBoxFuncCall(func_call) => {
+            witnesses.extend(func_call.get_inputs_vec().into_iter().filter_map(|input| {
+                match input.input {
+                    ConstantOrWitnessEnum::Witness(witness) => Some(witness),
+                    ConstantOrWitnessEnum::Constant(_) => None,
+                }
+            }));
+            witnesses.extend(func_call.get_outputs_vec());
+        }
+        Opcode::Directive(Directive::ToLeRadix { a, .. }) => {
+            witnesses.extend(expression_witnesses(a));
+        }
+        Opcode::MemoryInit { init, .. } => witnesses.extend(init),
+        Opcode::MemoryOp { op, predicate, .. } => {
+            witnesses.extend(expression_witnesses(&op.operation));
+            witnesses.extend(expression_witnesses(&op.index));
+            witnesses.extend(expression_witnesses(&op.value));
+            if let Some(predicate) = predicate {
+                witnesses.extend(expression_witnesses(predicate));
+            }
+        }
+        Opcode::BrilligCall { inputs, predicate, .. } => {
+            for input in inputs {
+                match input {
+                    BrilligInputs::Single(expr) => witnesses.extend(expression_witnesses(expr)),
+                    BrilligInputs::Array(expr_array) => {
+                        for expr in expr_array {
+                            witnesses.extend(expression_witnesses(expr));
+                        }
+                    }
+                    BrilligInputs::MemoryArray(_) => (),
+                }
+            }
+            if let Some(predicate) = predicate {
+                witnesses.extend(expression_witnesses(predicate));
+            }
+        }
+        Opcode::Call { inputs, outputs, predicate, .. } => {
+            witnesses.extend(inputs);
+            witnesses.extend(outputs);
+            if let Some(predicate) = predicate {
+                witnesses.extend(expression_witnesses(predicate));
+            }
+        }
+    }
+    witnesses
+}
+
+fn brillig_output_witnesses(outputs: &[BrilligOutputs]) -> Vec<Witness> {
+    outputs
+        .iter()
+        .flat_map(|output| match output {
+            BrilligOutputs::Simple(witness) => vec![*witness],
+            BrilligOutputs::Array(witness_array) => witness_array.clone(),
+        })
+        .collect()
+}
+
+fn expression_witnesses<F>(expr: &Expression<F>) -> impl Iterator<Item = Witness> + '_ {
+    expr.linear_combinations
+        .iter()
+        .map(|(_, witness)| *witness)
+        .chain(expr.mul_terms.iter().flat_map(|(_, lhs, rhs)| [*lhs, *rhs]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::compiler::optimizers::dead_code::DeadCodeOptimizer;
+    use acir::{
+        brillig::{BitSize, HeapArray, MemoryAddress, Opcode as BrilligOpcode},
+        circuit::{
+            brillig::{BrilligBytecode, BrilligFunctionId, BrilligInputs, BrilligOutputs},
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            Circuit, ExpressionWidth, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+
+    fn test_circuit(opcodes: Vec<Opcode<FieldElement>>) -> Circuit<FieldElement> {
+        Circuit {
+            current_witness_index: 4,
+            expression_width: ExpressionWidth::Bounded { width: 4 },
+            opcodes,
+            private_parameters: BTreeSet::from([Witness(0)]),
+            public_parameters: PublicInputs::default(),
+            return_values: PublicInputs(BTreeSet::from([Witness(1)])),
+            assert_messages: Default::default(),
+            recursive: false,
+        }
+    }
+
+    fn constant_brillig_function() -> BrilligBytecode<FieldElement> {
+        BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::Const {
+                    destination: MemoryAddress(0),
+                    bit_size: BitSize::Field,
+                    value: FieldElement::from(1u128),
+                },
+                BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 1 },
+            ],
+        }
+    }
+
+    fn brillig_call(output: Witness) -> Opcode<FieldElement> {
+        Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![BrilligInputs::Single(Witness(0).into())],
+            outputs: vec![BrilligOutputs::Simple(output)],
+            predicate: None,
+        }
+    }
+
+    fn range(witness: Witness) -> Opcode<FieldElement> {
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput::witness(witness, 8),
+        })
+    }
+
+    fn w1_equals_w0() -> Opcode<FieldElement> {
+        Opcode::AssertZero(&Expression::from(Witness(1)) - &Expression::from(Witness(0)))
+    }
+
+    #[test]
+    fn removes_unconstrained_brillig_output_and_its_range_check() {
+        // w2 = brillig(w0)
+        // RANGE(w2, 8)
+        // w1 - w0 = 0
+        let opcodes = vec![brillig_call(Witness(2)), range(Witness(2)), w1_equals_w0()];
+        let circuit = test_circuit(opcodes);
+        let brillig_functions = [constant_brillig_function()];
+
+        let (optimized_circuit, new_positions) =
+            DeadCodeOptimizer::new(circuit, &brillig_functions).eliminate_dead_code(vec![0, 1, 2]);
+
+        assert_eq!(optimized_circuit.opcodes, vec![w1_equals_w0()]);
+        assert_eq!(new_positions, vec![2]);
+    }
+
+    #[test]
+    fn removes_constraint_on_dead_witness() {
+        // w2 = brillig(w0)
+        // w3 - w2 * w0 = 0
+        // w1 - w0 = 0
+        let dead_constraint = Opcode::AssertZero(Expression {
+            mul_terms: vec![(-FieldElement::from(1u128), Witness(2), Witness(0))],
+            linear_combinations: vec![(FieldElement::from(1u128), Witness(3))],
+            q_c: FieldElement::from(0u128),
+        });
+        let opcodes = vec![brillig_call(Witness(2)), dead_constraint, w1_equals_w0()];
+        let circuit = test_circuit(opcodes);
+        let brillig_functions = [constant_brillig_function()];
+
+        let (optimized_circuit, new_positions) =
+            DeadCodeOptimizer::new(circuit, &brillig_functions).eliminate_dead_code(vec![0, 1, 2]);
+
+        assert_eq!(optimized_circuit.opcodes, vec![w1_equals_w0()]);
+        assert_eq!(new_positions, vec![2]);
+    }
+
+    #[test]
+    fn keeps_live_opcodes() {
+        // w2 = brillig(w0)
+        // RANGE(w2, 8)
+        // w1 - w2 = 0
+        let constraint =
+            Opcode::AssertZero(&Expression::from(Witness(1)) - &Expression::from(Witness(2)));
+        let opcodes = vec![brillig_call(Witness(2)), range(Witness(2)), constraint];
+        let circuit = test_circuit(opcodes.clone());
+        let brillig_functions = [constant_brillig_function()];
+
+        let (optimized_circuit, _) =
+            DeadCodeOptimizer::new(circuit, &brillig_functions).eliminate_dead_code(vec![0, 1, 2]);
+
+        assert_eq!(optimized_circuit.opcodes, opcodes);
+    }
+
+    #[test]
+    fn keeps_brillig_calls_which_can_trap() {
+        // w2 = brillig(w0), which may trap
+        // w1 - w0 = 0
+        let trapping_brillig_function = BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::Trap { revert_data: HeapArray::default() },
+                BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 1 },
+            ],
+        };
+        let opcodes = vec![brillig_call(Witness(2)), w1_equals_w0()];
+        let circuit = test_circuit(opcodes.clone());
+        let brillig_functions = [trapping_brillig_function];
+
+        let (optimized_circuit, _) =
+            DeadCodeOptimizer::new(circuit, &brillig_functions).eliminate_dead_code(vec![0, 1]);
+
+        assert_eq!(optimized_circuit.opcodes, opcodes);
+    }
+
+    #[test]
+    fn keeps_range_checks_on_parameters() {
+        let opcodes = vec![range(Witness(0)), w1_equals_w0()];
+        let circuit = test_circuit(opcodes.clone());
+
+        let (optimized_circuit, _) =
+            DeadCodeOptimizer::new(circuit, &[]).eliminate_dead_code(vec![0, 1]);
+
+        assert_eq!(optimized_circuit.opcodes, opcodes);
+    }
+}
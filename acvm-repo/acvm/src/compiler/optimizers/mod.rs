@@ -1,25 +1,42 @@
 use acir::{
-    circuit::{Circuit, Opcode},
+    circuit::{brillig::BrilligBytecode, Circuit, Opcode},
     AcirField,
 };
 
-// mod constant_backpropagation;
+mod constant_backpropagation;
+mod dead_code;
 mod general;
 mod redundant_range;
 mod unused_memory;
+mod witness_merging;
 
 pub(crate) use general::GeneralOptimizer;
 pub(crate) use redundant_range::RangeOptimizer;
 use tracing::info;
+pub(crate) use witness_merging::WitnessMergingOptimizer;
 
-// use self::constant_backpropagation::ConstantBackpropagationOptimizer;
+use self::constant_backpropagation::ConstantBackpropagationOptimizer;
+use self::dead_code::DeadCodeOptimizer;
 use self::unused_memory::UnusedMemoryOptimizer;
 
 use super::{transform_assert_messages, AcirTransformationMap};
 
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] independent optimizations to a [`Circuit`].
+///
+/// Brillig calls are never evaluated at compile time. Use [`optimize_with_brillig`] to allow this.
 pub fn optimize<F: AcirField>(acir: Circuit<F>) -> (Circuit<F>, AcirTransformationMap) {
-    let (mut acir, new_opcode_positions) = optimize_internal(acir);
+    optimize_with_brillig(acir, &[])
+}
+
+/// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] independent optimizations to a [`Circuit`].
+///
+/// `brillig_functions` are the unconstrained functions of the program which `acir` belongs to. These are used
+/// to evaluate any Brillig calls whose inputs are known at compile time.
+pub fn optimize_with_brillig<F: AcirField>(
+    acir: Circuit<F>,
+    brillig_functions: &[BrilligBytecode<F>],
+) -> (Circuit<F>, AcirTransformationMap) {
+    let (mut acir, new_opcode_positions) = optimize_internal(acir, brillig_functions);
 
     let transformation_map = AcirTransformationMap::new(new_opcode_positions);
 
@@ -29,8 +46,11 @@ pub fn optimize<F: AcirField>(acir: Circuit<F>) -> (Circuit<F>, AcirTransformati
 }
 
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] independent optimizations to a [`Circuit`].
-#[tracing::instrument(level = "trace", name = "optimize_acir" skip(acir))]
-pub(super) fn optimize_internal<F: AcirField>(acir: Circuit<F>) -> (Circuit<F>, Vec<usize>) {
+#[tracing::instrument(level = "trace", name = "optimize_acir" skip(acir, brillig_functions))]
+pub(super) fn optimize_internal<F: AcirField>(
+    acir: Circuit<F>,
+    brillig_functions: &[BrilligBytecode<F>],
+) -> (Circuit<F>, Vec<usize>) {
     // Track original acir opcode positions throughout the transformation passes of the compilation
     // by applying the modifications done to the circuit opcodes and also to the opcode_positions (delete and insert)
     let acir_opcode_positions = (0..acir.opcodes.len()).collect();
@@ -56,21 +76,38 @@ pub(super) fn optimize_internal<F: AcirField>(acir: Circuit<F>) -> (Circuit<F>,
         .collect();
     let acir = Circuit { opcodes, ..acir };
 
+    // Witness merging pass
+    let (acir, acir_opcode_positions) =
+        WitnessMergingOptimizer::merge_witnesses(acir, acir_opcode_positions);
+
     // Unused memory optimization pass
     let memory_optimizer = UnusedMemoryOptimizer::new(acir);
     let (acir, acir_opcode_positions) =
         memory_optimizer.remove_unused_memory_initializations(acir_opcode_positions);
 
-    // let (acir, acir_opcode_positions) =
-    // ConstantBackpropagationOptimizer::backpropagate_constants(acir, acir_opcode_positions);
+    // Constant backpropagation pass
+    let (acir, acir_opcode_positions) = ConstantBackpropagationOptimizer::backpropagate_constants(
+        acir,
+        brillig_functions,
+        acir_opcode_positions,
+    );
 
     // Range optimization pass
     let range_optimizer = RangeOptimizer::new(acir);
     let (acir, acir_opcode_positions) =
         range_optimizer.replace_redundant_ranges(acir_opcode_positions);
 
-    // let (acir, acir_opcode_positions) =
-    // ConstantBackpropagationOptimizer::backpropagate_constants(acir, acir_opcode_positions);
+    // Removing redundant range constraints may allow further constants to be determined.
+    let (acir, acir_opcode_positions) = ConstantBackpropagationOptimizer::backpropagate_constants(
+        acir,
+        brillig_functions,
+        acir_opcode_positions,
+    );
+
+    // Dead code elimination pass
+    let dead_code_optimizer = DeadCodeOptimizer::new(acir, brillig_functions);
+    let (acir, acir_opcode_positions) =
+        dead_code_optimizer.eliminate_dead_code(acir_opcode_positions);
 
     info!("Number of opcodes after: {}", acir.opcodes.len());
 
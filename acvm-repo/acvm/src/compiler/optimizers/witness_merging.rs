@@ -0,0 +1,519 @@
+use std::collections::{BTreeSet, HashMap};
+
+use acir::{
+    circuit::{
+        brillig::{BrilligInputs, BrilligOutputs},
+        directives::Directive,
+        opcodes::ConstantOrWitnessEnum,
+        AssertionPayload, Circuit, ExpressionOrMemory, Opcode,
+    },
+    native_types::{Expression, Witness},
+    AcirField,
+};
+
+use super::GeneralOptimizer;
+
+/// `WitnessMergingOptimizer` searches the whole circuit for witnesses which are constrained to be
+/// (scalar multiples of) each other and replaces each such set of witnesses with a single witness.
+///
+/// Two witnesses are merged if either:
+/// - they are related by an `AssertZero` opcode of the form `a * w1 + b * w2 = 0`, or
+/// - they are each defined by an `AssertZero` opcode as a scalar multiple of the same expression,
+///   e.g. `x + y - w1 = 0` and `2 * x + 2 * y - w2 = 0` where `w2 = 2 * w1`.
+///
+/// The opcodes which relate the merged witnesses are then redundant and are removed. This allows
+/// intermediate witnesses which were created independently for identical expressions (e.g. by the
+/// CSAT transformer) to be reused across opcodes.
+///
+/// Witnesses which form part of the circuit's interface or which are used by opcodes which can only
+/// take witnesses (e.g. memory opcodes, blackbox function calls and ACIR calls) are never replaced.
+/// Replacing one witness with a multiple of another never increases the width of an expression.
+pub(crate) struct WitnessMergingOptimizer<F> {
+    circuit: Circuit<F>,
+}
+
+impl<F: AcirField> WitnessMergingOptimizer<F> {
+    /// Creates a new `WitnessMergingOptimizer`
+    pub(crate) fn new(circuit: Circuit<F>) -> Self {
+        Self { circuit }
+    }
+
+    /// Returns a `Circuit` where each set of witnesses which are known to be (scalar multiples of) each other
+    /// has been replaced with a single witness, repeating until the circuit stabilizes.
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub(crate) fn merge_witnesses(
+        circuit: Circuit<F>,
+        order_list: Vec<usize>,
+    ) -> (Circuit<F>, Vec<usize>) {
+        let mut circuit = circuit;
+        let mut order_list = order_list;
+        loop {
+            let old_circuit_size = circuit.opcodes.len();
+
+            let optimizer = WitnessMergingOptimizer::new(circuit);
+            (circuit, order_list) = optimizer.merge_witnesses_iteration(order_list);
+
+            if circuit.opcodes.len() >= old_circuit_size {
+                return (circuit, order_list);
+            }
+        }
+    }
+
+    /// Applies a single round of witness merging to a `Circuit`.
+    pub(crate) fn merge_witnesses_iteration(
+        mut self,
+        order_list: Vec<usize>,
+    ) -> (Circuit<F>, Vec<usize>) {
+        let opcodes = std::mem::take(&mut self.circuit.opcodes);
+        let pinned_witnesses = self.pinned_witnesses(&opcodes);
+
+        let mut substitutions = WitnessSubstitutions::new(&pinned_witnesses);
+        // Maps a normalized expression to the witness which it defines.
+        let mut definitions: HashMap<Expression<F>, Definition<F>> = HashMap::new();
+        let mut redundant_opcodes = vec![false; opcodes.len()];
+
+        for (opcode, redundant) in opcodes.iter().zip(redundant_opcodes.iter_mut()) {
+            let Opcode::AssertZero(expr) = opcode else {
+                continue;
+            };
+
+            if let Some((a, w1, b, w2)) = as_scaled_equality(expr) {
+                // a * w1 + b * w2 = 0 => w2 = (-a / b) * w1
+                *redundant = substitutions.merge(w2, -a / b, w1);
+                continue;
+            }
+
+            for &(coefficient, witness) in &expr.linear_combinations {
+                let Some((scale, normalized_rest)) = defining_expression(expr, witness) else {
+                    continue;
+                };
+
+                let Some(definition) = definitions.get(&normalized_rest).copied() else {
+                    definitions.insert(normalized_rest, Definition { scale, coefficient, witness });
+                    continue;
+                };
+
+                // We have `scale_1 * N + coefficient_1 * w1 = 0` and `scale_2 * N + coefficient_2 * w2 = 0`
+                // so `w2 = (scale_2 * coefficient_1) / (scale_1 * coefficient_2) * w1`.
+                let factor = (scale * definition.coefficient) / (definition.scale * coefficient);
+                if substitutions.merge(witness, factor, definition.witness) {
+                    // This opcode is now a multiple of the opcode which defined `definition.witness`.
+                    *redundant = true;
+                    break;
+                }
+            }
+        }
+
+        let mut new_order_list = Vec::with_capacity(order_list.len());
+        let mut new_opcodes = Vec::with_capacity(opcodes.len());
+        for (idx, (opcode, redundant)) in opcodes.into_iter().zip(redundant_opcodes).enumerate() {
+            if redundant {
+                continue;
+            }
+
+            let new_opcode = match opcode {
+                Opcode::AssertZero(expr) => {
+                    let new_expr = substitutions.substitute(expr);
+                    if new_expr.is_zero() {
+                        continue;
+                    }
+                    Opcode::AssertZero(new_expr)
+                }
+                Opcode::BrilligCall { id, inputs, outputs, predicate } => {
+                    let inputs = inputs
+                        .into_iter()
+                        .map(|input| match input {
+                            BrilligInputs::Single(expr) => {
+                                BrilligInputs::Single(substitutions.substitute(expr))
+                            }
+                            BrilligInputs::Array(expr_array) => BrilligInputs::Array(
+                                expr_array
+                                    .into_iter()
+                                    .map(|expr| substitutions.substitute(expr))
+                                    .collect(),
+                            ),
+                            input @ BrilligInputs::MemoryArray(_) => input,
+                        })
+                        .collect();
+                    let predicate = predicate.map(|predicate| substitutions.substitute(predicate));
+
+                    Opcode::BrilligCall { id, inputs, outputs, predicate }
+                }
+                Opcode::Directive(Directive::ToLeRadix { a, b, radix }) => {
+                    Opcode::Directive(Directive::ToLeRadix {
+                        a: substitutions.substitute(a),
+                        b,
+                        radix,
+                    })
+                }
+                Opcode::Call { id, inputs, outputs, predicate } => {
+                    let predicate = predicate.map(|predicate| substitutions.substitute(predicate));
+
+                    Opcode::Call { id, inputs, outputs, predicate }
+                }
+                Opcode::BlackBoxFuncCall(_)
+                | Opcode::MemoryOp { .. }
+                | Opcode::MemoryInit { .. } => opcode,
+            };
+
+            new_opcodes.push(new_opcode);
+            new_order_list.push(order_list[idx]);
+        }
+
+        self.circuit.opcodes = new_opcodes;
+
+        (self.circuit, new_order_list)
+    }
+
+    /// Returns the witnesses which cannot be replaced, either because they form part of the circuit's interface
+    /// or because they're used by an opcode which we can't rewrite.
+    fn pinned_witnesses(&self, opcodes: &[Opcode<F>]) -> BTreeSet<Witness> {
+        let mut witnesses: BTreeSet<Witness> = self
+            .circuit
+            .private_parameters
+            .union(&self.circuit.public_parameters.0)
+            .chain(&self.circuit.return_values.0)
+            .copied()
+            .collect();
+
+        // Assertion payloads are evaluated using the witness map so we must keep any witnesses they reference.
+        for (_, payload) in &self.circuit.assert_messages {
+            if let AssertionPayload::Dynamic(_, payload) = payload {
+                for item in payload {
+                    if let ExpressionOrMemory::Expression(expr) = item {
+                        witnesses.extend(expression_witnesses(expr));
+                    }
+                }
+            }
+        }
+
+        for opcode in opcodes {
+            match opcode {
+                Opcode::BlackBoxFuncCall(func_call) => {
+                    witnesses.extend(func_call.get_inputs_vec().into_iter().filter_map(|input| {
+                        match input.input {
+                            ConstantOrWitnessEnum::Witness(witness) => Some(witness),
+                            ConstantOrWitnessEnum::Constant(_) => None,
+                        }
+                    }));
+                    witnesses.extend(func_call.get_outputs_vec());
+                }
+                Opcode::Directive(Directive::ToLeRadix { b, .. }) => {
+                    witnesses.extend(b);
+                }
+                Opcode::MemoryInit { init, .. } => {
+                    witnesses.extend(init);
+                }
+                Opcode::MemoryOp { op, predicate, .. } => {
+                    witnesses.extend(expression_witnesses(&op.operation));
+                    witnesses.extend(expression_witnesses(&op.index));
+                    witnesses.extend(expression_witnesses(&op.value));
+                    if let Some(predicate) = predicate {
+                        witnesses.extend(expression_witnesses(predicate));
+                    }
+                }
+                Opcode::BrilligCall { outputs, .. } => {
+                    for output in outputs {
+                        match output {
+                            BrilligOutputs::Simple(witness) => {
+                                witnesses.insert(*witness);
+                            }
+                            BrilligOutputs::Array(witness_array) => {
+                                witnesses.extend(witness_array);
+                            }
+                        }
+                    }
+                }
+                Opcode::Call { inputs, outputs, .. } => {
+                    witnesses.extend(inputs);
+                    witnesses.extend(outputs);
+                }
+                Opcode::AssertZero(_) => (),
+            }
+        }
+
+        witnesses
+    }
+}
+
+/// A witness `witness` defined by the constraint `scale * N + coefficient * witness = 0`
+/// where `N` is a normalized expression.
+#[derive(Clone, Copy)]
+struct Definition<F> {
+    scale: F,
+    coefficient: F,
+    witness: Witness,
+}
+
+/// Tracks the substitutions of witnesses by scalar multiples of other witnesses.
+///
+/// This is a union-find structure where each witness points to a scalar multiple of its parent.
+struct WitnessSubstitutions<'a, F> {
+    pinned_witnesses: &'a BTreeSet<Witness>,
+    parents: HashMap<Witness, (F, Witness)>,
+}
+
+impl<'a, F: AcirField> WitnessSubstitutions<'a, F> {
+    fn new(pinned_witnesses: &'a BTreeSet<Witness>) -> Self {
+        Self { pinned_witnesses, parents: HashMap::new() }
+    }
+
+    /// Returns `(factor, root)` such that `witness = factor * root`.
+    fn resolve(&self, witness: Witness) -> (F, Witness) {
+        let mut factor = F::one();
+        let mut current = witness;
+        while let Some((parent_factor, parent)) = self.parents.get(&current) {
+            factor = factor * *parent_factor;
+            current = *parent;
+        }
+        (factor, current)
+    }
+
+    /// Records that `witness = factor * other`.
+    ///
+    /// Returns `false` if this relation can't be used to replace either witness, either because they have already
+    /// been merged or because both witnesses are pinned.
+    fn merge(&mut self, witness: Witness, factor: F, other: Witness) -> bool {
+        if factor.is_zero() {
+            return false;
+        }
+
+        let (witness_factor, witness_root) = self.resolve(witness);
+        let (other_factor, other_root) = self.resolve(other);
+        if witness_root == other_root {
+            return false;
+        }
+
+        // witness_factor * witness_root = factor * other_factor * other_root
+        let relative_factor = factor * other_factor / witness_factor;
+
+        // Where possible we keep the earlier witness as this is usually the one which is solved first.
+        let replace_witness_root = match (
+            self.pinned_witnesses.contains(&witness_root),
+            self.pinned_witnesses.contains(&other_root),
+        ) {
+            (true, true) => return false,
+            (false, true) => true,
+            (true, false) => false,
+            (false, false) => witness_root > other_root,
+        };
+
+        if replace_witness_root {
+            self.parents.insert(witness_root, (relative_factor, other_root));
+        } else {
+            self.parents.insert(other_root, (relative_factor.inverse(), witness_root));
+        }
+        true
+    }
+
+    /// Replaces every merged witness in `expr` with the witness it was merged into.
+    fn substitute(&self, expr: Expression<F>) -> Expression<F> {
+        let needs_substitution =
+            expression_witnesses(&expr).iter().any(|witness| self.parents.contains_key(witness));
+        if !needs_substitution {
+            return expr;
+        }
+
+        let mul_terms = expr
+            .mul_terms
+            .into_iter()
+            .map(|(scale, lhs, rhs)| {
+                let (lhs_factor, lhs) = self.resolve(lhs);
+                let (rhs_factor, rhs) = self.resolve(rhs);
+                (scale * lhs_factor * rhs_factor, lhs, rhs)
+            })
+            .collect();
+        let linear_combinations = expr
+            .linear_combinations
+            .into_iter()
+            .map(|(scale, witness)| {
+                let (factor, witness) = self.resolve(witness);
+                (scale * factor, witness)
+            })
+            .collect();
+
+        let mut new_expr = GeneralOptimizer::optimize(Expression {
+            mul_terms,
+            linear_combinations,
+            q_c: expr.q_c,
+        });
+        new_expr.sort();
+        new_expr
+    }
+}
+
+/// Returns `(a, w1, b, w2)` if `expr` is of the form `a * w1 + b * w2 = 0`.
+fn as_scaled_equality<F: AcirField>(expr: &Expression<F>) -> Option<(F, Witness, F, Witness)> {
+    match expr.linear_combinations.as_slice() {
+        [(a, w1), (b, w2)]
+            if expr.mul_terms.is_empty() && expr.q_c.is_zero() && w1 != w2 && !b.is_zero() =>
+        {
+            Some((*a, *w1, *b, *w2))
+        }
+        _ => None,
+    }
+}
+
+/// Treats `expr` as the definition of `witness`, i.e. `expr = rest + coefficient * witness`.
+///
+/// Returns `rest` normalized by its first coefficient along with that coefficient, or `None` if `witness`
+/// can't be defined by `expr`.
+fn defining_expression<F: AcirField>(
+    expr: &Expression<F>,
+    witness: Witness,
+) -> Option<(F, Expression<F>)> {
+    let appears_in_mul_term =
+        expr.mul_terms.iter().any(|(_, lhs, rhs)| *lhs == witness || *rhs == witness);
+    if appears_in_mul_term {
+        return None;
+    }
+
+    let mut rest = expr.clone();
+    rest.linear_combinations.retain(|(_, term_witness)| *term_witness != witness);
+    let mut rest = GeneralOptimizer::optimize(rest);
+    if rest.is_const() {
+        return None;
+    }
+
+    rest.sort();
+    let scale = if let Some((coefficient, _)) = rest.linear_combinations.first() {
+        *coefficient
+    } else {
+        rest.mul_terms[0].0
+    };
+    Some((scale, &rest * scale.inverse()))
+}
+
+fn expression_witnesses<F>(expr: &Expression<F>) -> Vec<Witness> {
+    expr.linear_combinations
+        .iter()
+        .map(|(_, witness)| *witness)
+        .chain(expr.mul_terms.iter().flat_map(|(_, lhs, rhs)| [*lhs, *rhs]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::compiler::optimizers::witness_merging::WitnessMergingOptimizer;
+    use acir::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            Circuit, ExpressionWidth, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+
+    fn test_circuit(
+        opcodes: Vec<Opcode<FieldElement>>,
+        return_values: &[u32],
+    ) -> Circuit<FieldElement> {
+        Circuit {
+            current_witness_index: 10,
+            expression_width: ExpressionWidth::Bounded { width: 4 },
+            opcodes,
+            private_parameters: BTreeSet::from([Witness(0), Witness(1)]),
+            public_parameters: PublicInputs::default(),
+            return_values: PublicInputs(return_values.iter().copied().map(Witness).collect()),
+            assert_messages: Default::default(),
+            recursive: false,
+        }
+    }
+
+    fn expr(linear_combinations: &[(i128, u32)], q_c: i128) -> Expression<FieldElement> {
+        Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: linear_combinations
+                .iter()
+                .map(|(scale, witness)| (FieldElement::from(*scale), Witness(*witness)))
+                .collect(),
+            q_c: FieldElement::from(q_c),
+        }
+    }
+
+    #[test]
+    fn merges_equal_witnesses() {
+        // w2 - w0 - w1 = 0
+        // w3 - w2 = 0
+        // w4 - w3 - 1 = 0
+        let opcodes = vec![
+            Opcode::AssertZero(expr(&[(1, 2), (-1, 0), (-1, 1)], 0)),
+            Opcode::AssertZero(expr(&[(1, 3), (-1, 2)], 0)),
+            Opcode::AssertZero(expr(&[(1, 4), (-1, 3)], -1)),
+        ];
+        let circuit = test_circuit(opcodes, &[4]);
+
+        let (optimized_circuit, new_positions) =
+            WitnessMergingOptimizer::merge_witnesses(circuit, vec![0, 1, 2]);
+
+        let mut expected_use = expr(&[(-1, 2), (1, 4)], -1);
+        expected_use.sort();
+        assert_eq!(
+            optimized_circuit.opcodes,
+            vec![
+                Opcode::AssertZero(expr(&[(1, 2), (-1, 0), (-1, 1)], 0)),
+                Opcode::AssertZero(expected_use),
+            ]
+        );
+        assert_eq!(new_positions, vec![0, 2]);
+    }
+
+    #[test]
+    fn reuses_witness_for_scaled_identical_expressions() {
+        // w2 - w0 - w1 = 0
+        // w3 - 2 * w0 - 2 * w1 = 0
+        // w4 - w2 - w3 = 0
+        let opcodes = vec![
+            Opcode::AssertZero(expr(&[(1, 2), (-1, 0), (-1, 1)], 0)),
+            Opcode::AssertZero(expr(&[(1, 3), (-2, 0), (-2, 1)], 0)),
+            Opcode::AssertZero(expr(&[(1, 4), (-1, 2), (-1, 3)], 0)),
+        ];
+        let circuit = test_circuit(opcodes, &[4]);
+
+        // Further iterations would go on to merge `w2` into `w4`, so we only check the first one.
+        let (optimized_circuit, new_positions) =
+            WitnessMergingOptimizer::new(circuit).merge_witnesses_iteration(vec![0, 1, 2]);
+
+        // `w3` is replaced with `2 * w2` so the final opcode becomes `w4 - 3 * w2 = 0`.
+        let mut expected_use = expr(&[(-3, 2), (1, 4)], 0);
+        expected_use.sort();
+        assert_eq!(
+            optimized_circuit.opcodes,
+            vec![
+                Opcode::AssertZero(expr(&[(1, 2), (-1, 0), (-1, 1)], 0)),
+                Opcode::AssertZero(expected_use),
+            ]
+        );
+        assert_eq!(new_positions, vec![0, 2]);
+    }
+
+    #[test]
+    fn keeps_equality_between_pinned_witnesses() {
+        // w0 - w1 = 0
+        let opcodes = vec![Opcode::AssertZero(expr(&[(1, 0), (-1, 1)], 0))];
+        let circuit = test_circuit(opcodes.clone(), &[]);
+
+        let (optimized_circuit, _) = WitnessMergingOptimizer::merge_witnesses(circuit, vec![0]);
+
+        assert_eq!(optimized_circuit.opcodes, opcodes);
+    }
+
+    #[test]
+    fn does_not_replace_blackbox_inputs() {
+        // w2 - w0 = 0
+        // RANGE(w2, 8)
+        let opcodes = vec![
+            Opcode::AssertZero(expr(&[(1, 2), (-1, 0)], 0)),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                input: FunctionInput::witness(Witness(2), 8),
+            }),
+        ];
+        let circuit = test_circuit(opcodes.clone(), &[]);
+
+        let (optimized_circuit, _) = WitnessMergingOptimizer::merge_witnesses(circuit, vec![0, 1]);
+
+        assert_eq!(optimized_circuit.opcodes, opcodes);
+    }
+}
@@ -6,10 +6,13 @@ use acir::{
 };
 use indexmap::IndexMap;
 
+use super::gate_shape::{fits_in_one_gate, GateShape};
+
 /// A transformer which processes any [`Expression`]s to break them up such that they
 /// fit within the [`ProofSystemCompiler`][crate::ProofSystemCompiler]'s width.
 ///
-/// This transformer is only used when targeting the [`Bounded`][crate::ExpressionWidth::Bounded] configuration.
+/// This transformer is only used when targeting the [`Bounded`][crate::ExpressionWidth::Bounded] configuration
+/// or a custom [`GateShape`].
 ///
 /// This is done by creating intermediate variables to hold partial calculations and then combining them
 /// to calculate the original expression.
@@ -17,16 +20,23 @@ use indexmap::IndexMap;
 // Have a single transformer that you instantiate with a width, then pass many opcodes through
 pub(crate) struct CSatTransformer {
     width: usize,
+    /// The maximum number of multiplication terms in a single opcode
+    max_mul_terms: usize,
     /// Track the witness that can be solved
     solvable_witness: HashSet<Witness>,
 }
 
 impl CSatTransformer {
-    // Configure the width for the optimizer
-    pub(crate) fn new(width: usize) -> CSatTransformer {
+    // Configure the optimizer to target the given gate shape
+    pub(crate) fn new_for_gate_shape(gate_shape: &dyn GateShape) -> CSatTransformer {
+        Self::with_max_mul_terms(gate_shape.linear_terms(), gate_shape.mul_terms())
+    }
+
+    fn with_max_mul_terms(width: usize, max_mul_terms: usize) -> CSatTransformer {
         assert!(width > 2);
+        assert!(max_mul_terms > 0);
 
-        CSatTransformer { width, solvable_witness: HashSet::new() }
+        CSatTransformer { width, max_mul_terms, solvable_witness: HashSet::new() }
     }
 
     /// Check if the equation 'expression=0' can be solved, and if yes, add the solved witness to set of solvable witness
@@ -122,7 +132,7 @@ impl CSatTransformer {
         // Either it can be represented in a single arithmetic equation or it's fan-in is too large and we need intermediate variables for those
         // large-fan-in optimization is not this algorithms purpose.
         // If the opcode has 0 mul terms, then it is an add opcode and similarly it can either fit into a single assert-zero opcode or it has a large fan-in
-        if opcode.mul_terms.len() <= 1 {
+        if opcode.mul_terms.len() <= self.max_mul_terms {
             return opcode;
         }
 
@@ -326,34 +336,48 @@ impl CSatTransformer {
 
         // First check if this polynomial actually needs a partial opcode optimization
         // There is the chance that it fits perfectly within the assert-zero opcode
-        if fits_in_one_identity(&opcode, self.width) {
+        if fits_in_one_gate(&opcode, self.width, self.max_mul_terms) {
             return opcode;
         }
 
         // 2. Create Intermediate variables for the multiplication opcodes
+        // If the gate shape allows for multiple multiplication terms then we squash as many of these as will fit
+        // into each intermediate variable.
         let mut remaining_mul_terms = Vec::with_capacity(opcode.mul_terms.len());
+        let mut intermediate_opcode = Expression::default();
         for mul_term in opcode.mul_terms {
             if self.solvable_witness.contains(&mul_term.1)
                 && self.solvable_witness.contains(&mul_term.2)
             {
-                let mut intermediate_opcode = Expression::default();
-
-                // Push mul term into the opcode
-                intermediate_opcode.mul_terms.push(mul_term);
-                // Get an intermediate variable which squashes the multiplication term
-                let inter_var = Self::get_or_create_intermediate_vars(
-                    intermediate_variables,
-                    intermediate_opcode,
-                    num_witness,
-                );
-
-                // Add intermediate variable as a part of the fan-in for the original opcode
-                opcode.linear_combinations.push(inter_var);
-                self.mark_solvable(inter_var.1);
+                let mut extended_opcode = intermediate_opcode.clone();
+                extended_opcode.mul_terms.push(mul_term);
+
+                // We need to leave space for the intermediate variable.
+                let is_full = extended_opcode.mul_terms.len() > self.max_mul_terms
+                    || extended_opcode.width() >= self.width;
+                if is_full && !intermediate_opcode.mul_terms.is_empty() {
+                    self.squash_mul_terms(
+                        std::mem::take(&mut intermediate_opcode),
+                        &mut opcode.linear_combinations,
+                        intermediate_variables,
+                        num_witness,
+                    );
+                    intermediate_opcode.mul_terms.push(mul_term);
+                } else {
+                    intermediate_opcode = extended_opcode;
+                }
             } else {
                 remaining_mul_terms.push(mul_term);
             }
         }
+        if !intermediate_opcode.mul_terms.is_empty() {
+            self.squash_mul_terms(
+                intermediate_opcode,
+                &mut opcode.linear_combinations,
+                intermediate_variables,
+                num_witness,
+            );
+        }
 
         // Remove all of the mul terms as we have intermediate variables to represent them now
         opcode.mul_terms = remaining_mul_terms;
@@ -407,21 +431,33 @@ impl CSatTransformer {
         opcode.linear_combinations.extend(added);
         self.partial_opcode_scan_optimization(opcode, intermediate_variables, num_witness)
     }
-}
-
-/// Checks if this expression can fit into one arithmetic identity
-fn fits_in_one_identity<F: AcirField>(expr: &Expression<F>, width: usize) -> bool {
-    // A Polynomial with more than one mul term cannot fit into one opcode
-    if expr.mul_terms.len() > 1 {
-        return false;
-    };
 
-    expr.width() <= width
+    /// Replaces the multiplication terms in `intermediate_opcode` with an intermediate variable
+    /// which is added to `linear_combinations`.
+    fn squash_mul_terms<F: AcirField>(
+        &mut self,
+        intermediate_opcode: Expression<F>,
+        linear_combinations: &mut Vec<(F, Witness)>,
+        intermediate_variables: &mut IndexMap<Expression<F>, (F, Witness)>,
+        num_witness: &mut u32,
+    ) {
+        // Get an intermediate variable which squashes the multiplication terms
+        let inter_var = Self::get_or_create_intermediate_vars(
+            intermediate_variables,
+            intermediate_opcode,
+            num_witness,
+        );
+
+        // Add intermediate variable as a part of the fan-in for the original opcode
+        linear_combinations.push(inter_var);
+        self.mark_solvable(inter_var.1);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compiler::transformers::BoundedGateShape;
     use acir::{AcirField, FieldElement};
 
     #[test]
@@ -450,7 +486,7 @@ mod tests {
 
         let mut num_witness = 4;
 
-        let mut optimizer = CSatTransformer::new(3);
+        let mut optimizer = CSatTransformer::new_for_gate_shape(&BoundedGateShape::plonk(3));
         optimizer.mark_solvable(b);
         optimizer.mark_solvable(c);
         optimizer.mark_solvable(d);
@@ -516,7 +552,7 @@ mod tests {
 
         let mut num_witness = 4;
 
-        let mut optimizer = CSatTransformer::new(3);
+        let mut optimizer = CSatTransformer::new_for_gate_shape(&BoundedGateShape::plonk(3));
         optimizer.mark_solvable(a);
         optimizer.mark_solvable(c);
         optimizer.mark_solvable(d);
@@ -542,6 +578,41 @@ mod tests {
             ],
             q_c: FieldElement::zero(),
         };
-        assert!(fits_in_one_identity(&expr, 4));
+        assert!(fits_in_one_gate(&expr, 4, 1));
+    }
+
+    #[test]
+    fn groups_mul_terms_when_gate_shape_allows() {
+        let [a, b, c, d, e, f, g] = [0, 1, 2, 3, 4, 5, 6].map(Witness);
+
+        // a * b + c * d + e * f - g = 0
+        let opcode = Expression {
+            mul_terms: vec![
+                (FieldElement::one(), a, b),
+                (FieldElement::one(), c, d),
+                (FieldElement::one(), e, f),
+            ],
+            linear_combinations: vec![(-FieldElement::one(), g)],
+            q_c: FieldElement::zero(),
+        };
+
+        let mut intermediate_variables: IndexMap<
+            Expression<FieldElement>,
+            (FieldElement, Witness),
+        > = IndexMap::new();
+        let mut num_witness = 7;
+
+        let gate_shape = BoundedGateShape { linear_terms: 5, mul_terms: 2 };
+        let mut optimizer = CSatTransformer::new_for_gate_shape(&gate_shape);
+        for witness in [a, b, c, d, e, f] {
+            optimizer.mark_solvable(witness);
+        }
+        let got_optimized_opcode =
+            optimizer.transform(opcode, &mut intermediate_variables, &mut num_witness);
+
+        // `a * b + c * d` fits into a single intermediate variable, leaving `e * f` for a second.
+        assert_eq!(intermediate_variables.len(), 2);
+        assert!(got_optimized_opcode.mul_terms.is_empty());
+        assert_eq!(got_optimized_opcode.linear_combinations.len(), 3);
     }
 }
@@ -0,0 +1,209 @@
+use std::fmt::Display;
+
+use acir::{
+    circuit::{opcodes::BlackBoxFuncCall, Circuit, Opcode},
+    native_types::Expression,
+    AcirField,
+};
+
+/// Describes the arithmetic gates of the proving system which a [`Circuit`] is being compiled for.
+///
+/// The CSAT transformer uses this to split [`Opcode::AssertZero`] opcodes so that each of them fits into a single gate.
+pub trait GateShape {
+    /// The maximum number of distinct witnesses which a single gate can reference.
+    ///
+    /// Witnesses in multiplication terms count towards this unless they also appear in a linear term.
+    fn linear_terms(&self) -> usize;
+
+    /// The maximum number of multiplication terms in a single gate.
+    ///
+    /// The transformed circuit's [`ExpressionWidth`][acir::circuit::ExpressionWidth] only records the number of
+    /// linear terms, so this bound is not recorded in the circuit.
+    fn mul_terms(&self) -> usize;
+}
+
+/// A [`GateShape`] with fixed bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundedGateShape {
+    pub linear_terms: usize,
+    pub mul_terms: usize,
+}
+
+impl BoundedGateShape {
+    /// A PLONK-style gate with `width` wires and a single multiplication term.
+    ///
+    /// This is the gate shape targeted by [`ExpressionWidth::Bounded`][acir::circuit::ExpressionWidth::Bounded].
+    pub fn plonk(width: usize) -> Self {
+        BoundedGateShape { linear_terms: width, mul_terms: 1 }
+    }
+}
+
+impl GateShape for BoundedGateShape {
+    fn linear_terms(&self) -> usize {
+        self.linear_terms
+    }
+
+    fn mul_terms(&self) -> usize {
+        self.mul_terms
+    }
+}
+
+/// Checks if `expr` can fit into a single gate of the given shape.
+pub(crate) fn fits_in_one_gate<F: AcirField>(
+    expr: &Expression<F>,
+    linear_terms: usize,
+    mul_terms: usize,
+) -> bool {
+    expr.mul_terms.len() <= mul_terms && expr.width() <= linear_terms
+}
+
+/// An estimate of the cost of proving a [`Circuit`] using a proving system with a given [`GateShape`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CostReport {
+    /// The number of gates used by [`Opcode::AssertZero`] opcodes.
+    pub arithmetic_gates: usize,
+    /// The number of [`Opcode::AssertZero`] opcodes which don't fit into a single gate.
+    pub oversized_expressions: usize,
+    /// The number of range checks.
+    ///
+    /// How these are proven depends on the proving system, e.g. with a lookup table or by decomposing the value into bits.
+    pub range_checks: usize,
+    /// The total number of bits constrained by range checks.
+    pub range_check_bits: usize,
+    /// The number of other black box function calls.
+    pub black_box_calls: usize,
+    /// The number of memory opcodes.
+    pub memory_opcodes: usize,
+}
+
+impl CostReport {
+    /// Estimates the cost of proving `circuit` using the gates described by `gate_shape`.
+    pub fn new<F: AcirField>(circuit: &Circuit<F>, gate_shape: &dyn GateShape) -> Self {
+        let mut report = CostReport::default();
+        for opcode in &circuit.opcodes {
+            match opcode {
+                Opcode::AssertZero(expr) => {
+                    report.arithmetic_gates += 1;
+                    if !fits_in_one_gate(expr, gate_shape.linear_terms(), gate_shape.mul_terms()) {
+                        report.oversized_expressions += 1;
+                    }
+                }
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }) => {
+                    report.range_checks += 1;
+                    report.range_check_bits += input.num_bits() as usize;
+                }
+                Opcode::BlackBoxFuncCall(_) => report.black_box_calls += 1,
+                Opcode::MemoryInit { .. } | Opcode::MemoryOp { .. } => report.memory_opcodes += 1,
+                // Directives and Brillig calls are unconstrained and ACIR calls are proven separately.
+                Opcode::Directive(_) | Opcode::BrilligCall { .. } | Opcode::Call { .. } => (),
+            }
+        }
+        report
+    }
+}
+
+impl Display for CostReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "arithmetic gates: {}", self.arithmetic_gates)?;
+        if self.oversized_expressions > 0 {
+            writeln!(f, "expressions exceeding gate shape: {}", self.oversized_expressions)?;
+        }
+        writeln!(f, "range checks: {} ({} bits)", self.range_checks, self.range_check_bits)?;
+        writeln!(f, "black box calls: {}", self.black_box_calls)?;
+        write!(f, "memory opcodes: {}", self.memory_opcodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            Circuit, ExpressionWidth, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+        AcirField, FieldElement,
+    };
+
+    use super::{BoundedGateShape, CostReport, GateShape};
+    use crate::compiler::transformers::transform_to_gate_shape;
+
+    fn test_circuit(opcodes: Vec<Opcode<FieldElement>>) -> Circuit<FieldElement> {
+        Circuit {
+            current_witness_index: 6,
+            expression_width: ExpressionWidth::Unbounded,
+            opcodes,
+            private_parameters: BTreeSet::from_iter((0..6).map(Witness)),
+            public_parameters: PublicInputs::default(),
+            return_values: PublicInputs::default(),
+            assert_messages: Default::default(),
+            recursive: false,
+        }
+    }
+
+    #[test]
+    fn counts_range_checks_and_their_bits() {
+        let circuit = test_circuit(vec![
+            Opcode::AssertZero(Expression {
+                mul_terms: vec![(FieldElement::one(), Witness(0), Witness(1))],
+                linear_combinations: vec![(-FieldElement::one(), Witness(2))],
+                q_c: FieldElement::zero(),
+            }),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                input: FunctionInput::witness(Witness(2), 8),
+            }),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                input: FunctionInput::witness(Witness(3), 32),
+            }),
+        ]);
+
+        let report = CostReport::new(&circuit, &BoundedGateShape::plonk(3));
+        assert_eq!(
+            report,
+            CostReport {
+                arithmetic_gates: 1,
+                range_checks: 2,
+                range_check_bits: 40,
+                ..CostReport::default()
+            }
+        );
+    }
+
+    #[test]
+    fn transformed_opcodes_fit_the_mul_terms_of_the_gate_shape() {
+        // a * b + c * d + e * f + a + c + e - g = 0
+        let [a, b, c, d, e, f, g] = [0, 1, 2, 3, 4, 5, 6].map(Witness);
+        let circuit = test_circuit(vec![Opcode::AssertZero(Expression {
+            mul_terms: vec![
+                (FieldElement::one(), a, b),
+                (FieldElement::one(), c, d),
+                (FieldElement::one(), e, f),
+            ],
+            linear_combinations: vec![
+                (FieldElement::one(), a),
+                (FieldElement::one(), c),
+                (FieldElement::one(), e),
+                (-FieldElement::one(), g),
+            ],
+            q_c: FieldElement::zero(),
+        })]);
+
+        for gate_shape in [
+            BoundedGateShape::plonk(3),
+            BoundedGateShape::plonk(4),
+            BoundedGateShape { linear_terms: 5, mul_terms: 2 },
+            BoundedGateShape { linear_terms: 4, mul_terms: 3 },
+        ] {
+            let (transformed, _) = transform_to_gate_shape(circuit.clone(), &gate_shape);
+            for opcode in &transformed.opcodes {
+                let Opcode::AssertZero(expr) = opcode else { continue };
+                assert!(expr.mul_terms.len() <= gate_shape.mul_terms(), "{gate_shape:?}: {expr}");
+                assert!(expr.width() <= gate_shape.linear_terms(), "{gate_shape:?}: {expr}");
+            }
+            let report = CostReport::new(&transformed, &gate_shape);
+            assert_eq!(report.oversized_expressions, 0);
+        }
+    }
+}
@@ -6,10 +6,14 @@ use acir::{
 use indexmap::IndexMap;
 
 mod csat;
+mod gate_shape;
 
 pub(crate) use csat::CSatTransformer;
+pub use gate_shape::{BoundedGateShape, CostReport, GateShape};
 
-use super::{transform_assert_messages, AcirTransformationMap};
+use super::{
+    optimizers::WitnessMergingOptimizer, transform_assert_messages, AcirTransformationMap,
+};
 
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`].
 pub fn transform<F: AcirField>(
@@ -30,6 +34,23 @@ pub fn transform<F: AcirField>(
     (acir, transformation_map)
 }
 
+/// Transforms a [`Circuit`] such that each [`Opcode::AssertZero`] fits into a single gate of the given [`GateShape`].
+pub fn transform_to_gate_shape<F: AcirField>(
+    acir: Circuit<F>,
+    gate_shape: &dyn GateShape,
+) -> (Circuit<F>, AcirTransformationMap) {
+    let acir_opcode_positions = acir.opcodes.iter().enumerate().map(|(i, _)| i).collect();
+
+    let (mut acir, acir_opcode_positions) =
+        transform_to_gate_shape_internal(acir, gate_shape, acir_opcode_positions);
+
+    let transformation_map = AcirTransformationMap::new(acir_opcode_positions);
+
+    acir.assert_messages = transform_assert_messages(acir.assert_messages, &transformation_map);
+
+    (acir, transformation_map)
+}
+
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`].
 ///
 /// Accepts an injected `acir_opcode_positions` to allow transformations to be applied directly after optimizations.
@@ -39,18 +60,28 @@ pub(super) fn transform_internal<F: AcirField>(
     expression_width: ExpressionWidth,
     acir_opcode_positions: Vec<usize>,
 ) -> (Circuit<F>, Vec<usize>) {
-    let mut transformer = match &expression_width {
-        ExpressionWidth::Unbounded => {
-            return (acir, acir_opcode_positions);
-        }
-        ExpressionWidth::Bounded { width } => {
-            let mut csat = CSatTransformer::new(*width);
-            for value in acir.circuit_arguments() {
-                csat.mark_solvable(value);
-            }
-            csat
-        }
-    };
+    match expression_width {
+        ExpressionWidth::Unbounded => (acir, acir_opcode_positions),
+        ExpressionWidth::Bounded { width } => transform_to_gate_shape_internal(
+            acir,
+            &BoundedGateShape::plonk(width),
+            acir_opcode_positions,
+        ),
+    }
+}
+
+/// Applies the CSAT transformation targeting `gate_shape` to a [`Circuit`].
+///
+/// Accepts an injected `acir_opcode_positions` to allow transformations to be applied directly after optimizations.
+pub(super) fn transform_to_gate_shape_internal<F: AcirField>(
+    acir: Circuit<F>,
+    gate_shape: &dyn GateShape,
+    acir_opcode_positions: Vec<usize>,
+) -> (Circuit<F>, Vec<usize>) {
+    let mut transformer = CSatTransformer::new_for_gate_shape(gate_shape);
+    for value in acir.circuit_arguments() {
+        transformer.mark_solvable(value);
+    }
 
     // TODO: the code below is only for CSAT transformer
     // TODO it may be possible to refactor it in a way that we do not need to return early from the r1cs
@@ -160,11 +191,12 @@ pub(super) fn transform_internal<F: AcirField>(
 
     let acir = Circuit {
         current_witness_index,
-        expression_width,
+        expression_width: ExpressionWidth::Bounded { width: gate_shape.linear_terms() },
         opcodes: transformed_opcodes,
         // The transformer does not add new public inputs
         ..acir
     };
 
-    (acir, new_acir_opcode_positions)
+    // CSAT introduces intermediate witnesses for each opcode separately, so we look for any which can be shared.
+    WitnessMergingOptimizer::merge_witnesses(acir, new_acir_opcode_positions)
 }
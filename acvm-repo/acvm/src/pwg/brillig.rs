@@ -12,7 +12,7 @@ use acir::{
     AcirField,
 };
 use acvm_blackbox_solver::BlackBoxFunctionSolver;
-use brillig_vm::{FailureReason, MemoryValue, VMStatus, VM};
+use brillig_vm::{BrilligProfilingSamples, FailureReason, MemoryValue, VMStatus, VM};
 use serde::{Deserialize, Serialize};
 
 use crate::{pwg::OpcodeNotSolvable, OpcodeResolutionError};
@@ -155,6 +155,21 @@ impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
         self.vm.program_counter()
     }
 
+    /// Returns the number of Brillig opcodes which have been executed so far.
+    pub fn opcodes_executed(&self) -> usize {
+        self.vm.opcodes_executed()
+    }
+
+    /// Enables recording the call stack of each Brillig opcode executed.
+    pub fn enable_profiling(&mut self) {
+        self.vm.enable_profiling();
+    }
+
+    /// Takes the profiling samples recorded so far.
+    pub fn take_profiling_samples(&mut self) -> BrilligProfilingSamples {
+        self.vm.take_profiling_samples()
+    }
+
     fn handle_vm_status(
         &self,
         vm_status: VMStatus<F>,
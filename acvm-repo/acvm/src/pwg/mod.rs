@@ -1,6 +1,6 @@
 // Re-usable methods that backends can use to implement their PWG
 
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 
 use acir::{
     brillig::ForeignCallResult,
@@ -31,10 +31,13 @@ pub(crate) mod brillig;
 pub(crate) mod directives;
 // black box functions
 pub(crate) mod blackbox;
-mod memory_op;
+pub(crate) mod memory_op;
+// execution statistics
+mod stats;
 
 pub use self::brillig::{BrilligSolver, BrilligSolverStatus};
 pub use brillig::ForeignCallWaitInfo;
+pub use stats::{ExecutionStats, OperationStats, ProfilingSample};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ACVMStatus<F> {
@@ -182,6 +185,14 @@ pub struct ACVM<'a, F, B: BlackBoxFunctionSolver<F>> {
     unconstrained_functions: &'a [BrilligBytecode<F>],
 
     assertion_payloads: &'a [(OpcodeLocation, AssertionPayload<F>)],
+
+    /// Statistics about the execution, only collected if enabled with [`ACVM::enable_stats`].
+    stats: Option<ExecutionStats>,
+    /// The time at which the pending foreign call was requested, used to time foreign calls when collecting statistics.
+    foreign_call_started: Option<Instant>,
+
+    /// Samples of the Brillig opcodes executed, only collected if enabled with [`ACVM::enable_profiling`].
+    profiling_samples: Option<Vec<ProfilingSample>>,
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
@@ -206,9 +217,31 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
             acir_call_results: Vec::default(),
             unconstrained_functions,
             assertion_payloads,
+            stats: None,
+            foreign_call_started: None,
+            profiling_samples: None,
         }
     }
 
+    /// Enables collection of [`ExecutionStats`] for the remainder of the execution.
+    ///
+    /// This times every opcode so is disabled by default.
+    pub fn enable_stats(&mut self) {
+        self.stats.get_or_insert_with(ExecutionStats::default);
+    }
+
+    /// Returns the statistics collected so far, if collection has been enabled.
+    pub fn stats(&self) -> Option<&ExecutionStats> {
+        self.stats.as_ref()
+    }
+
+    /// Takes the statistics collected so far, if collection has been enabled.
+    ///
+    /// Collection remains enabled, starting again from empty statistics.
+    pub fn take_stats(&mut self) -> Option<ExecutionStats> {
+        self.stats.as_mut().map(std::mem::take)
+    }
+
     /// Returns a reference to the current state of the ACVM's [`WitnessMap`].
     ///
     /// Once execution has completed, the witness map can be extracted using [`ACVM::finalize`]
@@ -238,6 +271,16 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
         self.witness_map
     }
 
+    /// Enables recording the call stack of each Brillig opcode executed for the remainder of the execution.
+    pub fn enable_profiling(&mut self) {
+        self.profiling_samples.get_or_insert_with(Vec::new);
+    }
+
+    /// Takes the [`ProfilingSample`]s recorded so far, returning no samples if profiling isn't enabled.
+    pub fn take_profiling_samples(&mut self) -> Vec<ProfilingSample> {
+        self.profiling_samples.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
     /// Updates the current status of the VM.
     /// Returns the given status.
     fn status(&mut self, status: ACVMStatus<F>) -> ACVMStatus<F> {
@@ -258,6 +301,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
     /// Sets the status of the VM to `RequiresForeignCall`.
     /// Indicating that the VM is now waiting for a foreign call to be resolved.
     fn wait_for_foreign_call(&mut self, foreign_call: ForeignCallWaitInfo<F>) -> ACVMStatus<F> {
+        if self.stats.is_some() {
+            self.foreign_call_started = Some(Instant::now());
+        }
         self.status(ACVMStatus::RequiresForeignCall(foreign_call))
     }
 
@@ -274,8 +320,11 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
     ///
     /// The ACVM can then be restarted to solve the remaining Brillig VM process as well as the remaining ACIR opcodes.
     pub fn resolve_pending_foreign_call(&mut self, foreign_call_result: ForeignCallResult<F>) {
-        if !matches!(self.status, ACVMStatus::RequiresForeignCall(_)) {
+        let ACVMStatus::RequiresForeignCall(foreign_call) = &self.status else {
             panic!("ACVM is not expecting a foreign call response as no call was made");
+        };
+        if let (Some(stats), Some(started)) = (&mut self.stats, self.foreign_call_started.take()) {
+            stats.record_foreign_call(&foreign_call.function, started.elapsed());
         }
 
         let brillig_solver = self.brillig_solver.as_mut().expect("No active Brillig solver");
@@ -322,6 +371,23 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
     }
 
     pub fn solve_opcode(&mut self) -> ACVMStatus<F> {
+        if self.stats.is_none() {
+            return self.solve_opcode_internal();
+        }
+
+        let opcodes = self.opcodes;
+        let opcode = &opcodes[self.instruction_pointer];
+        let started = Instant::now();
+        let status = self.solve_opcode_internal();
+        let completed =
+            !matches!(status, ACVMStatus::RequiresForeignCall(_) | ACVMStatus::RequiresAcirCall(_));
+        if let Some(stats) = &mut self.stats {
+            stats.record_opcode(opcode, started.elapsed(), completed);
+        }
+        status
+    }
+
+    fn solve_opcode_internal(&mut self) -> ACVMStatus<F> {
         let opcode = &self.opcodes[self.instruction_pointer];
 
         let resolution = match opcode {
@@ -472,18 +538,47 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
         // there will be a cached `BrilligSolver` to avoid recomputation.
         let mut solver: BrilligSolver<'_, F, B> = match self.brillig_solver.take() {
             Some(solver) => solver,
-            None => BrilligSolver::new_call(
-                &self.witness_map,
-                &self.block_solvers,
-                inputs,
-                &self.unconstrained_functions[id.as_usize()].bytecode,
-                self.backend,
-                self.instruction_pointer,
-                *id,
-            )?,
+            None => {
+                let mut solver = BrilligSolver::new_call(
+                    &self.witness_map,
+                    &self.block_solvers,
+                    inputs,
+                    &self.unconstrained_functions[id.as_usize()].bytecode,
+                    self.backend,
+                    self.instruction_pointer,
+                    *id,
+                )?;
+                if self.profiling_samples.is_some() {
+                    solver.enable_profiling();
+                }
+                solver
+            }
         };
 
-        let result = solver.solve().map_err(|err| self.map_brillig_error(err))?;
+        let result = solver.solve();
+        if !matches!(result, Ok(BrilligSolverStatus::ForeignCallWait(_))) {
+            if let Some(stats) = &mut self.stats {
+                stats.brillig_opcodes += solver.opcodes_executed();
+            }
+            if let Some(samples) = &mut self.profiling_samples {
+                let acir_index = self.instruction_pointer;
+                samples.extend(solver.take_profiling_samples().into_iter().map(
+                    |(call_stack, count)| {
+                        let brillig_locations = call_stack.into_iter().map(|brillig_index| {
+                            OpcodeLocation::Brillig { acir_index, brillig_index }
+                        });
+                        ProfilingSample {
+                            call_stack: std::iter::once(OpcodeLocation::Acir(acir_index))
+                                .chain(brillig_locations)
+                                .collect(),
+                            brillig_function_id: Some(*id),
+                            count,
+                        }
+                    },
+                ));
+            }
+        }
+        let result = result.map_err(|err| self.map_brillig_error(err))?;
 
         match result {
             BrilligSolverStatus::ForeignCallWait(foreign_call) => {
@@ -0,0 +1,134 @@
+use std::{collections::BTreeMap, fmt::Display, time::Duration};
+
+use acir::circuit::{brillig::BrilligFunctionId, Opcode, OpcodeLocation};
+
+/// The number of times an operation was performed along with the total time spent performing it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperationStats {
+    pub count: usize,
+    pub time: Duration,
+}
+
+impl OperationStats {
+    fn record(&mut self, time: Duration, completed: bool) {
+        if completed {
+            self.count += 1;
+        }
+        self.time += time;
+    }
+
+    fn merge(&mut self, other: &OperationStats) {
+        self.count += other.count;
+        self.time += other.time;
+    }
+}
+
+/// Statistics collected while executing a circuit with the [ACVM][super::ACVM].
+///
+/// Collecting statistics is opt-in using [`ACVM::enable_stats`][super::ACVM::enable_stats] as it requires
+/// timing every opcode.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionStats {
+    /// Opcodes executed, keyed by the kind of opcode.
+    pub opcodes: BTreeMap<&'static str, OperationStats>,
+    /// Black box function calls executed, keyed by the name of the black box function.
+    pub black_box_calls: BTreeMap<&'static str, OperationStats>,
+    /// The total number of Brillig opcodes executed across all Brillig calls.
+    pub brillig_opcodes: usize,
+    /// Foreign calls made from Brillig, keyed by the name of the foreign call.
+    ///
+    /// The time recorded is the time between the ACVM requesting the foreign call and it being resolved.
+    pub foreign_calls: BTreeMap<String, OperationStats>,
+}
+
+impl ExecutionStats {
+    /// Records `time` spent solving `opcode`.
+    ///
+    /// Opcodes which must wait on the caller (e.g. to resolve a foreign call) are solved across several steps,
+    /// so the opcode is only counted once `completed` is set.
+    pub(super) fn record_opcode<F: Copy>(
+        &mut self,
+        opcode: &Opcode<F>,
+        time: Duration,
+        completed: bool,
+    ) {
+        self.opcodes.entry(opcode_kind(opcode)).or_default().record(time, completed);
+        if let Opcode::BlackBoxFuncCall(func_call) = opcode {
+            self.black_box_calls
+                .entry(func_call.get_black_box_func().name())
+                .or_default()
+                .record(time, completed);
+        }
+    }
+
+    pub(super) fn record_foreign_call(&mut self, name: &str, time: Duration) {
+        self.foreign_calls.entry(name.to_string()).or_default().record(time, true);
+    }
+
+    /// Adds the statistics from `other` into these statistics.
+    ///
+    /// This is used to combine the statistics of the separate ACVM instances which execute each function in a program.
+    pub fn merge(&mut self, other: &ExecutionStats) {
+        for (kind, stats) in &other.opcodes {
+            self.opcodes.entry(*kind).or_default().merge(stats);
+        }
+        for (name, stats) in &other.black_box_calls {
+            self.black_box_calls.entry(*name).or_default().merge(stats);
+        }
+        self.brillig_opcodes += other.brillig_opcodes;
+        for (name, stats) in &other.foreign_calls {
+            self.foreign_calls.entry(name.clone()).or_default().merge(stats);
+        }
+    }
+}
+
+/// The number of Brillig opcodes executed at a single call stack.
+///
+/// Collecting samples is opt-in using [`ACVM::enable_profiling`][super::ACVM::enable_profiling].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfilingSample {
+    /// The call stack at which the opcodes were executed.
+    ///
+    /// This starts with the location of the ACIR opcode which made the Brillig call and ends with the executed Brillig opcode.
+    pub call_stack: Vec<OpcodeLocation>,
+    /// The Brillig function which was being executed.
+    pub brillig_function_id: Option<BrilligFunctionId>,
+    /// The number of times an opcode was executed at this call stack.
+    pub count: usize,
+}
+
+fn opcode_kind<F>(opcode: &Opcode<F>) -> &'static str {
+    match opcode {
+        Opcode::AssertZero(_) => "assert_zero",
+        Opcode::BlackBoxFuncCall(_) => "black_box",
+        Opcode::Directive(_) => "directive",
+        Opcode::MemoryInit { .. } => "memory_init",
+        Opcode::MemoryOp { .. } => "memory_op",
+        Opcode::BrilligCall { .. } => "brillig_call",
+        Opcode::Call { .. } => "call",
+    }
+}
+
+impl Display for ExecutionStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write_section<K: Display>(
+            f: &mut std::fmt::Formatter<'_>,
+            title: &str,
+            stats: &BTreeMap<K, OperationStats>,
+        ) -> std::fmt::Result {
+            if stats.is_empty() {
+                return Ok(());
+            }
+            writeln!(f, "{title}:")?;
+            for (name, stats) in stats {
+                writeln!(f, "  {name:<24} {:>10} {:>12.3?}", stats.count, stats.time)?;
+            }
+            Ok(())
+        }
+
+        write_section(f, "ACIR opcodes", &self.opcodes)?;
+        write_section(f, "Black box functions", &self.black_box_calls)?;
+        writeln!(f, "Brillig opcodes executed: {}", self.brillig_opcodes)?;
+        write_section(f, "Foreign calls", &self.foreign_calls)
+    }
+}
@@ -1,19 +1,24 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::sync::Arc;
 
 use acir::{
     acir_field::GenericFieldElement,
-    brillig::{BinaryFieldOp, HeapArray, MemoryAddress, Opcode as BrilligOpcode, ValueOrArray},
+    brillig::{
+        BinaryFieldOp, BitSize, HeapArray, MemoryAddress, Opcode as BrilligOpcode, ValueOrArray,
+    },
     circuit::{
         brillig::{BrilligBytecode, BrilligFunctionId, BrilligInputs, BrilligOutputs},
-        opcodes::{BlackBoxFuncCall, BlockId, BlockType, FunctionInput, MemOp},
-        Opcode, OpcodeLocation,
+        opcodes::{AcirFunctionId, BlackBoxFuncCall, BlockId, BlockType, FunctionInput, MemOp},
+        Circuit, ExpressionWidth, Opcode, OpcodeLocation, PublicInputs,
     },
     native_types::{Expression, Witness, WitnessMap},
     AcirField, FieldElement,
 };
 
-use acvm::pwg::{ACVMStatus, ErrorLocation, ForeignCallWaitInfo, OpcodeResolutionError, ACVM};
+use acvm::compiler::optimize_with_brillig;
+use acvm::pwg::{
+    ACVMStatus, ErrorLocation, ForeignCallWaitInfo, OpcodeResolutionError, OperationStats, ACVM,
+};
 use acvm_blackbox_solver::StubbedBlackBoxSolver;
 use bn254_blackbox_solver::{field_from_hex, Bn254BlackBoxSolver, POSEIDON2_CONFIG};
 use brillig_vm::brillig::HeapValueType;
@@ -179,6 +184,83 @@ fn inversion_brillig_oracle_equivalence() {
     acvm.finalize();
 }
 
+#[test]
+fn execution_stats() {
+    // fn main(x: Field) {
+    //     let x_inverse = Oracle("invert", x);
+    //     assert(x * x_inverse == 1);
+    // }
+    let w_x = Witness(1);
+    let w_x_inverse = Witness(2);
+
+    let opcodes = vec![
+        Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![BrilligInputs::Single(w_x.into())],
+            outputs: vec![BrilligOutputs::Simple(w_x_inverse)],
+            predicate: None,
+        },
+        Opcode::AssertZero(Expression {
+            mul_terms: vec![(FieldElement::one(), w_x, w_x_inverse)],
+            linear_combinations: vec![],
+            q_c: -FieldElement::one(),
+        }),
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input: FunctionInput::witness(w_x, 8) }),
+    ];
+
+    let brillig_bytecode = BrilligBytecode {
+        bytecode: vec![
+            BrilligOpcode::CalldataCopy {
+                destination_address: MemoryAddress(0),
+                size: 1,
+                offset: 0,
+            },
+            BrilligOpcode::ForeignCall {
+                function: "invert".into(),
+                destinations: vec![ValueOrArray::MemoryAddress(MemoryAddress::from(0))],
+                destination_value_types: vec![HeapValueType::field()],
+                inputs: vec![ValueOrArray::MemoryAddress(MemoryAddress::from(0))],
+                input_value_types: vec![HeapValueType::field()],
+            },
+            BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 1 },
+        ],
+    };
+
+    let witness_assignments = BTreeMap::from([(w_x, FieldElement::from(2u128))]).into();
+    let unconstrained_functions = vec![brillig_bytecode];
+    let mut acvm = ACVM::new(
+        &StubbedBlackBoxSolver,
+        &opcodes,
+        witness_assignments,
+        &unconstrained_functions,
+        &[],
+    );
+    acvm.enable_stats();
+
+    let solver_status = acvm.solve();
+    assert!(matches!(solver_status, ACVMStatus::RequiresForeignCall(_)));
+    let foreign_call_result = FieldElement::from(2u128).inverse();
+    acvm.resolve_pending_foreign_call(foreign_call_result.into());
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+
+    let stats = acvm.take_stats().expect("stats should be collected");
+    fn counts<K: ToString>(stats: &BTreeMap<K, OperationStats>) -> Vec<(String, usize)> {
+        stats.iter().map(|(name, stats)| (name.to_string(), stats.count)).collect()
+    }
+    assert_eq!(
+        counts(&stats.opcodes),
+        vec![
+            ("assert_zero".to_string(), 1),
+            ("black_box".to_string(), 1),
+            ("brillig_call".to_string(), 1)
+        ]
+    );
+    assert_eq!(counts(&stats.black_box_calls), vec![("range".to_string(), 1)]);
+    // The foreign call opcode is only counted once despite being processed before and after it is resolved.
+    assert_eq!(stats.brillig_opcodes, 3);
+    assert_eq!(counts(&stats.foreign_calls), vec![("invert".to_string(), 1)]);
+}
+
 #[test]
 fn double_inversion_brillig_oracle() {
     // Opcodes below describe the following:
@@ -730,6 +812,265 @@ fn memory_operations() {
     assert_eq!(witness_map[&Witness(8)], FieldElement::from(6u128));
 }
 
+/// Solves the function `id` of a program, resolving any ACIR calls it makes by solving the called function.
+fn solve_program(
+    functions: &[Circuit<FieldElement>],
+    unconstrained_functions: &[BrilligBytecode<FieldElement>],
+    id: usize,
+    initial_witness: WitnessMap<FieldElement>,
+) -> WitnessMap<FieldElement> {
+    let circuit = &functions[id];
+    let mut acvm = ACVM::new(
+        &StubbedBlackBoxSolver,
+        &circuit.opcodes,
+        initial_witness,
+        unconstrained_functions,
+        &circuit.assert_messages,
+    );
+    loop {
+        match acvm.solve() {
+            ACVMStatus::Solved => break,
+            ACVMStatus::RequiresAcirCall(call_info) => {
+                let callee = call_info.id.as_usize();
+                let witness_map = solve_program(
+                    functions,
+                    unconstrained_functions,
+                    callee,
+                    call_info.initial_witness,
+                );
+                let return_values = functions[callee]
+                    .return_values
+                    .indices()
+                    .into_iter()
+                    .map(|index| witness_map[&Witness(index)]);
+                acvm.resolve_pending_acir_call(return_values.collect());
+            }
+            status => panic!("unexpected solver status: {status}"),
+        }
+    }
+    acvm.finalize()
+}
+
+/// Optimizes each function of a program and checks that for each set of inputs the optimized program
+/// returns the same values as the unoptimized program.
+fn assert_optimization_preserves_return_values(
+    functions: Vec<Circuit<FieldElement>>,
+    unconstrained_functions: Vec<BrilligBytecode<FieldElement>>,
+    inputs: Vec<WitnessMap<FieldElement>>,
+) {
+    let optimized_functions: Vec<_> = functions
+        .iter()
+        .map(|circuit| optimize_with_brillig(circuit.clone(), &unconstrained_functions).0)
+        .collect();
+
+    for initial_witness in inputs {
+        let expected =
+            solve_program(&functions, &unconstrained_functions, 0, initial_witness.clone());
+        let actual =
+            solve_program(&optimized_functions, &unconstrained_functions, 0, initial_witness);
+
+        for witness in &functions[0].return_values.0 {
+            assert_eq!(expected[witness], actual[witness], "return value {witness:?} differs");
+        }
+    }
+}
+
+fn circuit_with_interface(
+    opcodes: Vec<Opcode<FieldElement>>,
+    parameters: impl IntoIterator<Item = u32>,
+    return_values: impl IntoIterator<Item = u32>,
+) -> Circuit<FieldElement> {
+    let private_parameters: BTreeSet<_> = parameters.into_iter().map(Witness).collect();
+    let return_values: BTreeSet<_> = return_values.into_iter().map(Witness).collect();
+    Circuit {
+        current_witness_index: 0,
+        expression_width: ExpressionWidth::Unbounded,
+        opcodes,
+        private_parameters,
+        public_parameters: PublicInputs::default(),
+        return_values: PublicInputs(return_values),
+        assert_messages: Vec::new(),
+        recursive: false,
+    }
+}
+
+/// Returns an expression asserting that `witness` is equal to `value`.
+fn assert_constant(witness: Witness, value: u128) -> Opcode<FieldElement> {
+    Opcode::AssertZero(Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![(FieldElement::one(), witness)],
+        q_c: -FieldElement::from(value),
+    })
+}
+
+/// Returns an expression asserting that `sum` is equal to `lhs + rhs`.
+fn assert_sum(lhs: Witness, rhs: Witness, sum: Witness) -> Opcode<FieldElement> {
+    Opcode::AssertZero(Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![
+            (FieldElement::one(), lhs),
+            (FieldElement::one(), rhs),
+            (-FieldElement::one(), sum),
+        ],
+        q_c: FieldElement::zero(),
+    })
+}
+
+fn witness_map(values: impl IntoIterator<Item = (u32, u128)>) -> WitnessMap<FieldElement> {
+    WitnessMap::from(BTreeMap::from_iter(
+        values.into_iter().map(|(witness, value)| (Witness(witness), FieldElement::from(value))),
+    ))
+}
+
+#[test]
+fn optimization_preserves_memory_operations() {
+    // Witnesses 1 and 2 are constant, so the read at index 1 can be evaluated at compile time,
+    // while the read at index `w11` and the read of the value written from it depend on the program's inputs.
+    let block_id = BlockId(0);
+    let opcodes = vec![
+        assert_constant(Witness(1), 2),
+        assert_constant(Witness(2), 3),
+        Opcode::MemoryInit {
+            block_id,
+            init: vec![Witness(1), Witness(2), Witness(10)],
+            block_type: BlockType::Memory,
+        },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(3)),
+            predicate: None,
+        },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::read_at_mem_index(Witness(11).into(), Witness(4)),
+            predicate: None,
+        },
+        assert_sum(Witness(3), Witness(4), Witness(20)),
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::write_to_mem_index(FieldElement::from(2u128).into(), Witness(4).into()),
+            predicate: None,
+        },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::read_at_mem_index(FieldElement::from(2u128).into(), Witness(5)),
+            predicate: None,
+        },
+        assert_sum(Witness(5), Witness(10), Witness(21)),
+    ];
+    let main = circuit_with_interface(opcodes, [10, 11], [20, 21]);
+
+    let inputs = (0..3).map(|index| witness_map([(10, 7), (11, index)])).collect();
+    assert_optimization_preserves_return_values(vec![main], Vec::new(), inputs);
+}
+
+#[test]
+fn optimization_preserves_acir_calls() {
+    // fn main(x: Field) -> Field {
+    //     let y = 2;
+    //     foo(y, x) + y
+    // }
+    // fn foo(a: Field, b: Field) -> Field { a * b }
+    let main_opcodes = vec![
+        assert_constant(Witness(1), 2),
+        // The call's predicate is constant but only known once the constraint above has been solved.
+        assert_constant(Witness(5), 1),
+        Opcode::Call {
+            id: AcirFunctionId(1),
+            inputs: vec![Witness(1), Witness(2)],
+            outputs: vec![Witness(3)],
+            predicate: Some(Witness(5).into()),
+        },
+        assert_sum(Witness(3), Witness(1), Witness(4)),
+    ];
+    let main = circuit_with_interface(main_opcodes, [2], [4]);
+
+    let foo_opcodes = vec![Opcode::AssertZero(Expression {
+        mul_terms: vec![(FieldElement::one(), Witness(0), Witness(1))],
+        linear_combinations: vec![(-FieldElement::one(), Witness(2))],
+        q_c: FieldElement::zero(),
+    })];
+    let foo = circuit_with_interface(foo_opcodes, [0, 1], [2]);
+
+    let inputs = [0, 1, 5].into_iter().map(|x| witness_map([(2, x)])).collect();
+    assert_optimization_preserves_return_values(vec![main, foo], Vec::new(), inputs);
+}
+
+#[test]
+fn optimization_preserves_brillig_calls_with_false_predicate() {
+    // fn main(x: Field) -> Field {
+    //     let x_inverse = if false { invert(x) } else { 0 };
+    //     x + x_inverse
+    // }
+    let opcodes = vec![
+        Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![BrilligInputs::Single(Witness(1).into())],
+            outputs: vec![BrilligOutputs::Simple(Witness(2))],
+            predicate: Some(Expression::zero()),
+        },
+        assert_sum(Witness(1), Witness(2), Witness(3)),
+    ];
+    let main = circuit_with_interface(opcodes, [1], [3]);
+
+    // Brillig returns zero when dividing by zero, so this computes an inverse for every `x`. As the predicate
+    // is false the call must not be executed and its output must be zero instead.
+    let invert = BrilligBytecode {
+        bytecode: vec![
+            BrilligOpcode::CalldataCopy {
+                destination_address: MemoryAddress(0),
+                size: 1,
+                offset: 0,
+            },
+            BrilligOpcode::Const {
+                destination: MemoryAddress(1),
+                bit_size: BitSize::Field,
+                value: FieldElement::one(),
+            },
+            BrilligOpcode::BinaryFieldOp {
+                destination: MemoryAddress(0),
+                op: BinaryFieldOp::Div,
+                lhs: MemoryAddress(1),
+                rhs: MemoryAddress(0),
+            },
+            BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 1 },
+        ],
+    };
+
+    let inputs = [0, 1, 5].into_iter().map(|x| witness_map([(1, x)])).collect();
+    assert_optimization_preserves_return_values(vec![main.clone()], vec![invert.clone()], inputs);
+
+    // The output of the Brillig call is unconstrained so the optimized circuit must constrain the return value
+    // to be `x` regardless of which value a prover provides for it.
+    let (optimized, _) = optimize_with_brillig(main, &[invert]);
+    for x in [0, 1, 5] {
+        for hint in [0, 1, x + 1] {
+            let honest = witness_map([(1, x), (2, hint), (3, x)]);
+            assert!(assert_zero_opcodes_hold(&optimized, &honest));
+
+            let dishonest = witness_map([(1, x), (2, hint), (3, x + 1)]);
+            assert!(!assert_zero_opcodes_hold(&optimized, &dishonest));
+        }
+    }
+}
+
+/// Returns whether every `AssertZero` opcode in `circuit` holds for the values in `witness`.
+fn assert_zero_opcodes_hold(
+    circuit: &Circuit<FieldElement>,
+    witness: &WitnessMap<FieldElement>,
+) -> bool {
+    circuit.opcodes.iter().all(|opcode| match opcode {
+        Opcode::AssertZero(expr) => {
+            let mul_terms =
+                expr.mul_terms.iter().map(|(q, lhs, rhs)| *q * witness[lhs] * witness[rhs]);
+            let linear_terms = expr.linear_combinations.iter().map(|(q, w)| *q * witness[w]);
+            let sum = mul_terms.chain(linear_terms).fold(expr.q_c, |acc, term| acc + term);
+            sum.is_zero()
+        }
+        _ => true,
+    })
+}
+
 fn allowed_bigint_moduli() -> Vec<Vec<u8>> {
     let bn254_fq: Vec<u8> = vec![
         0x47, 0xFD, 0x7C, 0xD8, 0x16, 0x8C, 0x20, 0x3C, 0x8d, 0xca, 0x71, 0x68, 0x91, 0x6a, 0x81,
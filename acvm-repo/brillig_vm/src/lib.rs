@@ -11,6 +11,8 @@
 //! [acir]: https://crates.io/crates/acir
 //! [acvm]: https://crates.io/crates/acvm
 
+use std::collections::{BTreeMap, HashMap};
+
 use acir::brillig::{
     BinaryFieldOp, BinaryIntOp, BitSize, ForeignCallParam, ForeignCallResult, HeapArray,
     HeapValueType, HeapVector, IntegerBitSize, MemoryAddress, Opcode, ValueOrArray,
@@ -31,6 +33,9 @@ mod memory;
 /// The error call stack contains the opcode indexes of the call stack at the time of failure, plus the index of the opcode that failed.
 pub type ErrorCallStack = Vec<usize>;
 
+/// The number of opcodes executed by the VM, keyed by the call stack (ending with the opcode's index) at which they were executed.
+pub type BrilligProfilingSamples = BTreeMap<Vec<usize>, usize>;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum FailureReason {
     Trap { revert_data_offset: usize, revert_data_size: usize },
@@ -88,6 +93,56 @@ pub struct VM<'a, F, B: BlackBoxFunctionSolver<F>> {
     black_box_solver: &'a B,
     // The solver for big integers
     bigint_solver: BrilligBigintSolver,
+    /// The number of opcodes which have been executed
+    opcodes_executed: usize,
+    /// Samples of the opcodes executed, only collected if profiling has been enabled
+    profiler: Option<Profiler>,
+}
+
+/// Counts the opcodes executed at each call stack.
+///
+/// Call stacks are interned as frames which point to the frame of their caller, so recording a sample
+/// doesn't require the current call stack to be copied.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+struct Profiler {
+    /// The frame of the caller and the opcode index for each interned call stack.
+    frames: Vec<(Option<usize>, usize)>,
+    frame_ids: HashMap<(Option<usize>, usize), usize>,
+    /// The number of opcodes executed at each frame.
+    samples: Vec<usize>,
+    /// The frames of the `Call` opcodes on the VM's call stack.
+    caller_frames: Vec<usize>,
+}
+
+impl Profiler {
+    /// Returns the frame for the opcode at `program_counter` called from the current call stack.
+    fn frame(&mut self, program_counter: usize) -> usize {
+        let key = (self.caller_frames.last().copied(), program_counter);
+        *self.frame_ids.entry(key).or_insert_with(|| {
+            self.frames.push(key);
+            self.samples.push(0);
+            self.frames.len() - 1
+        })
+    }
+
+    fn take_samples(&mut self) -> BrilligProfilingSamples {
+        let mut samples = BrilligProfilingSamples::new();
+        for (frame, count) in self.samples.iter_mut().enumerate() {
+            if *count == 0 {
+                continue;
+            }
+            let mut call_stack = Vec::new();
+            let mut current = Some(frame);
+            while let Some(frame) = current {
+                let (caller, program_counter) = self.frames[frame];
+                call_stack.push(program_counter);
+                current = caller;
+            }
+            call_stack.reverse();
+            samples.insert(call_stack, std::mem::take(count));
+        }
+        samples
+    }
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
@@ -109,6 +164,8 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
             call_stack: Vec::new(),
             black_box_solver,
             bigint_solver: Default::default(),
+            opcodes_executed: 0,
+            profiler: None,
         }
     }
 
@@ -188,6 +245,31 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
         self.memory.write(MemoryAddress(ptr), value);
     }
 
+    /// Returns the number of opcodes which have been executed by the VM.
+    ///
+    /// A foreign call opcode is only counted once, even though it is processed again after its result is resolved.
+    pub fn opcodes_executed(&self) -> usize {
+        self.opcodes_executed
+    }
+
+    /// Enables recording the call stack of each opcode executed by the VM.
+    pub fn enable_profiling(&mut self) {
+        if self.profiler.is_none() {
+            // Profiling may be enabled part way through execution, e.g. when resuming after a foreign call.
+            let mut profiler = Profiler::default();
+            for &program_counter in &self.call_stack {
+                let frame = profiler.frame(program_counter);
+                profiler.caller_frames.push(frame);
+            }
+            self.profiler = Some(profiler);
+        }
+    }
+
+    /// Takes the profiling samples recorded so far, returning an empty set of samples if profiling isn't enabled.
+    pub fn take_profiling_samples(&mut self) -> BrilligProfilingSamples {
+        self.profiler.as_mut().map(Profiler::take_samples).unwrap_or_default()
+    }
+
     /// Returns the VM's current call stack, including the actual program
     /// counter in the last position of the returned vector.
     pub fn get_call_stack(&self) -> Vec<usize> {
@@ -196,6 +278,28 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
 
     /// Process a single opcode and modify the program counter.
     pub fn process_opcode(&mut self) -> VMStatus<F> {
+        let program_counter = self.program_counter;
+        let call_depth = self.call_stack.len();
+        let status = self.process_opcode_internal();
+        if !matches!(status, VMStatus::ForeignCallWait { .. }) {
+            self.opcodes_executed += 1;
+            if let Some(profiler) = &mut self.profiler {
+                let frame = profiler.frame(program_counter);
+                profiler.samples[frame] += 1;
+                // Keep the profiler's call stack in sync with the VM's after a `Call` or `Return`.
+                match self.call_stack.len().cmp(&call_depth) {
+                    std::cmp::Ordering::Greater => profiler.caller_frames.push(frame),
+                    std::cmp::Ordering::Less => {
+                        profiler.caller_frames.pop();
+                    }
+                    std::cmp::Ordering::Equal => (),
+                }
+            }
+        }
+        status
+    }
+
+    fn process_opcode_internal(&mut self) -> VMStatus<F> {
         let opcode = &self.bytecode[self.program_counter];
         match opcode {
             Opcode::BinaryFieldOp { op, lhs, rhs, destination: result } => {
@@ -1384,6 +1488,37 @@ mod tests {
         assert_eq!(memory, expected);
     }
 
+    #[test]
+    fn profiling_samples_record_call_stacks() {
+        let bit_size = BitSize::Integer(IntegerBitSize::U32);
+        let opcodes: Vec<Opcode<FieldElement>> = vec![
+            Opcode::Const { destination: MemoryAddress(0), value: 1u128.into(), bit_size },
+            Opcode::Call { location: 4 },
+            Opcode::Call { location: 4 },
+            Opcode::Stop { return_data_offset: 0, return_data_size: 0 },
+            // fn foo
+            Opcode::Const { destination: MemoryAddress(1), value: 2u128.into(), bit_size },
+            Opcode::Return {},
+        ];
+
+        let mut vm = VM::new(vec![], &opcodes, vec![], &StubbedBlackBoxSolver);
+        vm.enable_profiling();
+        brillig_execute(&mut vm);
+
+        let expected = BrilligProfilingSamples::from([
+            (vec![0], 1),
+            (vec![1], 1),
+            (vec![1, 4], 1),
+            (vec![1, 5], 1),
+            (vec![2], 1),
+            (vec![2, 4], 1),
+            (vec![2, 5], 1),
+            (vec![3], 1),
+        ]);
+        assert_eq!(vm.take_profiling_samples(), expected);
+        assert!(vm.take_profiling_samples().is_empty());
+    }
+
     /// Helper to execute brillig code
     fn brillig_execute_and_get_vm<F: AcirField>(
         calldata: Vec<F>,
@@ -52,10 +52,10 @@ pub fn generate_event_impls(
 
             let mut event_fields = vec![];
 
-            for (field_ident, field_type) in event_struct.fields.iter() {
+            for field in event_struct.fields.iter() {
                 event_fields.push((
-                    field_ident.0.contents.to_string(),
-                    field_type.typ.to_string().replace("plain::", ""),
+                    field.name.0.contents.to_string(),
+                    field.typ.typ.to_string().replace("plain::", ""),
                 ));
             }
 
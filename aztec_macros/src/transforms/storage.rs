@@ -1,8 +1,8 @@
 use acvm::acir::AcirField;
 use noirc_errors::Span;
 use noirc_frontend::ast::{
-    BlockExpression, Expression, ExpressionKind, FunctionDefinition, GenericTypeArgs, Ident,
-    Literal, NoirFunction, NoirStruct, Pattern, StatementKind, TypeImpl, UnresolvedType,
+    BlockExpression, Expression, ExpressionKind, FunctionDefinition, GenericTypeArgs, Literal,
+    NoirFunction, NoirStruct, Pattern, StatementKind, StructField, TypeImpl, UnresolvedType,
     UnresolvedTypeData,
 };
 use noirc_frontend::{
@@ -96,7 +96,7 @@ pub fn inject_context_in_storage(module: &mut SortedModule) -> Result<(), AztecM
     storage_struct
         .fields
         .iter_mut()
-        .map(|(_, field)| inject_context_in_storage_field(field))
+        .map(|field| inject_context_in_storage_field(&mut field.typ))
         .collect::<Result<Vec<_>, _>>()?;
     Ok(())
 }
@@ -115,7 +115,7 @@ pub fn check_for_storage_implementation(
 /// Auxiliary function to generate the storage constructor for a given field, using
 /// the Storage definition as a reference. Supports nesting.
 pub fn generate_storage_field_constructor(
-    (type_ident, unresolved_type): &(Ident, UnresolvedType),
+    StructField { name: type_ident, typ: unresolved_type, .. }: &StructField,
     slot: Expression,
 ) -> Result<Expression, AztecMacroError> {
     let typ = &unresolved_type.typ;
@@ -213,7 +213,7 @@ pub fn generate_storage_implementation(
         .iter()
         .flat_map(|field| {
             generate_storage_field_constructor(field, slot_zero.clone())
-                .map(|expression| (field.0.clone(), expression))
+                .map(|expression| (field.name.clone(), expression))
         })
         .collect();
 
@@ -515,10 +515,10 @@ pub fn generate_storage_layout(
     let mut storable_fields = vec![];
     let mut storable_fields_impl = vec![];
 
-    definition.fields.iter().for_each(|(field_ident, _)| {
-        storable_fields.push(format!("{}: dep::aztec::prelude::Storable", field_ident));
+    definition.fields.iter().for_each(|field| {
+        storable_fields.push(format!("{}: dep::aztec::prelude::Storable", field.name));
         storable_fields_impl
-            .push(format!("{}: dep::aztec::prelude::Storable {{ slot: 0 }}", field_ident,));
+            .push(format!("{}: dep::aztec::prelude::Storable {{ slot: 0 }}", field.name,));
     });
 
     let storage_fields_source = format!(
@@ -3,12 +3,12 @@ use noirc_frontend::{
         ArrayLiteral, AssignStatement, BlockExpression, CallExpression, CastExpression,
         ConstrainStatement, ConstructorExpression, Expression, ExpressionKind, ForLoopStatement,
         ForRange, FunctionReturnType, GenericTypeArgs, Ident, IfExpression, IndexExpression,
-        InfixExpression, LValue, Lambda, LetStatement, Literal, MemberAccessExpression,
-        MethodCallExpression, ModuleDeclaration, NoirFunction, NoirStruct, NoirTrait,
-        NoirTraitImpl, NoirTypeAlias, Path, PathSegment, Pattern, PrefixExpression, Statement,
-        StatementKind, TraitImplItem, TraitItem, TypeImpl, UnresolvedGeneric, UnresolvedGenerics,
-        UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression,
-        UseTree, UseTreeKind,
+        InfixExpression, LValue, Lambda, LetStatement, Literal, MatchExpression, MatchPattern,
+        MemberAccessExpression, MethodCallExpression, ModuleDeclaration, NoirEnumeration,
+        NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Path, PathSegment,
+        Pattern, PrefixExpression, Statement, StatementKind, TraitImplItem, TraitItem, TypeImpl,
+        UnresolvedGeneric, UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType,
+        UnresolvedTypeData, UnresolvedTypeExpression, UseTree, UseTreeKind,
     },
     parser::{Item, ItemKind, ParsedSubModule, ParserError},
     ParsedModule,
@@ -52,6 +52,7 @@ fn empty_item(item: &mut Item) {
         ItemKind::ModuleDecl(module_declaration) => empty_module_declaration(module_declaration),
         ItemKind::Import(use_tree) => empty_use_tree(use_tree),
         ItemKind::Struct(noir_struct) => empty_noir_struct(noir_struct),
+        ItemKind::Enum(noir_enum) => empty_noir_enum(noir_enum),
         ItemKind::TypeAlias(noir_type_alias) => empty_noir_type_alias(noir_type_alias),
     }
 }
@@ -186,13 +187,25 @@ fn empty_use_tree(use_tree: &mut UseTree) {
 fn empty_noir_struct(noir_struct: &mut NoirStruct) {
     noir_struct.span = Default::default();
     empty_ident(&mut noir_struct.name);
-    for (name, typ) in noir_struct.fields.iter_mut() {
-        empty_ident(name);
-        empty_unresolved_type(typ);
+    for field in noir_struct.fields.iter_mut() {
+        empty_ident(&mut field.name);
+        empty_unresolved_type(&mut field.typ);
     }
     empty_unresolved_generics(&mut noir_struct.generics);
 }
 
+fn empty_noir_enum(noir_enum: &mut NoirEnumeration) {
+    noir_enum.span = Default::default();
+    empty_ident(&mut noir_enum.name);
+    for variant in noir_enum.variants.iter_mut() {
+        empty_ident(&mut variant.name);
+        for typ in variant.parameters.iter_mut().flatten() {
+            empty_unresolved_type(typ);
+        }
+    }
+    empty_unresolved_generics(&mut noir_enum.generics);
+}
+
 fn empty_noir_type_alias(noir_type_alias: &mut NoirTypeAlias) {
     noir_type_alias.span = Default::default();
     empty_ident(&mut noir_type_alias.name);
@@ -216,6 +229,17 @@ fn empty_statement(statement: &mut Statement) {
         StatementKind::Expression(expression) => empty_expression(expression),
         StatementKind::Assign(assign_statement) => empty_assign_statement(assign_statement),
         StatementKind::For(for_loop_statement) => empty_for_loop_statement(for_loop_statement),
+        StatementKind::While(while_statement) => {
+            while_statement.span = Default::default();
+            empty_expression(&mut while_statement.condition);
+            empty_expression(&mut while_statement.block);
+        }
+        StatementKind::Loop(block) => empty_expression(block),
+        StatementKind::Return(value) => {
+            if let Some(value) = value {
+                empty_expression(value);
+            }
+        }
         StatementKind::Comptime(statement) => empty_statement(statement),
         StatementKind::Semi(expression) => empty_expression(expression),
         StatementKind::Break
@@ -259,6 +283,8 @@ fn empty_expression(expression: &mut Expression) {
         ExpressionKind::Cast(cast_expression) => empty_cast_expression(cast_expression),
         ExpressionKind::Infix(infix_expression) => empty_infix_expression(infix_expression),
         ExpressionKind::If(if_expression) => empty_if_expression(if_expression),
+        ExpressionKind::Match(match_expression) => empty_match_expression(match_expression),
+        ExpressionKind::Try(expression) => empty_expression(expression),
         ExpressionKind::Variable(path) => empty_path(path),
         ExpressionKind::Tuple(expressions) => {
             empty_expressions(expressions);
@@ -517,6 +543,44 @@ fn empty_if_expression(if_expression: &mut IfExpression) {
     }
 }
 
+fn empty_match_expression(match_expression: &mut MatchExpression) {
+    empty_expression(&mut match_expression.expression);
+    for (pattern, body) in match_expression.rules.iter_mut() {
+        empty_match_pattern(pattern);
+        empty_expression(body);
+    }
+}
+
+fn empty_match_pattern(pattern: &mut MatchPattern) {
+    match pattern {
+        MatchPattern::Wildcard(span) | MatchPattern::Literal(_, span) => {
+            *span = Default::default();
+        }
+        MatchPattern::Identifier(ident) => empty_ident(ident),
+        MatchPattern::Tuple(patterns, span) => {
+            *span = Default::default();
+            for pattern in patterns.iter_mut() {
+                empty_match_pattern(pattern);
+            }
+        }
+        MatchPattern::Struct(path, fields, span) => {
+            *span = Default::default();
+            empty_path(path);
+            for (name, pattern) in fields.iter_mut() {
+                empty_ident(name);
+                empty_match_pattern(pattern);
+            }
+        }
+        MatchPattern::Variant(path, arguments, span) => {
+            *span = Default::default();
+            empty_path(path);
+            for pattern in arguments.iter_mut().flatten() {
+                empty_match_pattern(pattern);
+            }
+        }
+    }
+}
+
 fn empty_lambda(lambda: &mut Lambda) {
     for (name, typ) in lambda.parameters.iter_mut() {
         empty_pattern(name);
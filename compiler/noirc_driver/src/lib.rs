@@ -569,7 +569,10 @@ pub fn compile_no_check(
         monomorphize(main_function, &mut context.def_interner)?
     };
 
-    let hash = fxhash::hash64(&program);
+    // Comptime inputs may change without changing the monomorphized program (e.g. if they are
+    // only used in comptime assertions), so they must also invalidate any cached artifact.
+    let comptime_inputs: Vec<_> = context.def_interner.comptime_inputs_read().collect();
+    let hash = fxhash::hash64(&(&program, comptime_inputs));
     let hashes_match = cached_program.as_ref().map_or(false, |program| program.hash == hash);
     if options.show_monomorphized {
         println!("{program}");
@@ -245,6 +245,7 @@ pub fn create_program(
             debug_variables.clone(),
             debug_functions.clone(),
             debug_types.clone(),
+            &program_artifact.program.unconstrained_functions,
         );
         program_artifact.add_circuit(circuit_artifact, is_main);
         is_main = false;
@@ -270,6 +271,7 @@ fn convert_generated_acir_into_circuit(
     debug_variables: DebugVariables,
     debug_functions: DebugFunctions,
     debug_types: DebugTypes,
+    brillig_functions: &[BrilligBytecode<FieldElement>],
 ) -> SsaCircuitArtifact {
     let opcodes = generated_acir.take_opcodes();
     let current_witness_index = generated_acir.current_witness_index().0;
@@ -322,7 +324,8 @@ fn convert_generated_acir_into_circuit(
         DebugInfo::new(locations, brillig_locations, debug_variables, debug_functions, debug_types);
 
     // Perform any ACIR-level optimizations
-    let (optimized_circuit, transformation_map) = acvm::compiler::optimize(circuit);
+    let (optimized_circuit, transformation_map) =
+        acvm::compiler::optimize_with_brillig(circuit, brillig_functions);
     debug_info.update_acir(transformation_map);
 
     SsaCircuitArtifact {
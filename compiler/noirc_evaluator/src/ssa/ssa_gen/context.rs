@@ -43,6 +43,10 @@ pub(super) struct FunctionContext<'a> {
     /// These are ordered such that an inner loop is at the end of the vector and
     /// outer loops are at the beginning. When a loop is finished, it is popped.
     loops: Vec<Loop>,
+
+    /// The block early `return`s jump to, if the current function has any, along with
+    /// every value returned by them. It is created when the first `return` is translated.
+    return_block: Option<(BasicBlockId, Vec<ValueId>)>,
 }
 
 /// Shared context for all functions during ssa codegen. This is the only
@@ -79,7 +83,9 @@ pub(super) struct SharedContext {
 #[derive(Copy, Clone)]
 pub(super) struct Loop {
     pub(super) loop_entry: BasicBlockId,
-    pub(super) loop_index: ValueId,
+    /// The index of a `for` loop, which `continue` increments before jumping back to the
+    /// loop entry. `while` and `loop` have no index.
+    pub(super) loop_index: Option<ValueId>,
     pub(super) loop_end: BasicBlockId,
 }
 
@@ -110,7 +116,8 @@ impl<'a> FunctionContext<'a> {
         let mut builder = FunctionBuilder::new(function_name, function_id);
         builder.set_runtime(runtime);
         let definitions = HashMap::default();
-        let mut this = Self { definitions, builder, shared_context, loops: Vec::new() };
+        let mut this =
+            Self { definitions, builder, shared_context, loops: Vec::new(), return_block: None };
         this.add_parameters_to_scope(parameters);
         this
     }
@@ -127,6 +134,7 @@ impl<'a> FunctionContext<'a> {
         force_brillig_runtime: bool,
     ) {
         self.definitions.clear();
+        self.return_block = None;
         if func.unconstrained || (force_brillig_runtime && func.inline_type != InlineType::Inline) {
             self.builder.new_brillig_function(func.name.clone(), id);
         } else {
@@ -936,7 +944,7 @@ impl<'a> FunctionContext<'a> {
     pub(crate) fn enter_loop(
         &mut self,
         loop_entry: BasicBlockId,
-        loop_index: ValueId,
+        loop_index: Option<ValueId>,
         loop_end: BasicBlockId,
     ) {
         self.loops.push(Loop { loop_entry, loop_index, loop_end });
@@ -950,6 +958,29 @@ impl<'a> FunctionContext<'a> {
         // The frontend should ensure break/continue are never used outside a loop
         *self.loops.last().expect("current_loop: not in a loop!")
     }
+
+    /// Returns the block early `return`s jump to, creating it if needed.
+    /// The block has a parameter for each of the given values returned by the function.
+    pub(crate) fn return_block(&mut self, return_values: &[ValueId]) -> BasicBlockId {
+        if let Some((return_block, returned_values)) = &mut self.return_block {
+            returned_values.extend_from_slice(return_values);
+            return *return_block;
+        }
+
+        let return_block = self.builder.insert_block();
+        for value in return_values {
+            let typ = self.builder.type_of_value(*value);
+            self.builder.add_block_parameter(return_block, typ);
+        }
+        self.return_block = Some((return_block, return_values.to_vec()));
+        return_block
+    }
+
+    /// Takes the return block of the current function, if any `return` was translated,
+    /// along with every value returned by an early `return`.
+    pub(crate) fn take_return_block(&mut self) -> Option<(BasicBlockId, Vec<ValueId>)> {
+        self.return_block.take()
+    }
 }
 
 /// True if the given operator cannot be encoded directly and needs
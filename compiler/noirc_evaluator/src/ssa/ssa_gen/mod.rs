@@ -127,8 +127,20 @@ impl<'a> FunctionContext<'a> {
     fn codegen_function_body(&mut self, body: &Expression) -> Result<(), RuntimeError> {
         let entry_block = self.increment_parameter_rcs();
         let return_value = self.codegen_expression(body)?;
-        let results = return_value.into_value_list(self);
-        self.end_scope(entry_block, &results);
+        let mut results = return_value.into_value_list(self);
+
+        match self.take_return_block() {
+            Some((return_block, mut returned_values)) => {
+                // Join the body's final value with any early returns. Parameters returned along
+                // any of these paths keep their reference counts, the rest are dropped here.
+                returned_values.extend_from_slice(&results);
+                self.builder.terminate_with_jmp(return_block, results);
+                self.builder.switch_to_block(return_block);
+                self.end_scope(entry_block, &returned_values);
+                results = self.builder.current_function.dfg.block_parameters(return_block).to_vec();
+            }
+            None => self.end_scope(entry_block, &results),
+        }
 
         self.builder.terminate_with_return(results);
         Ok(())
@@ -144,6 +156,8 @@ impl<'a> FunctionContext<'a> {
             Expression::Index(index) => self.codegen_index(index),
             Expression::Cast(cast) => self.codegen_cast(cast),
             Expression::For(for_expr) => self.codegen_for(for_expr),
+            Expression::While(while_expr) => self.codegen_while(while_expr),
+            Expression::Loop(body) => self.codegen_loop(body),
             Expression::If(if_expr) => self.codegen_if(if_expr),
             Expression::Tuple(tuple) => self.codegen_tuple(tuple),
             Expression::ExtractTupleField(tuple, index) => {
@@ -158,6 +172,7 @@ impl<'a> FunctionContext<'a> {
             Expression::Semi(semi) => self.codegen_semi(semi),
             Expression::Break => Ok(self.codegen_break()),
             Expression::Continue => Ok(self.codegen_continue()),
+            Expression::Return(expr) => self.codegen_return(expr),
         }
     }
 
@@ -489,7 +504,7 @@ impl<'a> FunctionContext<'a> {
 
         // Remember the blocks and variable used in case there are break/continue instructions
         // within the loop which need to jump to them.
-        self.enter_loop(loop_entry, loop_index, loop_end);
+        self.enter_loop(loop_entry, Some(loop_index), loop_end);
 
         self.builder.set_location(for_expr.start_range_location);
         let start_index = self.codegen_non_tuple_expression(&for_expr.start_range)?;
@@ -525,6 +540,67 @@ impl<'a> FunctionContext<'a> {
         Ok(Self::unit_value())
     }
 
+    /// Codegens a while loop, which is only allowed in unconstrained code.
+    ///
+    /// The expression `while cond { body }` is codegen'd as:
+    ///
+    ///   br loop_entry()
+    /// loop_entry():
+    ///   v0 = ... codegen cond ...
+    ///   brif v0, then: loop_body, else: loop_end
+    /// loop_body():
+    ///   ... codegen body ...
+    ///   br loop_entry()
+    /// loop_end():
+    ///   ... This is the current insert point after codegen_while finishes ...
+    fn codegen_while(&mut self, while_expr: &ast::While) -> Result<Values, RuntimeError> {
+        let loop_entry = self.builder.insert_block();
+        let loop_body = self.builder.insert_block();
+        let loop_end = self.builder.insert_block();
+
+        self.builder.terminate_with_jmp(loop_entry, Vec::new());
+
+        self.builder.switch_to_block(loop_entry);
+        let condition = self.codegen_non_tuple_expression(&while_expr.condition)?;
+        self.builder.terminate_with_jmpif(condition, loop_body, loop_end);
+
+        // `continue` re-evaluates the condition while `break` jumps straight to the end
+        self.enter_loop(loop_entry, None, loop_end);
+        self.builder.switch_to_block(loop_body);
+        self.codegen_expression(&while_expr.body)?;
+        self.builder.terminate_with_jmp(loop_entry, Vec::new());
+        self.exit_loop();
+
+        self.builder.switch_to_block(loop_end);
+        Ok(Self::unit_value())
+    }
+
+    /// Codegens a `loop { body }`, which is only allowed in unconstrained code:
+    ///
+    ///   br loop_body()
+    /// loop_body():
+    ///   ... codegen body ...
+    ///   br loop_body()
+    /// loop_end():
+    ///   ... This is the current insert point after codegen_loop finishes ...
+    ///
+    /// `loop_end` is only reachable through a `break` within the body.
+    fn codegen_loop(&mut self, body: &Expression) -> Result<Values, RuntimeError> {
+        let loop_body = self.builder.insert_block();
+        let loop_end = self.builder.insert_block();
+
+        self.builder.terminate_with_jmp(loop_body, Vec::new());
+
+        self.enter_loop(loop_body, None, loop_end);
+        self.builder.switch_to_block(loop_body);
+        self.codegen_expression(body)?;
+        self.builder.terminate_with_jmp(loop_body, Vec::new());
+        self.exit_loop();
+
+        self.builder.switch_to_block(loop_end);
+        Ok(Self::unit_value())
+    }
+
     /// Codegens an if expression, handling the case of what to do if there is no 'else'.
     ///
     /// For example, the expression `if cond { a } else { b }` is codegen'd as:
@@ -744,8 +820,23 @@ impl<'a> FunctionContext<'a> {
         let loop_ = self.current_loop();
 
         // Must remember to increment i before jumping
-        let new_loop_index = self.make_offset(loop_.loop_index, 1);
-        self.builder.terminate_with_jmp(loop_.loop_entry, vec![new_loop_index]);
+        let arguments = match loop_.loop_index {
+            Some(loop_index) => vec![self.make_offset(loop_index, 1)],
+            None => Vec::new(),
+        };
+        self.builder.terminate_with_jmp(loop_.loop_entry, arguments);
         Self::unit_value()
     }
+
+    /// Codegens an early return by jumping to the function's return block.
+    /// Any code following the return is unreachable and placed in a fresh block without predecessors.
+    fn codegen_return(&mut self, expr: &Expression) -> Result<Values, RuntimeError> {
+        let return_values = self.codegen_expression(expr)?.into_value_list(self);
+        let return_block = self.return_block(&return_values);
+        self.builder.terminate_with_jmp(return_block, return_values);
+
+        let unreachable_block = self.builder.insert_block();
+        self.builder.switch_to_block(unreachable_block);
+        Ok(Self::unit_value())
+    }
 }
@@ -0,0 +1,219 @@
+use std::fmt::Display;
+
+use acvm::FieldElement;
+use iter_extended::vecmap;
+use noirc_errors::Span;
+
+use crate::ast::{
+    BlockExpression, CallExpression, ConstructorExpression, Expression, ExpressionKind,
+    FunctionDefinition, FunctionReturnType, GenericTypeArgs, Ident, ItemVisibility, Literal,
+    NoirFunction, NoirStruct, Param, Path, Pattern, Statement, StatementKind, StructField,
+    TypeImpl, UnresolvedGeneric, UnresolvedGenerics, UnresolvedType, UnresolvedTypeData,
+    Visibility,
+};
+use crate::token::{Attributes, SecondaryAttribute};
+
+/// Name of the field holding the index of the active variant of a lowered enum.
+/// Field names starting with `$` can't be written in source programs so these
+/// never clash with user code.
+pub const ENUM_TAG_FIELD: &str = "$tag";
+
+/// Name of the field holding the arguments of the given variant of a lowered enum.
+pub fn enum_variant_field(variant: &str) -> String {
+    format!("${variant}")
+}
+
+/// Ast node for an enum
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoirEnumeration {
+    pub name: Ident,
+    pub attributes: Vec<SecondaryAttribute>,
+    pub generics: UnresolvedGenerics,
+    pub variants: Vec<NoirEnumVariant>,
+    pub span: Span,
+}
+
+/// A single variant of an enum: `Foo(A, B)`, or `Foo` if `parameters` is `None`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoirEnumVariant {
+    pub name: Ident,
+    pub parameters: Option<Vec<UnresolvedType>>,
+}
+
+impl NoirEnumeration {
+    /// Lowers this enum to a struct with a tag field followed by one field per
+    /// variant holding its arguments, along with an impl declaring a constructor
+    /// function for each variant.
+    ///
+    /// `zeroed` is the path to `std::mem::zeroed`, used to fill in the fields
+    /// of the variants which aren't being constructed.
+    pub fn into_struct_and_impl(self, zeroed: Path) -> (NoirStruct, TypeImpl) {
+        let span = self.span;
+        // These fields can't be named in source programs, and must be public so that
+        // `match` expressions in other modules can read them.
+        let mut fields = vec![StructField {
+            visibility: ItemVisibility::Public,
+            name: Ident::new(ENUM_TAG_FIELD.to_string(), span),
+            typ: UnresolvedTypeData::FieldElement.with_span(span),
+        }];
+
+        for variant in &self.variants {
+            let name =
+                Ident::new(enum_variant_field(&variant.name.0.contents), variant.name.span());
+            let typ = match &variant.parameters {
+                Some(parameters) => UnresolvedTypeData::Tuple(parameters.clone()),
+                None => UnresolvedTypeData::Unit,
+            };
+            let typ = typ.with_span(variant.name.span());
+            fields.push(StructField { visibility: ItemVisibility::Public, name, typ });
+        }
+
+        let self_type_args = vecmap(&self.generics, |generic| match generic {
+            UnresolvedGeneric::Variable(ident) | UnresolvedGeneric::Numeric { ident, .. } => {
+                UnresolvedTypeData::Named(Path::from_ident(ident.clone()), Default::default(), true)
+                    .with_span(ident.span())
+            }
+            UnresolvedGeneric::Resolved(id, span) => {
+                UnresolvedTypeData::Resolved(*id).with_span(*span)
+            }
+        });
+        let object_type = UnresolvedTypeData::Named(
+            Path::from_ident(self.name.clone()),
+            GenericTypeArgs { ordered_args: self_type_args, named_args: Vec::new() },
+            true,
+        )
+        .with_span(self.name.span());
+
+        let methods = self
+            .variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                let function = self.variant_constructor(index, variant, &zeroed);
+                (function, variant.name.span())
+            })
+            .collect();
+
+        let noir_struct = NoirStruct {
+            name: self.name,
+            attributes: self.attributes,
+            generics: self.generics.clone(),
+            fields,
+            span,
+        };
+
+        let type_impl = TypeImpl {
+            object_type,
+            type_span: span,
+            generics: self.generics,
+            where_clause: Vec::new(),
+            methods,
+        };
+
+        (noir_struct, type_impl)
+    }
+
+    /// Builds `pub fn Variant(arg0: A, arg1: B) -> Self`, which returns `Self` with
+    /// its tag set to `index` and the arguments stored in the variant's field.
+    fn variant_constructor(
+        &self,
+        index: usize,
+        variant: &NoirEnumVariant,
+        zeroed: &Path,
+    ) -> NoirFunction {
+        let span = variant.name.span();
+        let expression = |kind| Expression::new(kind, span);
+
+        let parameters =
+            vecmap(variant.parameters.iter().flatten().enumerate(), |(i, typ)| Param {
+                visibility: Visibility::Private,
+                pattern: Pattern::Identifier(Ident::new(format!("arg{i}"), span)),
+                typ: typ.clone(),
+                span,
+            });
+
+        let tag = Literal::Integer(FieldElement::from(index as u128), false);
+        let mut fields = vec![(
+            Ident::new(ENUM_TAG_FIELD.to_string(), span),
+            expression(ExpressionKind::Literal(tag)),
+        )];
+
+        for (other_index, other) in self.variants.iter().enumerate() {
+            let value = if other.parameters.is_none() {
+                ExpressionKind::Literal(Literal::Unit)
+            } else if other_index == index {
+                ExpressionKind::Tuple(vecmap(&parameters, |parameter| {
+                    let Pattern::Identifier(name) = &parameter.pattern else { unreachable!() };
+                    expression(ExpressionKind::Variable(Path::from_ident(name.clone())))
+                }))
+            } else {
+                ExpressionKind::Call(Box::new(CallExpression {
+                    func: Box::new(expression(ExpressionKind::Variable(zeroed.clone()))),
+                    arguments: Vec::new(),
+                    is_macro_call: false,
+                }))
+            };
+            let name = Ident::new(enum_variant_field(&other.name.0.contents), span);
+            fields.push((name, expression(value)));
+        }
+
+        let constructor = ExpressionKind::Constructor(Box::new(ConstructorExpression {
+            type_name: Path::from_single("Self".to_string(), span),
+            fields,
+            struct_type: None,
+        }));
+        let body = BlockExpression {
+            statements: vec![Statement {
+                kind: StatementKind::Expression(expression(constructor)),
+                span,
+            }],
+        };
+
+        let self_type = UnresolvedTypeData::Named(
+            Path::from_single("Self".to_string(), span),
+            Default::default(),
+            true,
+        );
+
+        NoirFunction::normal(FunctionDefinition {
+            name: variant.name.clone(),
+            attributes: Attributes::empty(),
+            is_unconstrained: false,
+            is_comptime: false,
+            visibility: ItemVisibility::Public,
+            generics: Vec::new(),
+            parameters,
+            body,
+            span,
+            where_clause: Vec::new(),
+            return_type: FunctionReturnType::Ty(self_type.with_span(span)),
+            return_visibility: Visibility::Private,
+        })
+    }
+}
+
+impl Display for NoirEnumeration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let generics = vecmap(&self.generics, |generic| generic.to_string());
+        let generics = if generics.is_empty() { "".into() } else { generics.join(", ") };
+
+        writeln!(f, "enum {}{} {{", self.name, generics)?;
+
+        for variant in self.variants.iter() {
+            writeln!(f, "    {variant},")?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl Display for NoirEnumVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(parameters) = &self.parameters {
+            let parameters = vecmap(parameters, ToString::to_string);
+            write!(f, "({})", parameters.join(", "))?;
+        }
+        Ok(())
+    }
+}
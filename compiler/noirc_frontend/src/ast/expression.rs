@@ -29,6 +29,8 @@ pub enum ExpressionKind {
     Cast(Box<CastExpression>),
     Infix(Box<InfixExpression>),
     If(Box<IfExpression>),
+    Match(Box<MatchExpression>),
+    Try(Box<Expression>),
     Variable(Path),
     Tuple(Vec<Expression>),
     Lambda(Box<Lambda>),
@@ -448,6 +450,43 @@ pub struct IfExpression {
     pub alternative: Option<Expression>,
 }
 
+/// `match expression { pattern => expression, ... }`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MatchExpression {
+    pub expression: Expression,
+    pub rules: Vec<(MatchPattern, Expression)>,
+}
+
+/// A pattern in a match rule. Unlike let patterns, these may be refutable.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MatchPattern {
+    /// `_`, matching anything
+    Wildcard(Span),
+    /// `x`, matching anything and binding it to a new variable
+    Identifier(Ident),
+    /// An integer or boolean literal such as `3`, `-1` or `true`
+    Literal(Literal, Span),
+    /// `(a, b)`
+    Tuple(Vec<MatchPattern>, Span),
+    /// `Foo { a, b: c }`, for a struct named `Foo`
+    Struct(Path, Vec<(Ident, MatchPattern)>, Span),
+    /// An enum variant: `Foo::Bar(a, b)`, or `Foo::Baz` if there are no arguments
+    Variant(Path, Option<Vec<MatchPattern>>, Span),
+}
+
+impl MatchPattern {
+    pub fn span(&self) -> Span {
+        match self {
+            MatchPattern::Identifier(ident) => ident.span(),
+            MatchPattern::Wildcard(span)
+            | MatchPattern::Literal(_, span)
+            | MatchPattern::Tuple(_, span)
+            | MatchPattern::Struct(_, _, span)
+            | MatchPattern::Variant(_, _, span) => *span,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Lambda {
     pub parameters: Vec<(Pattern, UnresolvedType)>,
@@ -595,6 +634,8 @@ impl Display for ExpressionKind {
             Cast(cast) => cast.fmt(f),
             Infix(infix) => infix.fmt(f),
             If(if_expr) => if_expr.fmt(f),
+            Match(match_expr) => match_expr.fmt(f),
+            Try(expr) => write!(f, "{expr}?"),
             Variable(path) => path.fmt(f),
             Constructor(constructor) => constructor.fmt(f),
             MemberAccess(access) => access.fmt(f),
@@ -766,6 +807,42 @@ impl Display for IfExpression {
     }
 }
 
+impl Display for MatchExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "match {} {{", self.expression)?;
+        for (pattern, branch) in &self.rules {
+            writeln!(f, "    {pattern} => {branch},")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Display for MatchPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchPattern::Wildcard(_) => write!(f, "_"),
+            MatchPattern::Identifier(name) => name.fmt(f),
+            MatchPattern::Literal(literal, _) => literal.fmt(f),
+            MatchPattern::Tuple(patterns, _) => {
+                let patterns = vecmap(patterns, ToString::to_string);
+                write!(f, "({})", patterns.join(", "))
+            }
+            MatchPattern::Struct(path, fields, _) => {
+                let fields = vecmap(fields, |(name, pattern)| format!("{name}: {pattern}"));
+                write!(f, "{} {{ {} }}", path, fields.join(", "))
+            }
+            MatchPattern::Variant(path, arguments, _) => {
+                write!(f, "{path}")?;
+                if let Some(arguments) = arguments {
+                    let arguments = vecmap(arguments, ToString::to_string);
+                    write!(f, "({})", arguments.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 impl Display for Lambda {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let parameters = vecmap(&self.parameters, |(name, r#type)| format!("{name}: {type}"));
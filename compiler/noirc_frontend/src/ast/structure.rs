@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use crate::ast::{Ident, UnresolvedGenerics, UnresolvedType};
+use crate::ast::{Ident, ItemVisibility, UnresolvedGenerics, UnresolvedType};
 use crate::token::SecondaryAttribute;
 
 use iter_extended::vecmap;
@@ -12,10 +12,19 @@ pub struct NoirStruct {
     pub name: Ident,
     pub attributes: Vec<SecondaryAttribute>,
     pub generics: UnresolvedGenerics,
-    pub fields: Vec<(Ident, UnresolvedType)>,
+    pub fields: Vec<StructField>,
     pub span: Span,
 }
 
+/// A single field of a struct, along with whether it can be accessed outside the
+/// module the struct is defined in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructField {
+    pub visibility: ItemVisibility,
+    pub name: Ident,
+    pub typ: UnresolvedType,
+}
+
 impl Display for NoirStruct {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let generics = vecmap(&self.generics, |generic| generic.to_string());
@@ -23,10 +32,19 @@ impl Display for NoirStruct {
 
         writeln!(f, "struct {}{} {{", self.name, generics)?;
 
-        for (name, typ) in self.fields.iter() {
-            writeln!(f, "    {name}: {typ},")?;
+        for field in self.fields.iter() {
+            writeln!(f, "    {field},")?;
         }
 
         write!(f, "}}")
     }
 }
+
+impl Display for StructField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.visibility != ItemVisibility::Private {
+            write!(f, "{} ", self.visibility)?;
+        }
+        write!(f, "{}: {}", self.name, self.typ)
+    }
+}
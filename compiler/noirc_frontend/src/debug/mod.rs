@@ -405,6 +405,15 @@ impl DebugInstrumenter {
                     self.walk_expr(alt);
                 }
             }
+            ast::ExpressionKind::Match(match_expr) => {
+                self.walk_expr(&mut match_expr.expression);
+                for (_, body) in match_expr.rules.iter_mut() {
+                    self.walk_expr(body);
+                }
+            }
+            ast::ExpressionKind::Try(expr) => {
+                self.walk_expr(expr);
+            }
             ast::ExpressionKind::Tuple(exprs) => {
                 exprs.iter_mut().for_each(|ref mut expr| {
                     self.walk_expr(expr);
@@ -460,6 +469,16 @@ impl DebugInstrumenter {
             ast::StatementKind::For(ref mut for_stmt) => {
                 self.walk_for(for_stmt);
             }
+            ast::StatementKind::While(ref mut while_stmt) => {
+                self.walk_expr(&mut while_stmt.condition);
+                self.walk_expr(&mut while_stmt.block);
+            }
+            ast::StatementKind::Loop(ref mut block) => {
+                self.walk_expr(block);
+            }
+            ast::StatementKind::Return(Some(ref mut value)) => {
+                self.walk_expr(value);
+            }
             _ => {} // Constrain, Error
         }
     }
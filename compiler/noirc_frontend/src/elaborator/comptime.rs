@@ -14,7 +14,9 @@ use crate::{
                 UnresolvedTrait, UnresolvedTraitImpl,
             },
             dc_mod,
+            errors::{DefCollectorErrorKind, DuplicateType},
         },
+        def_map::ModuleId,
         resolution::errors::ResolverError,
     },
     hir_def::expr::HirIdent,
@@ -22,7 +24,7 @@ use crate::{
     macros_api::{
         Expression, ExpressionKind, HirExpression, NodeInterner, SecondaryAttribute, StructId,
     },
-    node_interner::{DefinitionKind, DependencyId, FuncId, TraitId},
+    node_interner::{DefinitionKind, DependencyId, ExprId, FuncId, TraitId},
     parser::{self, TopLevelStatement},
     Type, TypeBindings,
 };
@@ -70,6 +72,30 @@ impl<'context> Elaborator<'context> {
         result
     }
 
+    /// Elaborates an expression as if it were written at the start of `function`'s body,
+    /// with the function's parameters in scope. Expects to be called on an elaborator
+    /// created by `elaborate_item_from_comptime` for the same function.
+    pub(crate) fn elaborate_expression_in_function(
+        &mut self,
+        function: FuncId,
+        expression: Expression,
+    ) -> ExprId {
+        let meta = self.interner.function_meta(&function);
+        let parameters = meta.parameter_idents.clone();
+        self.self_type = meta.self_type.clone();
+
+        // Parameters go in their own scope so that they shadow any comptime variables
+        self.scopes.start_scope();
+        for parameter in parameters {
+            let name = self.interner.definition_name(parameter.id).to_owned();
+            self.add_existing_variable_to_scope(name, parameter, false);
+        }
+
+        let expr_id = self.elaborate_expression(expression).0;
+        self.scopes.end_scope();
+        expr_id
+    }
+
     fn populate_scope_from_comptime_scopes(&mut self) {
         // Take the comptime scope to be our runtime scope.
         // Iterate from global scope to the most local scope so that the
@@ -246,6 +272,26 @@ impl<'context> Elaborator<'context> {
         Ok(new_arguments)
     }
 
+    /// Adds items produced at compile-time to the given module, elaborating them immediately
+    /// so that they can be used by code elaborated afterward.
+    pub(crate) fn add_items_to_module(
+        &mut self,
+        module: ModuleId,
+        items: Vec<TopLevelStatement>,
+        location: Location,
+    ) {
+        self.crate_id = module.krate;
+        self.local_module = module.local_id;
+        self.file = self.get_module(module).location.file;
+
+        let mut generated_items = CollectedItems::default();
+        self.add_items(items, &mut generated_items, location);
+
+        if !generated_items.is_empty() {
+            self.elaborate_items(generated_items);
+        }
+    }
+
     fn add_items(
         &mut self,
         items: Vec<TopLevelStatement>,
@@ -276,6 +322,17 @@ impl<'context> Elaborator<'context> {
                     self.interner.register_function(id, &function.def);
                 }
 
+                // Declare the function in its module so that it can be referred to by name
+                let name = function.name_ident().clone();
+                let visibility = function.def.visibility;
+                let module_data = Self::get_module_mut(self.def_maps, module);
+                let result = module_data.declare_function(name, visibility, id);
+
+                if let Err((first_def, second_def)) = result {
+                    let typ = DuplicateType::Function;
+                    self.push_err(DefCollectorErrorKind::Duplicate { typ, first_def, second_def });
+                }
+
                 let functions = vec![(self.local_module, id, function)];
                 generated_items.functions.push(UnresolvedFunctions {
                     file_id: self.file,
@@ -335,6 +392,7 @@ impl<'context> Elaborator<'context> {
             TopLevelStatement::Module(_)
             | TopLevelStatement::Import(_)
             | TopLevelStatement::Struct(_)
+            | TopLevelStatement::Enum(_)
             | TopLevelStatement::Trait(_)
             | TopLevelStatement::Impl(_)
             | TopLevelStatement::TypeAlias(_)
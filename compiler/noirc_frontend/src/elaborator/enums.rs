@@ -0,0 +1,517 @@
+use acvm::FieldElement;
+use iter_extended::vecmap;
+use noirc_errors::{Span, Spanned};
+
+use crate::{
+    ast::{
+        enum_variant_field, BinaryOpKind, BlockExpression, CallExpression, Expression,
+        ExpressionKind, Ident, IfExpression, InfixExpression, Literal, MatchExpression,
+        MatchPattern, MemberAccessExpression, Path, PathKind, Pattern, Statement, StatementKind,
+        UnresolvedTypeData, ENUM_TAG_FIELD,
+    },
+    hir::{
+        def_map::ModuleDefId,
+        resolution::{
+            errors::ResolverError,
+            path_resolver::{PathResolver, StandardPathResolver},
+        },
+        type_check::TypeCheckError,
+    },
+    node_interner::ExprId,
+    Shared, StructType, Type,
+};
+
+use super::{types::SELF_TYPE_NAME, Elaborator};
+
+/// Name of the hidden variable holding the value being matched on.
+/// Names starting with `$` can't be written in source programs.
+const MATCH_VARIABLE: &str = "$match";
+
+/// A simplified pattern, used to check match expressions for exhaustiveness
+/// and unreachable rules.
+#[derive(Debug, Clone)]
+enum Pat {
+    Wildcard,
+    Constructor(Constructor, Vec<Pat>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Constructor {
+    /// The variant of an enum at the given index
+    Variant(usize),
+    Bool(bool),
+    Integer(FieldElement, /*is_negative*/ bool),
+    /// The only constructor of tuples and structs
+    Product,
+}
+
+/// The tests and variable bindings needed to match a single rule's pattern.
+#[derive(Default)]
+struct LoweredPattern {
+    conditions: Vec<Expression>,
+    bindings: Vec<(Ident, Expression)>,
+}
+
+impl<'context> Elaborator<'context> {
+    /// Elaborates a match expression by binding the matched value to a hidden variable
+    /// and lowering the rules to a chain of if expressions, each one testing a rule's pattern.
+    /// Enum patterns test the tag field of the lowered enum, so later passes only see
+    /// struct accesses and ifs.
+    pub(super) fn elaborate_match(
+        &mut self,
+        match_expr: MatchExpression,
+        span: Span,
+    ) -> (ExprId, Type) {
+        let expression_span = match_expr.expression.span;
+        let (scrutinee, scrutinee_type) = self.elaborate_expression(match_expr.expression);
+
+        let variable = Ident::new(MATCH_VARIABLE.to_string(), expression_span);
+        let access = Expression::new(
+            ExpressionKind::Variable(Path::from_ident(variable.clone())),
+            expression_span,
+        );
+
+        let mut rows = Vec::new();
+        let mut rules = Vec::new();
+        for (pattern, body) in match_expr.rules {
+            let pattern_span = pattern.span();
+            let mut lowered = LoweredPattern::default();
+            let pattern =
+                self.lower_match_pattern(pattern, &scrutinee_type, access.clone(), &mut lowered);
+
+            let types = [scrutinee_type.clone()];
+            if self.is_useful(&rows, &[pattern.clone()], &types).is_none() {
+                self.push_err(TypeCheckError::UnreachableMatchRule { span: pattern_span });
+            }
+
+            rows.push(vec![pattern]);
+            rules.push((lowered, body));
+        }
+
+        if let Some(missing) = self.is_useful(&rows, &[Pat::Wildcard], &[scrutinee_type]) {
+            let missing = missing.into_iter().next().unwrap_or_default();
+            self.push_err(TypeCheckError::NonExhaustiveMatch { missing, span });
+        }
+
+        let mut chain = None;
+        for (lowered, body) in rules.into_iter().rev() {
+            let body_span = body.span;
+            let mut statements = vecmap(lowered.bindings, |(name, value)| {
+                let span = name.span();
+                let typ = UnresolvedTypeData::Unspecified.with_span(span);
+                let kind = StatementKind::new_let(((Pattern::Identifier(name), typ), value));
+                Statement { kind, span }
+            });
+            statements.push(Statement { kind: StatementKind::Expression(body), span: body_span });
+            let consequence =
+                Expression::new(ExpressionKind::Block(BlockExpression { statements }), body_span);
+
+            let condition = lowered.conditions.into_iter().reduce(|lhs, rhs| {
+                let span = lhs.span.merge(rhs.span);
+                infix(lhs, BinaryOpKind::And, rhs, span)
+            });
+
+            chain = Some(match (condition, chain) {
+                (Some(condition), Some(alternative)) => {
+                    let alternative = Some(alternative);
+                    let if_expr = IfExpression { condition, consequence, alternative };
+                    Expression::new(ExpressionKind::If(Box::new(if_expr)), span)
+                }
+                // The last rule doesn't need to be tested if the match is exhaustive,
+                // and any rules after one without conditions are unreachable.
+                _ => consequence,
+            });
+        }
+
+        let scrutinee = Expression::new(ExpressionKind::Resolved(scrutinee), expression_span);
+        let typ = UnresolvedTypeData::Unspecified.with_span(expression_span);
+        let kind = StatementKind::new_let(((Pattern::Identifier(variable), typ), scrutinee));
+        let mut statements = vec![Statement { kind, span: expression_span }];
+
+        if let Some(chain) = chain {
+            statements.push(Statement { kind: StatementKind::Expression(chain), span });
+        }
+
+        let block = ExpressionKind::Block(BlockExpression { statements });
+        self.elaborate_expression(Expression::new(block, span))
+    }
+
+    /// Checks the given pattern against the type of the value it matches, collecting
+    /// the conditions and bindings needed to match it against `access`.
+    /// Patterns with errors are treated as wildcards.
+    fn lower_match_pattern(
+        &mut self,
+        pattern: MatchPattern,
+        typ: &Type,
+        access: Expression,
+        lowered: &mut LoweredPattern,
+    ) -> Pat {
+        match pattern {
+            MatchPattern::Wildcard(_) => Pat::Wildcard,
+            MatchPattern::Identifier(name) => {
+                lowered.bindings.push((name, access));
+                Pat::Wildcard
+            }
+            MatchPattern::Literal(literal, span) => {
+                let constructor = match &literal {
+                    Literal::Bool(value) => Constructor::Bool(*value),
+                    Literal::Integer(value, is_negative) => {
+                        Constructor::Integer(*value, *is_negative)
+                    }
+                    _ => unreachable!("Only integer and boolean literals are parsed as patterns"),
+                };
+                let literal = Expression::new(ExpressionKind::Literal(literal), span);
+                lowered.conditions.push(infix(access, BinaryOpKind::Equal, literal, span));
+                Pat::Constructor(constructor, Vec::new())
+            }
+            MatchPattern::Tuple(patterns, span) => {
+                let element_types = vecmap(&patterns, |_| self.interner.next_type_variable());
+                let tuple_type = if patterns.is_empty() {
+                    Type::Unit
+                } else {
+                    Type::Tuple(element_types.clone())
+                };
+
+                if !self.unify_pattern_type(&tuple_type, typ, span) {
+                    return Pat::Wildcard;
+                }
+
+                let mut arguments = Vec::with_capacity(patterns.len());
+                for (index, (pattern, element_type)) in
+                    patterns.into_iter().zip(element_types).enumerate()
+                {
+                    let access = member_access(access.clone(), index.to_string(), span);
+                    arguments.push(self.lower_match_pattern(
+                        pattern,
+                        &element_type,
+                        access,
+                        lowered,
+                    ));
+                }
+                Pat::Constructor(Constructor::Product, arguments)
+            }
+            MatchPattern::Struct(path, mut fields, span) => {
+                let (struct_type, generics) = match self.lookup_type_or_error(path) {
+                    Some(Type::Struct(struct_type, generics)) => (struct_type, generics),
+                    Some(typ) => {
+                        self.push_err(ResolverError::NonStructUsedInConstructor { typ, span });
+                        return Pat::Wildcard;
+                    }
+                    None => return Pat::Wildcard,
+                };
+
+                if struct_type.borrow().is_enum() {
+                    let name = struct_type.borrow().name.to_string();
+                    self.push_err(TypeCheckError::StructSyntaxOnEnum { name, span });
+                    return Pat::Wildcard;
+                }
+
+                let struct_type_with_generics = Type::Struct(struct_type.clone(), generics.clone());
+                if !self.unify_pattern_type(&struct_type_with_generics, typ, span) {
+                    return Pat::Wildcard;
+                }
+
+                let field_types = struct_type.borrow().get_fields(&generics);
+                let mut arguments = Vec::with_capacity(field_types.len());
+                for (name, field_type) in field_types {
+                    let position = fields.iter().position(|(field, _)| field.0.contents == name);
+                    let argument = match position {
+                        Some(position) => {
+                            let (_, pattern) = fields.remove(position);
+                            let access = member_access(access.clone(), name, span);
+                            self.lower_match_pattern(pattern, &field_type, access, lowered)
+                        }
+                        None => Pat::Wildcard,
+                    };
+                    arguments.push(argument);
+                }
+
+                for (field, _) in fields {
+                    let struct_definition = struct_type.borrow().name.clone();
+                    self.push_err(ResolverError::NoSuchField { field, struct_definition });
+                }
+
+                Pat::Constructor(Constructor::Product, arguments)
+            }
+            MatchPattern::Variant(path, arguments, span) => {
+                let Some((enum_type, index)) = self.lookup_enum_variant(&path) else {
+                    let path = path.to_string();
+                    self.push_err(TypeCheckError::ExpectedEnumVariant { path, span });
+                    return Pat::Wildcard;
+                };
+
+                let generics = enum_type.borrow().instantiate(self.interner);
+                let expected = Type::Struct(enum_type.clone(), generics.clone());
+                if !self.unify_pattern_type(&expected, typ, span) {
+                    return Pat::Wildcard;
+                }
+
+                let (argument_types, variant_field) = {
+                    let enum_type = enum_type.borrow();
+                    let variant = &enum_type.variants.as_ref().expect("Expected an enum")[index];
+                    let variant_field = enum_variant_field(&variant.name.0.contents);
+                    (enum_type.variant_arguments(index, &generics), variant_field)
+                };
+                let arguments = arguments.unwrap_or_default();
+
+                if arguments.len() != argument_types.len() {
+                    let expected = argument_types.len();
+                    let found = arguments.len();
+                    self.push_err(TypeCheckError::ArityMisMatch { expected, found, span });
+                    return Pat::Wildcard;
+                }
+
+                let tag = member_access(access.clone(), ENUM_TAG_FIELD.to_string(), span);
+                let index_literal = Literal::Integer(FieldElement::from(index as u128), false);
+                let index_literal = Expression::new(ExpressionKind::Literal(index_literal), span);
+                lowered.conditions.push(infix(tag, BinaryOpKind::Equal, index_literal, span));
+
+                let variant_access = member_access(access, variant_field, span);
+
+                let mut patterns = Vec::with_capacity(arguments.len());
+                for (position, (argument, argument_type)) in
+                    arguments.into_iter().zip(argument_types).enumerate()
+                {
+                    let access = member_access(variant_access.clone(), position.to_string(), span);
+                    patterns.push(self.lower_match_pattern(
+                        argument,
+                        &argument_type,
+                        access,
+                        lowered,
+                    ));
+                }
+                Pat::Constructor(Constructor::Variant(index), patterns)
+            }
+        }
+    }
+
+    /// Unifies the type of a pattern with the type of the value it matches,
+    /// returning false if they don't match.
+    fn unify_pattern_type(&mut self, pattern_type: &Type, expected: &Type, span: Span) -> bool {
+        let error_count = self.errors.len();
+        self.unify(pattern_type, expected, || TypeCheckError::TypeMismatch {
+            expected_typ: expected.to_string(),
+            expr_typ: pattern_type.to_string(),
+            expr_span: span,
+        });
+        self.errors.len() == error_count
+    }
+
+    /// If the given path refers to a variant of an enum, such as `Foo::Bar`,
+    /// returns the enum along with the index of the variant.
+    pub(super) fn lookup_enum_variant(
+        &mut self,
+        path: &Path,
+    ) -> Option<(Shared<StructType>, usize)> {
+        let (variant, prefix) = path.segments.split_last()?;
+        if prefix.is_empty() {
+            return None;
+        }
+
+        let is_self_type = path.kind == PathKind::Plain
+            && prefix.len() == 1
+            && prefix[0].ident.0.contents == SELF_TYPE_NAME;
+
+        let enum_type = if is_self_type {
+            match &self.self_type {
+                Some(Type::Struct(struct_type, _)) => struct_type.clone(),
+                _ => return None,
+            }
+        } else {
+            // The full path is resolved again when elaborated, so resolve the prefix
+            // without recording any references to it.
+            let prefix = Path { segments: prefix.to_vec(), kind: path.kind, span: path.span };
+            let resolver = StandardPathResolver::new(self.module_id());
+            match resolver.resolve(self.def_maps, prefix, &mut None).ok()?.module_def_id {
+                ModuleDefId::TypeId(struct_id) => self.get_struct(struct_id),
+                _ => return None,
+            }
+        };
+
+        let index = enum_type.borrow().variant_index(&variant.ident.0.contents)?;
+        Some((enum_type, index))
+    }
+
+    /// Elaborates a path to an enum variant without arguments, such as `Foo::Bar`,
+    /// to a call to the variant's constructor. Returns `None` for any other path.
+    pub(super) fn elaborate_unit_variant(
+        &mut self,
+        path: &Path,
+        span: Span,
+    ) -> Option<(ExprId, Type)> {
+        let (enum_type, index) = self.lookup_enum_variant(path)?;
+        if !enum_type.borrow().variants.as_ref()?[index].is_unit {
+            return None;
+        }
+
+        let (function, _) = self.elaborate_variable(path.clone());
+        let func = Box::new(Expression::new(ExpressionKind::Resolved(function), span));
+        let call = CallExpression { func, arguments: Vec::new(), is_macro_call: false };
+        Some(self.elaborate_expression(Expression::new(ExpressionKind::Call(Box::new(call)), span)))
+    }
+
+    /// Returns whether `row` matches any value which isn't matched by any of `rows`,
+    /// where `types` holds the type of each column. If so, an example of such a value
+    /// is returned, rendered as one pattern per column.
+    ///
+    /// This is the usefulness algorithm from "Warnings for pattern matching" by Luc Maranget.
+    fn is_useful(&self, rows: &[Vec<Pat>], row: &[Pat], types: &[Type]) -> Option<Vec<String>> {
+        let Some((head, rest)) = row.split_first() else {
+            return rows.is_empty().then(Vec::new);
+        };
+        let typ = types[0].follow_bindings();
+        let rest_types = &types[1..];
+
+        let check_constructor =
+            |constructor: &Constructor, arguments: Vec<Pat>, argument_types: Vec<Type>| {
+                let arity = arguments.len();
+                let rows = specialize(rows, constructor, arity);
+                let row: Vec<_> = arguments.into_iter().chain(rest.iter().cloned()).collect();
+                let types: Vec<_> =
+                    argument_types.into_iter().chain(rest_types.iter().cloned()).collect();
+
+                let mut witness = self.is_useful(&rows, &row, &types)?;
+                let arguments = witness.drain(..arity).collect();
+                witness.insert(0, display_constructor(&typ, constructor, arguments));
+                Some(witness)
+            };
+
+        match head {
+            Pat::Constructor(constructor, arguments) => {
+                let argument_types = constructors(&typ)
+                    .and_then(|all| all.into_iter().find(|(other, _)| other == constructor))
+                    .map(|(_, types)| types)
+                    .filter(|types| types.len() == arguments.len())
+                    .unwrap_or_else(|| vec![Type::Error; arguments.len()]);
+
+                check_constructor(constructor, arguments.clone(), argument_types)
+            }
+            Pat::Wildcard => {
+                let used: Vec<_> = rows
+                    .iter()
+                    .filter_map(|row| match &row[0] {
+                        Pat::Constructor(constructor, _) => Some(constructor.clone()),
+                        Pat::Wildcard => None,
+                    })
+                    .collect();
+                let all = constructors(&typ);
+
+                // If every constructor of the type is used, the wildcard is useful if it is
+                // useful in place of any one of them.
+                if let Some(all) = &all {
+                    if all.iter().all(|(constructor, _)| used.contains(constructor)) {
+                        return all.iter().find_map(|(constructor, argument_types)| {
+                            let arguments = vec![Pat::Wildcard; argument_types.len()];
+                            check_constructor(constructor, arguments, argument_types.clone())
+                        });
+                    }
+                }
+
+                // Otherwise it is useful if it is useful against the rows starting with a wildcard
+                let default_rows = rows
+                    .iter()
+                    .filter(|row| matches!(row[0], Pat::Wildcard))
+                    .map(|row| row[1..].to_vec())
+                    .collect::<Vec<_>>();
+                let mut witness = self.is_useful(&default_rows, rest, rest_types)?;
+
+                let missing = all
+                    .filter(|_| !used.is_empty())
+                    .and_then(|all| {
+                        all.into_iter().find(|(constructor, _)| !used.contains(constructor))
+                    })
+                    .map(|(constructor, argument_types)| {
+                        let arguments = vec!["_".to_string(); argument_types.len()];
+                        display_constructor(&typ, &constructor, arguments)
+                    });
+                witness.insert(0, missing.unwrap_or_else(|| "_".to_string()));
+                Some(witness)
+            }
+        }
+    }
+}
+
+/// Returns every constructor of the given type along with the types of its arguments,
+/// or `None` if the type has too many values to list them, as with integers.
+fn constructors(typ: &Type) -> Option<Vec<(Constructor, Vec<Type>)>> {
+    match typ {
+        Type::Bool => Some(vec![
+            (Constructor::Bool(false), Vec::new()),
+            (Constructor::Bool(true), Vec::new()),
+        ]),
+        Type::Unit => Some(vec![(Constructor::Product, Vec::new())]),
+        Type::Tuple(elements) => Some(vec![(Constructor::Product, elements.clone())]),
+        Type::Struct(struct_type, generics) => {
+            let struct_type = struct_type.borrow();
+            match &struct_type.variants {
+                Some(variants) => Some(vecmap(0..variants.len(), |index| {
+                    (Constructor::Variant(index), struct_type.variant_arguments(index, generics))
+                })),
+                None => {
+                    let fields = vecmap(struct_type.get_fields(generics), |(_, typ)| typ);
+                    Some(vec![(Constructor::Product, fields)])
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Keeps the rows whose first pattern could match the given constructor,
+/// replacing that pattern with the patterns for the constructor's arguments.
+fn specialize(rows: &[Vec<Pat>], constructor: &Constructor, arity: usize) -> Vec<Vec<Pat>> {
+    let rows = rows.iter().filter_map(|row| {
+        let (head, rest) = row.split_first()?;
+        let mut specialized = match head {
+            Pat::Wildcard => vec![Pat::Wildcard; arity],
+            Pat::Constructor(other, arguments)
+                if other == constructor && arguments.len() == arity =>
+            {
+                arguments.clone()
+            }
+            Pat::Constructor(..) => return None,
+        };
+        specialized.extend_from_slice(rest);
+        Some(specialized)
+    });
+    rows.collect()
+}
+
+/// Renders a pattern for the given constructor, used to report missing patterns.
+fn display_constructor(typ: &Type, constructor: &Constructor, arguments: Vec<String>) -> String {
+    match (constructor, typ) {
+        (Constructor::Variant(index), Type::Struct(enum_type, _)) => {
+            let enum_type = enum_type.borrow();
+            let variant = &enum_type.variants.as_ref().expect("Expected an enum")[*index];
+            if variant.is_unit {
+                format!("{}::{}", enum_type.name, variant.name)
+            } else {
+                format!("{}::{}({})", enum_type.name, variant.name, arguments.join(", "))
+            }
+        }
+        (Constructor::Bool(value), _) => value.to_string(),
+        (Constructor::Integer(value, true), _) => format!("-{value}"),
+        (Constructor::Integer(value, false), _) => value.to_string(),
+        (Constructor::Product, Type::Struct(struct_type, generics)) => {
+            let struct_type = struct_type.borrow();
+            let fields = struct_type.get_fields(generics).into_iter().zip(arguments);
+            let fields = vecmap(fields, |((name, _), argument)| format!("{name}: {argument}"));
+            format!("{} {{ {} }}", struct_type.name, fields.join(", "))
+        }
+        (Constructor::Product, _) | (Constructor::Variant(_), _) => {
+            format!("({})", arguments.join(", "))
+        }
+    }
+}
+
+fn member_access(lhs: Expression, field: String, span: Span) -> Expression {
+    let rhs = Ident::new(field, span);
+    let access = MemberAccessExpression { lhs, rhs };
+    Expression::new(ExpressionKind::MemberAccess(Box::new(access)), span)
+}
+
+fn infix(lhs: Expression, operator: BinaryOpKind, rhs: Expression, span: Span) -> Expression {
+    let operator = Spanned::from(span, operator);
+    let infix = InfixExpression { lhs, operator, rhs };
+    Expression::new(ExpressionKind::Infix(Box::new(infix)), span)
+}
@@ -5,8 +5,9 @@ use rustc_hash::FxHashSet as HashSet;
 
 use crate::{
     ast::{
-        ArrayLiteral, ConstructorExpression, IfExpression, InfixExpression, Lambda,
-        UnresolvedTypeExpression,
+        ArrayLiteral, ConstructorExpression, IfExpression, InfixExpression, Lambda, Path, PathKind,
+        PathSegment, Pattern, Statement, StatementKind, UnresolvedTypeData,
+        UnresolvedTypeExpression, ENUM_TAG_FIELD,
     },
     hir::{
         comptime::{self, InterpreterError},
@@ -34,6 +35,10 @@ use crate::{
 
 use super::{Elaborator, LambdaContext};
 
+/// Name of the hidden variable holding the operand of a `?` expression.
+/// Names starting with `$` can't be written in source programs.
+const TRY_VARIABLE: &str = "$try";
+
 impl<'context> Elaborator<'context> {
     pub(crate) fn elaborate_expression(&mut self, expr: Expression) -> (ExprId, Type) {
         let (hir_expr, typ) = match expr.kind {
@@ -50,7 +55,14 @@ impl<'context> Elaborator<'context> {
             ExpressionKind::Cast(cast) => self.elaborate_cast(*cast, expr.span),
             ExpressionKind::Infix(infix) => return self.elaborate_infix(*infix, expr.span),
             ExpressionKind::If(if_) => self.elaborate_if(*if_),
-            ExpressionKind::Variable(variable) => return self.elaborate_variable(variable),
+            ExpressionKind::Match(match_) => return self.elaborate_match(*match_, expr.span),
+            ExpressionKind::Try(operand) => return self.elaborate_try(*operand, expr.span),
+            ExpressionKind::Variable(variable) => {
+                if let Some(variant) = self.elaborate_unit_variant(&variable, expr.span) {
+                    return variant;
+                }
+                return self.elaborate_variable(variable);
+            }
             ExpressionKind::Tuple(tuple) => self.elaborate_tuple(tuple),
             ExpressionKind::Lambda(lambda) => self.elaborate_lambda(*lambda),
             ExpressionKind::Parenthesized(expr) => return self.elaborate_expression(*expr),
@@ -87,6 +99,32 @@ impl<'context> Elaborator<'context> {
 
     fn elaborate_block_expression(&mut self, block: BlockExpression) -> (HirBlockExpression, Type) {
         self.push_scope();
+        let result = self.elaborate_block_statements(block);
+        self.pop_scope();
+        result
+    }
+
+    /// Elaborates a function's body, recording the variables still in scope at its end
+    /// so they can be looked up by name afterward, e.g. by `FunctionDefinition::variable_type`.
+    pub(super) fn elaborate_function_body(
+        &mut self,
+        id: FuncId,
+        body: BlockExpression,
+    ) -> (HirExpression, Type) {
+        self.push_scope();
+        let (block, typ) = self.elaborate_block_statements(body);
+
+        // Later scopes shadow earlier ones, so the body's variables take precedence over parameters
+        let scopes = &self.scopes.current_scope_tree().0;
+        let variables = scopes.iter().flat_map(|scope| &scope.0);
+        let variables = variables.map(|(name, meta)| (name.clone(), meta.ident.id)).collect();
+        self.interner.set_function_variables(id, variables);
+
+        self.pop_scope();
+        (HirExpression::Block(block), typ)
+    }
+
+    fn elaborate_block_statements(&mut self, block: BlockExpression) -> (HirBlockExpression, Type) {
         let mut block_type = Type::Unit;
         let mut statements = Vec::with_capacity(block.statements.len());
 
@@ -109,7 +147,6 @@ impl<'context> Elaborator<'context> {
             }
         }
 
-        self.pop_scope();
         (HirBlockExpression { statements }, block_type)
     }
 
@@ -452,6 +489,15 @@ impl<'context> Elaborator<'context> {
             }
         };
 
+        // Only the constructor functions generated for each variant may use struct syntax on an enum
+        let is_variant_constructor =
+            constructor.fields.iter().any(|(name, _)| name.0.contents == ENUM_TAG_FIELD);
+        if r#type.borrow().is_enum() && !is_variant_constructor {
+            let name = r#type.borrow().name.to_string();
+            self.push_err(TypeCheckError::StructSyntaxOnEnum { name, span });
+            return (HirExpression::Error, Type::Error);
+        }
+
         let turbofish_span = last_segment.turbofish_span();
 
         let struct_generics = self.resolve_struct_turbofish_generics(
@@ -530,11 +576,21 @@ impl<'context> Elaborator<'context> {
             }
 
             if let Some(expected_index) = expected_index {
+                let struct_type = struct_type.borrow();
                 self.interner.add_struct_member_reference(
-                    struct_type.borrow().id,
+                    struct_type.id,
                     expected_index,
                     Location::new(field_name.span(), self.file),
                 );
+
+                let visibility = struct_type.field_at(expected_index).visibility;
+                let name = &field_name.0.contents;
+                self.check_struct_field_visibility(
+                    &struct_type,
+                    name,
+                    visibility,
+                    field_name.span(),
+                );
             }
 
             ret.push((field_name, resolved));
@@ -683,6 +739,104 @@ impl<'context> Elaborator<'context> {
         (HirExpression::If(if_expr), ret_type)
     }
 
+    /// Elaborates `operand?` on an `Option` or `Result` by desugaring it to an early return
+    /// of the `None` or `Err` case, followed by unwrapping the operand. For a `Result`:
+    ///
+    /// ```noir
+    /// {
+    ///     let $try = operand;
+    ///     if $try.is_err() {
+    ///         return std::result::Result::Err($try.unwrap_err_unchecked());
+    ///     }
+    ///     $try.unwrap_unchecked()
+    /// }
+    /// ```
+    fn elaborate_try(&mut self, operand: Expression, span: Span) -> (ExprId, Type) {
+        let in_constrained_function = self.in_constrained_function();
+        if in_constrained_function {
+            self.push_err(ResolverError::TryInConstrainedFn { span });
+        }
+
+        let operand_span = operand.span;
+        let (operand, operand_type) = self.elaborate_expression(operand);
+
+        let is_result = match operand_type.follow_bindings() {
+            Type::Struct(struct_type, _) => {
+                let struct_type = struct_type.borrow();
+                match struct_type.name.0.contents.as_str() {
+                    "Option" if struct_type.id.krate().is_stdlib() => Some(false),
+                    "Result" if struct_type.id.krate().is_stdlib() => Some(true),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        let Some(is_result) = is_result else {
+            if operand_type != Type::Error {
+                let typ = operand_type;
+                self.push_err(TypeCheckError::InvalidTryOperand { typ, span: operand_span });
+            }
+            return (operand, Type::Error);
+        };
+
+        // Desugaring would only repeat the error above through the early `return`
+        if in_constrained_function {
+            return (operand, Type::Error);
+        }
+
+        let variable = Ident::new(TRY_VARIABLE.to_string(), operand_span);
+        let access = || {
+            let path = Path::from_ident(variable.clone());
+            Expression::new(ExpressionKind::Variable(path), operand_span)
+        };
+
+        let (check, residual_segments, residual_arguments) = if is_result {
+            let error = method_call(access(), "unwrap_err_unchecked", span);
+            ("is_err", ["result", "Result", "Err"], vec![error])
+        } else {
+            ("is_none", ["option", "Option", "none"], Vec::new())
+        };
+
+        let residual_function = self.stdlib_path(&residual_segments, span);
+        let residual_function = Expression::new(ExpressionKind::Variable(residual_function), span);
+        let residual = Expression::call(residual_function, false, residual_arguments, span);
+        let early_return = Statement { kind: StatementKind::Return(Some(residual)), span };
+        let consequence = BlockExpression { statements: vec![early_return] };
+        let if_expr = IfExpression {
+            condition: method_call(access(), check, span),
+            consequence: Expression::new(ExpressionKind::Block(consequence), span),
+            alternative: None,
+        };
+
+        let operand = Expression::new(ExpressionKind::Resolved(operand), operand_span);
+        let typ = UnresolvedTypeData::Unspecified.with_span(operand_span);
+        let let_kind = StatementKind::new_let(((Pattern::Identifier(variable), typ), operand));
+        let if_kind = ExpressionKind::If(Box::new(if_expr));
+        let unwrap = method_call(access(), "unwrap_unchecked", span);
+
+        let statements = vec![
+            Statement { kind: let_kind, span: operand_span },
+            Statement { kind: StatementKind::Semi(Expression::new(if_kind, span)), span },
+            Statement { kind: StatementKind::Expression(unwrap), span },
+        ];
+        let block = ExpressionKind::Block(BlockExpression { statements });
+        self.elaborate_expression(Expression::new(block, span))
+    }
+
+    /// Returns a path to the given item of the standard library.
+    /// Within the standard library itself `std` isn't in scope, so the path starts with `crate`.
+    fn stdlib_path(&self, segments: &[&str], span: Span) -> Path {
+        let (kind, mut path) = if self.crate_id.is_stdlib() {
+            (PathKind::Crate, Vec::new())
+        } else {
+            (PathKind::Dep, vec!["std"])
+        };
+        path.extend(segments);
+        let segments = vecmap(path, |segment| PathSegment::from(Ident::new(segment.into(), span)));
+        Path { segments, kind, span }
+    }
+
     fn elaborate_tuple(&mut self, tuple: Vec<Expression>) -> (HirExpression, Type) {
         let mut element_ids = Vec::with_capacity(tuple.len());
         let mut element_types = Vec::with_capacity(tuple.len());
@@ -700,7 +854,15 @@ impl<'context> Elaborator<'context> {
         self.push_scope();
         let scope_index = self.scopes.current_scope_index();
 
-        self.lambda_stack.push(LambdaContext { captures: Vec::new(), scope_index });
+        // The return type is resolved first so that any `return` in the body can be checked against it
+        let return_type = self.resolve_inferred_type(lambda.return_type);
+        let function_context_depth = self.function_context.len();
+        self.lambda_stack.push(LambdaContext {
+            captures: Vec::new(),
+            scope_index,
+            return_type: return_type.clone(),
+            function_context_depth,
+        });
 
         let mut arg_types = Vec::with_capacity(lambda.parameters.len());
         let parameters = vecmap(lambda.parameters, |(pattern, typ)| {
@@ -710,7 +872,6 @@ impl<'context> Elaborator<'context> {
             (self.elaborate_pattern(pattern, typ.clone(), parameter), typ)
         });
 
-        let return_type = self.resolve_inferred_type(lambda.return_type);
         let body_span = lambda.body.span;
         let (body, body_type) = self.elaborate_expression(lambda.body);
 
@@ -860,3 +1021,15 @@ impl<'context> Elaborator<'context> {
         Some((self.interner.expression(&expr_id), typ))
     }
 }
+
+fn method_call(object: Expression, method_name: &str, span: Span) -> Expression {
+    let method_name = Ident::new(method_name.to_string(), span);
+    let call = MethodCallExpression {
+        object,
+        method_name,
+        generics: None,
+        arguments: Vec::new(),
+        is_macro_call: false,
+    };
+    Expression::new(ExpressionKind::MethodCall(Box::new(call)), span)
+}
@@ -19,7 +19,7 @@ use crate::{
         expr::{HirCapturedVar, HirIdent},
         function::{FunctionBody, Parameters},
         traits::TraitConstraint,
-        types::{Generics, Kind, ResolvedGeneric},
+        types::{Generics, Kind, ResolvedGeneric, StructField},
     },
     macros_api::{
         BlockExpression, Ident, NodeInterner, NoirFunction, NoirStruct, Pattern,
@@ -53,6 +53,7 @@ use crate::{
 };
 
 mod comptime;
+mod enums;
 mod expressions;
 mod lints;
 mod patterns;
@@ -84,6 +85,11 @@ pub struct LambdaContext {
     /// the index in the scope tree
     /// (sometimes being filled by ScopeTree's find method)
     pub scope_index: usize,
+    /// The lambda's return type, which any early `return` within it must match
+    pub return_type: Type,
+    /// The number of function contexts when the lambda was entered, used to tell
+    /// whether a `comptime` block was entered within the lambda
+    pub function_context_depth: usize,
 }
 
 pub struct Elaborator<'context> {
@@ -428,7 +434,8 @@ impl<'context> Elaborator<'context> {
                 (HirFunction::empty(), Type::Error)
             }
             FunctionKind::Normal | FunctionKind::Recursive => {
-                let (block, body_type) = self.elaborate_block(body);
+                let body = statements::replace_trailing_return(body);
+                let (block, body_type) = self.elaborate_function_body(id, body);
                 let expr_id = self.intern_expr(block, body_span);
                 self.interner.push_expr_type(expr_id, body_type.clone());
                 (HirFunction::unchecked_from_expr(expr_id), body_type)
@@ -788,6 +795,16 @@ impl<'context> Elaborator<'context> {
         }
 
         let return_type = Box::new(self.resolve_type(func.return_type()));
+        if is_entry_point && return_type.contains_enum() {
+            let span = func.return_type().span;
+            let typ = return_type.as_ref().clone();
+            self.push_err(TypeCheckError::EnumInEntryPoint { typ, span });
+        }
+
+        let has_opaque_return_type = matches!(return_type.as_ref(), Type::TraitAsType(..))
+            && trait_id.is_none()
+            && !func.kind.can_ignore_return_type();
+        let opaque_return_type = has_opaque_return_type.then(|| self.interner.next_type_variable());
 
         let mut typ = Type::Function(
             parameter_types,
@@ -830,6 +847,7 @@ impl<'context> Elaborator<'context> {
             parameter_idents,
             return_type: func.def.return_type.clone(),
             return_visibility: func.def.return_visibility,
+            opaque_return_type,
             has_body: !func.def.body.is_empty(),
             trait_constraints,
             is_entry_point,
@@ -856,7 +874,9 @@ impl<'context> Elaborator<'context> {
         has_inline_attribute: bool,
         span: Span,
     ) {
-        if (is_entry_point && !typ.is_valid_for_program_input())
+        if is_entry_point && typ.contains_enum() {
+            self.push_err(TypeCheckError::EnumInEntryPoint { typ: typ.clone(), span });
+        } else if (is_entry_point && !typ.is_valid_for_program_input())
             || (has_inline_attribute && !typ.is_valid_non_inlined_function_input())
         {
             self.push_err(TypeCheckError::InvalidTypeForEntryPoint { span });
@@ -1240,7 +1260,7 @@ impl<'context> Elaborator<'context> {
         &mut self,
         unresolved: &NoirStruct,
         struct_id: StructId,
-    ) -> Vec<(Ident, Type)> {
+    ) -> Vec<StructField> {
         self.recover_generics(|this| {
             this.current_item = Some(DependencyId::Struct(struct_id));
 
@@ -1249,8 +1269,10 @@ impl<'context> Elaborator<'context> {
             let struct_def = this.interner.get_struct(struct_id);
             this.add_existing_generics(&unresolved.generics, &struct_def.borrow().generics);
 
-            let fields = vecmap(&unresolved.fields, |(ident, typ)| {
-                (ident.clone(), this.resolve_type(typ.clone()))
+            let fields = vecmap(&unresolved.fields, |field| StructField {
+                visibility: field.visibility,
+                name: field.name.clone(),
+                typ: this.resolve_type(field.typ.clone()),
             });
 
             this.resolving_ids.remove(&struct_id);
@@ -1437,6 +1459,30 @@ impl<'context> Elaborator<'context> {
         }
     }
 
+    /// The type an early `return` at the current position must have: that of the innermost
+    /// lambda or function. Returns `None` within a `comptime` block, which can't return early.
+    fn current_return_type(&self) -> Option<Type> {
+        if let Some(lambda) = self.lambda_stack.last() {
+            if lambda.function_context_depth == self.function_context.len() {
+                return Some(lambda.return_type.clone());
+            }
+        }
+
+        // The first context is the global context, followed by the function-specific context.
+        // Any context after that is a `comptime {}` block's.
+        if self.function_context.len() > 2 {
+            return None;
+        }
+
+        match self.current_item {
+            Some(DependencyId::Function(id)) => {
+                let meta = self.interner.function_meta(&id);
+                Some(meta.opaque_return_type.as_ref().unwrap_or(meta.return_type()).clone())
+            }
+            _ => None,
+        }
+    }
+
     /// True if we're currently within a constrained function.
     /// Defaults to `true` if the current function is unknown.
     fn in_constrained_function(&self) -> bool {
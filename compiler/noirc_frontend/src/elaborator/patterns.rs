@@ -257,6 +257,17 @@ impl<'context> Elaborator<'context> {
             if unseen_fields.contains(&field) {
                 unseen_fields.remove(&field);
                 seen_fields.insert(field.clone());
+
+                let struct_type = struct_type.borrow();
+                if let Some(visibility) = struct_type.field_visibility(&field.0.contents) {
+                    let span = field.span();
+                    self.check_struct_field_visibility(
+                        &struct_type,
+                        &field.0.contents,
+                        visibility,
+                        span,
+                    );
+                }
             } else if seen_fields.contains(&field) {
                 // duplicate field
                 self.push_err(ResolverError::DuplicateField { field: field.clone() });
@@ -592,7 +603,7 @@ impl<'context> Elaborator<'context> {
         // E.g. `fn foo<T>(t: T, field: Field) -> T` has type `forall T. fn(T, Field) -> T`.
         // We must instantiate identifiers at every call site to replace this T with a new type
         // variable to handle generic functions.
-        let t = self.interner.id_type_substitute_trait_as_type(ident.id);
+        let t = self.definition_type_with_opaque_return(ident.id);
 
         let definition = self.interner.try_definition(ident.id);
         let function_generic_count = definition.map_or(0, |definition| match &definition.kind {
@@ -211,8 +211,13 @@ impl<'context> Elaborator<'context> {
 
     fn check_for_unused_variables_in_local_scope(decl_map: Scope, unused_vars: &mut Vec<HirIdent>) {
         let unused_variables = decl_map.filter(|(variable_name, metadata)| {
+            // Variables starting with `$` are introduced by the compiler, e.g. when lowering match expressions
+            let is_internal = variable_name.starts_with('$');
             let has_underscore_prefix = variable_name.starts_with('_'); // XXX: This is used for development mode, and will be removed
-            metadata.warn_if_unused && metadata.num_times_used == 0 && !has_underscore_prefix
+            metadata.warn_if_unused
+                && metadata.num_times_used == 0
+                && !has_underscore_prefix
+                && !is_internal
         });
         unused_vars.extend(unused_variables.map(|(_, meta)| meta.ident.clone()));
     }
@@ -1,21 +1,27 @@
 use noirc_errors::{Location, Span};
 
 use crate::{
-    ast::{AssignStatement, ConstrainStatement, LValue},
+    ast::{
+        AssignStatement, BlockExpression, ConstrainStatement, ExpressionKind, Ident,
+        ItemVisibility, LValue, Literal, WhileStatement,
+    },
     hir::{
-        resolution::errors::ResolverError,
+        resolution::{errors::ResolverError, import::struct_member_is_visible},
         type_check::{Source, TypeCheckError},
     },
     hir_def::{
         expr::HirIdent,
         stmt::{
             HirAssignStatement, HirConstrainStatement, HirForStatement, HirLValue, HirLetStatement,
+            HirWhileStatement,
         },
+        types::StructType,
     },
     macros_api::{
-        ForLoopStatement, ForRange, HirStatement, LetStatement, Path, Statement, StatementKind,
+        Expression, ForLoopStatement, ForRange, HirStatement, LetStatement, Path, Statement,
+        StatementKind,
     },
-    node_interner::{DefinitionId, DefinitionKind, GlobalId, StmtId},
+    node_interner::{DefinitionId, DefinitionKind, ExprId, GlobalId, StmtId},
     Type,
 };
 
@@ -28,8 +34,11 @@ impl<'context> Elaborator<'context> {
             StatementKind::Constrain(constrain) => self.elaborate_constrain(constrain),
             StatementKind::Assign(assign) => self.elaborate_assign(assign),
             StatementKind::For(for_stmt) => self.elaborate_for(for_stmt),
+            StatementKind::While(while_stmt) => self.elaborate_while(while_stmt, statement.span),
+            StatementKind::Loop(block) => self.elaborate_loop(block, statement.span),
             StatementKind::Break => self.elaborate_jump(true, statement.span),
             StatementKind::Continue => self.elaborate_jump(false, statement.span),
+            StatementKind::Return(value) => self.elaborate_return(value, statement.span),
             StatementKind::Comptime(statement) => self.elaborate_comptime_statement(*statement),
             StatementKind::Expression(expr) => {
                 let (expr, typ) = self.elaborate_expression(expr);
@@ -204,6 +213,47 @@ impl<'context> Elaborator<'context> {
         (statement, Type::Unit)
     }
 
+    pub(super) fn elaborate_while(
+        &mut self,
+        while_loop: WhileStatement,
+        span: Span,
+    ) -> (HirStatement, Type) {
+        if self.in_constrained_function() {
+            self.push_err(ResolverError::LoopInConstrainedFn { is_while: true, span });
+        }
+
+        let condition_span = while_loop.condition.span;
+        let (condition, condition_type) = self.elaborate_expression(while_loop.condition);
+
+        self.unify(&condition_type, &Type::Bool, || TypeCheckError::TypeMismatch {
+            expected_typ: Type::Bool.to_string(),
+            expr_typ: condition_type.to_string(),
+            expr_span: condition_span,
+        });
+
+        let block = self.elaborate_loop_block(while_loop.block);
+        (HirStatement::While(HirWhileStatement { condition, block }), Type::Unit)
+    }
+
+    pub(super) fn elaborate_loop(&mut self, block: Expression, span: Span) -> (HirStatement, Type) {
+        if self.in_constrained_function() {
+            self.push_err(ResolverError::LoopInConstrainedFn { is_while: false, span });
+        }
+
+        let block = self.elaborate_loop_block(block);
+        (HirStatement::Loop(block), Type::Unit)
+    }
+
+    /// Elaborates the body of a loop, in which `break` and `continue` are allowed.
+    fn elaborate_loop_block(&mut self, block: Expression) -> ExprId {
+        self.nested_loops += 1;
+        self.push_scope();
+        let (block, _block_type) = self.elaborate_expression(block);
+        self.pop_scope();
+        self.nested_loops -= 1;
+        block
+    }
+
     fn elaborate_jump(&mut self, is_break: bool, span: noirc_errors::Span) -> (HirStatement, Type) {
         let in_constrained_function = self.in_constrained_function();
 
@@ -218,6 +268,39 @@ impl<'context> Elaborator<'context> {
         (expr, self.interner.next_type_variable())
     }
 
+    /// Elaborates an early return, checking the returned value against the return type of the
+    /// enclosing function or lambda. As evaluation never continues past it, the statement itself
+    /// may have any type.
+    pub(super) fn elaborate_return(
+        &mut self,
+        value: Option<Expression>,
+        span: Span,
+    ) -> (HirStatement, Type) {
+        let value =
+            value.unwrap_or_else(|| Expression::new(ExpressionKind::Literal(Literal::Unit), span));
+        let value_span = value.span;
+        let (value, value_type) = self.elaborate_expression(value);
+
+        let Some(return_type) = self.current_return_type() else {
+            // Keep the value as a plain statement so the comptime block can still be evaluated
+            self.push_err(ResolverError::ReturnInComptimeBlock { span });
+            return (HirStatement::Expression(value), Type::Error);
+        };
+
+        if self.in_constrained_function() {
+            self.push_err(ResolverError::ReturnInConstrainedFn { span });
+        }
+
+        self.unify(&value_type, &return_type, || TypeCheckError::TypeMismatch {
+            expected_typ: return_type.to_string(),
+            expr_typ: value_type.to_string(),
+            expr_span: value_span,
+        });
+
+        let typ = self.interner.next_type_variable();
+        (HirStatement::Return(value, typ.clone()), typ)
+    }
+
     fn get_lvalue_name_and_span(&self, lvalue: &HirLValue) -> (String, Span) {
         match lvalue {
             HirLValue::Ident(name, _) => {
@@ -382,10 +465,12 @@ impl<'context> Elaborator<'context> {
         match &lhs_type {
             Type::Struct(s, args) => {
                 let s = s.borrow();
-                if let Some((field, index)) = s.get_field(field_name, args) {
+                if let Some((field, visibility, index)) = s.get_field(field_name, args) {
                     let reference_location = Location::new(span, self.file);
                     self.interner.add_struct_member_reference(s.id, index, reference_location);
 
+                    self.check_struct_field_visibility(&s, field_name, visibility, span);
+
                     return Some((field, index));
                 }
             }
@@ -440,6 +525,21 @@ impl<'context> Elaborator<'context> {
         None
     }
 
+    /// Issues an error if the given field of the given struct isn't visible from the current module
+    pub(super) fn check_struct_field_visibility(
+        &mut self,
+        struct_type: &StructType,
+        field_name: &str,
+        visibility: ItemVisibility,
+        span: Span,
+    ) {
+        if !struct_member_is_visible(struct_type.id, visibility, self.module_id(), self.def_maps) {
+            let field = Ident::new(field_name.to_string(), span);
+            let struct_definition = struct_type.name.clone();
+            self.push_err(ResolverError::PrivateStructField { field, struct_definition });
+        }
+    }
+
     fn elaborate_comptime_statement(&mut self, statement: Statement) -> (HirStatement, Type) {
         // We have to push a new FunctionContext so that we can resolve any constraints
         // in this comptime block early before the function as a whole finishes elaborating.
@@ -459,3 +559,18 @@ impl<'context> Elaborator<'context> {
         (HirStatement::Expression(expr), typ)
     }
 }
+
+/// A `return` as the last statement of a function body is just its tail expression, so it is
+/// rewritten into one. This lets constrained functions use it, as no early exit is involved.
+pub(super) fn replace_trailing_return(mut body: BlockExpression) -> BlockExpression {
+    if let Some(statement) = body.statements.last_mut() {
+        if let StatementKind::Return(value) = &mut statement.kind {
+            let span = statement.span;
+            let value = value
+                .take()
+                .unwrap_or_else(|| Expression::new(ExpressionKind::Literal(Literal::Unit), span));
+            statement.kind = StatementKind::Expression(value);
+        }
+    }
+    body
+}
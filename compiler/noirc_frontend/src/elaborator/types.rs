@@ -24,7 +24,7 @@ use crate::{
             HirBinaryOp, HirCallExpression, HirMemberAccess, HirMethodReference,
             HirPrefixExpression,
         },
-        function::{FuncMeta, Parameters},
+        function::{FuncMeta, FunctionBody, Parameters},
         traits::{NamedType, TraitConstraint},
     },
     macros_api::{
@@ -32,8 +32,8 @@ use crate::{
         SecondaryAttribute, Signedness, UnaryOp, UnresolvedType, UnresolvedTypeData,
     },
     node_interner::{
-        DefinitionKind, DependencyId, ExprId, FuncId, GlobalId, ImplSearchErrorKind, TraitId,
-        TraitImplKind, TraitMethodId,
+        DefinitionId, DefinitionKind, DependencyId, ExprId, FuncId, GlobalId, ImplSearchErrorKind,
+        TraitId, TraitImplKind, TraitMethodId,
     },
     Generics, Kind, ResolvedGeneric, Type, TypeBinding, TypeBindings, TypeVariable,
     TypeVariableKind,
@@ -1528,44 +1528,74 @@ impl<'context> Elaborator<'context> {
 
     pub fn type_check_function_body(&mut self, body_type: Type, meta: &FuncMeta, body_id: ExprId) {
         let (expr_span, empty_function) = self.function_info(body_id);
-        let declared_return_type = meta.return_type();
+
+        // For `impl Trait` the body is checked against the hidden type instead, binding it.
+        let declared_return_type =
+            meta.opaque_return_type.as_ref().unwrap_or_else(|| meta.return_type());
 
         let func_span = self.interner.expr_span(&body_id); // XXX: We could be more specific and return the span of the last stmt, however stmts do not have spans yet
-        if let Type::TraitAsType(trait_id, _, generics) = declared_return_type {
-            if self
-                .interner
-                .lookup_trait_implementation(
-                    &body_type,
-                    *trait_id,
-                    &generics.ordered,
-                    &generics.named,
-                )
-                .is_err()
-            {
-                self.push_err(TypeCheckError::TypeMismatchWithSource {
-                    expected: declared_return_type.clone(),
-                    actual: body_type,
-                    span: func_span,
-                    source: Source::Return(meta.return_type.clone(), expr_span),
-                });
+        self.unify_with_coercions(&body_type, declared_return_type, body_id, func_span, || {
+            let mut error = TypeCheckError::TypeMismatchWithSource {
+                expected: declared_return_type.clone(),
+                actual: body_type.clone(),
+                span: func_span,
+                source: Source::Return(meta.return_type.clone(), expr_span),
+            };
+
+            if empty_function {
+                error = error.add_context(
+                    "implicitly returns `()` as its body has no tail or `return` expression",
+                );
             }
-        } else {
-            self.unify_with_coercions(&body_type, declared_return_type, body_id, func_span, || {
-                let mut error = TypeCheckError::TypeMismatchWithSource {
-                    expected: declared_return_type.clone(),
-                    actual: body_type.clone(),
-                    span: func_span,
-                    source: Source::Return(meta.return_type.clone(), expr_span),
-                };
-
-                if empty_function {
-                    error = error.add_context(
-                        "implicitly returns `()` as its body has no tail or `return` expression",
-                    );
-                }
-                error
+            error
+        });
+
+        if let Some(opaque_type) = &meta.opaque_return_type {
+            self.check_opaque_return_type(opaque_type, meta, body_id);
+        }
+    }
+
+    /// The type hidden behind an `impl Trait` return type must implement the trait.
+    /// This is checked once the function's remaining type variables are defaulted.
+    fn check_opaque_return_type(&mut self, opaque_type: &Type, meta: &FuncMeta, body_id: ExprId) {
+        if let Type::TraitAsType(trait_id, _, trait_generics) = meta.return_type() {
+            let constraint = TraitConstraint {
+                typ: opaque_type.clone(),
+                trait_id: *trait_id,
+                trait_generics: trait_generics.clone(),
+                span: meta.return_type.get_type().span,
+            };
+            self.push_trait_constraint(constraint, body_id);
+        }
+    }
+
+    /// Returns the type of the given definition. For a function returning `impl Trait`, the
+    /// return type is replaced with the concrete type hidden behind it, elaborating the function
+    /// first if needed so that the hidden type is known.
+    pub(super) fn definition_type_with_opaque_return(
+        &mut self,
+        definition_id: DefinitionId,
+    ) -> Type {
+        let typ = self.interner.definition_type(definition_id);
+        let func_id = match self.interner.try_definition(definition_id).map(|def| &def.kind) {
+            Some(DefinitionKind::Function(func_id)) => *func_id,
+            _ => return typ,
+        };
+
+        let meta = self.interner.function_meta(&func_id);
+        if meta.opaque_return_type.is_none() {
+            return typ;
+        }
+
+        if matches!(meta.function_body, FunctionBody::Unresolved(..)) {
+            self.elaborate_item_from_comptime(None, |elaborator| {
+                elaborator.elaborate_function(func_id);
             });
         }
+
+        let opaque_type = self.interner.function_meta(&func_id).opaque_return_type.clone();
+        let opaque_type = opaque_type.expect("Checked above").follow_bindings();
+        replace_return_type(typ, opaque_type)
     }
 
     fn function_info(&self, function_body_id: ExprId) -> (noirc_errors::Span, bool) {
@@ -1865,3 +1895,16 @@ fn verify_mutable_reference(interner: &NodeInterner, rhs: ExprId) -> Result<(),
         _ => Ok(()),
     }
 }
+
+/// Replaces the return type of a (possibly generic) function type
+fn replace_return_type(typ: Type, return_type: Type) -> Type {
+    match typ {
+        Type::Function(args, _, env, unconstrained) => {
+            Type::Function(args, Box::new(return_type), env, unconstrained)
+        }
+        Type::Forall(generics, typ) => {
+            Type::Forall(generics, Box::new(replace_return_type(*typ, return_type)))
+        }
+        other => other,
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     ast::TraitBound,
     hir::{def_collector::dc_crate::CompilationError, type_check::NoMatchingImplFoundError},
     parser::ParserError,
-    token::Token,
+    token::SpannedToken,
     Type,
 };
 use acvm::{acir::AcirField, BlackBoxResolutionError, FieldElement};
@@ -13,6 +13,8 @@ use fm::FileId;
 use iter_extended::vecmap;
 use noirc_errors::{CustomDiagnostic, Location};
 
+use super::Value;
+
 /// The possible errors that can halt the interpreter.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InterpreterError {
@@ -53,6 +55,10 @@ pub enum InterpreterError {
         typ: Type,
         location: Location,
     },
+    NonBoolUsedInWhile {
+        typ: Type,
+        location: Location,
+    },
     FailingConstraint {
         message: Option<String>,
         location: Location,
@@ -145,7 +151,7 @@ pub enum InterpreterError {
     },
     FailedToParseMacro {
         error: ParserError,
-        tokens: Rc<Vec<Token>>,
+        tokens: Rc<Vec<SpannedToken>>,
         rule: &'static str,
         file: FileId,
     },
@@ -194,6 +200,51 @@ pub enum InterpreterError {
         candidates: Vec<String>,
         location: Location,
     },
+    ComptimeError {
+        message: String,
+        location: Location,
+    },
+    ComptimeWarning {
+        message: String,
+        location: Location,
+    },
+    InvalidAttribute {
+        attribute: String,
+        location: Location,
+    },
+    CannotAddItemToDependency {
+        location: Location,
+    },
+    FileNotInComptimeAllowlist {
+        path: String,
+        location: Location,
+    },
+    FailedToReadComptimeFile {
+        path: String,
+        location: Location,
+    },
+    ComptimeFileLengthMismatch {
+        path: String,
+        expected: usize,
+        actual: usize,
+        location: Location,
+    },
+    EnvVarNotInComptimeAllowlist {
+        name: String,
+        location: Location,
+    },
+    InvalidEnvVarTokens {
+        name: String,
+        location: Location,
+    },
+    StatementUsedAsExpression {
+        statement: String,
+        location: Location,
+    },
+    ExpressionNotAssignable {
+        expression: String,
+        location: Location,
+    },
 
     Unimplemented {
         item: String,
@@ -204,9 +255,10 @@ pub enum InterpreterError {
     },
 
     // These cases are not errors, they are just used to prevent us from running more code
-    // until the loop can be resumed properly. These cases will never be displayed to users.
+    // until the loop or function can be resumed properly. These cases will never be displayed to users.
     Break,
     Continue,
+    Return(Value),
 }
 
 #[allow(unused)]
@@ -235,6 +287,7 @@ impl InterpreterError {
             | InterpreterError::NonFunctionCalled { location, .. }
             | InterpreterError::NonBoolUsedInIf { location, .. }
             | InterpreterError::NonBoolUsedInConstrain { location, .. }
+            | InterpreterError::NonBoolUsedInWhile { location, .. }
             | InterpreterError::FailingConstraint { location, .. }
             | InterpreterError::NoMethodFound { location, .. }
             | InterpreterError::NonIntegerUsedInLoop { location, .. }
@@ -269,6 +322,17 @@ impl InterpreterError {
             | InterpreterError::FailedToResolveTraitBound { location, .. }
             | InterpreterError::FunctionAlreadyResolved { location, .. }
             | InterpreterError::MultipleMatchingImpls { location, .. }
+            | InterpreterError::ComptimeError { location, .. }
+            | InterpreterError::ComptimeWarning { location, .. }
+            | InterpreterError::InvalidAttribute { location, .. }
+            | InterpreterError::CannotAddItemToDependency { location }
+            | InterpreterError::FileNotInComptimeAllowlist { location, .. }
+            | InterpreterError::FailedToReadComptimeFile { location, .. }
+            | InterpreterError::ComptimeFileLengthMismatch { location, .. }
+            | InterpreterError::EnvVarNotInComptimeAllowlist { location, .. }
+            | InterpreterError::InvalidEnvVarTokens { location, .. }
+            | InterpreterError::StatementUsedAsExpression { location, .. }
+            | InterpreterError::ExpressionNotAssignable { location, .. }
             | InterpreterError::TypeAnnotationsNeededForMethodCall { location } => *location,
 
             InterpreterError::FailedToParseMacro { error, file, .. } => {
@@ -277,8 +341,8 @@ impl InterpreterError {
             InterpreterError::NoMatchingImplFound { error, file } => {
                 Location::new(error.span, *file)
             }
-            InterpreterError::Break | InterpreterError::Continue => {
-                panic!("Tried to get the location of Break/Continue error!")
+            InterpreterError::Break | InterpreterError::Continue | InterpreterError::Return(_) => {
+                panic!("Tried to get the location of Break/Continue/Return error!")
             }
         }
     }
@@ -354,6 +418,11 @@ impl<'a> From<&'a InterpreterError> for CustomDiagnostic {
                 let msg = format!("Expected a `bool` but found `{typ}`");
                 CustomDiagnostic::simple_error(msg, String::new(), location.span)
             }
+            InterpreterError::NonBoolUsedInWhile { typ, location } => {
+                let msg = format!("Expected a `bool` but found `{typ}`");
+                let secondary = "While conditions must be a boolean value".to_string();
+                CustomDiagnostic::simple_error(msg, secondary, location.span)
+            }
             InterpreterError::FailingConstraint { message, location, call_stack } => {
                 let (primary, secondary) = match message {
                     Some(msg) => (msg.clone(), "Assertion failed".into()),
@@ -531,6 +600,7 @@ impl<'a> From<&'a InterpreterError> for CustomDiagnostic {
             InterpreterError::NoMatchingImplFound { error, .. } => error.into(),
             InterpreterError::Break => unreachable!("Uncaught InterpreterError::Break"),
             InterpreterError::Continue => unreachable!("Uncaught InterpreterError::Continue"),
+            InterpreterError::Return(_) => unreachable!("Uncaught InterpreterError::Return"),
             InterpreterError::TraitDefinitionMustBeAPath { location } => {
                 let msg = "Trait definition arguments must be a variable or path".to_string();
                 CustomDiagnostic::simple_error(msg, String::new(), location.span)
@@ -555,6 +625,65 @@ impl<'a> From<&'a InterpreterError> for CustomDiagnostic {
                 }
                 error
             }
+            InterpreterError::ComptimeError { message, location } => {
+                CustomDiagnostic::simple_error(message.clone(), String::new(), location.span)
+            }
+            InterpreterError::ComptimeWarning { message, location } => {
+                CustomDiagnostic::simple_warning(message.clone(), String::new(), location.span)
+            }
+            InterpreterError::InvalidAttribute { attribute, location } => {
+                let msg = format!("`{attribute}` is not a valid attribute");
+                let secondary =
+                    "Attributes are written without the surrounding `#[` and `]`, e.g. `foo(a)`"
+                        .to_string();
+                CustomDiagnostic::simple_error(msg, secondary, location.span)
+            }
+            InterpreterError::CannotAddItemToDependency { location } => {
+                let msg = "Items can only be added to modules in the current crate".to_string();
+                CustomDiagnostic::simple_error(msg, String::new(), location.span)
+            }
+            InterpreterError::FileNotInComptimeAllowlist { path, location } => {
+                let msg = format!("`{path}` is not in this package's comptime file allowlist");
+                let secondary =
+                    "Add it to `files` in the `[comptime]` section of Nargo.toml".to_string();
+                CustomDiagnostic::simple_error(msg, secondary, location.span)
+            }
+            InterpreterError::FailedToReadComptimeFile { path, location } => {
+                let msg = format!("Failed to read `{path}`");
+                let secondary = "The file is allowlisted but could not be read".to_string();
+                CustomDiagnostic::simple_error(msg, secondary, location.span)
+            }
+            InterpreterError::ComptimeFileLengthMismatch { path, expected, actual, location } => {
+                let msg = format!(
+                    "Expected `{path}` to be {expected} bytes long, but it is {actual} bytes long"
+                );
+                CustomDiagnostic::simple_error(msg, String::new(), location.span)
+            }
+            InterpreterError::EnvVarNotInComptimeAllowlist { name, location } => {
+                let msg = format!(
+                    "Environment variable `{name}` is not in this package's comptime allowlist"
+                );
+                let secondary =
+                    "Add it to `env` in the `[comptime]` section of Nargo.toml".to_string();
+                CustomDiagnostic::simple_error(msg, secondary, location.span)
+            }
+            InterpreterError::InvalidEnvVarTokens { name, location } => {
+                let msg =
+                    format!("The value of environment variable `{name}` is not valid Noir tokens");
+                CustomDiagnostic::simple_error(msg, String::new(), location.span)
+            }
+            InterpreterError::StatementUsedAsExpression { statement, location } => {
+                let msg =
+                    format!("`{statement}` is a statement and cannot be used as an expression");
+                CustomDiagnostic::simple_error(msg, String::new(), location.span)
+            }
+            InterpreterError::ExpressionNotAssignable { expression, location } => {
+                let msg = format!("Cannot assign to `{expression}`");
+                let secondary =
+                    "Only variables, fields, indices and dereferences can be assigned to"
+                        .to_string();
+                CustomDiagnostic::simple_error(msg, secondary, location.span)
+            }
             InterpreterError::TypeAnnotationsNeededForMethodCall { location } => {
                 let mut error = CustomDiagnostic::simple_error(
                     "Object type is unknown in method call".to_string(),
@@ -6,7 +6,7 @@ use crate::ast::{
     ConstructorExpression, ExpressionKind, ForLoopStatement, ForRange, GenericTypeArgs, Ident,
     IfExpression, IndexExpression, InfixExpression, LValue, Lambda, LetStatement, Literal,
     MemberAccessExpression, MethodCallExpression, Path, PathSegment, Pattern, PrefixExpression,
-    UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression,
+    UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression, WhileStatement,
 };
 use crate::ast::{ConstrainStatement, Expression, Statement, StatementKind};
 use crate::hir_def::expr::{HirArrayLiteral, HirBlockExpression, HirExpression, HirIdent};
@@ -57,8 +57,17 @@ impl HirStatement {
                 block: for_stmt.block.to_display_ast(interner),
                 span,
             }),
+            HirStatement::While(while_stmt) => StatementKind::While(WhileStatement {
+                condition: while_stmt.condition.to_display_ast(interner),
+                block: while_stmt.block.to_display_ast(interner),
+                span,
+            }),
+            HirStatement::Loop(block) => StatementKind::Loop(block.to_display_ast(interner)),
             HirStatement::Break => StatementKind::Break,
             HirStatement::Continue => StatementKind::Continue,
+            HirStatement::Return(value, _) => {
+                StatementKind::Return(Some(value.to_display_ast(interner)))
+            }
             HirStatement::Expression(expr) => {
                 StatementKind::Expression(expr.to_display_ast(interner))
             }
@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The files and environment variables a crate's comptime code is allowed to read.
+///
+/// These come from the `[comptime]` allowlist in the crate's `Nargo.toml` and are read
+/// by the package manager ahead of time, so that the compiler itself never needs to access
+/// the file system or environment.
+#[derive(Debug, Default, Clone)]
+pub struct ComptimeInputs {
+    /// The root directory of the package, used to report which files were read
+    pub root_dir: PathBuf,
+
+    /// Each allowlisted file, keyed by its path relative to the package root.
+    /// The contents are `None` if the file could not be read.
+    pub files: BTreeMap<String, Option<Vec<u8>>>,
+
+    /// Each allowlisted environment variable, with its value or `None` if it is not set.
+    pub env: BTreeMap<String, Option<String>>,
+}
+
+impl ComptimeInputs {
+    /// Looks up an allowlisted file, so that e.g. `./data/x` and `data/x` refer to the same file
+    pub fn get_file(&self, path: &str) -> Option<&Option<Vec<u8>>> {
+        self.files.get(&normalize_path(path))
+    }
+}
+
+/// An external input read by comptime code during compilation.
+/// Tooling can use these to know when a crate needs to be recompiled.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ComptimeInput {
+    File(PathBuf),
+    EnvVar(String),
+}
+
+/// Normalizes a relative path so that equivalent spellings of it compare equal:
+/// `.` components are dropped, `..` components are resolved where possible, and
+/// both `/` and `\` are accepted as separators.
+pub fn normalize_path(path: &str) -> String {
+    let mut components: Vec<&str> = Vec::new();
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => (),
+            ".." if components.last().is_some_and(|last| *last != "..") => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+    components.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_path;
+
+    #[test]
+    fn normalizes_equivalent_paths() {
+        assert_eq!(normalize_path("data/x.txt"), "data/x.txt");
+        assert_eq!(normalize_path("./data/x.txt"), "data/x.txt");
+        assert_eq!(normalize_path("data//./x.txt"), "data/x.txt");
+        assert_eq!(normalize_path("data\\x.txt"), "data/x.txt");
+        assert_eq!(normalize_path("data/other/../x.txt"), "data/x.txt");
+        assert_eq!(normalize_path("../x.txt"), "../x.txt");
+    }
+}
@@ -29,7 +29,7 @@ use crate::{
         },
         stmt::{
             HirAssignStatement, HirConstrainStatement, HirForStatement, HirLValue, HirLetStatement,
-            HirPattern,
+            HirPattern, HirWhileStatement,
         },
     },
     macros_api::{HirLiteral, HirStatement, NodeInterner},
@@ -155,7 +155,7 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
         }
 
         let function_body = self.get_function_body(function, location)?;
-        let result = self.evaluate(function_body)?;
+        let result = self.evaluate_function_body(function_body)?;
         self.exit_function(previous_state);
         Ok(result)
     }
@@ -253,12 +253,20 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
             self.define(param.ident.id, arg);
         }
 
-        let result = self.evaluate(closure.body)?;
+        let result = self.evaluate_function_body(closure.body)?;
 
         self.exit_function(previous_state);
         Ok(result)
     }
 
+    /// Evaluates the body of a function or closure, stopping at any early `return`
+    fn evaluate_function_body(&mut self, body: ExprId) -> IResult<Value> {
+        match self.evaluate(body) {
+            Err(InterpreterError::Return(value)) => Ok(value),
+            result => result,
+        }
+    }
+
     /// Enters a function, pushing a new scope and resetting any required state.
     /// Returns the previous values of the internal state, to be reset when
     /// `exit_function` is called.
@@ -1426,8 +1434,8 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
 
     fn evaluate_quote(&mut self, mut tokens: Tokens, expr_id: ExprId) -> IResult<Value> {
         let location = self.elaborator.interner.expr_location(&expr_id);
-        let tokens = self.substitute_unquoted_values_into_tokens(tokens, location)?;
-        Ok(Value::Quoted(Rc::new(tokens)))
+        let (tokens, file) = self.substitute_unquoted_values_into_tokens(tokens, location)?;
+        Ok(Value::Quoted(Rc::new(tokens), file))
     }
 
     pub fn evaluate_statement(&mut self, statement: StmtId) -> IResult<Value> {
@@ -1436,8 +1444,11 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
             HirStatement::Constrain(constrain) => self.evaluate_constrain(constrain),
             HirStatement::Assign(assign) => self.evaluate_assign(assign),
             HirStatement::For(for_) => self.evaluate_for(for_),
+            HirStatement::While(while_) => self.evaluate_while(while_),
+            HirStatement::Loop(block) => self.evaluate_loop(block),
             HirStatement::Break => self.evaluate_break(statement),
             HirStatement::Continue => self.evaluate_continue(statement),
+            HirStatement::Return(value, _) => Err(InterpreterError::Return(self.evaluate(value)?)),
             HirStatement::Expression(expression) => self.evaluate(expression),
             HirStatement::Comptime(statement) => self.evaluate_comptime(statement),
             HirStatement::Semi(expression) => {
@@ -1627,6 +1638,62 @@ impl<'local, 'interner> Interpreter<'local, 'interner> {
         Ok(Value::Unit)
     }
 
+    fn evaluate_while(&mut self, while_: HirWhileStatement) -> IResult<Value> {
+        let was_in_loop = std::mem::replace(&mut self.in_loop, true);
+
+        let result = loop {
+            let condition = match self.evaluate(while_.condition) {
+                Ok(Value::Bool(value)) => value,
+                Ok(value) => {
+                    let location = self.elaborator.interner.expr_location(&while_.condition);
+                    let typ = value.get_type().into_owned();
+                    break Err(InterpreterError::NonBoolUsedInWhile { typ, location });
+                }
+                Err(error) => break Err(error),
+            };
+
+            if !condition {
+                break Ok(Value::Unit);
+            }
+
+            match self.evaluate_loop_block(while_.block) {
+                Ok(true) => (),
+                Ok(false) => break Ok(Value::Unit),
+                Err(error) => break Err(error),
+            }
+        };
+
+        self.in_loop = was_in_loop;
+        result
+    }
+
+    fn evaluate_loop(&mut self, block: ExprId) -> IResult<Value> {
+        let was_in_loop = std::mem::replace(&mut self.in_loop, true);
+
+        let result = loop {
+            match self.evaluate_loop_block(block) {
+                Ok(true) => (),
+                Ok(false) => break Ok(Value::Unit),
+                Err(error) => break Err(error),
+            }
+        };
+
+        self.in_loop = was_in_loop;
+        result
+    }
+
+    /// Evaluates one iteration of a loop's body, returning false if it executed a `break`.
+    fn evaluate_loop_block(&mut self, block: ExprId) -> IResult<bool> {
+        self.push_scope();
+        let result = match self.evaluate(block) {
+            Ok(_) | Err(InterpreterError::Continue) => Ok(true),
+            Err(InterpreterError::Break) => Ok(false),
+            Err(other) => Err(other),
+        };
+        self.pop_scope();
+        result
+    }
+
     fn evaluate_break(&mut self, id: StmtId) -> IResult<Value> {
         if self.in_loop {
             Err(InterpreterError::Break)
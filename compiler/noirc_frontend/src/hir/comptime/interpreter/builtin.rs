@@ -6,31 +6,41 @@ use std::{
 use acvm::{AcirField, FieldElement};
 use builtin_helpers::{
     block_expression_to_value, check_argument_count, check_function_not_yet_resolved,
-    check_one_argument, check_three_arguments, check_two_arguments, get_expr, get_field,
-    get_function_def, get_module, get_quoted, get_slice, get_struct, get_trait_constraint,
-    get_trait_def, get_trait_impl, get_tuple, get_type, get_u32, get_unresolved_type,
-    hir_pattern_to_tokens, mutate_func_meta_type, parse, replace_func_meta_parameters,
-    replace_func_meta_return_type,
+    check_one_argument, check_three_arguments, check_two_arguments, get_block_expression, get_expr,
+    get_expression, get_expressions, get_field, get_function_def, get_lvalue, get_module,
+    get_quoted, get_slice, get_statement, get_str, get_struct, get_struct_field,
+    get_trait_constraint, get_trait_def, get_trait_impl, get_tuple, get_type, get_u32,
+    get_unresolved_type, has_named_attribute, hir_pattern_to_tokens, lex, mutate_func_meta_type,
+    parse, parse_secondary_attribute, quoted_location, replace_func_meta_parameters,
+    replace_func_meta_return_type, secondary_attributes_to_value, tokens_to_string,
 };
 use chumsky::{prelude::choice, Parser};
+use fm::FileId;
 use im::Vector;
 use iter_extended::{try_vecmap, vecmap};
-use noirc_errors::Location;
+use noirc_errors::{Location, Spanned};
 use num_bigint::BigUint;
 use rustc_hash::FxHashMap as HashMap;
 
 use crate::{
     ast::{
-        ArrayLiteral, BlockExpression, ConstrainKind, Expression, ExpressionKind, FunctionKind,
-        FunctionReturnType, IntegerBitSize, LValue, Literal, Statement, StatementKind, UnaryOp,
-        UnresolvedType, UnresolvedTypeData, Visibility,
+        ArrayLiteral, AssignStatement, BinaryOpKind, BlockExpression, CallExpression,
+        CastExpression, ConstrainKind, ConstrainStatement, Expression, ExpressionKind,
+        FunctionKind, FunctionReturnType, IfExpression, IndexExpression, InfixExpression,
+        IntegerBitSize, LValue, Literal, MemberAccessExpression, MethodCallExpression,
+        PrefixExpression, Statement, StatementKind, UnaryOp, UnresolvedType, UnresolvedTypeData,
+        Visibility,
+    },
+    graph::CrateId,
+    hir::comptime::{
+        errors::IResult, normalize_path, value::ExprValue, ComptimeInput, InterpreterError, Value,
     },
-    hir::comptime::{errors::IResult, value::ExprValue, InterpreterError, Value},
     hir_def::function::FunctionBody,
+    lexer::Lexer,
     macros_api::{HirExpression, HirLiteral, ModuleDefId, NodeInterner, Signedness},
     node_interner::{DefinitionKind, TraitImplKind},
     parser::{self},
-    token::Token,
+    token::{SpannedToken, Token},
     QuotedType, Shared, Type,
 };
 
@@ -54,6 +64,7 @@ impl<'local, 'context> Interpreter<'local, 'context> {
             "array_len" => array_len(interner, arguments, location),
             "assert_constant" => Ok(Value::Bool(true)),
             "as_slice" => as_slice(interner, arguments, location),
+            "env_var" => env_var(self, arguments, return_type, location),
             "expr_as_array" => expr_as_array(interner, arguments, return_type, location),
             "expr_as_assert" => expr_as_assert(interner, arguments, return_type, location),
             "expr_as_assign" => expr_as_assign(interner, arguments, return_type, location),
@@ -68,6 +79,7 @@ impl<'local, 'context> Interpreter<'local, 'context> {
             "expr_as_if" => expr_as_if(interner, arguments, return_type, location),
             "expr_as_index" => expr_as_index(interner, arguments, return_type, location),
             "expr_as_integer" => expr_as_integer(interner, arguments, return_type, location),
+            "expr_as_let" => expr_as_let(interner, arguments, return_type, location),
             "expr_as_member_access" => {
                 expr_as_member_access(interner, arguments, return_type, location)
             }
@@ -84,11 +96,43 @@ impl<'local, 'context> Interpreter<'local, 'context> {
             "expr_as_tuple" => expr_as_tuple(interner, arguments, return_type, location),
             "expr_as_unary_op" => expr_as_unary_op(interner, arguments, return_type, location),
             "expr_as_unsafe" => expr_as_unsafe(interner, arguments, return_type, location),
+            "expr_error" => emit_comptime_diagnostic(self, arguments, location, false),
             "expr_has_semicolon" => expr_has_semicolon(interner, arguments, location),
             "expr_is_break" => expr_is_break(interner, arguments, location),
             "expr_is_continue" => expr_is_continue(interner, arguments, location),
+            "expr_new_array" => expr_new_array(interner, arguments, location),
+            "expr_new_assert" => expr_new_assert(interner, arguments, location),
+            "expr_new_assign" => expr_new_assign(interner, arguments, location),
+            "expr_new_binary_op" => expr_new_binary_op(interner, arguments, location),
+            "expr_new_block" => expr_new_block(interner, arguments, location),
+            "expr_new_cast" => expr_new_cast(interner, arguments, location),
+            "expr_new_comptime" => expr_new_comptime(interner, arguments, location),
+            "expr_new_function_call" => expr_new_function_call(interner, arguments, location),
+            "expr_new_if" => expr_new_if(interner, arguments, location),
+            "expr_new_index" => expr_new_index(interner, arguments, location),
+            "expr_new_let" => expr_new_let(interner, arguments, location),
+            "expr_new_member_access" => expr_new_member_access(interner, arguments, location),
+            "expr_new_method_call" => expr_new_method_call(interner, arguments, location),
+            "expr_new_repeated_element_array" => {
+                expr_new_repeated_element_array(interner, arguments, location)
+            }
+            "expr_new_repeated_element_slice" => {
+                expr_new_repeated_element_slice(interner, arguments, location)
+            }
+            "expr_new_slice" => expr_new_slice(interner, arguments, location),
+            "expr_new_tuple" => expr_new_tuple(interner, arguments, location),
+            "expr_new_unary_op" => expr_new_unary_op(interner, arguments, location),
+            "expr_new_unsafe" => expr_new_unsafe(interner, arguments, location),
+            "expr_resolve" => expr_resolve(self, arguments, location),
+            "expr_warn" => emit_comptime_diagnostic(self, arguments, location, true),
             "is_unconstrained" => Ok(Value::Bool(true)),
+            "function_def_add_attribute" => function_def_add_attribute(self, arguments, location),
+            "function_def_attributes" => function_def_attributes(interner, arguments, location),
             "function_def_body" => function_def_body(interner, arguments, location),
+            "function_def_error" => function_def_emit(self, arguments, location, false),
+            "function_def_has_named_attribute" => {
+                function_def_has_named_attribute(interner, arguments, location)
+            }
             "function_def_name" => function_def_name(interner, arguments, location),
             "function_def_parameters" => function_def_parameters(interner, arguments, location),
             "function_def_return_type" => function_def_return_type(interner, arguments, location),
@@ -97,9 +141,20 @@ impl<'local, 'context> Interpreter<'local, 'context> {
             "function_def_set_return_type" => {
                 function_def_set_return_type(self, arguments, location)
             }
+            "function_def_variable_type" => {
+                function_def_variable_type(self, arguments, return_type, location)
+            }
+            "function_def_warn" => function_def_emit(self, arguments, location, true),
+            "module_add_attribute" => module_add_attribute(self, arguments, location),
+            "module_add_item" => module_add_item(self, arguments, location),
+            "module_attributes" => module_attributes(interner, arguments, location),
             "module_functions" => module_functions(self, arguments, location),
+            "module_has_named_attribute" => {
+                module_has_named_attribute(interner, arguments, location)
+            }
             "module_is_contract" => module_is_contract(self, arguments, location),
             "module_name" => module_name(interner, arguments, location),
+            "module_structs" => module_structs(self, arguments, location),
             "modulus_be_bits" => modulus_be_bits(interner, arguments, location),
             "modulus_be_bytes" => modulus_be_bytes(interner, arguments, location),
             "modulus_le_bits" => modulus_le_bits(interner, arguments, location),
@@ -110,15 +165,25 @@ impl<'local, 'context> Interpreter<'local, 'context> {
             "quoted_as_trait_constraint" => quoted_as_trait_constraint(self, arguments, location),
             "quoted_as_type" => quoted_as_type(self, arguments, location),
             "quoted_eq" => quoted_eq(arguments, location),
+            "quoted_error" => emit_comptime_diagnostic(self, arguments, location, false),
+            "quoted_warn" => emit_comptime_diagnostic(self, arguments, location, true),
+            "read_file" => read_file(self, arguments, return_type, location),
             "slice_insert" => slice_insert(interner, arguments, location),
             "slice_pop_back" => slice_pop_back(interner, arguments, location, call_stack),
             "slice_pop_front" => slice_pop_front(interner, arguments, location, call_stack),
             "slice_push_back" => slice_push_back(interner, arguments, location),
             "slice_push_front" => slice_push_front(interner, arguments, location),
             "slice_remove" => slice_remove(interner, arguments, location, call_stack),
+            "struct_def_add_attribute" => struct_def_add_attribute(self, arguments, location),
             "struct_def_as_type" => struct_def_as_type(interner, arguments, location),
+            "struct_def_attributes" => struct_def_attributes(interner, arguments, location),
+            "struct_def_error" => struct_def_emit(self, arguments, location, false),
             "struct_def_fields" => struct_def_fields(interner, arguments, location),
             "struct_def_generics" => struct_def_generics(interner, arguments, location),
+            "struct_def_has_named_attribute" => {
+                struct_def_has_named_attribute(interner, arguments, location)
+            }
+            "struct_def_warn" => struct_def_emit(self, arguments, location, true),
             "to_le_radix" => to_le_radix(arguments, location),
             "trait_constraint_eq" => trait_constraint_eq(interner, arguments, location),
             "trait_constraint_hash" => trait_constraint_hash(interner, arguments, location),
@@ -145,6 +210,12 @@ impl<'local, 'context> Interpreter<'local, 'context> {
             "type_is_bool" => type_is_bool(arguments, location),
             "type_is_field" => type_is_field(arguments, location),
             "type_of" => type_of(arguments, location),
+            "typed_expr_as_function_definition" => {
+                typed_expr_as_function_definition(interner, arguments, return_type, location)
+            }
+            "typed_expr_get_type" => {
+                typed_expr_get_type(interner, arguments, return_type, location)
+            }
             "unresolved_type_is_field" => unresolved_type_is_field(interner, arguments, location),
             "zeroed" => zeroed(return_type),
             _ => {
@@ -155,6 +226,43 @@ impl<'local, 'context> Interpreter<'local, 'context> {
     }
 }
 
+/// Reports an error or warning from comptime code without halting evaluation.
+fn push_comptime_diagnostic(
+    interpreter: &mut Interpreter,
+    message: (Value, Location),
+    location: Location,
+    is_warning: bool,
+) -> IResult<Value> {
+    let message = get_str(interpreter.elaborator.interner, message)?.to_string();
+    let error = if is_warning {
+        InterpreterError::ComptimeWarning { message, location }
+    } else {
+        InterpreterError::ComptimeError { message, location }
+    };
+    interpreter.elaborator.errors.push(error.into_compilation_error_pair());
+    Ok(Value::Unit)
+}
+
+// fn error<let N: u32>(self, message: str<N>)
+// fn warn<let N: u32>(self, message: str<N>)
+// Quoted code and expressions are reported at the code they were created from. Quoted code spliced
+// together from several files has no single location, so it's reported at the call site instead.
+fn emit_comptime_diagnostic(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+    is_warning: bool,
+) -> IResult<Value> {
+    let ((value, self_location), message) = check_two_arguments(arguments, location)?;
+    let location = match &value {
+        Value::Quoted(tokens, file) => quoted_location(tokens, *file),
+        Value::Expr(_, expr_location) => Some(*expr_location),
+        _ => None,
+    };
+    let location = location.unwrap_or(self_location);
+    push_comptime_diagnostic(interpreter, message, location, is_warning)
+}
+
 fn failing_constraint<T>(
     message: impl Into<String>,
     location: Location,
@@ -228,6 +336,24 @@ fn slice_push_back(
     Ok(Value::Slice(values, typ))
 }
 
+/// fn add_attribute<let N: u32>(self, attribute: str<N>)
+fn struct_def_add_attribute(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (self_argument, attribute) = check_two_arguments(arguments, location)?;
+    let struct_id = get_struct(self_argument)?;
+    let attribute = get_str(interpreter.elaborator.interner, attribute)?;
+    let attribute = parse_secondary_attribute(&attribute, location)?;
+
+    interpreter
+        .elaborator
+        .interner
+        .update_struct_attributes(struct_id, |attributes| attributes.push(attribute));
+    Ok(Value::Unit)
+}
+
 /// fn as_type(self) -> Type
 fn struct_def_as_type(
     interner: &NodeInterner,
@@ -247,6 +373,35 @@ fn struct_def_as_type(
     Ok(Value::Type(Type::Struct(struct_def_rc, generics)))
 }
 
+/// fn attributes(self) -> [Quoted]
+fn struct_def_attributes(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let argument = check_one_argument(arguments, location)?;
+    let struct_id = get_struct(argument)?;
+    Ok(secondary_attributes_to_value(interner.struct_attributes(&struct_id), location))
+}
+
+/// fn error<let N: u32>(self, message: str<N>)
+/// fn warn<let N: u32>(self, message: str<N>)
+fn struct_def_emit(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+    is_warning: bool,
+) -> IResult<Value> {
+    let (self_argument, message) = check_two_arguments(arguments, location)?;
+    let struct_id = get_struct(self_argument)?;
+    let struct_def = interpreter.elaborator.interner.get_struct(struct_id);
+    let struct_def = struct_def.borrow();
+    let struct_location = Location::new(struct_def.name.span(), struct_def.location.file);
+    drop(struct_def);
+
+    push_comptime_diagnostic(interpreter, message, struct_location, is_warning)
+}
+
 /// fn generics(self) -> [Type]
 fn struct_def_generics(
     interner: &NodeInterner,
@@ -279,9 +434,11 @@ fn struct_def_fields(
 
     let mut fields = im::Vector::new();
 
-    for (name, typ) in struct_def.get_fields_as_written() {
-        let name = Value::Quoted(Rc::new(vec![Token::Ident(name)]));
-        let typ = Value::Type(typ);
+    for field in struct_def.get_fields_as_written() {
+        let span = field.name.span();
+        let name = SpannedToken::new(Token::Ident(field.name.0.contents), span);
+        let name = Value::Quoted(Rc::new(vec![name]), Some(struct_def.location.file));
+        let typ = Value::Type(field.typ);
         fields.push_back(Value::Tuple(vec![name, typ]));
     }
 
@@ -292,6 +449,18 @@ fn struct_def_fields(
     Ok(Value::Slice(fields, typ))
 }
 
+/// fn has_named_attribute(self, name: Quoted) -> bool
+fn struct_def_has_named_attribute(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (self_argument, name) = check_two_arguments(arguments, location)?;
+    let struct_id = get_struct(self_argument)?;
+    let name = get_quoted(name)?;
+    Ok(Value::Bool(has_named_attribute(&name, interner.struct_attributes(&struct_id))))
+}
+
 fn slice_remove(
     interner: &mut NodeInterner,
     arguments: Vec<(Value, Location)>,
@@ -381,13 +550,20 @@ fn quoted_as_expr(
     location: Location,
 ) -> IResult<Value> {
     let argument = check_one_argument(arguments, location)?;
+    let expr_location = match &argument.0 {
+        Value::Quoted(tokens, file) => quoted_location(tokens, *file),
+        _ => None,
+    };
+    let expr_location = expr_location.unwrap_or(argument.1);
 
-    let expr_parser = parser::expression().map(|expr| Value::expression(expr.kind));
-    let statement_parser = parser::fresh_statement().map(Value::statement);
-    let lvalue_parser = parser::lvalue(parser::expression()).map(Value::lvalue);
+    let expr_parser = parser::expression().map(|expr| ExprValue::Expression(expr.kind));
+    let statement_parser = parser::fresh_statement().map(ExprValue::Statement);
+    let lvalue_parser = parser::lvalue(parser::expression()).map(ExprValue::LValue);
     let parser = choice((expr_parser, statement_parser, lvalue_parser));
 
-    let expr = parse(argument, parser, "an expression").ok();
+    // The parsed expression is given the location of the quoted code rather than where it's parsed
+    let expr =
+        parse(argument, parser, "an expression").ok().map(|expr| Value::Expr(expr, expr_location));
 
     option(return_type, expr)
 }
@@ -666,6 +842,132 @@ fn type_of(arguments: Vec<(Value, Location)>, location: Location) -> IResult<Val
     Ok(Value::Type(typ))
 }
 
+// fn read_file<let N: u32, let M: u32>(path: str<N>) -> [u8; M]
+fn read_file(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    return_type: Type,
+    location: Location,
+) -> IResult<Value> {
+    let argument = check_one_argument(arguments, location)?;
+    let path = get_str(interpreter.elaborator.interner, argument)?.to_string();
+
+    let Type::Array(length, element_type) = return_type.follow_bindings() else {
+        panic!("Expected read_file to return an array");
+    };
+    let Some(expected) = length.evaluate_to_u32() else {
+        let message = format!("Type annotation needed for the length of the contents of `{path}`");
+        return Err(InterpreterError::ComptimeError { message, location });
+    };
+
+    let crate_id = comptime_inputs_crate(interpreter);
+    let interner = &mut interpreter.elaborator.interner;
+    let inputs = interner.get_comptime_inputs(crate_id);
+    let Some((file, root_dir)) =
+        inputs.and_then(|inputs| Some((inputs.get_file(&path)?, inputs.root_dir.clone())))
+    else {
+        return Err(InterpreterError::FileNotInComptimeAllowlist { path, location });
+    };
+    let Some(bytes) = file.clone() else {
+        return Err(InterpreterError::FailedToReadComptimeFile { path, location });
+    };
+
+    let input = ComptimeInput::File(root_dir.join(normalize_path(&path)));
+    interner.record_comptime_input_read(input, Some(bytes.clone()));
+
+    let (expected, actual) = (expected as usize, bytes.len());
+    if actual != expected {
+        return Err(InterpreterError::ComptimeFileLengthMismatch {
+            path,
+            expected,
+            actual,
+            location,
+        });
+    }
+
+    let bytes = bytes.into_iter().map(Value::U8).collect();
+    Ok(Value::Array(bytes, Type::Array(length, element_type)))
+}
+
+// fn env_var<let N: u32>(name: str<N>) -> Option<Quoted>
+fn env_var(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    return_type: Type,
+    location: Location,
+) -> IResult<Value> {
+    let argument = check_one_argument(arguments, location)?;
+    let name = get_str(interpreter.elaborator.interner, argument)?.to_string();
+
+    let crate_id = comptime_inputs_crate(interpreter);
+    let interner = &mut interpreter.elaborator.interner;
+    let inputs = interner.get_comptime_inputs(crate_id);
+    let Some(value) = inputs.and_then(|inputs| inputs.env.get(&name)).cloned() else {
+        return Err(InterpreterError::EnvVarNotInComptimeAllowlist { name, location });
+    };
+
+    let contents = value.clone().map(String::into_bytes);
+    interner.record_comptime_input_read(ComptimeInput::EnvVar(name.clone()), contents);
+
+    let value = match value {
+        Some(value) => {
+            let (tokens, errors) = Lexer::lex(&value);
+            if !errors.is_empty() {
+                return Err(InterpreterError::InvalidEnvVarTokens { name, location });
+            }
+            let tokens = tokens.0.into_iter().filter(|token| *token != Token::EOF);
+            let tokens = tokens.map(|token| SpannedToken::new(token.into_token(), location.span));
+            Some(Value::Quoted(Rc::new(tokens.collect()), Some(location.file)))
+        }
+        None => None,
+    };
+    option(return_type, value)
+}
+
+/// Comptime inputs are allowlisted per crate, so they are looked up using the crate of the
+/// function doing the reading rather than the crate currently being compiled.
+fn comptime_inputs_crate(interpreter: &Interpreter) -> CrateId {
+    match interpreter.current_function {
+        Some(function) => interpreter.elaborator.interner.function_meta(&function).source_crate,
+        None => interpreter.crate_id,
+    }
+}
+
+// fn as_function_definition(self) -> Option<FunctionDefinition>
+fn typed_expr_as_function_definition(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    return_type: Type,
+    location: Location,
+) -> IResult<Value> {
+    let self_argument = check_one_argument(arguments, location)?;
+    let expr_id = get_typed_expr(self_argument)?;
+
+    let func_id = match interner.expression(&expr_id) {
+        HirExpression::Ident(ident, _) => match interner.definition(ident.id).kind {
+            DefinitionKind::Function(func_id) => Some(Value::FunctionDefinition(func_id)),
+            _ => None,
+        },
+        _ => None,
+    };
+    option(return_type, func_id)
+}
+
+// fn get_type(self) -> Option<Type>
+fn typed_expr_get_type(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    return_type: Type,
+    location: Location,
+) -> IResult<Value> {
+    let self_argument = check_one_argument(arguments, location)?;
+    let expr_id = get_typed_expr(self_argument)?;
+
+    let typ = interner.id_type(expr_id).follow_bindings();
+    let typ = if typ == Type::Error { None } else { Some(Value::Type(typ)) };
+    option(return_type, typ)
+}
+
 // fn constraint_hash(constraint: TraitConstraint) -> Field
 fn trait_constraint_hash(
     _interner: &mut NodeInterner,
@@ -862,12 +1164,13 @@ fn expr_as_array(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Literal(Literal::Array(
             ArrayLiteral::Standard(exprs),
         ))) = expr
         {
-            let exprs = exprs.into_iter().map(|expr| Value::expression(expr.kind)).collect();
+            let exprs =
+                exprs.into_iter().map(|expression| Value::expression(expression, file)).collect();
             let typ = Type::Slice(Box::new(Type::Quoted(QuotedType::Expr)));
             Some(Value::Slice(exprs, typ))
         } else {
@@ -883,10 +1186,10 @@ fn expr_as_assert(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type.clone(), location, |expr| {
+    expr_as(interner, arguments, return_type.clone(), location, |expr, file| {
         if let ExprValue::Statement(StatementKind::Constrain(constrain)) = expr {
             if constrain.2 == ConstrainKind::Assert {
-                let predicate = Value::expression(constrain.0.kind);
+                let predicate = Value::expression(constrain.0, file);
 
                 let option_type = extract_option_generic_type(return_type);
                 let Type::Tuple(mut tuple_types) = option_type else {
@@ -895,7 +1198,7 @@ fn expr_as_assert(
                 assert_eq!(tuple_types.len(), 2);
 
                 let option_type = tuple_types.pop().unwrap();
-                let message = constrain.1.map(|message| Value::expression(message.kind));
+                let message = constrain.1.map(|expression| Value::expression(expression, file));
                 let message = option(option_type, message).ok()?;
 
                 Some(Value::Tuple(vec![predicate, message]))
@@ -915,10 +1218,10 @@ fn expr_as_assign(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Statement(StatementKind::Assign(assign)) = expr {
-            let lhs = Value::lvalue(assign.lvalue);
-            let rhs = Value::expression(assign.expression.kind);
+            let lhs = Value::lvalue(assign.lvalue, file);
+            let rhs = Value::expression(assign.expression, file);
             Some(Value::Tuple(vec![lhs, rhs]))
         } else {
             None
@@ -933,7 +1236,7 @@ fn expr_as_binary_op(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type.clone(), location, |expr| {
+    expr_as(interner, arguments, return_type.clone(), location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Infix(infix_expr)) = expr {
             let option_type = extract_option_generic_type(return_type);
             let Type::Tuple(mut tuple_types) = option_type else {
@@ -951,8 +1254,8 @@ fn expr_as_binary_op(
             fields.insert(Rc::new("op".to_string()), Value::Field(binary_op_value.into()));
 
             let unary_op = Value::Struct(fields, binary_op_type);
-            let lhs = Value::expression(infix_expr.lhs.kind);
-            let rhs = Value::expression(infix_expr.rhs.kind);
+            let lhs = Value::expression(infix_expr.lhs, file);
+            let rhs = Value::expression(infix_expr.rhs, file);
             Some(Value::Tuple(vec![lhs, unary_op, rhs]))
         } else {
             None
@@ -967,9 +1270,9 @@ fn expr_as_block(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Block(block_expr)) = expr {
-            Some(block_expression_to_value(block_expr))
+            Some(block_expression_to_value(block_expr, file))
         } else {
             None
         }
@@ -983,7 +1286,7 @@ fn expr_as_bool(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, _| {
         if let ExprValue::Expression(ExpressionKind::Literal(Literal::Bool(bool))) = expr {
             Some(Value::Bool(bool))
         } else {
@@ -999,9 +1302,9 @@ fn expr_as_cast(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Cast(cast)) = expr {
-            let lhs = Value::expression(cast.lhs.kind);
+            let lhs = Value::expression(cast.lhs, file);
             let typ = Value::UnresolvedType(cast.r#type.typ);
             Some(Value::Tuple(vec![lhs, typ]))
         } else {
@@ -1019,9 +1322,9 @@ fn expr_as_comptime(
 ) -> IResult<Value> {
     use ExpressionKind::Block;
 
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Comptime(block_expr, _)) = expr {
-            Some(block_expression_to_value(block_expr))
+            Some(block_expression_to_value(block_expr, file))
         } else if let ExprValue::Statement(StatementKind::Comptime(statement)) = expr {
             let typ = Type::Slice(Box::new(Type::Quoted(QuotedType::Expr)));
 
@@ -1031,10 +1334,10 @@ fn expr_as_comptime(
             // return the for statement as a single expression)
             if let StatementKind::Expression(Expression { kind: Block(block), .. }) = statement.kind
             {
-                Some(block_expression_to_value(block))
+                Some(block_expression_to_value(block, file))
             } else {
                 let mut elements = Vector::new();
-                elements.push_back(Value::statement(statement.kind));
+                elements.push_back(Value::statement(*statement, file));
                 Some(Value::Slice(elements, typ))
             }
         } else {
@@ -1050,11 +1353,12 @@ fn expr_as_function_call(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Call(call_expression)) = expr {
-            let function = Value::expression(call_expression.func.kind);
+            let function = Value::expression(*call_expression.func, file);
             let arguments = call_expression.arguments.into_iter();
-            let arguments = arguments.map(|argument| Value::expression(argument.kind)).collect();
+            let arguments =
+                arguments.map(|expression| Value::expression(expression, file)).collect();
             let arguments =
                 Value::Slice(arguments, Type::Slice(Box::new(Type::Quoted(QuotedType::Expr))));
             Some(Value::Tuple(vec![function, arguments]))
@@ -1071,7 +1375,7 @@ fn expr_as_if(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type.clone(), location, |expr| {
+    expr_as(interner, arguments, return_type.clone(), location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::If(if_expr)) = expr {
             // Get the type of `Option<Expr>`
             let option_type = extract_option_generic_type(return_type.clone());
@@ -1083,12 +1387,12 @@ fn expr_as_if(
 
             let alternative = option(
                 alternative_option_type,
-                if_expr.alternative.map(|e| Value::expression(e.kind)),
+                if_expr.alternative.map(|expression| Value::expression(expression, file)),
             );
 
             Some(Value::Tuple(vec![
-                Value::expression(if_expr.condition.kind),
-                Value::expression(if_expr.consequence.kind),
+                Value::expression(if_expr.condition, file),
+                Value::expression(if_expr.consequence, file),
                 alternative.ok()?,
             ]))
         } else {
@@ -1104,11 +1408,11 @@ fn expr_as_index(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Index(index_expr)) = expr {
             Some(Value::Tuple(vec![
-                Value::expression(index_expr.collection.kind),
-                Value::expression(index_expr.index.kind),
+                Value::expression(index_expr.collection, file),
+                Value::expression(index_expr.index, file),
             ]))
         } else {
             None
@@ -1123,7 +1427,7 @@ fn expr_as_integer(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type.clone(), location, |expr| match expr {
+    expr_as(interner, arguments, return_type.clone(), location, |expr, _| match expr {
         ExprValue::Expression(ExpressionKind::Literal(Literal::Integer(field, sign))) => {
             Some(Value::Tuple(vec![Value::Field(field), Value::Bool(sign)]))
         }
@@ -1140,6 +1444,38 @@ fn expr_as_integer(
     })
 }
 
+// fn as_let(self) -> Option<(Quoted, Option<UnresolvedType>, Expr)>
+fn expr_as_let(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    return_type: Type,
+    location: Location,
+) -> IResult<Value> {
+    expr_as(interner, arguments, return_type.clone(), location, |expr, file| match expr {
+        ExprValue::Statement(StatementKind::Let(let_statement)) if !let_statement.comptime => {
+            let option_type = extract_option_generic_type(return_type);
+            let Type::Tuple(mut tuple_types) = option_type else {
+                panic!("Expected the return type option generic arg to be a tuple");
+            };
+            assert_eq!(tuple_types.len(), 3);
+            tuple_types.pop().unwrap();
+            let option_type = tuple_types.pop().unwrap();
+
+            let pattern = let_statement.pattern.to_string();
+            let pattern = lex(&pattern, let_statement.pattern.span());
+            let pattern = Value::Quoted(Rc::new(pattern), Some(file));
+            let typ = match let_statement.r#type.typ {
+                UnresolvedTypeData::Unspecified => None,
+                typ => Some(Value::UnresolvedType(typ)),
+            };
+            let typ = option(option_type, typ).ok()?;
+            let expression = Value::expression(let_statement.expression, file);
+            Some(Value::Tuple(vec![pattern, typ, expression]))
+        }
+        _ => None,
+    })
+}
+
 // fn as_member_access(self) -> Option<(Expr, Quoted)>
 fn expr_as_member_access(
     interner: &NodeInterner,
@@ -1147,17 +1483,22 @@ fn expr_as_member_access(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| match expr {
+    expr_as(interner, arguments, return_type, location, |expr, file| match expr {
         ExprValue::Expression(ExpressionKind::MemberAccess(member_access)) => {
-            let tokens = Rc::new(vec![Token::Ident(member_access.rhs.0.contents.clone())]);
+            let name = Token::Ident(member_access.rhs.0.contents.clone());
+            let tokens = Rc::new(vec![SpannedToken::new(name, member_access.rhs.span())]);
             Some(Value::Tuple(vec![
-                Value::expression(member_access.lhs.kind),
-                Value::Quoted(tokens),
+                Value::expression(member_access.lhs, file),
+                Value::Quoted(tokens, Some(file)),
             ]))
         }
         ExprValue::LValue(crate::ast::LValue::MemberAccess { object, field_name, span: _ }) => {
-            let tokens = Rc::new(vec![Token::Ident(field_name.0.contents.clone())]);
-            Some(Value::Tuple(vec![Value::lvalue(*object), Value::Quoted(tokens)]))
+            let name = Token::Ident(field_name.0.contents.clone());
+            let tokens = Rc::new(vec![SpannedToken::new(name, field_name.span())]);
+            Some(Value::Tuple(vec![
+                Value::lvalue(*object, file),
+                Value::Quoted(tokens, Some(file)),
+            ]))
         }
         _ => None,
     })
@@ -1170,13 +1511,13 @@ fn expr_as_method_call(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::MethodCall(method_call)) = expr {
-            let object = Value::expression(method_call.object.kind);
+            let object = Value::expression(method_call.object, file);
 
-            let name_tokens =
-                Rc::new(vec![Token::Ident(method_call.method_name.0.contents.clone())]);
-            let name = Value::Quoted(name_tokens);
+            let name = Token::Ident(method_call.method_name.0.contents.clone());
+            let name = SpannedToken::new(name, method_call.method_name.span());
+            let name = Value::Quoted(Rc::new(vec![name]), Some(file));
 
             let generics = method_call.generics.unwrap_or_default().into_iter();
             let generics = generics.map(|generic| Value::UnresolvedType(generic.typ)).collect();
@@ -1186,7 +1527,8 @@ fn expr_as_method_call(
             );
 
             let arguments = method_call.arguments.into_iter();
-            let arguments = arguments.map(|argument| Value::expression(argument.kind)).collect();
+            let arguments =
+                arguments.map(|expression| Value::expression(expression, file)).collect();
             let arguments =
                 Value::Slice(arguments, Type::Slice(Box::new(Type::Quoted(QuotedType::Expr))));
 
@@ -1204,14 +1546,14 @@ fn expr_as_repeated_element_array(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Literal(Literal::Array(
             ArrayLiteral::Repeated { repeated_element, length },
         ))) = expr
         {
             Some(Value::Tuple(vec![
-                Value::expression(repeated_element.kind),
-                Value::expression(length.kind),
+                Value::expression(*repeated_element, file),
+                Value::expression(*length, file),
             ]))
         } else {
             None
@@ -1226,14 +1568,14 @@ fn expr_as_repeated_element_slice(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Literal(Literal::Slice(
             ArrayLiteral::Repeated { repeated_element, length },
         ))) = expr
         {
             Some(Value::Tuple(vec![
-                Value::expression(repeated_element.kind),
-                Value::expression(length.kind),
+                Value::expression(*repeated_element, file),
+                Value::expression(*length, file),
             ]))
         } else {
             None
@@ -1248,12 +1590,13 @@ fn expr_as_slice(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Literal(Literal::Slice(
             ArrayLiteral::Standard(exprs),
         ))) = expr
         {
-            let exprs = exprs.into_iter().map(|expr| Value::expression(expr.kind)).collect();
+            let exprs =
+                exprs.into_iter().map(|expression| Value::expression(expression, file)).collect();
             let typ = Type::Slice(Box::new(Type::Quoted(QuotedType::Expr)));
             Some(Value::Slice(exprs, typ))
         } else {
@@ -1269,10 +1612,12 @@ fn expr_as_tuple(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Tuple(expressions)) = expr {
-            let expressions =
-                expressions.into_iter().map(|expr| Value::expression(expr.kind)).collect();
+            let expressions = expressions
+                .into_iter()
+                .map(|expression| Value::expression(expression, file))
+                .collect();
             let typ = Type::Slice(Box::new(Type::Quoted(QuotedType::Expr)));
             Some(Value::Slice(expressions, typ))
         } else {
@@ -1288,7 +1633,7 @@ fn expr_as_unary_op(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type.clone(), location, |expr| {
+    expr_as(interner, arguments, return_type.clone(), location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Prefix(prefix_expr)) = expr {
             let option_type = extract_option_generic_type(return_type);
             let Type::Tuple(mut tuple_types) = option_type else {
@@ -1311,7 +1656,7 @@ fn expr_as_unary_op(
             fields.insert(Rc::new("op".to_string()), Value::Field(unary_op_value.into()));
 
             let unary_op = Value::Struct(fields, unary_op_type);
-            let rhs = Value::expression(prefix_expr.rhs.kind);
+            let rhs = Value::expression(prefix_expr.rhs, file);
             Some(Value::Tuple(vec![unary_op, rhs]))
         } else {
             None
@@ -1326,9 +1671,9 @@ fn expr_as_unsafe(
     return_type: Type,
     location: Location,
 ) -> IResult<Value> {
-    expr_as(interner, arguments, return_type, location, |expr| {
+    expr_as(interner, arguments, return_type, location, |expr, file| {
         if let ExprValue::Expression(ExpressionKind::Unsafe(block_expr, _)) = expr {
-            Some(block_expression_to_value(block_expr))
+            Some(block_expression_to_value(block_expr, file))
         } else {
             None
         }
@@ -1368,7 +1713,383 @@ fn expr_is_continue(
     Ok(Value::Bool(matches!(expr_value, ExprValue::Statement(StatementKind::Continue))))
 }
 
+// fn new_array(exprs: [Expr]) -> Expr
+fn expr_new_array(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let exprs = check_one_argument(arguments, location)?;
+    let exprs = get_expressions(interner, exprs)?;
+    let kind = ExpressionKind::Literal(Literal::Array(ArrayLiteral::Standard(exprs)));
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_assert(predicate: Expr, msg: Option<Expr>) -> Expr
+fn expr_new_assert(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (predicate, (message, message_location)) = check_two_arguments(arguments, location)?;
+    let predicate = get_expression(interner, predicate)?;
+    let message = unwrap_option(message)
+        .map(|message| get_expression(interner, (message, message_location)))
+        .transpose()?;
+
+    let kind =
+        StatementKind::Constrain(ConstrainStatement(predicate, message, ConstrainKind::Assert));
+    Ok(Value::statement(Statement { kind, span: location.span }, location.file))
+}
+
+// fn new_assign(lhs: Expr, rhs: Expr) -> Expr
+fn expr_new_assign(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (lhs, rhs) = check_two_arguments(arguments, location)?;
+    let lvalue = get_lvalue(interner, lhs)?;
+    let expression = get_expression(interner, rhs)?;
+    let kind = StatementKind::Assign(AssignStatement { lvalue, expression });
+    Ok(Value::statement(Statement { kind, span: location.span }, location.file))
+}
+
+// fn new_binary_op(lhs: Expr, op: BinaryOp, rhs: Expr) -> Expr
+fn expr_new_binary_op(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (lhs, op, rhs) = check_three_arguments(arguments, location)?;
+    let lhs = get_expression(interner, lhs)?;
+    let op_span = op.1.span;
+    let operator = Spanned::from(op_span, get_binary_op(interner, op)?);
+    let rhs = get_expression(interner, rhs)?;
+    let kind = ExpressionKind::Infix(Box::new(InfixExpression { lhs, operator, rhs }));
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_block(exprs: [Expr]) -> Expr
+fn expr_new_block(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let exprs = check_one_argument(arguments, location)?;
+    let block = get_block_expression(interner, exprs)?;
+    Ok(Value::expression(
+        Expression::new(ExpressionKind::Block(block), location.span),
+        location.file,
+    ))
+}
+
+// fn new_cast(expr: Expr, typ: UnresolvedType) -> Expr
+fn expr_new_cast(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (expr, typ) = check_two_arguments(arguments, location)?;
+    let lhs = get_expression(interner, expr)?;
+    let typ_span = typ.1.span;
+    let r#type = UnresolvedType { typ: get_unresolved_type(interner, typ)?, span: typ_span };
+    let kind = ExpressionKind::Cast(Box::new(CastExpression { lhs, r#type }));
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_comptime(exprs: [Expr]) -> Expr
+fn expr_new_comptime(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let exprs = check_one_argument(arguments, location)?;
+    let block = get_block_expression(interner, exprs)?;
+    let kind = ExpressionKind::Comptime(block, location.span);
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_if(condition: Expr, consequence: Expr, alternative: Option<Expr>) -> Expr
+fn expr_new_if(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (condition, consequence, (alternative, alternative_location)) =
+        check_three_arguments(arguments, location)?;
+    let condition = get_expression(interner, condition)?;
+
+    // Both branches are blocks containing the given expression, as in `if c { e1 } else { e2 }`
+    let block = |value: (Value, Location)| -> IResult<Expression> {
+        let statement = get_statement(interner, value)?;
+        let span = statement.span;
+        let block = BlockExpression { statements: vec![statement] };
+        Ok(Expression::new(ExpressionKind::Block(block), span))
+    };
+    let consequence = block(consequence)?;
+    let alternative = unwrap_option(alternative)
+        .map(|alternative| block((alternative, alternative_location)))
+        .transpose()?;
+
+    let if_expr = IfExpression { condition, consequence, alternative };
+    Ok(Value::expression(
+        Expression::new(ExpressionKind::If(Box::new(if_expr)), location.span),
+        location.file,
+    ))
+}
+
+// fn new_index(object: Expr, index: Expr) -> Expr
+fn expr_new_index(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (object, index) = check_two_arguments(arguments, location)?;
+    let collection = get_expression(interner, object)?;
+    let index = get_expression(interner, index)?;
+    let kind = ExpressionKind::Index(Box::new(IndexExpression { collection, index }));
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_let(pattern: Quoted, typ: Option<UnresolvedType>, expr: Expr) -> Expr
+fn expr_new_let(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (pattern, (typ, typ_location), expr) = check_three_arguments(arguments, location)?;
+    let pattern = parse(pattern, parser::pattern(), "a pattern")?;
+    let typ = match unwrap_option(typ) {
+        Some(typ) => get_unresolved_type(interner, (typ, typ_location))?,
+        None => UnresolvedTypeData::Unspecified,
+    };
+    let typ = UnresolvedType { typ, span: typ_location.span };
+    let expr = get_expression(interner, expr)?;
+
+    let kind = StatementKind::new_let(((pattern, typ), expr));
+    Ok(Value::statement(Statement { kind, span: location.span }, location.file))
+}
+
+// fn new_member_access(object: Expr, name: Quoted) -> Expr
+fn expr_new_member_access(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (object, name) = check_two_arguments(arguments, location)?;
+    let lhs = get_expression(interner, object)?;
+    let rhs = parse(name, parser::ident(), "an identifier")?;
+    let kind = ExpressionKind::MemberAccess(Box::new(MemberAccessExpression { lhs, rhs }));
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_function_call(function: Expr, arguments: [Expr]) -> Expr
+fn expr_new_function_call(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (function, arguments) = check_two_arguments(arguments, location)?;
+    let func = Box::new(get_expression(interner, function)?);
+    let arguments = get_expressions(interner, arguments)?;
+    let call = CallExpression { func, arguments, is_macro_call: false };
+    Ok(Value::expression(
+        Expression::new(ExpressionKind::Call(Box::new(call)), location.span),
+        location.file,
+    ))
+}
+
+// fn new_method_call(object: Expr, name: Quoted, generics: [UnresolvedType], arguments: [Expr]) -> Expr
+fn expr_new_method_call(
+    interner: &NodeInterner,
+    mut arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    check_argument_count(4, &arguments, location)?;
+
+    let method_arguments = arguments.pop().unwrap();
+    let (generics, generics_location) = arguments.pop().unwrap();
+    let name = arguments.pop().unwrap();
+    let object = arguments.pop().unwrap();
+
+    let object = get_expression(interner, object)?;
+    let method_name = parse(name, parser::ident(), "an identifier")?;
+
+    let (generics, _) = get_slice(interner, (generics, generics_location))?;
+    let generics = try_vecmap(generics, |generic| {
+        let typ = get_unresolved_type(interner, (generic, generics_location))?;
+        Ok(UnresolvedType { typ, span: generics_location.span })
+    })?;
+    let generics = if generics.is_empty() { None } else { Some(generics) };
+
+    let arguments = get_expressions(interner, method_arguments)?;
+
+    let method_call =
+        MethodCallExpression { object, method_name, generics, arguments, is_macro_call: false };
+    let kind = ExpressionKind::MethodCall(Box::new(method_call));
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_repeated_element_array(expr: Expr, length: Expr) -> Expr
+fn expr_new_repeated_element_array(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let array = get_repeated_element_array(interner, arguments, location)?;
+    let kind = ExpressionKind::Literal(Literal::Array(array));
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_repeated_element_slice(expr: Expr, length: Expr) -> Expr
+fn expr_new_repeated_element_slice(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let array = get_repeated_element_array(interner, arguments, location)?;
+    let kind = ExpressionKind::Literal(Literal::Slice(array));
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_slice(exprs: [Expr]) -> Expr
+fn expr_new_slice(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let exprs = check_one_argument(arguments, location)?;
+    let exprs = get_expressions(interner, exprs)?;
+    let kind = ExpressionKind::Literal(Literal::Slice(ArrayLiteral::Standard(exprs)));
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_tuple(exprs: [Expr]) -> Expr
+fn expr_new_tuple(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let exprs = check_one_argument(arguments, location)?;
+    let exprs = get_expressions(interner, exprs)?;
+    Ok(Value::expression(
+        Expression::new(ExpressionKind::Tuple(exprs), location.span),
+        location.file,
+    ))
+}
+
+// fn new_unary_op(op: UnaryOp, rhs: Expr) -> Expr
+fn expr_new_unary_op(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (op, rhs) = check_two_arguments(arguments, location)?;
+    let operator = get_unary_op(interner, op)?;
+    let rhs = get_expression(interner, rhs)?;
+    let kind = ExpressionKind::Prefix(Box::new(PrefixExpression { operator, rhs }));
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// fn new_unsafe(exprs: [Expr]) -> Expr
+fn expr_new_unsafe(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let exprs = check_one_argument(arguments, location)?;
+    let block = get_block_expression(interner, exprs)?;
+    let kind = ExpressionKind::Unsafe(block, location.span);
+    Ok(Value::expression(Expression::new(kind, location.span), location.file))
+}
+
+// Helper function for `expr_new_repeated_element_array` and `expr_new_repeated_element_slice`
+fn get_repeated_element_array(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<ArrayLiteral> {
+    let (expr, length) = check_two_arguments(arguments, location)?;
+    let repeated_element = Box::new(get_expression(interner, expr)?);
+    let length = Box::new(get_expression(interner, length)?);
+    Ok(ArrayLiteral::Repeated { repeated_element, length })
+}
+
+/// Returns the operator of a `BinaryOp` value. Its `op` field is the index of the operator,
+/// which should match the values used in noir_stdlib/src/meta/op.nr
+fn get_binary_op(
+    interner: &NodeInterner,
+    (value, location): (Value, Location),
+) -> IResult<BinaryOpKind> {
+    let typ = value.get_type().into_owned();
+    let op = get_field((get_struct_field(interner, "op", (value, location))?, location))?;
+    Ok(match op.try_into_u128() {
+        Some(0) => BinaryOpKind::Add,
+        Some(1) => BinaryOpKind::Subtract,
+        Some(2) => BinaryOpKind::Multiply,
+        Some(3) => BinaryOpKind::Divide,
+        Some(4) => BinaryOpKind::Equal,
+        Some(5) => BinaryOpKind::NotEqual,
+        Some(6) => BinaryOpKind::Less,
+        Some(7) => BinaryOpKind::LessEqual,
+        Some(8) => BinaryOpKind::Greater,
+        Some(9) => BinaryOpKind::GreaterEqual,
+        Some(10) => BinaryOpKind::And,
+        Some(11) => BinaryOpKind::Or,
+        Some(12) => BinaryOpKind::Xor,
+        Some(13) => BinaryOpKind::ShiftRight,
+        Some(14) => BinaryOpKind::ShiftLeft,
+        Some(15) => BinaryOpKind::Modulo,
+        _ => return Err(InterpreterError::IntegerOutOfRangeForType { value: op, typ, location }),
+    })
+}
+
+/// Returns the operator of a `UnaryOp` value, the inverse of the conversion in `expr_as_unary_op`
+fn get_unary_op(interner: &NodeInterner, (value, location): (Value, Location)) -> IResult<UnaryOp> {
+    let typ = value.get_type().into_owned();
+    let op = get_field((get_struct_field(interner, "op", (value, location))?, location))?;
+    Ok(match op.try_into_u128() {
+        Some(0) => UnaryOp::Minus,
+        Some(1) => UnaryOp::Not,
+        Some(2) => UnaryOp::MutableReference,
+        Some(3) => UnaryOp::Dereference { implicitly_added: false },
+        _ => return Err(InterpreterError::IntegerOutOfRangeForType { value: op, typ, location }),
+    })
+}
+
+// fn resolve(self, in_function: Option<FunctionDefinition>) -> TypedExpr
+fn expr_resolve(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (self_argument, (in_function, in_function_location)) =
+        check_two_arguments(arguments, location)?;
+    let self_argument_location = self_argument.1;
+    let expr_value = get_expr(interpreter.elaborator.interner, self_argument)?;
+    let expression = Value::Expr(expr_value, self_argument_location)
+        .into_expression(interpreter.elaborator.interner, self_argument_location)?;
+
+    let in_function = unwrap_option(in_function)
+        .map(|function| get_function_def((function, in_function_location)))
+        .transpose()?;
+
+    let expr_id = if let Some(function) = in_function {
+        interpreter.elaborate_item(Some(function), |elaborator| {
+            elaborator.elaborate_expression_in_function(function, expression)
+        })
+    } else {
+        interpreter.elaborate_item(interpreter.current_function, |elaborator| {
+            elaborator.elaborate_expression(expression).0
+        })
+    };
+
+    Ok(Value::TypedExpr(expr_id))
+}
+
 // Helper function for implementing the `expr_as_...` functions.
+// `f` is also given the file of the expression, which its sub-expressions are in too.
 fn expr_as<F>(
     interner: &NodeInterner,
     arguments: Vec<(Value, Location)>,
@@ -1377,9 +2098,13 @@ fn expr_as<F>(
     f: F,
 ) -> IResult<Value>
 where
-    F: FnOnce(ExprValue) -> Option<Value>,
+    F: FnOnce(ExprValue, FileId) -> Option<Value>,
 {
     let self_argument = check_one_argument(arguments, location)?;
+    let file = match &self_argument.0 {
+        Value::Expr(_, expr_location) => expr_location.file,
+        _ => self_argument.1.file,
+    };
     let mut expr_value = get_expr(interner, self_argument)?;
     loop {
         match expr_value {
@@ -1403,10 +2128,38 @@ where
         }
     }
 
-    let option_value = f(expr_value);
+    let option_value = f(expr_value, file);
     option(return_type, option_value)
 }
 
+// fn add_attribute<let N: u32>(self, attribute: str<N>)
+fn function_def_add_attribute(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (self_argument, attribute) = check_two_arguments(arguments, location)?;
+    let func_id = get_function_def(self_argument)?;
+    let attribute = get_str(interpreter.elaborator.interner, attribute)?;
+    let attribute = parse_secondary_attribute(&attribute, location)?;
+
+    let modifiers = interpreter.elaborator.interner.function_modifiers_mut(&func_id);
+    modifiers.attributes.secondary.push(attribute);
+    Ok(Value::Unit)
+}
+
+// fn attributes(self) -> [Quoted]
+fn function_def_attributes(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let self_argument = check_one_argument(arguments, location)?;
+    let func_id = get_function_def(self_argument)?;
+    let attributes = &interner.function_attributes(&func_id).secondary;
+    Ok(secondary_attributes_to_value(attributes, location))
+}
+
 // fn body(self) -> Expr
 fn function_def_body(
     interner: &NodeInterner,
@@ -1416,13 +2169,62 @@ fn function_def_body(
     let self_argument = check_one_argument(arguments, location)?;
     let func_id = get_function_def(self_argument)?;
     let func_meta = interner.function_meta(&func_id);
-    if let FunctionBody::Unresolved(_, block_expr, _) = &func_meta.function_body {
-        Ok(Value::expression(ExpressionKind::Block(block_expr.clone())))
+    if let FunctionBody::Unresolved(_, block_expr, span) = &func_meta.function_body {
+        let body = ExpressionKind::Block(block_expr.clone());
+        Ok(Value::expression(Expression::new(body, *span), func_meta.location.file))
     } else {
         Err(InterpreterError::FunctionAlreadyResolved { location })
     }
 }
 
+// fn variable_type(self, name: Quoted) -> Option<Type>
+fn function_def_variable_type(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    return_type: Type,
+    location: Location,
+) -> IResult<Value> {
+    let (self_argument, name) = check_two_arguments(arguments, location)?;
+    let func_id = get_function_def(self_argument)?;
+    let name = tokens_to_string(&get_quoted(name)?);
+
+    // The body needs to be elaborated for its variables to have types
+    interpreter.get_function_body(func_id, location)?;
+
+    let interner = &interpreter.elaborator.interner;
+    let typ = interner.find_local_definition(func_id, &name).map(|definition_id| {
+        Value::Type(interner.definition_type(definition_id).follow_bindings())
+    });
+    option(return_type, typ)
+}
+
+// fn error<let N: u32>(self, message: str<N>)
+// fn warn<let N: u32>(self, message: str<N>)
+fn function_def_emit(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+    is_warning: bool,
+) -> IResult<Value> {
+    let (self_argument, message) = check_two_arguments(arguments, location)?;
+    let func_id = get_function_def(self_argument)?;
+    let function_location = interpreter.elaborator.interner.function_meta(&func_id).name.location;
+    push_comptime_diagnostic(interpreter, message, function_location, is_warning)
+}
+
+// fn has_named_attribute(self, name: Quoted) -> bool
+fn function_def_has_named_attribute(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (self_argument, name) = check_two_arguments(arguments, location)?;
+    let func_id = get_function_def(self_argument)?;
+    let name = get_quoted(name)?;
+    let attributes = &interner.function_attributes(&func_id).secondary;
+    Ok(Value::Bool(has_named_attribute(&name, attributes)))
+}
+
 // fn name(self) -> Quoted
 fn function_def_name(
     interner: &NodeInterner,
@@ -1432,8 +2234,9 @@ fn function_def_name(
     let self_argument = check_one_argument(arguments, location)?;
     let func_id = get_function_def(self_argument)?;
     let name = interner.function_name(&func_id).to_string();
-    let tokens = Rc::new(vec![Token::Ident(name)]);
-    Ok(Value::Quoted(tokens))
+    let location = interner.function_meta(&func_id).name.location;
+    let tokens = Rc::new(vec![SpannedToken::new(Token::Ident(name), location.span)]);
+    Ok(Value::Quoted(tokens, Some(location.file)))
 }
 
 // fn parameters(self) -> [(Quoted, Type)]
@@ -1450,7 +2253,8 @@ fn function_def_parameters(
         .parameters
         .iter()
         .map(|(hir_pattern, typ, _visibility)| {
-            let name = Value::Quoted(Rc::new(hir_pattern_to_tokens(interner, hir_pattern)));
+            let name = hir_pattern_to_tokens(interner, hir_pattern);
+            let name = Value::Quoted(Rc::new(name), Some(func_meta.location.file));
             let typ = Value::Type(typ.clone());
             Value::Tuple(vec![name, typ])
         })
@@ -1589,6 +2393,57 @@ fn function_def_set_return_type(
     Ok(Value::Unit)
 }
 
+// fn add_attribute<let N: u32>(self, attribute: str<N>)
+fn module_add_attribute(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (self_argument, attribute) = check_two_arguments(arguments, location)?;
+    let module_id = get_module(self_argument)?;
+    let attribute = get_str(interpreter.elaborator.interner, attribute)?;
+    let attribute = parse_secondary_attribute(&attribute, location)?;
+
+    interpreter.elaborator.interner.add_module_secondary_attribute(module_id, attribute);
+    Ok(Value::Unit)
+}
+
+// fn add_item(self, item: Quoted)
+fn module_add_item(
+    interpreter: &mut Interpreter,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (self_argument, item) = check_two_arguments(arguments, location)?;
+    let module_id = get_module(self_argument)?;
+
+    if module_id.krate != interpreter.crate_id {
+        return Err(InterpreterError::CannotAddItemToDependency { location });
+    }
+
+    let (item, item_location) = item;
+    let items = item.into_top_level_items(item_location, interpreter.elaborator.interner)?;
+
+    let current_function = interpreter.current_function;
+    interpreter.elaborate_item(current_function, |elaborator| {
+        elaborator.add_items_to_module(module_id, items, location);
+    });
+
+    Ok(Value::Unit)
+}
+
+// fn attributes(self) -> [Quoted]
+fn module_attributes(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let self_argument = check_one_argument(arguments, location)?;
+    let module_id = get_module(self_argument)?;
+    let attributes = interner.module_secondary_attributes(&module_id);
+    Ok(secondary_attributes_to_value(attributes, location))
+}
+
 // fn functions(self) -> [FunctionDefinition]
 fn module_functions(
     interpreter: &Interpreter,
@@ -1613,6 +2468,19 @@ fn module_functions(
     Ok(Value::Slice(func_ids, slice_type))
 }
 
+// fn has_named_attribute(self, name: Quoted) -> bool
+fn module_has_named_attribute(
+    interner: &NodeInterner,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let (self_argument, name) = check_two_arguments(arguments, location)?;
+    let module_id = get_module(self_argument)?;
+    let name = get_quoted(name)?;
+    let attributes = interner.module_secondary_attributes(&module_id);
+    Ok(Value::Bool(has_named_attribute(&name, attributes)))
+}
+
 // fn is_contract(self) -> bool
 fn module_is_contract(
     interpreter: &Interpreter,
@@ -1632,9 +2500,33 @@ fn module_name(
 ) -> IResult<Value> {
     let self_argument = check_one_argument(arguments, location)?;
     let module_id = get_module(self_argument)?;
-    let name = &interner.module_attributes(&module_id).name;
-    let tokens = Rc::new(vec![Token::Ident(name.clone())]);
-    Ok(Value::Quoted(tokens))
+    let module = interner.module_attributes(&module_id);
+    let name = SpannedToken::new(Token::Ident(module.name.clone()), module.location.span);
+    Ok(Value::Quoted(Rc::new(vec![name]), Some(module.location.file)))
+}
+
+// fn structs(self) -> [StructDefinition]
+fn module_structs(
+    interpreter: &Interpreter,
+    arguments: Vec<(Value, Location)>,
+    location: Location,
+) -> IResult<Value> {
+    let self_argument = check_one_argument(arguments, location)?;
+    let module_id = get_module(self_argument)?;
+    let module_data = interpreter.elaborator.get_module(module_id);
+    let struct_ids = module_data
+        .type_definitions()
+        .filter_map(|module_def_id| {
+            if let ModuleDefId::TypeId(struct_id) = module_def_id {
+                Some(Value::StructDefinition(struct_id))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let slice_type = Type::Slice(Box::new(Type::Quoted(QuotedType::StructDefinition)));
+    Ok(Value::Slice(struct_ids, slice_type))
 }
 
 fn modulus_be_bits(
@@ -1740,6 +2632,21 @@ pub(crate) fn option(option_type: Type, value: Option<Value>) -> IResult<Value>
     Ok(Value::Struct(fields, option_type))
 }
 
+/// Returns the value held by an `Option`, or `None` if it is empty.
+/// `value` must be a value of an `Option` type.
+pub(crate) fn unwrap_option(value: Value) -> Option<Value> {
+    let Value::Struct(mut fields, _) = value else {
+        panic!("Expected an Option value");
+    };
+
+    let is_some = fields.remove(&Rc::new("_is_some".to_string()));
+    let value = fields.remove(&Rc::new("_value".to_string()));
+    match (is_some, value) {
+        (Some(Value::Bool(true)), Some(value)) => Some(value),
+        _ => None,
+    }
+}
+
 /// Given a type, assert that it's an Option<T> and return the Type for T
 pub(crate) fn extract_option_generic_type(typ: Type) -> Type {
     let Type::Struct(struct_type, mut generics) = typ else {
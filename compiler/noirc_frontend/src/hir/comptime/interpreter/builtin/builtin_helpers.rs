@@ -1,12 +1,14 @@
 use std::rc::Rc;
 
 use acvm::FieldElement;
-use noirc_errors::Location;
+use fm::FileId;
+use iter_extended::{try_vecmap, vecmap};
+use noirc_errors::{Location, Span};
 
 use crate::{
     ast::{
-        BlockExpression, ExpressionKind, IntegerBitSize, LValue, Signedness, StatementKind,
-        UnresolvedTypeData,
+        BlockExpression, Expression, ExpressionKind, IntegerBitSize, LValue, Signedness, Statement,
+        StatementKind, UnaryOp, UnresolvedTypeData,
     },
     hir::{
         comptime::{
@@ -21,10 +23,11 @@ use crate::{
         function::{FuncMeta, FunctionBody},
         stmt::HirPattern,
     },
+    lexer::Lexer,
     macros_api::{NodeInterner, StructId},
-    node_interner::{FuncId, TraitId, TraitImplId},
+    node_interner::{ExprId, FuncId, TraitId, TraitImplId},
     parser::NoirParser,
-    token::{Token, Tokens},
+    token::{Attribute, SecondaryAttribute, SpannedToken, Token, Tokens},
     QuotedType, Type,
 };
 
@@ -118,6 +121,24 @@ pub(crate) fn get_tuple(
     }
 }
 
+/// Returns the value of the given field of a struct value
+pub(crate) fn get_struct_field(
+    interner: &NodeInterner,
+    field_name: &str,
+    (value, location): (Value, Location),
+) -> IResult<Value> {
+    match value {
+        Value::Struct(mut fields, typ) => match fields.remove(&Rc::new(field_name.to_string())) {
+            Some(field) => Ok(field),
+            None => {
+                let field_name = field_name.to_string();
+                Err(InterpreterError::ExpectedStructToHaveField { typ, field_name, location })
+            }
+        },
+        value => type_mismatch(value, interner.next_type_variable(), location),
+    }
+}
+
 pub(crate) fn get_field((value, location): (Value, Location)) -> IResult<FieldElement> {
     match value {
         Value::Field(value) => Ok(value),
@@ -160,7 +181,7 @@ pub(crate) fn get_expr(
     (value, location): (Value, Location),
 ) -> IResult<ExprValue> {
     match value {
-        Value::Expr(expr) => match expr {
+        Value::Expr(expr, _) => match expr {
             ExprValue::Expression(ExpressionKind::Interned(id)) => {
                 Ok(ExprValue::Expression(interner.get_expression_kind(id).clone()))
             }
@@ -176,6 +197,133 @@ pub(crate) fn get_expr(
     }
 }
 
+/// Returns an `Expr` value as an expression. Expression statements and lvalues are converted
+/// to the expression they contain, while any other statement is an error.
+pub(crate) fn get_expression(
+    interner: &NodeInterner,
+    (value, location): (Value, Location),
+) -> IResult<Expression> {
+    let span = expr_value_span(&value, location);
+    let expr = get_expr(interner, (value, location))?;
+    expr_value_to_expression(expr, span, location)
+}
+
+/// Returns each `Expr` in a slice of them as an expression, see `get_expression`.
+pub(crate) fn get_expressions(
+    interner: &NodeInterner,
+    (value, location): (Value, Location),
+) -> IResult<Vec<Expression>> {
+    let (values, _) = get_slice(interner, (value, location))?;
+    try_vecmap(values, |value| get_expression(interner, (value, location)))
+}
+
+/// Returns an `Expr` value as a statement, wrapping expressions and lvalues in an expression statement.
+pub(crate) fn get_statement(
+    interner: &NodeInterner,
+    (value, location): (Value, Location),
+) -> IResult<Statement> {
+    let span = expr_value_span(&value, location);
+    let kind = match get_expr(interner, (value, location))? {
+        ExprValue::Expression(kind) => StatementKind::Expression(Expression::new(kind, span)),
+        ExprValue::Statement(kind) => kind,
+        ExprValue::LValue(lvalue) => StatementKind::Expression(lvalue.as_expression()),
+    };
+    Ok(Statement { kind, span })
+}
+
+/// Returns a slice of `Expr` values as a block. As when the statements are written out
+/// one after the other, every expression but the last one gets a semicolon.
+pub(crate) fn get_block_expression(
+    interner: &NodeInterner,
+    (value, location): (Value, Location),
+) -> IResult<BlockExpression> {
+    let (values, _) = get_slice(interner, (value, location))?;
+    let last_index = values.len().saturating_sub(1);
+
+    let mut statements = Vec::with_capacity(values.len());
+    for (index, value) in values.into_iter().enumerate() {
+        let mut statement = get_statement(interner, (value, location))?;
+        statement.kind = match statement.kind {
+            StatementKind::Expression(expression) if index != last_index => {
+                StatementKind::Semi(expression)
+            }
+            kind => kind,
+        };
+        statements.push(statement);
+    }
+
+    Ok(BlockExpression { statements })
+}
+
+/// Returns an `Expr` value as something that can be assigned to, or an error if it is
+/// any other kind of expression.
+pub(crate) fn get_lvalue(
+    interner: &NodeInterner,
+    (value, location): (Value, Location),
+) -> IResult<LValue> {
+    let span = expr_value_span(&value, location);
+    let expression = match get_expr(interner, (value, location))? {
+        ExprValue::LValue(lvalue) => return Ok(lvalue),
+        expr => expr_value_to_expression(expr, span, location)?,
+    };
+
+    let display = expression.to_string();
+    expression_to_lvalue(interner, expression)
+        .ok_or(InterpreterError::ExpressionNotAssignable { expression: display, location })
+}
+
+fn expr_value_span(value: &Value, location: Location) -> Span {
+    match value {
+        Value::Expr(_, expr_location) => expr_location.span,
+        _ => location.span,
+    }
+}
+
+fn expr_value_to_expression(
+    expr: ExprValue,
+    span: Span,
+    location: Location,
+) -> IResult<Expression> {
+    match expr {
+        ExprValue::Expression(kind) => Ok(Expression::new(kind, span)),
+        ExprValue::Statement(StatementKind::Expression(expression))
+        | ExprValue::Statement(StatementKind::Semi(expression)) => Ok(expression),
+        ExprValue::Statement(statement) => {
+            let statement = statement.to_string();
+            Err(InterpreterError::StatementUsedAsExpression { statement, location })
+        }
+        ExprValue::LValue(lvalue) => Ok(lvalue.as_expression()),
+    }
+}
+
+/// Like `LValue::from_expression` but returns `None` instead of panicking for expressions
+/// that can't be assigned to. Interned expressions are looked up so they can be checked too.
+fn expression_to_lvalue(interner: &NodeInterner, expression: Expression) -> Option<LValue> {
+    let span = expression.span;
+    match expression.kind {
+        ExpressionKind::Variable(path) => path.as_ident().cloned().map(LValue::Ident),
+        ExpressionKind::MemberAccess(member_access) => {
+            let object = Box::new(expression_to_lvalue(interner, member_access.lhs)?);
+            Some(LValue::MemberAccess { object, field_name: member_access.rhs, span })
+        }
+        ExpressionKind::Index(index) => {
+            let array = Box::new(expression_to_lvalue(interner, index.collection)?);
+            Some(LValue::Index { array, index: index.index, span })
+        }
+        ExpressionKind::Prefix(prefix)
+            if matches!(prefix.operator, UnaryOp::Dereference { implicitly_added: false }) =>
+        {
+            let lvalue = expression_to_lvalue(interner, prefix.rhs)?;
+            Some(LValue::Dereference(Box::new(lvalue), span))
+        }
+        ExpressionKind::Interned(id) => {
+            let kind = interner.get_expression_kind(id).clone();
+            expression_to_lvalue(interner, Expression::new(kind, span))
+        }
+        _ => None,
+    }
+}
+
 pub(crate) fn get_function_def((value, location): (Value, Location)) -> IResult<FuncId> {
     match value {
         Value::FunctionDefinition(id) => Ok(id),
@@ -227,13 +375,33 @@ pub(crate) fn get_type((value, location): (Value, Location)) -> IResult<Type> {
     }
 }
 
-pub(crate) fn get_quoted((value, location): (Value, Location)) -> IResult<Rc<Vec<Token>>> {
+pub(crate) fn get_quoted((value, location): (Value, Location)) -> IResult<Rc<Vec<SpannedToken>>> {
     match value {
-        Value::Quoted(tokens) => Ok(tokens),
+        Value::Quoted(tokens, _) => Ok(tokens),
         value => type_mismatch(value, Type::Quoted(QuotedType::Quoted), location),
     }
 }
 
+pub(crate) fn get_str(
+    interner: &NodeInterner,
+    (value, location): (Value, Location),
+) -> IResult<Rc<String>> {
+    match value {
+        Value::String(string) => Ok(string),
+        value => {
+            let expected = Type::String(Box::new(interner.next_type_variable()));
+            type_mismatch(value, expected, location)
+        }
+    }
+}
+
+pub(crate) fn get_typed_expr((value, location): (Value, Location)) -> IResult<ExprId> {
+    match value {
+        Value::TypedExpr(expr_id) => Ok(expr_id),
+        value => type_mismatch(value, Type::Quoted(QuotedType::TypedExpr), location),
+    }
+}
+
 pub(crate) fn get_unresolved_type(
     interner: &NodeInterner,
     (value, location): (Value, Location),
@@ -256,13 +424,15 @@ fn type_mismatch<T>(value: Value, expected: Type, location: Location) -> IResult
     Err(InterpreterError::TypeMismatch { expected, actual, location })
 }
 
+/// Converts a pattern to tokens, each with the span of the whole pattern
 pub(crate) fn hir_pattern_to_tokens(
     interner: &NodeInterner,
     hir_pattern: &HirPattern,
-) -> Vec<Token> {
+) -> Vec<SpannedToken> {
     let mut tokens = Vec::new();
     gather_hir_pattern_tokens(interner, hir_pattern, &mut tokens);
-    tokens
+    let span = hir_pattern.span();
+    vecmap(tokens, |token| SpannedToken::new(token, span))
 }
 
 fn gather_hir_pattern_tokens(
@@ -351,7 +521,7 @@ pub(super) fn parse<T>(
 }
 
 pub(super) fn parse_tokens<T>(
-    tokens: Rc<Vec<Token>>,
+    tokens: Rc<Vec<SpannedToken>>,
     quoted: Tokens,
     location: Location,
     parser: impl NoirParser<T>,
@@ -396,10 +566,72 @@ pub(super) fn replace_func_meta_return_type(typ: &mut Type, return_type: Type) {
     }
 }
 
-pub(super) fn block_expression_to_value(block_expr: BlockExpression) -> Value {
+pub(super) fn block_expression_to_value(block_expr: BlockExpression, file: FileId) -> Value {
     let typ = Type::Slice(Box::new(Type::Quoted(QuotedType::Expr)));
     let statements = block_expr.statements.into_iter();
-    let statements = statements.map(|statement| Value::statement(statement.kind)).collect();
+    let statements = statements.map(|statement| Value::statement(statement, file)).collect();
 
     Value::Slice(statements, typ)
 }
+
+/// Converts each attribute to its contents as a `Quoted` value, e.g. `foo(a)` for `#[foo(a)]`.
+/// Attributes don't track their own spans so each token is given `location` instead.
+pub(super) fn secondary_attributes_to_value(
+    attributes: &[SecondaryAttribute],
+    location: Location,
+) -> Value {
+    let attributes = attributes.iter().map(|attribute| {
+        let tokens = lex(&attribute.contents(), location.span);
+        Value::Quoted(Rc::new(tokens), Some(location.file))
+    });
+
+    let typ = Type::Slice(Box::new(Type::Quoted(QuotedType::Quoted)));
+    Value::Slice(attributes.collect(), typ)
+}
+
+/// Lexes the given source into tokens suitable for a `Quoted` value, dropping the trailing EOF.
+/// `input` isn't part of any source file so each token is given `span` instead.
+pub(super) fn lex(input: &str, span: Span) -> Vec<SpannedToken> {
+    let (tokens, _) = Lexer::lex(input);
+    let tokens = tokens.0.into_iter().filter(|token| *token != Token::EOF);
+    tokens.map(|token| SpannedToken::new(token.into_token(), span)).collect()
+}
+
+/// Returns the location covering all of the given tokens, or `None` if there are no tokens
+/// or they don't all come from the same file.
+pub(super) fn quoted_location(tokens: &[SpannedToken], file: Option<FileId>) -> Option<Location> {
+    let span = tokens.iter().map(SpannedToken::to_span).reduce(Span::merge)?;
+    Some(Location::new(span, file?))
+}
+
+/// True if any of the given attributes has the given name, e.g. `foo` for `#[foo(a)]`
+pub(super) fn has_named_attribute(
+    name: &[SpannedToken],
+    attributes: &[SecondaryAttribute],
+) -> bool {
+    let name = tokens_to_string(name);
+    attributes.iter().any(|attribute| attribute.name() == name)
+}
+
+/// Concatenates the given tokens, e.g. to get the name in `quote { foo }`
+pub(super) fn tokens_to_string(tokens: &[SpannedToken]) -> String {
+    tokens.iter().map(|token| token.to_string()).collect()
+}
+
+/// Parses the contents of an attribute as written between `#[` and `]`
+pub(super) fn parse_secondary_attribute(
+    attribute: &str,
+    location: Location,
+) -> IResult<SecondaryAttribute> {
+    let (tokens, errors) = Lexer::lex(&format!("#[{attribute}]"));
+    let mut tokens = tokens.0.into_iter().map(SpannedToken::into_token);
+
+    match (tokens.next(), tokens.next()) {
+        (Some(Token::Attribute(Attribute::Secondary(attribute))), Some(Token::EOF))
+            if errors.is_empty() =>
+        {
+            Ok(attribute)
+        }
+        _ => Err(InterpreterError::InvalidAttribute { attribute: attribute.to_string(), location }),
+    }
+}
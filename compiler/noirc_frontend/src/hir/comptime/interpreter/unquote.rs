@@ -1,8 +1,9 @@
+use fm::FileId;
 use noirc_errors::Location;
 
 use crate::{
-    hir::comptime::errors::IResult,
-    token::{Token, Tokens},
+    hir::comptime::{errors::IResult, Value},
+    token::{SpannedToken, Token, Tokens},
 };
 
 use super::Interpreter;
@@ -10,25 +11,36 @@ use super::Interpreter;
 impl<'local, 'interner> Interpreter<'local, 'interner> {
     /// Evaluates any expressions within UnquoteMarkers in the given token list
     /// and replaces the expression held by the marker with the evaluated value
-    /// in expression form.
+    /// in expression form. Tokens keep their spans, while any value which isn't
+    /// already quoted is given the span of its unquote marker.
+    ///
+    /// Also returns the file all of the resulting tokens are in, or `None` if a
+    /// quoted value from another file was unquoted into them.
     pub(super) fn substitute_unquoted_values_into_tokens(
         &mut self,
         tokens: Tokens,
         location: Location,
-    ) -> IResult<Vec<Token>> {
+    ) -> IResult<(Vec<SpannedToken>, Option<FileId>)> {
         let mut new_tokens = Vec::with_capacity(tokens.0.len());
+        let mut file = Some(location.file);
 
         for token in tokens.0 {
-            match token.into_token() {
+            match token.token() {
                 Token::UnquoteMarker(id) => {
-                    let value = self.evaluate(id)?;
+                    let value = self.evaluate(*id)?;
+                    if let Value::Quoted(_, value_file) = &value {
+                        if *value_file != file {
+                            file = None;
+                        }
+                    }
+                    let location = Location::new(token.to_span(), location.file);
                     let tokens = value.into_tokens(self.elaborator.interner, location)?;
                     new_tokens.extend(tokens);
                 }
-                token => new_tokens.push(token),
+                _ => new_tokens.push(token),
             }
         }
 
-        Ok(new_tokens)
+        Ok((new_tokens, file))
     }
 }
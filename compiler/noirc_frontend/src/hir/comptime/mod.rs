@@ -1,9 +1,11 @@
 mod errors;
 mod hir_to_display_ast;
+mod inputs;
 mod interpreter;
 mod tests;
 mod value;
 
 pub use errors::InterpreterError;
+pub use inputs::{normalize_path, ComptimeInput, ComptimeInputs};
 pub use interpreter::Interpreter;
 pub use value::Value;
@@ -2,6 +2,7 @@ use std::{borrow::Cow, fmt::Display, rc::Rc};
 
 use acvm::{AcirField, FieldElement};
 use chumsky::Parser;
+use fm::FileId;
 use im::Vector;
 use iter_extended::{try_vecmap, vecmap};
 use noirc_errors::{Location, Span};
@@ -12,8 +13,8 @@ use crate::{
         ArrayLiteral, AssignStatement, BlockExpression, CallExpression, CastExpression,
         ConstrainStatement, ConstructorExpression, ForLoopStatement, ForRange, Ident, IfExpression,
         IndexExpression, InfixExpression, IntegerBitSize, LValue, Lambda, LetStatement,
-        MemberAccessExpression, MethodCallExpression, PrefixExpression, Signedness, Statement,
-        StatementKind, UnresolvedTypeData,
+        MatchExpression, MemberAccessExpression, MethodCallExpression, PrefixExpression,
+        Signedness, Statement, StatementKind, UnresolvedTypeData, WhileStatement,
     },
     hir::{def_map::ModuleId, type_check::generics::TraitGenerics},
     hir_def::{
@@ -56,10 +57,13 @@ pub enum Value {
     Pointer(Shared<Value>, /* auto_deref */ bool),
     Array(Vector<Value>, Type),
     Slice(Vector<Value>, Type),
-    /// Quoted tokens don't have spans because otherwise inserting them in the middle of other
-    /// tokens can cause larger spans to be before lesser spans, causing an assert. They may also
-    /// be inserted into separate files entirely.
-    Quoted(Rc<Vec<Token>>),
+    /// Quoted tokens keep the spans of the code they were quoted from so that diagnostics can
+    /// point to them, along with the file those spans are in. The file is `None` if the tokens
+    /// were spliced together from several files. These spans are replaced with the span of the
+    /// unquote site whenever the tokens are parsed, since otherwise inserting them in the middle
+    /// of other tokens can cause larger spans to be before lesser spans, causing an assert.
+    /// They may also be inserted into separate files entirely.
+    Quoted(Rc<Vec<SpannedToken>>, Option<FileId>),
     StructDefinition(StructId),
     TraitConstraint(TraitId, TraitGenerics),
     TraitDefinition(TraitId),
@@ -68,7 +72,9 @@ pub enum Value {
     ModuleDefinition(ModuleId),
     Type(Type),
     Zeroed(Type),
-    Expr(ExprValue),
+    /// An expression along with the location of the code it was created from
+    Expr(ExprValue, Location),
+    TypedExpr(ExprId),
     UnresolvedType(UnresolvedTypeData),
 }
 
@@ -80,16 +86,17 @@ pub enum ExprValue {
 }
 
 impl Value {
-    pub(crate) fn expression(expr: ExpressionKind) -> Self {
-        Value::Expr(ExprValue::Expression(expr))
+    pub(crate) fn expression(expr: Expression, file: FileId) -> Self {
+        Value::Expr(ExprValue::Expression(expr.kind), Location::new(expr.span, file))
     }
 
-    pub(crate) fn statement(statement: StatementKind) -> Self {
-        Value::Expr(ExprValue::Statement(statement))
+    pub(crate) fn statement(statement: Statement, file: FileId) -> Self {
+        Value::Expr(ExprValue::Statement(statement.kind), Location::new(statement.span, file))
     }
 
-    pub(crate) fn lvalue(lvaue: LValue) -> Self {
-        Value::Expr(ExprValue::LValue(lvaue))
+    pub(crate) fn lvalue(lvalue: LValue, file: FileId) -> Self {
+        let location = Location::new(lvalue.span(), file);
+        Value::Expr(ExprValue::LValue(lvalue), location)
     }
 
     pub(crate) fn get_type(&self) -> Cow<Type> {
@@ -119,7 +126,7 @@ impl Value {
             Value::Struct(_, typ) => return Cow::Borrowed(typ),
             Value::Array(_, typ) => return Cow::Borrowed(typ),
             Value::Slice(_, typ) => return Cow::Borrowed(typ),
-            Value::Quoted(_) => Type::Quoted(QuotedType::Quoted),
+            Value::Quoted(..) => Type::Quoted(QuotedType::Quoted),
             Value::StructDefinition(_) => Type::Quoted(QuotedType::StructDefinition),
             Value::Pointer(element, auto_deref) => {
                 if *auto_deref {
@@ -136,7 +143,8 @@ impl Value {
             Value::ModuleDefinition(_) => Type::Quoted(QuotedType::Module),
             Value::Type(_) => Type::Quoted(QuotedType::Type),
             Value::Zeroed(typ) => return Cow::Borrowed(typ),
-            Value::Expr(_) => Type::Quoted(QuotedType::Expr),
+            Value::Expr(..) => Type::Quoted(QuotedType::Expr),
+            Value::TypedExpr(_) => Type::Quoted(QuotedType::TypedExpr),
             Value::UnresolvedType(_) => Type::Quoted(QuotedType::UnresolvedType),
         })
     }
@@ -242,7 +250,7 @@ impl Value {
                     try_vecmap(elements, |element| element.into_expression(interner, location))?;
                 ExpressionKind::Literal(Literal::Slice(ArrayLiteral::Standard(elements)))
             }
-            Value::Quoted(tokens) => {
+            Value::Quoted(tokens, _) => {
                 // Wrap the tokens in '{' and '}' so that we can parse statements as well.
                 let mut tokens_to_parse = add_token_spans(tokens.clone(), location.span);
                 tokens_to_parse.0.insert(0, SpannedToken::new(Token::LeftBrace, location.span));
@@ -258,13 +266,15 @@ impl Value {
                     }
                 };
             }
-            Value::Expr(ExprValue::Expression(expr)) => expr,
-            Value::Expr(ExprValue::Statement(statement)) => {
+            Value::Expr(ExprValue::Expression(expr), _) => expr,
+            Value::Expr(ExprValue::Statement(statement), _) => {
                 ExpressionKind::Block(BlockExpression {
                     statements: vec![Statement { kind: statement, span: location.span }],
                 })
             }
-            Value::Expr(ExprValue::LValue(_))
+            // This expression was already elaborated so it doesn't need to be resolved again
+            Value::TypedExpr(expr_id) => ExpressionKind::Resolved(expr_id),
+            Value::Expr(ExprValue::LValue(_), _)
             | Value::Pointer(..)
             | Value::StructDefinition(_)
             | Value::TraitConstraint(..)
@@ -388,7 +398,10 @@ impl Value {
                 })?;
                 HirExpression::Literal(HirLiteral::Slice(HirArrayLiteral::Standard(elements)))
             }
-            Value::Quoted(tokens) => HirExpression::Unquote(add_token_spans(tokens, location.span)),
+            Value::Quoted(tokens, _) => {
+                HirExpression::Unquote(add_token_spans(tokens, location.span))
+            }
+            Value::TypedExpr(expr_id) => return Ok(expr_id),
             Value::Expr(..)
             | Value::Pointer(..)
             | Value::StructDefinition(_)
@@ -416,17 +429,17 @@ impl Value {
         self,
         interner: &mut NodeInterner,
         location: Location,
-    ) -> IResult<Vec<Token>> {
+    ) -> IResult<Vec<SpannedToken>> {
         let token = match self {
-            Value::Quoted(tokens) => return Ok(unwrap_rc(tokens)),
+            Value::Quoted(tokens, _) => return Ok(unwrap_rc(tokens)),
             Value::Type(typ) => Token::QuotedType(interner.push_quoted_type(typ)),
-            Value::Expr(ExprValue::Expression(expr)) => {
+            Value::Expr(ExprValue::Expression(expr), _) => {
                 Token::InternedExpr(interner.push_expression_kind(expr))
             }
-            Value::Expr(ExprValue::Statement(statement)) => {
+            Value::Expr(ExprValue::Statement(statement), _) => {
                 Token::InternedStatement(interner.push_statement_kind(statement))
             }
-            Value::Expr(ExprValue::LValue(lvalue)) => {
+            Value::Expr(ExprValue::LValue(lvalue), _) => {
                 Token::InternedLValue(interner.push_lvalue(lvalue))
             }
             Value::UnresolvedType(typ) => {
@@ -434,7 +447,7 @@ impl Value {
             }
             other => Token::UnquoteMarker(other.into_hir_expression(interner, location)?),
         };
-        Ok(vec![token])
+        Ok(vec![SpannedToken::new(token, location.span)])
     }
 
     /// Converts any unsigned `Value` into a `u128`.
@@ -460,7 +473,7 @@ impl Value {
         interner: &NodeInterner,
     ) -> IResult<Vec<TopLevelStatement>> {
         match self {
-            Value::Quoted(tokens) => parse_tokens(tokens, parser::top_level_items(), location),
+            Value::Quoted(tokens, _) => parse_tokens(tokens, parser::top_level_items(), location),
             _ => {
                 let typ = self.get_type().into_owned();
                 let value = self.display(interner).to_string();
@@ -483,7 +496,7 @@ pub(crate) fn unwrap_rc<T: Clone>(rc: Rc<T>) -> T {
 }
 
 fn parse_tokens<T>(
-    tokens: Rc<Vec<Token>>,
+    tokens: Rc<Vec<SpannedToken>>,
     parser: impl NoirParser<T>,
     location: Location,
 ) -> IResult<T> {
@@ -498,9 +511,10 @@ fn parse_tokens<T>(
     }
 }
 
-pub(crate) fn add_token_spans(tokens: Rc<Vec<Token>>, span: Span) -> Tokens {
+/// Replaces the span of each token with `span`, the location the tokens are unquoted at
+pub(crate) fn add_token_spans(tokens: Rc<Vec<SpannedToken>>, span: Span) -> Tokens {
     let tokens = unwrap_rc(tokens);
-    Tokens(vecmap(tokens, |token| SpannedToken::new(token, span)))
+    Tokens(vecmap(tokens, |token| SpannedToken::new(token.into_token(), span)))
 }
 
 pub struct ValuePrinter<'value, 'interner> {
@@ -553,10 +567,10 @@ impl<'value, 'interner> Display for ValuePrinter<'value, 'interner> {
                 let values = vecmap(values, |value| value.display(self.interner).to_string());
                 write!(f, "&[{}]", values.join(", "))
             }
-            Value::Quoted(tokens) => {
+            Value::Quoted(tokens, _) => {
                 write!(f, "quote {{")?;
                 for token in tokens.iter() {
-                    match token {
+                    match token.token() {
                         Token::QuotedType(id) => {
                             write!(f, " {}", self.interner.get_quoted_type(*id))?;
                         }
@@ -612,15 +626,16 @@ impl<'value, 'interner> Display for ValuePrinter<'value, 'interner> {
             Value::ModuleDefinition(_) => write!(f, "(module)"),
             Value::Zeroed(typ) => write!(f, "(zeroed {typ})"),
             Value::Type(typ) => write!(f, "{}", typ),
-            Value::Expr(ExprValue::Expression(expr)) => {
+            Value::Expr(ExprValue::Expression(expr), _) => {
                 write!(f, "{}", remove_interned_in_expression_kind(self.interner, expr.clone()))
             }
-            Value::Expr(ExprValue::Statement(statement)) => {
+            Value::Expr(ExprValue::Statement(statement), _) => {
                 write!(f, "{}", remove_interned_in_statement_kind(self.interner, statement.clone()))
             }
-            Value::Expr(ExprValue::LValue(lvalue)) => {
+            Value::Expr(ExprValue::LValue(lvalue), _) => {
                 write!(f, "{}", remove_interned_in_lvalue(self.interner, lvalue.clone()))
             }
+            Value::TypedExpr(expr_id) => write!(f, "{}", expr_id.to_display_ast(self.interner)),
             Value::UnresolvedType(typ) => {
                 if let UnresolvedTypeData::Interned(id) = typ {
                     let typ = self.interner.get_unresolved_type_data(*id);
@@ -709,6 +724,15 @@ fn remove_interned_in_expression_kind(
                 .alternative
                 .map(|alternative| remove_interned_in_expression(interner, alternative)),
         })),
+        ExpressionKind::Match(match_expr) => ExpressionKind::Match(Box::new(MatchExpression {
+            expression: remove_interned_in_expression(interner, match_expr.expression),
+            rules: vecmap(match_expr.rules, |(pattern, body)| {
+                (pattern, remove_interned_in_expression(interner, body))
+            }),
+        })),
+        ExpressionKind::Try(expr) => {
+            ExpressionKind::Try(Box::new(remove_interned_in_expression(interner, *expr)))
+        }
         ExpressionKind::Variable(_) => expr,
         ExpressionKind::Tuple(expressions) => ExpressionKind::Tuple(vecmap(expressions, |expr| {
             remove_interned_in_expression(interner, expr)
@@ -824,6 +848,17 @@ fn remove_interned_in_statement_kind(
             block: remove_interned_in_expression(interner, for_loop.block),
             ..for_loop
         }),
+        StatementKind::While(while_loop) => StatementKind::While(WhileStatement {
+            condition: remove_interned_in_expression(interner, while_loop.condition),
+            block: remove_interned_in_expression(interner, while_loop.block),
+            span: while_loop.span,
+        }),
+        StatementKind::Loop(block) => {
+            StatementKind::Loop(remove_interned_in_expression(interner, block))
+        }
+        StatementKind::Return(value) => {
+            StatementKind::Return(value.map(|value| remove_interned_in_expression(interner, value)))
+        }
         StatementKind::Comptime(statement) => {
             StatementKind::Comptime(Box::new(remove_interned_in_statement(interner, *statement)))
         }
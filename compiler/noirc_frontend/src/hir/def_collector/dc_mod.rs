@@ -4,15 +4,16 @@ use std::vec;
 
 use acvm::{AcirField, FieldElement};
 use fm::{FileId, FileManager, FILE_EXTENSION};
+use iter_extended::vecmap;
 use noirc_errors::{Location, Span};
 use num_bigint::BigUint;
 use num_traits::Num;
 use rustc_hash::FxHashMap as HashMap;
 
 use crate::ast::{
-    FunctionDefinition, Ident, ItemVisibility, LetStatement, ModuleDeclaration, NoirFunction,
-    NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Pattern, TraitImplItem, TraitItem,
-    TypeImpl,
+    FunctionDefinition, Ident, ItemVisibility, LetStatement, ModuleDeclaration, NoirEnumeration,
+    NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Path, PathKind, PathSegment,
+    Pattern, TraitImplItem, TraitItem, TypeImpl,
 };
 use crate::hir::resolution::errors::ResolverError;
 use crate::macros_api::{Expression, NodeInterner, UnresolvedType, UnresolvedTypeData};
@@ -21,10 +22,10 @@ use crate::{
     graph::CrateId,
     hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait},
     macros_api::MacroProcessor,
-    node_interner::{FunctionModifiers, TraitId, TypeAliasId},
+    node_interner::{FunctionModifiers, StructId, TraitId, TypeAliasId},
     parser::{SortedModule, SortedSubModule},
 };
-use crate::{Generics, Kind, ResolvedGeneric, Type, TypeVariable};
+use crate::{EnumVariant, Generics, Kind, ResolvedGeneric, Type, TypeVariable};
 
 use super::{
     dc_crate::{
@@ -93,13 +94,16 @@ pub fn collect_defs(
 
     errors.extend(collector.collect_structs(context, ast.types, crate_id));
 
+    let mut impls = ast.impls;
+    errors.extend(collector.collect_enums(context, ast.enums, &mut impls, crate_id));
+
     errors.extend(collector.collect_type_aliases(context, ast.type_aliases, crate_id));
 
     errors.extend(collector.collect_functions(context, ast.functions, crate_id));
 
     collector.collect_trait_impls(context, ast.trait_impls, crate_id);
 
-    collector.collect_impls(context, ast.impls, crate_id);
+    collector.collect_impls(context, impls, crate_id);
 
     errors
 }
@@ -282,65 +286,124 @@ impl<'a> ModCollector<'a> {
     ) -> Vec<(CompilationError, FileId)> {
         let mut definition_errors = vec![];
         for struct_definition in types {
-            self.check_duplicate_field_names(&struct_definition, &mut definition_errors);
-
-            let name = struct_definition.name.clone();
+            self.collect_struct(context, struct_definition, krate, &mut definition_errors);
+        }
+        definition_errors
+    }
 
-            let unresolved = UnresolvedStruct {
-                file_id: self.file_id,
-                module_id: self.module_id,
-                struct_def: struct_definition,
+    /// Collect any enum definitions declared within the ast.
+    ///
+    /// Each enum is lowered to a struct holding a tag and the arguments of each
+    /// variant, and an impl with a constructor function per variant which is
+    /// pushed onto `impls`.
+    fn collect_enums(
+        &mut self,
+        context: &mut Context,
+        enums: Vec<NoirEnumeration>,
+        impls: &mut Vec<TypeImpl>,
+        krate: CrateId,
+    ) -> Vec<(CompilationError, FileId)> {
+        let mut definition_errors = vec![];
+        for mut enum_definition in enums {
+            self.remove_duplicate_variants(&mut enum_definition, &mut definition_errors);
+
+            let variants = vecmap(&enum_definition.variants, |variant| EnumVariant {
+                name: variant.name.clone(),
+                is_unit: variant.parameters.is_none(),
+            });
+
+            // Within the standard library `std` isn't in scope, so refer to `zeroed` via `crate`
+            let (kind, segments) = if krate.is_stdlib() {
+                (PathKind::Crate, vec!["mem", "zeroed"])
+            } else {
+                (PathKind::Dep, vec!["std", "mem", "zeroed"])
             };
+            let span = enum_definition.span;
+            let segments = vecmap(segments, |segment| {
+                PathSegment::from(Ident::new(segment.to_string(), span))
+            });
+            let zeroed_path = Path { segments, kind, span };
+
+            let (struct_definition, type_impl) = enum_definition.into_struct_and_impl(zeroed_path);
+            let id = self.collect_struct(context, struct_definition, krate, &mut definition_errors);
+
+            if let Some(id) = id {
+                context.def_interner.update_struct(id, |struct_type| {
+                    struct_type.variants = Some(variants);
+                });
+                impls.push(type_impl);
+            }
+        }
+        definition_errors
+    }
 
-            let resolved_generics = context.resolve_generics(
-                &unresolved.struct_def.generics,
-                &mut definition_errors,
-                self.file_id,
-            );
+    /// Collects a single struct definition, returning its id if it could be declared.
+    fn collect_struct(
+        &mut self,
+        context: &mut Context,
+        struct_definition: NoirStruct,
+        krate: CrateId,
+        definition_errors: &mut Vec<(CompilationError, FileId)>,
+    ) -> Option<StructId> {
+        self.check_duplicate_field_names(&struct_definition, definition_errors);
 
-            // Create the corresponding module for the struct namespace
-            let id = match self.push_child_module(
-                context,
-                &name,
-                Location::new(name.span(), self.file_id),
-                false,
-                false,
-            ) {
-                Ok(module_id) => context.def_interner.new_struct(
-                    &unresolved,
-                    resolved_generics,
-                    krate,
-                    module_id.local_id,
-                    self.file_id,
-                ),
-                Err(error) => {
-                    definition_errors.push((error.into(), self.file_id));
-                    continue;
-                }
-            };
+        let name = struct_definition.name.clone();
 
-            // Add the struct to scope so its path can be looked up later
-            let result = self.def_collector.def_map.modules[self.module_id.0]
-                .declare_struct(name.clone(), id);
+        let unresolved = UnresolvedStruct {
+            file_id: self.file_id,
+            module_id: self.module_id,
+            struct_def: struct_definition,
+        };
 
-            if let Err((first_def, second_def)) = result {
-                let error = DefCollectorErrorKind::Duplicate {
-                    typ: DuplicateType::TypeDefinition,
-                    first_def,
-                    second_def,
-                };
+        let resolved_generics = context.resolve_generics(
+            &unresolved.struct_def.generics,
+            definition_errors,
+            self.file_id,
+        );
+
+        // Create the corresponding module for the struct namespace
+        let id = match self.push_child_module(
+            context,
+            &name,
+            Location::new(name.span(), self.file_id),
+            false,
+            false,
+        ) {
+            Ok(module_id) => context.def_interner.new_struct(
+                &unresolved,
+                resolved_generics,
+                krate,
+                module_id.local_id,
+                self.file_id,
+            ),
+            Err(error) => {
                 definition_errors.push((error.into(), self.file_id));
+                return None;
             }
+        };
 
-            // And store the TypeId -> StructType mapping somewhere it is reachable
-            self.def_collector.items.types.insert(id, unresolved);
+        // Add the struct to scope so its path can be looked up later
+        let result =
+            self.def_collector.def_map.modules[self.module_id.0].declare_struct(name.clone(), id);
 
-            if context.def_interner.is_in_lsp_mode() {
-                let parent_module_id = ModuleId { krate, local_id: self.module_id };
-                context.def_interner.register_struct(id, name.to_string(), parent_module_id);
-            }
+        if let Err((first_def, second_def)) = result {
+            let error = DefCollectorErrorKind::Duplicate {
+                typ: DuplicateType::TypeDefinition,
+                first_def,
+                second_def,
+            };
+            definition_errors.push((error.into(), self.file_id));
         }
-        definition_errors
+
+        // And store the TypeId -> StructType mapping somewhere it is reachable
+        self.def_collector.items.types.insert(id, unresolved);
+
+        if context.def_interner.is_in_lsp_mode() {
+            let parent_module_id = ModuleId { krate, local_id: self.module_id };
+            context.def_interner.register_struct(id, name.to_string(), parent_module_id);
+        }
+
+        Some(id)
     }
 
     fn check_duplicate_field_names(
@@ -349,7 +412,8 @@ impl<'a> ModCollector<'a> {
         definition_errors: &mut Vec<(CompilationError, FileId)>,
     ) {
         let mut seen_field_names = std::collections::HashSet::new();
-        for (field_name, _) in &struct_definition.fields {
+        for field in &struct_definition.fields {
+            let field_name = &field.name;
             if seen_field_names.insert(field_name) {
                 continue;
             }
@@ -366,6 +430,33 @@ impl<'a> ModCollector<'a> {
         }
     }
 
+    /// Reports and removes any variants sharing a name with an earlier variant, so that
+    /// the lowered struct and impl don't report duplicate fields and functions as well.
+    fn remove_duplicate_variants(
+        &self,
+        enum_definition: &mut NoirEnumeration,
+        definition_errors: &mut Vec<(CompilationError, FileId)>,
+    ) {
+        let mut seen_variant_names: HashMap<String, Ident> = HashMap::default();
+        enum_definition.variants.retain(|variant| {
+            let name = variant.name.0.contents.clone();
+            if let Some(first_def) = seen_variant_names.get(&name) {
+                definition_errors.push((
+                    DefCollectorErrorKind::DuplicateEnumVariant {
+                        first_def: first_def.clone(),
+                        second_def: variant.name.clone(),
+                    }
+                    .into(),
+                    self.file_id,
+                ));
+                false
+            } else {
+                seen_variant_names.insert(name, variant.name.clone());
+                true
+            }
+        });
+    }
+
     /// Collect any type aliases definitions declared within the ast.
     /// Returns a vector of errors if any type aliases were already defined.
     fn collect_type_aliases(
@@ -632,6 +723,10 @@ impl<'a> ModCollector<'a> {
                 submodule.is_contract,
             ) {
                 Ok(child) => {
+                    for attribute in submodule.outer_attributes {
+                        context.def_interner.add_module_secondary_attribute(child, attribute);
+                    }
+
                     errors.extend(collect_defs(
                         self.def_collector,
                         submodule.contents,
@@ -726,6 +821,11 @@ impl<'a> ModCollector<'a> {
                 // Track that the "foo" in `mod foo;` points to the module "foo"
                 context.def_interner.add_module_reference(child_mod_id, location);
 
+                for attribute in &mod_decl.outer_attributes {
+                    let attribute = attribute.clone();
+                    context.def_interner.add_module_secondary_attribute(child_mod_id, attribute);
+                }
+
                 errors.extend(collect_defs(
                     self.def_collector,
                     ast,
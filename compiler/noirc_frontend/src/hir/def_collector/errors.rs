@@ -29,6 +29,8 @@ pub enum DefCollectorErrorKind {
     Duplicate { typ: DuplicateType, first_def: Ident, second_def: Ident },
     #[error("duplicate struct field {first_def}")]
     DuplicateField { first_def: Ident, second_def: Ident },
+    #[error("duplicate enum variant {first_def}")]
+    DuplicateEnumVariant { first_def: Ident, second_def: Ident },
     #[error("unresolved import")]
     UnresolvedModuleDecl { mod_name: Ident, expected_path: String, alternative_path: String },
     #[error("overlapping imports")]
@@ -152,6 +154,19 @@ impl<'a> From<&'a DefCollectorErrorKind> for Diagnostic {
                     diag
                 }
             }
+            DefCollectorErrorKind::DuplicateEnumVariant { first_def, second_def } => {
+                let primary_message = format!(
+                    "Duplicate definitions of enum variant with name {} found",
+                    &first_def.0.contents
+                );
+                let mut diag = Diagnostic::simple_error(
+                    primary_message,
+                    "First definition found here".to_string(),
+                    first_def.0.span(),
+                );
+                diag.add_secondary("Second definition found here".to_string(), second_def.0.span());
+                diag
+            }
             DefCollectorErrorKind::UnresolvedModuleDecl { mod_name, expected_path, alternative_path } => {
                 let span = mod_name.0.span();
                 let mod_name = &mod_name.0.contents;
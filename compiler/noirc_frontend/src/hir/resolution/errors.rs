@@ -34,6 +34,8 @@ pub enum ResolverError {
     DuplicateField { field: Ident },
     #[error("No such field in struct")]
     NoSuchField { field: Ident, struct_definition: Ident },
+    #[error("Private field in struct")]
+    PrivateStructField { field: Ident, struct_definition: Ident },
     #[error("Missing fields from struct")]
     MissingFields { span: Span, missing_fields: Vec<String>, struct_definition: Ident },
     #[error("Unneeded 'mut', pattern is already marked as mutable")]
@@ -92,6 +94,14 @@ pub enum ResolverError {
     JumpInConstrainedFn { is_break: bool, span: Span },
     #[error("break/continue are only allowed within loops")]
     JumpOutsideLoop { is_break: bool, span: Span },
+    #[error("while/loop are only allowed in unconstrained functions")]
+    LoopInConstrainedFn { is_while: bool, span: Span },
+    #[error("The `?` operator is only allowed in unconstrained functions")]
+    TryInConstrainedFn { span: Span },
+    #[error("Cannot return early from a `comptime` block")]
+    ReturnInComptimeBlock { span: Span },
+    #[error("Early returns are only allowed in unconstrained functions")]
+    ReturnInConstrainedFn { span: Span },
     #[error("Only `comptime` globals can be mutable")]
     MutableGlobal { span: Span },
     #[error("Self-referential structs are not supported")]
@@ -193,6 +203,13 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                     field.span(),
                 )
             }
+            ResolverError::PrivateStructField { field, struct_definition } => {
+                Diagnostic::simple_error(
+                    format!("field {field} of struct {struct_definition} is private"),
+                    "not visible from the current module".to_string(),
+                    field.span(),
+                )
+            }
             ResolverError::MissingFields { span, missing_fields, struct_definition } => {
                 let plural = if missing_fields.len() != 1 { "s" } else { "" };
                 let remaining_fields_names = match &missing_fields[..] {
@@ -380,6 +397,37 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                     *span,
                 )
             },
+            ResolverError::LoopInConstrainedFn { is_while, span } => {
+                let item = if *is_while { "while" } else { "loop" };
+                let mut diagnostic = Diagnostic::simple_error(
+                    format!("`{item}` is only allowed in unconstrained functions"),
+                    "Constrained code must always have a known number of loop iterations".into(),
+                    *span,
+                );
+                diagnostic.add_note("Use a `for` loop over a bounded range instead".into());
+                diagnostic
+            },
+            ResolverError::TryInConstrainedFn { span } => {
+                Diagnostic::simple_error(
+                    "The `?` operator is only allowed in unconstrained functions".into(),
+                    "Constrained code cannot return early".into(),
+                    *span,
+                )
+            },
+            ResolverError::ReturnInComptimeBlock { span } => {
+                Diagnostic::simple_error(
+                    "Cannot return early from a `comptime` block".into(),
+                    "A `comptime` block is evaluated on its own, outside of the enclosing function".into(),
+                    *span,
+                )
+            },
+            ResolverError::ReturnInConstrainedFn { span } => {
+                Diagnostic::simple_error(
+                    "Early returns are only allowed in unconstrained functions".into(),
+                    "Constrained code cannot return early. A `return` is only allowed as the last statement of the function body".into(),
+                    *span,
+                )
+            },
             ResolverError::MutableGlobal { span } => {
                 Diagnostic::simple_error(
                     "Only `comptime` globals may be mutable".into(),
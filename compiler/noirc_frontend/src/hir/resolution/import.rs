@@ -3,7 +3,7 @@ use thiserror::Error;
 
 use crate::graph::CrateId;
 use crate::hir::def_collector::dc_crate::CompilationError;
-use crate::node_interner::ReferenceId;
+use crate::node_interner::{ReferenceId, StructId};
 use std::collections::BTreeMap;
 
 use crate::ast::{Ident, ItemVisibility, Path, PathKind, PathSegment};
@@ -402,6 +402,32 @@ pub fn can_reference_module_id(
     }
 }
 
+/// Returns true if a member of the given struct, such as a field, with the given
+/// visibility can be referenced from `current_module`.
+///
+/// Each struct gets its own module for its namespace, so visibility is checked
+/// against that module's parent: the module the struct is defined in.
+pub fn struct_member_is_visible(
+    struct_id: StructId,
+    visibility: ItemVisibility,
+    current_module: ModuleId,
+    def_maps: &BTreeMap<CrateId, CrateDefMap>,
+) -> bool {
+    let struct_module = struct_id.module_id();
+    let struct_def_map = &def_maps[&struct_module.krate];
+    let local_id =
+        struct_def_map.modules[struct_module.local_id.0].parent.unwrap_or(struct_module.local_id);
+    let defining_module = ModuleId { krate: struct_module.krate, local_id };
+
+    can_reference_module_id(
+        def_maps,
+        current_module.krate,
+        current_module.local_id,
+        defining_module,
+        visibility,
+    )
+}
+
 // Returns true if `current` is a (potentially nested) child module of `target`.
 // This is also true if `current == target`.
 fn module_descendent_of_target(
@@ -41,6 +41,8 @@ pub enum TypeCheckError {
     OverflowingAssignment { expr: FieldElement, ty: Type, range: String, span: Span },
     #[error("Type {typ:?} cannot be used in a {place:?}")]
     TypeCannotBeUsed { typ: Type, place: &'static str, span: Span },
+    #[error("The `?` operator can only be applied to an `Option` or `Result`, not to {typ:?}")]
+    InvalidTryOperand { typ: Type, span: Span },
     #[error("Expected type {expected_typ:?} is not the same as {expr_typ:?}")]
     TypeMismatch { expected_typ: String, expr_typ: String, expr_span: Span },
     #[error("Expected type {expected} is not the same as {actual}")]
@@ -150,6 +152,8 @@ pub enum TypeCheckError {
     NonConstantSliceLength { span: Span },
     #[error("Only sized types may be used in the entry point to a program")]
     InvalidTypeForEntryPoint { span: Span },
+    #[error("Enums may not be used in the entry point to a program")]
+    EnumInEntryPoint { typ: Type, span: Span },
     #[error("Mismatched number of parameters in trait implementation")]
     MismatchTraitImplNumParameters {
         actual_num_parameters: usize,
@@ -174,6 +178,14 @@ pub enum TypeCheckError {
     UnspecifiedType { span: Span },
     #[error("Binding `{typ}` here to the `_` inside would create a cyclic type")]
     CyclicType { typ: Type, span: Span },
+    #[error("Match expression is not exhaustive, pattern `{missing}` is not covered")]
+    NonExhaustiveMatch { missing: String, span: Span },
+    #[error("Unreachable match rule")]
+    UnreachableMatchRule { span: Span },
+    #[error("`{path}` is not an enum variant")]
+    ExpectedEnumVariant { path: String, span: Span },
+    #[error("Enum `{name}` can't be constructed or matched with struct syntax")]
+    StructSyntaxOnEnum { name: String, span: Span },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -196,6 +208,11 @@ impl<'a> From<&'a TypeCheckError> for Diagnostic {
                 String::new(),
                 *span,
             ),
+            TypeCheckError::InvalidTryOperand { typ, span } => Diagnostic::simple_error(
+                format!("The `?` operator can only be applied to an `Option` or `Result`, not to `{typ}`"),
+                "Expected an `Option` or `Result` here".into(),
+                *span,
+            ),
             TypeCheckError::Context { err, ctx } => {
                 let mut diag = Diagnostic::from(err.as_ref());
                 diag.add_note(ctx.to_string());
@@ -371,6 +388,11 @@ impl<'a> From<&'a TypeCheckError> for Diagnostic {
             TypeCheckError::InvalidTypeForEntryPoint { span } => Diagnostic::simple_error(
                 "Only sized types may be used in the entry point to a program".to_string(),
                 "Slices, references, or any type containing them may not be used in main, contract functions, or foldable functions".to_string(), *span),
+            TypeCheckError::EnumInEntryPoint { typ, span } => Diagnostic::simple_error(
+                error.to_string(),
+                format!("`{typ}` is or contains an enum, which has no ABI representation"),
+                *span,
+            ),
             TypeCheckError::MismatchTraitImplNumParameters {
                 expected_num_parameters,
                 actual_num_parameters,
@@ -424,6 +446,21 @@ impl<'a> From<&'a TypeCheckError> for Diagnostic {
             TypeCheckError::CyclicType { typ: _, span } => {
                 Diagnostic::simple_error(error.to_string(), "Cyclic types have unlimited size and are prohibited in Noir".into(), *span)
             }
+            TypeCheckError::NonExhaustiveMatch { missing, span } => {
+                let secondary = format!("Add a rule matching `{missing}` or a wildcard `_` rule");
+                Diagnostic::simple_error(error.to_string(), secondary, *span)
+            }
+            TypeCheckError::UnreachableMatchRule { span } => {
+                let secondary = "Every value is already matched by a previous rule".to_string();
+                Diagnostic::simple_warning(error.to_string(), secondary, *span)
+            }
+            TypeCheckError::ExpectedEnumVariant { span, .. } => {
+                Diagnostic::simple_error(error.to_string(), String::new(), *span)
+            }
+            TypeCheckError::StructSyntaxOnEnum { span, .. } => {
+                let secondary = "Use one of the enum's variants instead".to_string();
+                Diagnostic::simple_error(error.to_string(), secondary, *span)
+            }
         }
     }
 }
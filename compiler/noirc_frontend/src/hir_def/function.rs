@@ -109,6 +109,11 @@ pub struct FuncMeta {
 
     pub return_visibility: Visibility,
 
+    /// For a function returning `impl Trait`, the concrete type hidden behind it.
+    /// This starts as a fresh type variable which is bound once the function's body
+    /// has been elaborated.
+    pub opaque_return_type: Option<Type>,
+
     /// The type of this function. Either a Type::Function
     /// or a Type::Forall for generic functions.
     pub typ: Type,
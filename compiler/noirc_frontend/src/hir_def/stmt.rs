@@ -16,8 +16,14 @@ pub enum HirStatement {
     Constrain(HirConstrainStatement),
     Assign(HirAssignStatement),
     For(HirForStatement),
+    While(HirWhileStatement),
+    Loop(ExprId),
     Break,
     Continue,
+    /// An early return of the given value from the enclosing function.
+    /// The type is that of the statement itself, which is unconstrained
+    /// since evaluation never continues past it.
+    Return(ExprId, Type),
     Expression(ExprId),
     Semi(ExprId),
     Comptime(StmtId),
@@ -50,6 +56,13 @@ pub struct HirForStatement {
     pub block: ExprId,
 }
 
+/// Corresponds to `while condition { block }` in the source code
+#[derive(Debug, Clone)]
+pub struct HirWhileStatement {
+    pub condition: ExprId,
+    pub block: ExprId,
+}
+
 /// Corresponds to `lvalue = expression;` in the source code
 #[derive(Debug, Clone)]
 pub struct HirAssignStatement {
@@ -15,7 +15,7 @@ use noirc_errors::{Location, Span};
 use noirc_printable_type::PrintableType;
 
 use crate::{
-    ast::{Ident, Signedness},
+    ast::{enum_variant_field, Ident, ItemVisibility, Signedness},
     node_interner::StructId,
 };
 
@@ -153,6 +153,7 @@ pub enum QuotedType {
     Quoted,
     TopLevelItem,
     Type,
+    TypedExpr,
     StructDefinition,
     TraitConstraint,
     TraitDefinition,
@@ -181,10 +182,34 @@ pub struct StructType {
     /// Fields are ordered and private, they should only
     /// be accessed through get_field(), get_fields(), or instantiate()
     /// since these will handle applying generic arguments to fields as well.
-    fields: Vec<(Ident, Type)>,
+    fields: Vec<StructField>,
 
     pub generics: Generics,
     pub location: Location,
+
+    /// Set if this struct is the lowered form of an `enum`. In that case its first
+    /// field is the tag selecting the active variant, followed by one field per
+    /// variant holding a tuple of that variant's arguments.
+    pub variants: Option<Vec<EnumVariant>>,
+}
+
+/// A field of a struct type. Private fields may only be accessed from the module
+/// the struct is defined in and its children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructField {
+    pub visibility: ItemVisibility,
+    pub name: Ident,
+    pub typ: Type,
+}
+
+/// A variant of an enum lowered to a struct
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumVariant {
+    pub name: Ident,
+
+    /// True for variants declared without any parentheses, such as `None`.
+    /// These can be referred to without calling them.
+    pub is_unit: bool,
 }
 
 /// Corresponds to generic lists such as `<T, U>` in the source program.
@@ -247,17 +272,17 @@ impl StructType {
         name: Ident,
 
         location: Location,
-        fields: Vec<(Ident, Type)>,
+        fields: Vec<StructField>,
         generics: Generics,
     ) -> StructType {
-        StructType { id, fields, name, location, generics }
+        StructType { id, fields, name, location, generics, variants: None }
     }
 
     /// To account for cyclic references between structs, a struct's
     /// fields are resolved strictly after the struct itself is initially
     /// created. Therefore, this method is used to set the fields once they
     /// become known.
-    pub fn set_fields(&mut self, fields: Vec<(Ident, Type)>) {
+    pub fn set_fields(&mut self, fields: Vec<StructField>) {
         assert!(self.fields.is_empty());
         self.fields = fields;
     }
@@ -266,12 +291,16 @@ impl StructType {
         self.fields.len()
     }
 
-    /// Returns the field matching the given field name, as well as its field index.
-    pub fn get_field(&self, field_name: &str, generic_args: &[Type]) -> Option<(Type, usize)> {
+    /// Returns the field matching the given field name, as well as its visibility and field index.
+    pub fn get_field(
+        &self,
+        field_name: &str,
+        generic_args: &[Type],
+    ) -> Option<(Type, ItemVisibility, usize)> {
         assert_eq!(self.generics.len(), generic_args.len());
 
-        self.fields.iter().enumerate().find(|(_, (name, _))| name.0.contents == field_name).map(
-            |(i, (_, typ))| {
+        self.fields.iter().enumerate().find(|(_, field)| field.name.0.contents == field_name).map(
+            |(i, field)| {
                 let substitutions = self
                     .generics
                     .iter()
@@ -279,7 +308,7 @@ impl StructType {
                     .map(|(old, new)| (old.type_var.id(), (old.type_var.clone(), new.clone())))
                     .collect();
 
-                (typ.substitute(&substitutions), i)
+                (field.typ.substitute(&substitutions), field.visibility, i)
             },
         )
     }
@@ -295,35 +324,41 @@ impl StructType {
             .map(|(old, new)| (old.type_var.id(), (old.type_var.clone(), new.clone())))
             .collect();
 
-        vecmap(&self.fields, |(name, typ)| {
-            let name = name.0.contents.clone();
-            (name, typ.substitute(&substitutions))
+        vecmap(&self.fields, |field| {
+            let name = field.name.0.contents.clone();
+            (name, field.typ.substitute(&substitutions))
         })
     }
 
-    /// Returns the name and raw types of each field of this type.
+    /// Returns each field of this type as written, with its raw type.
     /// This will not substitute any generic arguments so a generic field like `x`
-    /// in `struct Foo<T> { x: T }` will return a `("x", T)` pair.
+    /// in `struct Foo<T> { x: T }` will have type `T`.
     ///
     /// This method is almost never what is wanted for type checking or monomorphization,
     /// prefer to use `get_fields` whenever possible.
-    pub fn get_fields_as_written(&self) -> Vec<(String, Type)> {
-        vecmap(&self.fields, |(name, typ)| (name.0.contents.clone(), typ.clone()))
+    pub fn get_fields_as_written(&self) -> Vec<StructField> {
+        self.fields.clone()
     }
 
     /// Returns the field at the given index. Panics if no field exists at the given index.
-    pub fn field_at(&self, index: usize) -> &(Ident, Type) {
+    pub fn field_at(&self, index: usize) -> &StructField {
         &self.fields[index]
     }
 
+    /// Returns the visibility of the field with the given name, if it exists.
+    pub fn field_visibility(&self, field_name: &str) -> Option<ItemVisibility> {
+        let field = self.fields.iter().find(|field| field.name.0.contents == field_name)?;
+        Some(field.visibility)
+    }
+
     pub fn field_names(&self) -> BTreeSet<Ident> {
-        self.fields.iter().map(|(name, _)| name.clone()).collect()
+        self.fields.iter().map(|field| field.name.clone()).collect()
     }
 
     /// Search the fields of a struct for any types with a `TypeKind::Numeric`
     pub fn find_numeric_generics_in_fields(&self, found_names: &mut Vec<String>) {
-        for (_, field) in self.fields.iter() {
-            field.find_numeric_type_vars(found_names);
+        for field in self.fields.iter() {
+            field.typ.find_numeric_type_vars(found_names);
         }
     }
 
@@ -333,7 +368,7 @@ impl StructType {
     /// TODO(https://github.com/noir-lang/noir/issues/5156): This is outdated and we should remove this implicit searching for numeric generics
     pub fn generic_is_numeric(&self, index_of_generic: usize) -> bool {
         let target_id = self.generics[index_of_generic].type_var.id();
-        self.fields.iter().any(|(_, field)| field.contains_numeric_typevar(target_id))
+        self.fields.iter().any(|field| field.typ.contains_numeric_typevar(target_id))
     }
 
     /// Instantiate this struct type, returning a Vec of the new generic args (in
@@ -341,6 +376,27 @@ impl StructType {
     pub fn instantiate(&self, interner: &mut NodeInterner) -> Vec<Type> {
         vecmap(&self.generics, |_| interner.next_type_variable())
     }
+
+    pub fn is_enum(&self) -> bool {
+        self.variants.is_some()
+    }
+
+    /// Returns the index of the variant with the given name, if this struct is an enum.
+    pub fn variant_index(&self, name: &str) -> Option<usize> {
+        let variants = self.variants.as_ref()?;
+        variants.iter().position(|variant| variant.name.0.contents == name)
+    }
+
+    /// Returns the types of the arguments of the variant at the given index,
+    /// after being applied to the given generic arguments.
+    pub fn variant_arguments(&self, index: usize, generic_args: &[Type]) -> Vec<Type> {
+        let variant = &self.variants.as_ref().expect("Expected an enum")[index];
+        let field = enum_variant_field(&variant.name.0.contents);
+        match self.get_field(&field, generic_args) {
+            Some((Type::Tuple(arguments), _, _)) => arguments,
+            _ => Vec::new(),
+        }
+    }
 }
 
 impl std::fmt::Display for StructType {
@@ -741,6 +797,7 @@ impl std::fmt::Display for QuotedType {
             QuotedType::Quoted => write!(f, "Quoted"),
             QuotedType::TopLevelItem => write!(f, "TopLevelItem"),
             QuotedType::Type => write!(f, "Type"),
+            QuotedType::TypedExpr => write!(f, "TypedExpr"),
             QuotedType::StructDefinition => write!(f, "StructDefinition"),
             QuotedType::TraitDefinition => write!(f, "TraitDefinition"),
             QuotedType::TraitConstraint => write!(f, "TraitConstraint"),
@@ -1132,6 +1189,25 @@ impl Type {
         }
     }
 
+    /// True if this type is an enum or contains one. Enums are lowered to structs holding the
+    /// arguments of every variant, which isn't a layout that can be exposed in a program's ABI.
+    pub(crate) fn contains_enum(&self) -> bool {
+        match self {
+            Type::Struct(definition, generics) => {
+                let definition = definition.borrow();
+                definition.is_enum()
+                    || definition
+                        .get_fields(generics)
+                        .into_iter()
+                        .any(|(_, field)| field.contains_enum())
+            }
+            Type::Alias(alias, generics) => alias.borrow().get_type(generics).contains_enum(),
+            Type::Array(_, element) | Type::Slice(element) => element.contains_enum(),
+            Type::Tuple(elements) => elements.iter().any(Type::contains_enum),
+            _ => false,
+        }
+    }
+
     /// Returns the number of `Forall`-quantified type variables on this type.
     /// Returns 0 if this is not a Type::Forall
     pub fn generic_count(&self) -> usize {
@@ -1816,8 +1892,8 @@ impl Type {
             // only to have to call .into_iter again afterward. Trying to elide
             // collecting to a Vec leads to us dropping the temporary Ref before
             // the iterator is returned
-            Type::Struct(def, args) => vecmap(&def.borrow().fields, |(name, _)| {
-                let name = &name.0.contents;
+            Type::Struct(def, args) => vecmap(&def.borrow().fields, |field| {
+                let name = &field.name.0.contents;
                 let typ = def.borrow().get_field(name, args).unwrap().0;
                 (name.clone(), typ)
             }),
@@ -1835,7 +1911,7 @@ impl Type {
     pub fn get_field_type(&self, field_name: &str) -> Option<Type> {
         match self.follow_bindings() {
             Type::Struct(def, args) => {
-                def.borrow().get_field(field_name, &args).map(|(typ, _)| typ)
+                def.borrow().get_field(field_name, &args).map(|(typ, _, _)| typ)
             }
             Type::Tuple(fields) => {
                 let mut fields = fields.into_iter().enumerate();
@@ -2417,6 +2493,14 @@ impl From<&Type> for PrintableType {
             Type::Struct(def, ref args) => {
                 let struct_type = def.borrow();
                 let fields = struct_type.get_fields(args);
+                if let Some(variants) = &struct_type.variants {
+                    // Skip the tag field, the remaining fields hold the arguments of each variant
+                    let variants = variants.iter().zip(fields.into_iter().skip(1));
+                    let variants = vecmap(variants, |(variant, (_, typ))| {
+                        (variant.name.to_string(), typ.into())
+                    });
+                    return PrintableType::Enum { name: struct_type.name.to_string(), variants };
+                }
                 let fields = vecmap(fields, |(name, typ)| (name, typ.into()));
                 PrintableType::Struct { fields, name: struct_type.name.to_string() }
             }
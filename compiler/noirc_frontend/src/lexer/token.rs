@@ -99,8 +99,12 @@ pub enum BorrowedToken<'input> {
     Bang,
     /// $
     DollarSign,
+    /// ?
+    Question,
     /// =
     Assign,
+    /// =>
+    FatArrow,
     #[allow(clippy::upper_case_acronyms)]
     EOF,
 
@@ -213,8 +217,12 @@ pub enum Token {
     Bang,
     /// =
     Assign,
+    /// =>
+    FatArrow,
     /// $
     DollarSign,
+    /// ?
+    Question,
     #[allow(clippy::upper_case_acronyms)]
     EOF,
 
@@ -284,8 +292,10 @@ pub fn token_to_borrowed_token(token: &Token) -> BorrowedToken<'_> {
         Token::DoubleColon => BorrowedToken::DoubleColon,
         Token::Semicolon => BorrowedToken::Semicolon,
         Token::Assign => BorrowedToken::Assign,
+        Token::FatArrow => BorrowedToken::FatArrow,
         Token::Bang => BorrowedToken::Bang,
         Token::DollarSign => BorrowedToken::DollarSign,
+        Token::Question => BorrowedToken::Question,
         Token::EOF => BorrowedToken::EOF,
         Token::Invalid(c) => BorrowedToken::Invalid(*c),
         Token::Whitespace(ref s) => BorrowedToken::Whitespace(s),
@@ -410,8 +420,10 @@ impl fmt::Display for Token {
             Token::DoubleColon => write!(f, "::"),
             Token::Semicolon => write!(f, ";"),
             Token::Assign => write!(f, "="),
+            Token::FatArrow => write!(f, "=>"),
             Token::Bang => write!(f, "!"),
             Token::DollarSign => write!(f, "$"),
+            Token::Question => write!(f, "?"),
             Token::EOF => write!(f, "end of input"),
             Token::Invalid(c) => write!(f, "{c}"),
             Token::Whitespace(ref s) => write!(f, "{s}"),
@@ -870,21 +882,32 @@ pub enum SecondaryAttribute {
     Varargs,
 }
 
-impl fmt::Display for SecondaryAttribute {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl SecondaryAttribute {
+    /// The contents of this attribute, without the surrounding `#[` and `]`
+    pub fn contents(&self) -> String {
         match self {
-            SecondaryAttribute::Deprecated(None) => write!(f, "#[deprecated]"),
-            SecondaryAttribute::Deprecated(Some(ref note)) => {
-                write!(f, r#"#[deprecated("{note}")]"#)
-            }
-            SecondaryAttribute::Custom(ref k) => write!(f, "#[{k}]"),
-            SecondaryAttribute::ContractLibraryMethod => write!(f, "#[contract_library_method]"),
-            SecondaryAttribute::Export => write!(f, "#[export]"),
-            SecondaryAttribute::Field(ref k) => write!(f, "#[field({k})]"),
-            SecondaryAttribute::Abi(ref k) => write!(f, "#[abi({k})]"),
-            SecondaryAttribute::Varargs => write!(f, "#[varargs]"),
+            SecondaryAttribute::Deprecated(None) => "deprecated".to_string(),
+            SecondaryAttribute::Deprecated(Some(ref note)) => format!(r#"deprecated("{note}")"#),
+            SecondaryAttribute::Custom(ref k) => k.clone(),
+            SecondaryAttribute::ContractLibraryMethod => "contract_library_method".to_string(),
+            SecondaryAttribute::Export => "export".to_string(),
+            SecondaryAttribute::Field(ref k) => format!("field({k})"),
+            SecondaryAttribute::Abi(ref k) => format!("abi({k})"),
+            SecondaryAttribute::Varargs => "varargs".to_string(),
         }
     }
+
+    /// The name of this attribute, e.g. `foo` for `#[foo(a, b)]`
+    pub fn name(&self) -> String {
+        let contents = self.contents();
+        contents.split('(').next().unwrap_or_default().trim().to_string()
+    }
+}
+
+impl fmt::Display for SecondaryAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#[{}]", self.contents())
+    }
 }
 
 impl AsRef<str> for FunctionAttribute {
@@ -934,6 +957,7 @@ pub enum Keyword {
     Crate,
     Dep,
     Else,
+    Enum,
     Expr,
     Field,
     Fn,
@@ -945,6 +969,8 @@ pub enum Keyword {
     Impl,
     In,
     Let,
+    Loop,
+    Match,
     Mod,
     Module,
     Mut,
@@ -963,6 +989,7 @@ pub enum Keyword {
     TraitImpl,
     Type,
     TypeType,
+    TypedExpr,
     Unchecked,
     Unconstrained,
     UnresolvedType,
@@ -989,6 +1016,7 @@ impl fmt::Display for Keyword {
             Keyword::Crate => write!(f, "crate"),
             Keyword::Dep => write!(f, "dep"),
             Keyword::Else => write!(f, "else"),
+            Keyword::Enum => write!(f, "enum"),
             Keyword::Expr => write!(f, "Expr"),
             Keyword::Field => write!(f, "Field"),
             Keyword::Fn => write!(f, "fn"),
@@ -1000,6 +1028,8 @@ impl fmt::Display for Keyword {
             Keyword::Impl => write!(f, "impl"),
             Keyword::In => write!(f, "in"),
             Keyword::Let => write!(f, "let"),
+            Keyword::Loop => write!(f, "loop"),
+            Keyword::Match => write!(f, "match"),
             Keyword::Mod => write!(f, "mod"),
             Keyword::Module => write!(f, "Module"),
             Keyword::Mut => write!(f, "mut"),
@@ -1018,6 +1048,7 @@ impl fmt::Display for Keyword {
             Keyword::TraitImpl => write!(f, "TraitImpl"),
             Keyword::Type => write!(f, "type"),
             Keyword::TypeType => write!(f, "Type"),
+            Keyword::TypedExpr => write!(f, "TypedExpr"),
             Keyword::Unchecked => write!(f, "unchecked"),
             Keyword::Unconstrained => write!(f, "unconstrained"),
             Keyword::UnresolvedType => write!(f, "UnresolvedType"),
@@ -1047,6 +1078,7 @@ impl Keyword {
             "crate" => Keyword::Crate,
             "dep" => Keyword::Dep,
             "else" => Keyword::Else,
+            "enum" => Keyword::Enum,
             "Expr" => Keyword::Expr,
             "Field" => Keyword::Field,
             "fn" => Keyword::Fn,
@@ -1058,6 +1090,8 @@ impl Keyword {
             "impl" => Keyword::Impl,
             "in" => Keyword::In,
             "let" => Keyword::Let,
+            "loop" => Keyword::Loop,
+            "match" => Keyword::Match,
             "mod" => Keyword::Mod,
             "Module" => Keyword::Module,
             "mut" => Keyword::Mut,
@@ -1075,6 +1109,7 @@ impl Keyword {
             "TraitImpl" => Keyword::TraitImpl,
             "type" => Keyword::Type,
             "Type" => Keyword::TypeType,
+            "TypedExpr" => Keyword::TypedExpr,
             "StructDefinition" => Keyword::StructDefinition,
             "unchecked" => Keyword::Unchecked,
             "unconstrained" => Keyword::Unconstrained,
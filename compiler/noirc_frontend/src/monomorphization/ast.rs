@@ -36,6 +36,8 @@ pub enum Expression {
     Index(Index),
     Cast(Cast),
     For(For),
+    While(While),
+    Loop(Box<Expression>),
     If(If),
     Tuple(Vec<Expression>),
     ExtractTupleField(Box<Expression>, usize),
@@ -46,6 +48,7 @@ pub enum Expression {
     Semi(Box<Expression>),
     Break,
     Continue,
+    Return(Box<Expression>),
 }
 
 /// A definition is either a local (variable), function, or is a built-in
@@ -98,6 +101,12 @@ pub struct For {
     pub end_range_location: Location,
 }
 
+#[derive(Debug, Clone, Hash)]
+pub struct While {
+    pub condition: Box<Expression>,
+    pub body: Box<Expression>,
+}
+
 #[derive(Debug, Clone, Hash)]
 pub enum Literal {
     Array(ArrayLiteral),
@@ -314,12 +314,7 @@ impl<'interner> Monomorphizer<'interner> {
         let name = self.interner.function_name(&f).to_owned();
 
         let body_expr_id = self.interner.function(&f).as_expr();
-        let body_return_type = self.interner.id_type(body_expr_id);
-        let return_type = match meta.return_type() {
-            Type::TraitAsType(..) => &body_return_type,
-            other => other,
-        };
-
+        let return_type = meta.opaque_return_type.as_ref().unwrap_or_else(|| meta.return_type());
         let return_type = Self::convert_type(return_type, meta.location)?;
         let unconstrained = modifiers.is_unconstrained;
 
@@ -638,12 +633,32 @@ impl<'interner> Monomorphizer<'interner> {
                     block,
                 }))
             }
+            HirStatement::While(while_loop) => {
+                let condition = Box::new(self.expr(while_loop.condition)?);
+                let body = Box::new(self.expr(while_loop.block)?);
+                Ok(ast::Expression::While(ast::While { condition, body }))
+            }
+            HirStatement::Loop(block) => Ok(ast::Expression::Loop(Box::new(self.expr(block)?))),
             HirStatement::Expression(expr) => self.expr(expr),
             HirStatement::Semi(expr) => {
                 self.expr(expr).map(|expr| ast::Expression::Semi(Box::new(expr)))
             }
             HirStatement::Break => Ok(ast::Expression::Break),
             HirStatement::Continue => Ok(ast::Expression::Continue),
+            HirStatement::Return(expr, typ) => {
+                let location = self.interner.expr_location(&expr);
+                let value = ast::Expression::Return(Box::new(self.expr(expr)?));
+
+                // Code following a `return` is unreachable, but the statement may still be used
+                // where a value is expected, e.g. as the last statement of an `if` branch. In that
+                // case it is followed by a placeholder value of the expected type.
+                let typ = match typ.follow_bindings() {
+                    HirType::TypeVariable(..) | HirType::Unit => return Ok(value),
+                    typ => Self::convert_type(&typ, location)?,
+                };
+                let placeholder = self.zeroed_value_of_type(&typ, location);
+                Ok(ast::Expression::Block(vec![value, placeholder]))
+            }
             HirStatement::Error => unreachable!(),
 
             // All `comptime` statements & expressions should be removed before runtime.
@@ -49,6 +49,11 @@ impl AstPrinter {
                 write!(f, " as {})", cast.r#type)
             }
             Expression::For(for_expr) => self.print_for(for_expr, f),
+            Expression::While(while_expr) => self.print_while(while_expr, f),
+            Expression::Loop(body) => {
+                write!(f, "loop {{")?;
+                self.print_loop_body(body, f)
+            }
             Expression::If(if_expr) => self.print_if(if_expr, f),
             Expression::Tuple(tuple) => self.print_tuple(tuple, f),
             Expression::ExtractTupleField(expr, index) => {
@@ -75,6 +80,10 @@ impl AstPrinter {
             }
             Expression::Break => write!(f, "break"),
             Expression::Continue => write!(f, "continue"),
+            Expression::Return(expr) => {
+                write!(f, "return ")?;
+                self.print_expr(expr, f)
+            }
         }
     }
 
@@ -207,6 +216,29 @@ impl AstPrinter {
         write!(f, "}}")
     }
 
+    fn print_while(
+        &mut self,
+        while_expr: &super::ast::While,
+        f: &mut Formatter,
+    ) -> Result<(), std::fmt::Error> {
+        write!(f, "while ")?;
+        self.print_expr(&while_expr.condition, f)?;
+        write!(f, " {{")?;
+        self.print_loop_body(&while_expr.body, f)
+    }
+
+    fn print_loop_body(
+        &mut self,
+        body: &Expression,
+        f: &mut Formatter,
+    ) -> Result<(), std::fmt::Error> {
+        self.indent_level += 1;
+        self.print_expr_expect_block(body, f)?;
+        self.indent_level -= 1;
+        self.next_line(f)?;
+        write!(f, "}}")
+    }
+
     fn print_if(
         &mut self,
         if_expr: &super::ast::If,
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt;
 use std::hash::Hash;
 use std::marker::Copy;
@@ -81,9 +82,22 @@ pub struct NodeInterner {
     // Contains the source module each function was defined in
     function_modules: HashMap<FuncId, ModuleId>,
 
+    // The parameters and local variables still in scope at the end of each elaborated
+    // function's body, by name. Variables declared in nested blocks are not included.
+    function_variables: HashMap<FuncId, HashMap<String, DefinitionId>>,
+
     // The location of each module
     module_attributes: HashMap<ModuleId, ModuleAttributes>,
 
+    // Secondary attributes on modules, such as `#[foo] mod bar;`
+    module_secondary_attributes: HashMap<ModuleId, Vec<SecondaryAttribute>>,
+
+    /// The files and environment variables each crate's comptime code may read
+    comptime_inputs: HashMap<CrateId, comptime::ComptimeInputs>,
+
+    /// Every file and environment variable comptime code has read so far
+    comptime_inputs_read: BTreeMap<comptime::ComptimeInput, Option<Vec<u8>>>,
+
     /// This graph tracks dependencies between different global definitions.
     /// This is used to ensure the absence of dependency cycles for globals and types.
     dependency_graph: DiGraph<DependencyId, ()>,
@@ -610,7 +624,11 @@ impl Default for NodeInterner {
             function_definition_ids: HashMap::default(),
             function_modifiers: HashMap::default(),
             function_modules: HashMap::default(),
+            function_variables: HashMap::default(),
             module_attributes: HashMap::default(),
+            module_secondary_attributes: HashMap::default(),
+            comptime_inputs: HashMap::default(),
+            comptime_inputs_read: Default::default(),
             func_id_to_trait: HashMap::default(),
             dependency_graph: petgraph::graph::DiGraph::new(),
             dependency_graph_indices: HashMap::default(),
@@ -1067,6 +1085,42 @@ impl NodeInterner {
         &self.module_attributes[module_id]
     }
 
+    pub fn module_secondary_attributes(&self, module_id: &ModuleId) -> &[SecondaryAttribute] {
+        self.module_secondary_attributes.get(module_id).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn add_module_secondary_attribute(
+        &mut self,
+        module_id: ModuleId,
+        attribute: SecondaryAttribute,
+    ) {
+        self.module_secondary_attributes.entry(module_id).or_default().push(attribute);
+    }
+
+    pub fn set_comptime_inputs(&mut self, crate_id: CrateId, inputs: comptime::ComptimeInputs) {
+        self.comptime_inputs.insert(crate_id, inputs);
+    }
+
+    pub fn get_comptime_inputs(&self, crate_id: CrateId) -> Option<&comptime::ComptimeInputs> {
+        self.comptime_inputs.get(&crate_id)
+    }
+
+    pub(crate) fn record_comptime_input_read(
+        &mut self,
+        input: comptime::ComptimeInput,
+        contents: Option<Vec<u8>>,
+    ) {
+        self.comptime_inputs_read.insert(input, contents);
+    }
+
+    /// Returns each file and environment variable read by comptime code during compilation,
+    /// along with the contents that were read (`None` for an unset environment variable)
+    pub fn comptime_inputs_read(
+        &self,
+    ) -> impl Iterator<Item = (&comptime::ComptimeInput, &Option<Vec<u8>>)> {
+        self.comptime_inputs_read.iter()
+    }
+
     pub fn try_module_attributes(&self, module_id: &ModuleId) -> Option<&ModuleAttributes> {
         self.module_attributes.get(module_id)
     }
@@ -1141,6 +1195,24 @@ impl NodeInterner {
         self.definitions.get(id.0)
     }
 
+    /// Records the variables in scope at the end of the given function's body
+    pub(crate) fn set_function_variables(
+        &mut self,
+        func_id: FuncId,
+        variables: HashMap<String, DefinitionId>,
+    ) {
+        self.function_variables.insert(func_id, variables);
+    }
+
+    /// Returns the parameter or local variable named `name` that is in scope at the end of
+    /// the given function's body, if the function has been elaborated.
+    /// If the name is shadowed, this is the definition that shadows the others.
+    pub fn find_local_definition(&self, func_id: FuncId, name: &str) -> Option<DefinitionId> {
+        let definition_id = *self.function_variables.get(&func_id)?.get(name)?;
+        let is_local = matches!(self.definition(definition_id).kind, DefinitionKind::Local(_));
+        is_local.then_some(definition_id)
+    }
+
     /// Returns the name of the definition
     ///
     /// This is needed as the Environment needs to map variable names to witness indices
@@ -1247,28 +1319,6 @@ impl NodeInterner {
         self.definition_to_type.get(&id).cloned().unwrap_or(Type::Error)
     }
 
-    pub fn id_type_substitute_trait_as_type(&self, def_id: DefinitionId) -> Type {
-        let typ = self.definition_type(def_id);
-        if let Type::Function(args, ret, env, unconstrained) = &typ {
-            let def = self.definition(def_id);
-            if let Type::TraitAsType(..) = ret.as_ref() {
-                if let DefinitionKind::Function(func_id) = def.kind {
-                    let f = self.function(&func_id);
-                    let func_body = f.as_expr();
-                    let ret_type = self.id_type(func_body);
-                    let new_type = Type::Function(
-                        args.clone(),
-                        Box::new(ret_type),
-                        env.clone(),
-                        *unconstrained,
-                    );
-                    return new_type;
-                }
-            }
-        }
-        typ
-    }
-
     /// Returns the span of an item stored in the Interner
     pub fn id_location(&self, index: impl Into<Index> + Copy) -> Location {
         self.try_id_location(index)
@@ -36,8 +36,6 @@ pub enum ParserErrorReason {
     ConstrainDeprecated,
     #[error("Invalid type expression: '{0}'. Only unsigned integer constants up to `u32`, globals, generics, +, -, *, /, and % may be used in this context.")]
     InvalidTypeExpression(Expression),
-    #[error("Early 'return' is unsupported")]
-    EarlyReturn,
     #[error("Patterns aren't allowed in a trait's function declarations")]
     PatternInTraitFunctionParameter,
     #[error("Patterns aren't allowed in a trait impl's associated constants")]
@@ -68,6 +66,8 @@ pub enum ParserErrorReason {
     AssociatedTypesNotAllowedInPaths,
     #[error("Associated types are not allowed on a method call")]
     AssociatedTypesNotAllowedInMethodCalls,
+    #[error("Unexpected '{0}', only integer and boolean literals are allowed in match patterns")]
+    InvalidMatchPatternLiteral(Token),
 }
 
 /// Represents a parsing error, or a parsing error in the making.
@@ -12,10 +12,11 @@ mod labels;
 mod parser;
 
 use crate::ast::{
-    Expression, Ident, ImportStatement, LetStatement, ModuleDeclaration, NoirFunction, NoirStruct,
-    NoirTrait, NoirTraitImpl, NoirTypeAlias, Recoverable, StatementKind, TypeImpl, UseTree,
+    Expression, Ident, ImportStatement, LetStatement, ModuleDeclaration, NoirEnumeration,
+    NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Recoverable, StatementKind,
+    TypeImpl, UseTree,
 };
-use crate::token::{Keyword, Token};
+use crate::token::{Keyword, SecondaryAttribute, Token};
 
 use chumsky::prelude::*;
 use chumsky::primitive::Container;
@@ -23,6 +24,7 @@ pub use errors::ParserError;
 pub use errors::ParserErrorReason;
 use noirc_errors::Span;
 pub use parser::path::path_no_turbofish;
+pub use parser::primitives::ident;
 pub use parser::traits::trait_bound;
 pub use parser::{
     block, expression, fresh_statement, lvalue, parse_program, parse_type, pattern, top_level_items,
@@ -34,6 +36,7 @@ pub enum TopLevelStatement {
     Module(ModuleDeclaration),
     Import(UseTree),
     Struct(NoirStruct),
+    Enum(NoirEnumeration),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -50,6 +53,7 @@ impl TopLevelStatement {
             TopLevelStatement::Module(m) => Some(ItemKind::ModuleDecl(m)),
             TopLevelStatement::Import(i) => Some(ItemKind::Import(i)),
             TopLevelStatement::Struct(s) => Some(ItemKind::Struct(s)),
+            TopLevelStatement::Enum(e) => Some(ItemKind::Enum(e)),
             TopLevelStatement::Trait(t) => Some(ItemKind::Trait(t)),
             TopLevelStatement::TraitImpl(t) => Some(ItemKind::TraitImpl(t)),
             TopLevelStatement::Impl(i) => Some(ItemKind::Impl(i)),
@@ -235,6 +239,7 @@ pub struct SortedModule {
     pub imports: Vec<ImportStatement>,
     pub functions: Vec<NoirFunction>,
     pub types: Vec<NoirStruct>,
+    pub enums: Vec<NoirEnumeration>,
     pub traits: Vec<NoirTrait>,
     pub trait_impls: Vec<NoirTraitImpl>,
     pub impls: Vec<TypeImpl>,
@@ -266,6 +271,10 @@ impl std::fmt::Display for SortedModule {
             write!(f, "{type_}")?;
         }
 
+        for enum_ in &self.enums {
+            write!(f, "{enum_}")?;
+        }
+
         for function in &self.functions {
             write!(f, "{function}")?;
         }
@@ -301,6 +310,7 @@ impl ParsedModule {
                 ItemKind::Import(import) => module.push_import(import),
                 ItemKind::Function(func) => module.push_function(func),
                 ItemKind::Struct(typ) => module.push_type(typ),
+                ItemKind::Enum(typ) => module.push_enum(typ),
                 ItemKind::Trait(noir_trait) => module.push_trait(noir_trait),
                 ItemKind::TraitImpl(trait_impl) => module.push_trait_impl(trait_impl),
                 ItemKind::Impl(r#impl) => module.push_impl(r#impl),
@@ -326,6 +336,7 @@ pub enum ItemKind {
     Import(UseTree),
     Function(NoirFunction),
     Struct(NoirStruct),
+    Enum(NoirEnumeration),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -341,6 +352,7 @@ pub enum ItemKind {
 pub struct ParsedSubModule {
     pub name: Ident,
     pub contents: ParsedModule,
+    pub outer_attributes: Vec<SecondaryAttribute>,
     pub is_contract: bool,
 }
 
@@ -349,6 +361,7 @@ impl ParsedSubModule {
         SortedSubModule {
             name: self.name,
             contents: self.contents.into_sorted(),
+            outer_attributes: self.outer_attributes,
             is_contract: self.is_contract,
         }
     }
@@ -356,6 +369,9 @@ impl ParsedSubModule {
 
 impl std::fmt::Display for SortedSubModule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for attribute in &self.outer_attributes {
+            writeln!(f, "{attribute}")?;
+        }
         write!(f, "mod {} {{", self.name)?;
 
         for line in self.contents.to_string().lines() {
@@ -370,6 +386,7 @@ impl std::fmt::Display for SortedSubModule {
 pub struct SortedSubModule {
     pub name: Ident,
     pub contents: SortedModule,
+    pub outer_attributes: Vec<SecondaryAttribute>,
     pub is_contract: bool,
 }
 
@@ -382,6 +399,10 @@ impl SortedModule {
         self.types.push(typ);
     }
 
+    fn push_enum(&mut self, typ: NoirEnumeration) {
+        self.enums.push(typ);
+    }
+
     fn push_trait(&mut self, noir_trait: NoirTrait) {
         self.traits.push(noir_trait);
     }
@@ -501,6 +522,7 @@ impl std::fmt::Display for TopLevelStatement {
             TopLevelStatement::Trait(t) => t.fmt(f),
             TopLevelStatement::TraitImpl(i) => i.fmt(f),
             TopLevelStatement::Struct(s) => s.fmt(f),
+            TopLevelStatement::Enum(e) => e.fmt(f),
             TopLevelStatement::Impl(i) => i.fmt(f),
             TopLevelStatement::TypeAlias(t) => t.fmt(f),
             TopLevelStatement::SubModule(s) => s.fmt(f),
@@ -39,7 +39,7 @@ use crate::ast::{
     BinaryOp, BinaryOpKind, BlockExpression, ForLoopStatement, ForRange, GenericTypeArgs, Ident,
     IfExpression, InfixExpression, LValue, Literal, ModuleDeclaration, NoirTypeAlias, Param, Path,
     Pattern, Recoverable, Statement, TypeImpl, UnaryRhsMemberAccess, UnaryRhsMethodCall, UseTree,
-    UseTreeKind, Visibility,
+    UseTreeKind, Visibility, WhileStatement,
 };
 use crate::ast::{
     Expression, ExpressionKind, LetStatement, StatementKind, UnresolvedType, UnresolvedTypeData,
@@ -56,11 +56,12 @@ use noirc_errors::{Span, Spanned};
 
 mod assertion;
 mod attributes;
+mod enums;
 mod function;
 mod lambdas;
 mod literals;
 pub(super) mod path;
-mod primitives;
+pub(super) mod primitives;
 mod structs;
 pub(super) mod traits;
 mod types;
@@ -192,6 +193,7 @@ pub fn top_level_items() -> impl NoirParser<Vec<TopLevelStatement>> {
 
 /// top_level_statement: function_definition
 ///                    | struct_definition
+///                    | enum_definition
 ///                    | trait_definition
 ///                    | implementation
 ///                    | submodule
@@ -204,6 +206,7 @@ fn top_level_statement<'a>(
     choice((
         function::function_definition(false).map(TopLevelStatement::Function),
         structs::struct_definition(),
+        enums::enum_definition(),
         traits::trait_definition(),
         traits::trait_implementation(),
         implementation(),
@@ -283,27 +286,43 @@ fn global_declaration() -> impl NoirParser<TopLevelStatement> {
     .map(TopLevelStatement::Global)
 }
 
-/// submodule: 'mod' ident '{' module '}'
+/// submodule: attributes 'mod' ident '{' module '}'
 fn submodule(module_parser: impl NoirParser<ParsedModule>) -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Mod)
-        .ignore_then(ident())
+    attributes::attributes()
+        .then_ignore(keyword(Keyword::Mod))
+        .then(ident())
         .then_ignore(just(Token::LeftBrace))
         .then(module_parser)
         .then_ignore(just(Token::RightBrace))
-        .map(|(name, contents)| {
-            TopLevelStatement::SubModule(ParsedSubModule { name, contents, is_contract: false })
+        .validate(|((attributes, name), contents), span, emit| {
+            let outer_attributes =
+                attributes::validate_secondary_attributes(attributes, span, emit);
+            TopLevelStatement::SubModule(ParsedSubModule {
+                name,
+                contents,
+                outer_attributes,
+                is_contract: false,
+            })
         })
 }
 
-/// contract: 'contract' ident '{' module '}'
+/// contract: attributes 'contract' ident '{' module '}'
 fn contract(module_parser: impl NoirParser<ParsedModule>) -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Contract)
-        .ignore_then(ident())
+    attributes::attributes()
+        .then_ignore(keyword(Keyword::Contract))
+        .then(ident())
         .then_ignore(just(Token::LeftBrace))
         .then(module_parser)
         .then_ignore(just(Token::RightBrace))
-        .map(|(name, contents)| {
-            TopLevelStatement::SubModule(ParsedSubModule { name, contents, is_contract: true })
+        .validate(|((attributes, name), contents), span, emit| {
+            let outer_attributes =
+                attributes::validate_secondary_attributes(attributes, span, emit);
+            TopLevelStatement::SubModule(ParsedSubModule {
+                name,
+                contents,
+                outer_attributes,
+                is_contract: true,
+            })
         })
 }
 
@@ -432,9 +451,13 @@ fn optional_type_annotation<'a>() -> impl NoirParser<UnresolvedType> + 'a {
 }
 
 fn module_declaration() -> impl NoirParser<TopLevelStatement> {
-    keyword(Keyword::Mod)
-        .ignore_then(ident())
-        .map(|ident| TopLevelStatement::Module(ModuleDeclaration { ident }))
+    attributes::attributes().then_ignore(keyword(Keyword::Mod)).then(ident()).validate(
+        |(attributes, ident), span, emit| {
+            let outer_attributes =
+                attributes::validate_secondary_attributes(attributes, span, emit);
+            TopLevelStatement::Module(ModuleDeclaration { ident, outer_attributes })
+        },
+    )
 }
 
 fn use_statement() -> impl NoirParser<TopLevelStatement> {
@@ -483,8 +506,15 @@ where
             declaration(expr_parser.clone()),
             assignment(expr_parser.clone()),
             if_statement(expr_no_constructors.clone(), statement.clone()),
+            enums::match_statement(
+                expr_parser.clone(),
+                expr_no_constructors.clone(),
+                statement.clone(),
+            ),
             block_statement(statement.clone()),
             for_loop(expr_no_constructors.clone(), statement.clone()),
+            while_loop(expr_no_constructors.clone(), statement.clone()),
+            loop_statement(statement.clone()),
             break_statement(),
             continue_statement(),
             return_statement(expr_parser.clone()),
@@ -757,10 +787,7 @@ where
     P: ExprParser + 'a,
 {
     ignore_then_commit(keyword(Keyword::Return), expr_parser.or_not())
-        .validate(|_, span, emit| {
-            emit(ParserError::with_reason(ParserErrorReason::EarlyReturn, span));
-            StatementKind::Error
-        })
+        .map(StatementKind::Return)
         .labelled(ParsingRuleLabel::Statement)
 }
 
@@ -893,6 +920,7 @@ where
         ArrayIndex(Expression),
         Cast(UnresolvedType),
         MemberAccess(UnaryRhsMemberAccess),
+        Try,
         /// This is to allow `foo.` (no identifier afterwards) to be parsed as `foo`
         /// and produce an error, rather than just erroring (for LSP).
         JustADot,
@@ -944,6 +972,9 @@ where
         })
         .labelled(ParsingRuleLabel::FieldAccess);
 
+    // `?` in `atom?`
+    let try_rhs = just(Token::Question).to(UnaryRhs::Try);
+
     let just_a_dot =
         just(Token::Dot).map(|_| UnaryRhs::JustADot).validate(|value, span, emit_error| {
             emit_error(ParserError::with_reason(
@@ -953,7 +984,7 @@ where
             value
         });
 
-    let rhs = choice((call_rhs, array_rhs, cast_rhs, member_rhs, just_a_dot));
+    let rhs = choice((call_rhs, array_rhs, cast_rhs, member_rhs, try_rhs, just_a_dot));
 
     foldl_with_span(
         atom(expr_parser, expr_no_constructors, statement, allow_constructors),
@@ -967,6 +998,7 @@ where
             UnaryRhs::MemberAccess(field) => {
                 Expression::member_access_or_method_call(lhs, field, span)
             }
+            UnaryRhs::Try => Expression::new(ExpressionKind::Try(Box::new(lhs)), span),
             UnaryRhs::JustADot => lhs,
         },
     )
@@ -1062,6 +1094,29 @@ where
         })
 }
 
+fn while_loop<'a, P, S>(
+    expr_no_constructors: P,
+    statement: S,
+) -> impl NoirParser<StatementKind> + 'a
+where
+    P: ExprParser + 'a,
+    S: NoirParser<StatementKind> + 'a,
+{
+    keyword(Keyword::While)
+        .ignore_then(expr_no_constructors)
+        .then(block_expr(statement))
+        .map_with_span(|(condition, block), span| {
+            StatementKind::While(WhileStatement { condition, block, span })
+        })
+}
+
+fn loop_statement<'a, S>(statement: S) -> impl NoirParser<StatementKind> + 'a
+where
+    S: NoirParser<StatementKind> + 'a,
+{
+    keyword(Keyword::Loop).ignore_then(block_expr(statement)).map(StatementKind::Loop)
+}
+
 /// The 'range' of a for loop. Either an actual range `start .. end` or an array expression.
 fn for_range<P>(expr_no_constructors: P) -> impl NoirParser<ForRange>
 where
@@ -1156,7 +1211,8 @@ where
     S: NoirParser<StatementKind> + 'a,
 {
     choice((
-        if_expr(expr_no_constructors, statement.clone()),
+        if_expr(expr_no_constructors.clone(), statement.clone()),
+        enums::match_expr(expr_parser.clone(), expr_no_constructors, statement.clone()),
         slice_expr(expr_parser.clone()),
         array_expr(expr_parser.clone()),
         if allow_constructors {
@@ -1465,6 +1521,25 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_while_loop() {
+        parse_all(
+            while_loop(expression_no_constructors(expression()), fresh_statement()),
+            vec!["while x < y {}", "while true { foo; bar }", "while i != 0 { i -= 1; }"],
+        );
+
+        parse_all_failing(
+            while_loop(expression_no_constructors(expression()), fresh_statement()),
+            vec!["while {}", "while x < y", "while Foo { a: 1 }.a == 1 {}"],
+        );
+    }
+
+    #[test]
+    fn parse_loop() {
+        parse_all(loop_statement(fresh_statement()), vec!["loop {}", "loop { foo; break; }"]);
+        parse_all_failing(loop_statement(fresh_statement()), vec!["loop", "loop x"]);
+    }
+
     #[test]
     fn parse_parenthesized_expression() {
         parse_all(
@@ -1517,6 +1592,15 @@ mod test {
     fn parse_module_declaration() {
         parse_with(module_declaration(), "mod foo").unwrap();
         parse_with(module_declaration(), "mod 1").unwrap_err();
+
+        let TopLevelStatement::Module(module) =
+            parse_with(module_declaration(), "#[foo] #[bar(1)] mod baz").unwrap()
+        else {
+            panic!("Expected a module declaration");
+        };
+        assert_eq!(module.ident.0.contents, "baz");
+        assert_eq!(module.outer_attributes.len(), 2);
+        assert_eq!(module.outer_attributes[1].name(), "bar");
     }
 
     #[test]
@@ -1585,6 +1669,18 @@ mod test {
         parse_all(expression(), cases);
     }
 
+    #[test]
+    fn parse_try() {
+        let cases = vec!["a?", "foo(x)?", "a.b()?.c()?", "-x?", "x? + y?"];
+        parse_all(expression(), cases);
+
+        let expression = parse_with(expression(), "foo.bar()?").unwrap();
+        let ExpressionKind::Try(operand) = expression.kind else {
+            panic!("Expected a try expression");
+        };
+        assert!(matches!(operand.kind, ExpressionKind::MethodCall(_)));
+    }
+
     #[test]
     fn parse_constructor() {
         let cases = vec![
@@ -1656,25 +1752,30 @@ mod test {
         let cases = [
             Case {
                 source: "{ return 42; }",
-                expect: concat!("{\n", "    Error\n", "}",),
-                errors: 1,
+                expect: concat!("{\n", "    return 42\n", "}",),
+                errors: 0,
             },
             Case {
                 source: "{ return 1; return 2; }",
-                expect: concat!("{\n", "    Error\n", "    Error\n", "}"),
-                errors: 2,
+                expect: concat!("{\n", "    return 1\n", "    return 2\n", "}"),
+                errors: 0,
             },
             Case {
                 source: "{ return 123; let foo = 4 + 3; }",
-                expect: concat!("{\n", "    Error\n", "    let foo: unspecified = (4 + 3)\n", "}"),
-                errors: 1,
+                expect: concat!(
+                    "{\n",
+                    "    return 123\n",
+                    "    let foo: unspecified = (4 + 3)\n",
+                    "}"
+                ),
+                errors: 0,
             },
             Case {
                 source: "{ return 1 + 2 }",
-                expect: concat!("{\n", "    Error\n", "}",),
-                errors: 2,
+                expect: concat!("{\n", "    return (1 + 2)\n", "}",),
+                errors: 1,
             },
-            Case { source: "{ return; }", expect: concat!("{\n", "    Error\n", "}",), errors: 1 },
+            Case { source: "{ return; }", expect: concat!("{\n", "    return\n", "}",), errors: 0 },
         ];
 
         check_cases_with_errors(&cases[..], block(fresh_statement()));
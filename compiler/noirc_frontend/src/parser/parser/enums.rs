@@ -0,0 +1,250 @@
+use chumsky::prelude::*;
+
+use crate::ast::{
+    Expression, ExpressionKind, Literal, MatchExpression, MatchPattern, NoirEnumVariant,
+    NoirEnumeration, StatementKind,
+};
+use crate::{
+    parser::{
+        labels::ParsingRuleLabel,
+        parser::{
+            attributes::{attributes, validate_secondary_attributes},
+            block_expr, function, parse_type,
+            path::path_no_turbofish,
+            primitives::{ident, keyword, token_kind},
+        },
+        ExprParser, NoirParser, ParserError, ParserErrorReason, TopLevelStatement,
+    },
+    token::{Keyword, Token, TokenKind},
+};
+
+/// enum_definition: attributes 'enum' ident generics '{' enum_variant ... '}'
+pub(super) fn enum_definition() -> impl NoirParser<TopLevelStatement> {
+    use Token::*;
+
+    let variants = enum_variant()
+        .separated_by(just(Comma))
+        .allow_trailing()
+        .delimited_by(just(LeftBrace), just(RightBrace))
+        .recover_with(nested_delimiters(
+            LeftBrace,
+            RightBrace,
+            [(LeftParen, RightParen), (LeftBracket, RightBracket)],
+            |_| vec![],
+        ));
+
+    attributes()
+        .then_ignore(keyword(Keyword::Enum))
+        .then(ident())
+        .then(function::generics())
+        .then(variants)
+        .validate(|(((attributes, name), generics), variants), span, emit| {
+            let attributes = validate_secondary_attributes(attributes, span, emit);
+            TopLevelStatement::Enum(NoirEnumeration { name, attributes, generics, variants, span })
+        })
+}
+
+/// enum_variant: ident
+///             | ident '(' type, ... ')'
+fn enum_variant() -> impl NoirParser<NoirEnumVariant> {
+    let parameters = parse_type()
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .delimited_by(just(Token::LeftParen), just(Token::RightParen));
+
+    ident().then(parameters.or_not()).map(|(name, parameters)| NoirEnumVariant { name, parameters })
+}
+
+/// match_expr: 'match' expression '{' match_rule ... '}'
+///
+/// match_rule: match_pattern '=>' block ','?
+///           | match_pattern '=>' expression ','
+///
+/// The comma after the last rule is optional.
+pub(super) fn match_expr<'a, P1, P2, S>(
+    expr_parser: P1,
+    expr_no_constructors: P2,
+    statement: S,
+) -> impl NoirParser<ExpressionKind> + 'a
+where
+    P1: ExprParser + 'a,
+    P2: ExprParser + 'a,
+    S: NoirParser<StatementKind> + 'a,
+{
+    use Token::*;
+
+    let block_rule = match_pattern()
+        .then_ignore(just(FatArrow))
+        .then(block_expr(statement))
+        .then_ignore(just(Comma).or_not());
+
+    let expression_rule = match_pattern()
+        .then_ignore(just(FatArrow))
+        .then(expr_parser)
+        .then_ignore(just(Comma).ignored().or(just(RightBrace).rewind().ignored()));
+
+    let rules = block_rule
+        .or(expression_rule)
+        .repeated()
+        .delimited_by(just(LeftBrace), just(RightBrace))
+        .recover_with(nested_delimiters(
+            LeftBrace,
+            RightBrace,
+            [(LeftParen, RightParen), (LeftBracket, RightBracket)],
+            |_| vec![],
+        ));
+
+    keyword(Keyword::Match).ignore_then(expr_no_constructors).then(rules).map(
+        |(expression, rules)| {
+            ExpressionKind::Match(Box::new(MatchExpression { expression, rules }))
+        },
+    )
+}
+
+/// A match expression in statement position, which doesn't require a trailing semicolon.
+pub(super) fn match_statement<'a, P1, P2, S>(
+    expr_parser: P1,
+    expr_no_constructors: P2,
+    statement: S,
+) -> impl NoirParser<StatementKind> + 'a
+where
+    P1: ExprParser + 'a,
+    P2: ExprParser + 'a,
+    S: NoirParser<StatementKind> + 'a,
+{
+    match_expr(expr_parser, expr_no_constructors, statement)
+        .map_with_span(|kind, span| StatementKind::Expression(Expression::new(kind, span)))
+}
+
+/// match_pattern: '_'
+///              | ident
+///              | '-'? integer | bool
+///              | '(' match_pattern, ... ')'
+///              | path '(' match_pattern, ... ')'
+///              | path '{' field_pattern, ... '}'
+///              | path
+pub(super) fn match_pattern() -> impl NoirParser<MatchPattern> {
+    recursive(|pattern| {
+        let literal = just(Token::Minus).or_not().then(token_kind(TokenKind::Literal)).validate(
+            |(minus, token), span, emit| match token {
+                Token::Int(value) => {
+                    MatchPattern::Literal(Literal::Integer(value, minus.is_some()), span)
+                }
+                Token::Bool(value) if minus.is_none() => {
+                    MatchPattern::Literal(Literal::Bool(value), span)
+                }
+                other => {
+                    emit(ParserError::with_reason(
+                        ParserErrorReason::InvalidMatchPatternLiteral(other),
+                        span,
+                    ));
+                    MatchPattern::Wildcard(span)
+                }
+            },
+        );
+
+        let tuple = pattern
+            .clone()
+            .separated_by(just(Token::Comma))
+            .allow_trailing()
+            .delimited_by(just(Token::LeftParen), just(Token::RightParen))
+            .map_with_span(MatchPattern::Tuple);
+
+        let arguments = pattern
+            .clone()
+            .separated_by(just(Token::Comma))
+            .allow_trailing()
+            .delimited_by(just(Token::LeftParen), just(Token::RightParen));
+
+        let short_field = ident().map(|name| (name.clone(), MatchPattern::Identifier(name)));
+        let long_field = ident().then_ignore(just(Token::Colon)).then(pattern);
+        let fields = long_field
+            .or(short_field)
+            .separated_by(just(Token::Comma))
+            .allow_trailing()
+            .delimited_by(just(Token::LeftBrace), just(Token::RightBrace));
+
+        let path_pattern = path_no_turbofish()
+            .then(arguments.map(Ok).or(fields.map(Err)).or_not())
+            .map_with_span(|(path, rest), span| match rest {
+                Some(Ok(arguments)) => MatchPattern::Variant(path, Some(arguments), span),
+                Some(Err(fields)) => MatchPattern::Struct(path, fields, span),
+                None => match path.as_ident() {
+                    Some(name) if name.0.contents == "_" => MatchPattern::Wildcard(span),
+                    Some(name) => MatchPattern::Identifier(name.clone()),
+                    None => MatchPattern::Variant(path, None, span),
+                },
+            });
+
+        choice((literal, tuple, path_pattern))
+    })
+    .labelled(ParsingRuleLabel::Pattern)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::parser::test_helpers::*;
+    use crate::parser::parser::{expression, expression_no_constructors, fresh_statement};
+
+    #[test]
+    fn parse_enums() {
+        let cases = vec![
+            "enum Foo {}",
+            "enum Foo { A }",
+            "enum Foo { A, B(Field), C(u8, bool), }",
+            "enum Foo<T> { A(T), B }",
+            "#[attribute] enum Foo { A(Field) }",
+        ];
+        parse_all(enum_definition(), cases);
+
+        let failing = vec!["enum {}", "enum Foo { A: Field }", "enum Foo { A(pub Field) }"];
+        parse_all_failing(enum_definition(), failing);
+    }
+
+    #[test]
+    fn parse_match_patterns() {
+        let cases = vec![
+            "_",
+            "x",
+            "3",
+            "-3",
+            "true",
+            "(x, _)",
+            "Foo::A",
+            "Foo::B(x, (y, 1))",
+            "Foo { a, b: Bar::C(_) }",
+            "crate::Foo::A(_)",
+        ];
+        parse_all(match_pattern(), cases);
+
+        let failing = vec!["\"foo\"", "-true", "Foo::A(x"];
+        parse_all_failing(match_pattern(), failing);
+    }
+
+    #[test]
+    fn parse_match_expressions() {
+        let parser =
+            match_expr(expression(), expression_no_constructors(expression()), fresh_statement());
+        let cases = vec![
+            "match x {}",
+            "match x { _ => 1 }",
+            "match x { Foo::A(y) => y, Foo::B => 0, }",
+            "match x { Foo::A(y) => { y } Foo::B => { 0 } }",
+            "match (x, y) { (true, _) => 1, (false, z) => z }",
+        ];
+        parse_all(parser.clone(), cases);
+
+        let failing = vec!["match x { _ => 1 _ => 2 }", "match x { _ 1 }", "match { _ => 1 }"];
+        parse_all_failing(parser, failing);
+    }
+
+    #[test]
+    fn match_pattern_without_arguments_is_a_binding() {
+        let pattern = parse_with(match_pattern(), "foo").unwrap();
+        assert!(matches!(pattern, MatchPattern::Identifier(name) if name.0.contents == "foo"));
+
+        let pattern = parse_with(match_pattern(), "Foo::A").unwrap();
+        assert!(matches!(pattern, MatchPattern::Variant(_, None, _)));
+    }
+}
@@ -52,7 +52,7 @@ pub(super) fn path_segment_no_turbofish() -> impl NoirParser<PathSegment> {
     ident().map(PathSegment::from)
 }
 
-pub(super) fn ident() -> impl NoirParser<Ident> {
+pub fn ident() -> impl NoirParser<Ident> {
     token_kind(TokenKind::Ident).map_with_span(Ident::from_token)
 }
 
@@ -1,6 +1,6 @@
 use chumsky::prelude::*;
 
-use crate::ast::{Ident, NoirStruct, UnresolvedType};
+use crate::ast::{NoirStruct, StructField};
 use crate::{
     parser::{
         parser::{
@@ -38,17 +38,23 @@ pub(super) fn struct_definition() -> impl NoirParser<TopLevelStatement> {
         })
 }
 
-fn struct_fields() -> impl NoirParser<Vec<(Ident, UnresolvedType)>> {
-    ident()
+/// struct_field: visibility_modifier ident ':' type
+fn struct_fields() -> impl NoirParser<Vec<StructField>> {
+    function::visibility_modifier()
+        .then(ident())
         .then_ignore(just(Token::Colon))
         .then(parse_type())
+        .map(|((visibility, name), typ)| StructField { visibility, name, typ })
         .separated_by(just(Token::Comma))
         .allow_trailing()
 }
 
 #[cfg(test)]
 mod test {
+    use iter_extended::vecmap;
+
     use super::*;
+    use crate::ast::ItemVisibility;
     use crate::parser::parser::test_helpers::*;
 
     #[test]
@@ -59,6 +65,7 @@ mod test {
             "struct Bar { ident: Field, }",
             "struct Baz { ident: Field, other: Field }",
             "#[attribute] struct Baz { ident: Field, other: Field }",
+            "struct Baz { pub ident: Field, pub(crate) other: Field, private: Field }",
         ];
         parse_all(struct_definition(), cases);
 
@@ -67,7 +74,25 @@ mod test {
             "struct Foo { bar: pub Field }",
             "struct Foo { bar: pub Field }",
             "#[oracle(some)] struct Foo { bar: Field }",
+            "struct Foo { pub pub bar: Field }",
+            "struct Foo { pub(super) bar: Field }",
         ];
         parse_all_failing(struct_definition(), failing);
     }
+
+    #[test]
+    fn parse_struct_field_visibility() {
+        let TopLevelStatement::Struct(noir_struct) = parse_with(
+            struct_definition(),
+            "struct Foo { pub a: Field, pub(crate) b: Field, c: Field }",
+        )
+        .unwrap() else {
+            panic!("Expected a struct");
+        };
+        let visibilities = vecmap(noir_struct.fields, |field| field.visibility);
+        assert_eq!(
+            visibilities,
+            vec![ItemVisibility::Public, ItemVisibility::PublicCrate, ItemVisibility::Private]
+        );
+    }
 }
@@ -86,6 +86,7 @@ pub(super) fn comptime_type() -> impl NoirParser<UnresolvedType> {
         function_definition_type(),
         module_type(),
         type_of_quoted_types(),
+        typed_expr_type(),
         top_level_item_type(),
         quoted_type(),
     ))
@@ -153,6 +154,13 @@ fn type_of_quoted_types() -> impl NoirParser<UnresolvedType> {
         .map_with_span(|_, span| UnresolvedTypeData::Quoted(QuotedType::Type).with_span(span))
 }
 
+/// This is the type `TypedExpr` - the type of an expression that has already been
+/// resolved and type-checked.
+fn typed_expr_type() -> impl NoirParser<UnresolvedType> {
+    keyword(Keyword::TypedExpr)
+        .map_with_span(|_, span| UnresolvedTypeData::Quoted(QuotedType::TypedExpr).with_span(span))
+}
+
 /// This is the type of a quoted, unparsed token stream.
 fn quoted_type() -> impl NoirParser<UnresolvedType> {
     keyword(Keyword::Quoted)
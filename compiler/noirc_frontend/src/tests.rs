@@ -13,6 +13,7 @@ use fm::FileId;
 
 use iter_extended::vecmap;
 use noirc_errors::Location;
+use noirc_printable_type::PrintableType;
 
 use crate::hir::comptime::InterpreterError;
 use crate::hir::def_collector::dc_crate::CompilationError;
@@ -21,7 +22,7 @@ use crate::hir::def_map::ModuleData;
 use crate::hir::resolution::errors::ResolverError;
 use crate::hir::resolution::import::PathResolutionError;
 use crate::hir::type_check::TypeCheckError;
-use crate::hir::Context;
+use crate::hir::{Context, ParsedFiles};
 use crate::node_interner::{NodeInterner, StmtId};
 
 use crate::hir::def_collector::dc_crate::DefCollector;
@@ -54,9 +55,39 @@ pub(crate) fn get_program(src: &str) -> (ParsedModule, Context, Vec<(Compilation
     let root = std::path::Path::new("/");
     let fm = FileManager::new(root);
 
-    let mut context = Context::new(fm, Default::default());
+    let context = Context::new(fm, Default::default());
+    collect_program(context, FileId::dummy(), src)
+}
+
+/// Like `get_program` but for a crate spread over several `(path, source)` files. The first
+/// file is the crate root and the others can be declared as its modules, e.g. `mod foo;` for
+/// a `/foo.nr` next to a `/main.nr` root.
+pub(crate) fn get_program_in_files(
+    files: &[(&str, &str)],
+) -> (Context<'static, 'static>, Vec<(CompilationError, FileId)>) {
+    let root = std::path::Path::new("/");
+    let mut fm = FileManager::new(root);
+    let mut parsed_files = ParsedFiles::default();
+
+    let file_ids = vecmap(files, |(path, source)| {
+        let file_id = fm.add_file_with_source(std::path::Path::new(path), source.to_string());
+        let file_id = file_id.expect("Expected each file to have a distinct path");
+        parsed_files.insert(file_id, parse_program(source));
+        file_id
+    });
+
+    let context = Context::new(fm, parsed_files);
+    let (_, context, errors) = collect_program(context, file_ids[0], files[0].1);
+    (context, errors)
+}
+
+/// Collects and elaborates the crate whose root file is `root_file_id` with source `src`
+fn collect_program<'a>(
+    mut context: Context<'a, 'a>,
+    root_file_id: FileId,
+    src: &str,
+) -> (ParsedModule, Context<'a, 'a>, Vec<(CompilationError, FileId)>) {
     context.def_interner.populate_dummy_operator_traits();
-    let root_file_id = FileId::dummy();
     let root_crate_id = context.crate_graph.add_crate_root(root_file_id);
 
     let (program, parser_errors) = parse_program(src);
@@ -858,6 +889,9 @@ fn find_lambda_captures(stmts: &[StmtId], interner: &NodeInterner, result: &mut
             HirStatement::Constrain(constr_stmt) => constr_stmt.0,
             HirStatement::Semi(semi_expr) => semi_expr,
             HirStatement::For(for_loop) => for_loop.block,
+            HirStatement::While(while_loop) => while_loop.block,
+            HirStatement::Loop(block) => block,
+            HirStatement::Return(value, _) => value,
             HirStatement::Error => panic!("Invalid HirStatement!"),
             HirStatement::Break => panic!("Unexpected break"),
             HirStatement::Continue => panic!("Unexpected continue"),
@@ -1316,6 +1350,195 @@ fn break_and_continue_outside_loop() {
     assert_eq!(get_program_errors(src).len(), 2);
 }
 
+#[test]
+fn while_and_loop_in_unconstrained_fn() {
+    let src = r#"
+        unconstrained fn main() {
+            let mut i = 0;
+            while i < 10 {
+                i += 1;
+                if i == 2 {
+                    continue;
+                }
+            }
+            loop {
+                if i == 0 {
+                    break;
+                }
+                i -= 1;
+            }
+        }
+    "#;
+    assert_no_errors(src);
+}
+
+#[test]
+fn while_and_loop_in_constrained_fn() {
+    let src = r#"
+        fn main() {
+            let mut i = 0;
+            while i < 10 {
+                i += 1;
+            }
+            loop {
+                i -= 1;
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 2);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::LoopInConstrainedFn { is_while: true, .. })
+    ));
+    assert!(matches!(
+        errors[1].0,
+        CompilationError::ResolverError(ResolverError::LoopInConstrainedFn { is_while: false, .. })
+    ));
+}
+
+#[test]
+fn try_operator_in_constrained_fn() {
+    let src = r#"
+        fn main(x: Field) {
+            let _ = x?;
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 2);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::TryInConstrainedFn { .. })
+    ));
+    assert!(matches!(
+        errors[1].0,
+        CompilationError::TypeError(TypeCheckError::InvalidTryOperand { .. })
+    ));
+}
+
+#[test]
+fn try_operator_requires_stdlib_option_or_result() {
+    let src = r#"
+        struct Option<T> {
+            value: T,
+        }
+
+        unconstrained fn main(x: Option<Field>) -> pub Field {
+            let y = x?;
+            y.value
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    let CompilationError::TypeError(TypeCheckError::InvalidTryOperand { typ, .. }) = &errors[0].0
+    else {
+        panic!("Expected an invalid try operand error, got {:?}", errors[0].0);
+    };
+    assert_eq!(typ.to_string(), "Option<Field>");
+}
+
+#[test]
+fn early_return_in_constrained_fn() {
+    let src = r#"
+        fn main(x: Field) -> pub Field {
+            if x == 0 {
+                return 1;
+            }
+            x
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::ReturnInConstrainedFn { .. })
+    ));
+}
+
+#[test]
+fn trailing_return_in_constrained_fn() {
+    let src = r#"
+        fn main(x: Field) -> pub Field {
+            let y = x + 1;
+            return y;
+        }
+    "#;
+    assert_no_errors(src);
+}
+
+#[test]
+fn early_return_in_unconstrained_fn() {
+    let src = r#"
+        unconstrained fn main(x: u32) -> pub u32 {
+            for i in 0..10 {
+                if i == x {
+                    return i;
+                }
+            }
+            0
+        }
+    "#;
+    assert_no_errors(src);
+}
+
+#[test]
+fn early_return_type_mismatch() {
+    let src = r#"
+        unconstrained fn main(x: u32) -> pub u32 {
+            if x == 10 {
+                return;
+            }
+            x
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn return_in_comptime_block() {
+    let src = r#"
+        fn main() {
+            comptime {
+                return;
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::ResolverError(ResolverError::ReturnInComptimeBlock { .. })
+    ));
+}
+
+#[test]
+fn while_condition_must_be_bool() {
+    let src = r#"
+        unconstrained fn main() {
+            let i = 1;
+            while i {}
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+    ));
+}
+
 // Regression for #2540
 #[test]
 fn for_loop_over_array() {
@@ -1963,6 +2186,166 @@ fn quote_code_fragments() {
     assert!(matches!(&errors[0].0, CompilationError::InterpreterError(FailingConstraint { .. })));
 }
 
+#[test]
+fn comptime_diagnostics_point_to_quoted_code() {
+    // Quoted tokens keep the spans of the code they were quoted from, so errors
+    // issued on them point there rather than at the comptime code issuing them.
+    let src = r#"
+        #[builtin(quoted_error)]
+        fn error<let N: u32>(_quoted: Quoted, _message: str<N>) {}
+
+        #[builtin(struct_def_fields)]
+        fn fields(_s: StructDefinition) -> [(Quoted, Type)] {}
+
+        #[check_fields]
+        struct Foo {
+            bar: Field,
+        }
+
+        comptime fn check_fields(s: StructDefinition) {
+            let (name, _) = fields(s)[0];
+            error(name, "field error");
+        }
+
+        fn main() {
+            comptime {
+                error(quote { 1 + 2 }, "quote error");
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+
+    // Defining builtins outside of the stdlib is also an error, which we ignore here
+    let mut diagnostics: Vec<_> = errors
+        .into_iter()
+        .filter_map(|(error, _)| match error {
+            CompilationError::InterpreterError(InterpreterError::ComptimeError {
+                message,
+                location,
+            }) => {
+                let span = location.span;
+                Some((message, &src[span.start() as usize..span.end() as usize]))
+            }
+            _ => None,
+        })
+        .collect();
+    diagnostics.sort();
+
+    assert_eq!(
+        diagnostics,
+        vec![("field error".to_string(), "bar"), ("quote error".to_string(), "1 + 2")]
+    );
+}
+
+#[test]
+fn comptime_diagnostics_point_to_quoted_code_in_other_files() {
+    // The macro lives in a different file than the struct it is applied to, so each
+    // diagnostic must carry the file its quoted code came from. Code spliced together
+    // from several files has no single file and falls back to the argument's location.
+    let main = r#"
+        mod macros;
+
+        #[macros::check_fields]
+        struct Foo {
+            bar: Field,
+        }
+
+        fn main() {}
+    "#;
+    let macros = r#"
+        #[builtin(quoted_error)]
+        fn error<let N: u32>(_quoted: Quoted, _message: str<N>) {}
+
+        #[builtin(struct_def_fields)]
+        fn fields(_s: StructDefinition) -> [(Quoted, Type)] {}
+
+        pub comptime fn check_fields(s: StructDefinition) {
+            let (name, _) = fields(s)[0];
+            error(name, "field error");
+            error(quote { 1 + 2 }, "quote error");
+            error(quote { $name + 2 }, "spliced error");
+        }
+    "#;
+    let (context, errors) = get_program_in_files(&[("/main.nr", main), ("/macros.nr", macros)]);
+    let file_manager = &context.file_manager;
+    let main_file = file_manager.name_to_id("/main.nr".into()).unwrap();
+    let macros_file = file_manager.name_to_id("/macros.nr".into()).unwrap();
+
+    // Defining builtins outside of the stdlib is also an error, which we ignore here
+    let mut diagnostics: Vec<_> = errors
+        .into_iter()
+        .filter_map(|(error, _)| match error {
+            CompilationError::InterpreterError(InterpreterError::ComptimeError {
+                message,
+                location,
+            }) => {
+                let source = file_manager.fetch_file(location.file).unwrap();
+                let span = location.span;
+                let text = &source[span.start() as usize..span.end() as usize];
+                Some((message, location.file, text))
+            }
+            _ => None,
+        })
+        .collect();
+    diagnostics.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            ("field error".to_string(), main_file, "bar"),
+            ("quote error".to_string(), macros_file, "1 + 2"),
+            ("spliced error".to_string(), macros_file, "quote { $name + 2 }"),
+        ]
+    );
+}
+
+#[test]
+fn comptime_expr_constructors_error_on_invalid_parts() {
+    // Building an expression from parts that can't go in that position is an error
+    // rather than a panic, e.g. a `let` used as an operand.
+    let src = r#"
+        struct Option<T> {
+            _is_some: bool,
+            _value: T,
+        }
+
+        #[builtin(quoted_as_expr)]
+        fn as_expr(_quoted: Quoted) -> Option<Expr> {}
+
+        #[builtin(expr_new_index)]
+        fn new_index(_object: Expr, _index: Expr) -> Expr {}
+
+        #[builtin(expr_new_assign)]
+        fn new_assign(_lhs: Expr, _rhs: Expr) -> Expr {}
+
+        fn foo() {
+            comptime {
+                let array = as_expr(quote { array })._value;
+                let index = as_expr(quote { let x = 1 })._value;
+                let _ = new_index(array, index);
+            }
+        }
+
+        fn main() {
+            comptime {
+                let lhs = as_expr(quote { 1 + 2 })._value;
+                let rhs = as_expr(quote { 3 })._value;
+                let _ = new_assign(lhs, rhs);
+            }
+        }
+    "#;
+    let errors = get_program_errors(src);
+
+    assert!(errors.iter().any(|(error, _)| matches!(
+        error,
+        CompilationError::InterpreterError(InterpreterError::StatementUsedAsExpression { .. })
+    )));
+    assert!(errors.iter().any(|(error, _)| matches!(
+        error,
+        CompilationError::InterpreterError(InterpreterError::ExpressionNotAssignable { .. })
+    )));
+}
+
 #[test]
 fn impl_stricter_than_trait_no_trait_method_constraints() {
     // This test ensures that the error we get from the where clause on the trait impl method
@@ -3198,6 +3581,107 @@ fn as_trait_path_syntax_no_impl() {
     assert!(matches!(&errors[0].0, TypeError(TypeCheckError::NoMatchingImplFound { .. })));
 }
 
+#[test]
+fn impl_trait_return_type_used_before_definition() {
+    let src = r#"
+    fn main() {
+        let wrapped = wrap(1);
+        assert(wrapped.value() == 1);
+        assert(add_one(wrap(2)).value() == 3);
+    }
+
+    trait Value {
+        fn value(self) -> Field;
+    }
+
+    struct Wrapper<T, Env> {
+        inner: T,
+        f: fn[Env](Field) -> Field,
+    }
+
+    impl<T, Env> Value for Wrapper<T, Env> where T: Value {
+        fn value(self) -> Field {
+            (self.f)(self.inner.value())
+        }
+    }
+
+    impl Value for Field {
+        fn value(self) -> Field {
+            self
+        }
+    }
+
+    fn wrap(x: Field) -> impl Value {
+        x
+    }
+
+    fn add_one(inner: impl Value) -> impl Value {
+        let one = 1;
+        Wrapper { inner, f: |x| x + one }
+    }
+    "#;
+    assert_no_errors(src);
+}
+
+#[test]
+fn impl_trait_return_type_must_implement_trait() {
+    let src = r#"
+    trait Value {
+        fn value(self) -> Field;
+    }
+
+    fn foo() -> impl Value {
+        true
+    }
+
+    fn main() {
+        let _ = foo();
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    use CompilationError::TypeError;
+    assert!(matches!(&errors[0].0, TypeError(TypeCheckError::NoMatchingImplFound { .. })));
+}
+
+#[test]
+fn impl_trait_return_type_requires_a_single_type() {
+    let src = r#"
+    trait Value {
+        fn value(self) -> Field;
+    }
+
+    impl Value for Field {
+        fn value(self) -> Field {
+            self
+        }
+    }
+
+    impl Value for bool {
+        fn value(self) -> Field {
+            self as Field
+        }
+    }
+
+    unconstrained fn main(x: Field) -> pub Field {
+        foo(x).value()
+    }
+
+    unconstrained fn foo(x: Field) -> impl Value {
+        if x == 0 {
+            return false;
+        }
+        x
+    }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    use CompilationError::TypeError;
+    assert!(matches!(&errors[0].0, TypeError(TypeCheckError::TypeMismatchWithSource { .. })));
+}
+
 #[test]
 fn errors_on_unused_import() {
     let src = r#"
@@ -3231,3 +3715,330 @@ fn errors_on_unused_import() {
 
     assert_eq!(ident.to_string(), "bar");
 }
+
+#[test]
+fn enum_with_unit_variants_and_match() {
+    let src = r#"
+    enum Direction {
+        North,
+        South,
+        East,
+        West,
+    }
+
+    fn turn(direction: Direction) -> Direction {
+        match direction {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    fn main() {
+        let _ = turn(Direction::North);
+    }
+    "#;
+    assert_no_errors(src);
+}
+
+#[test]
+fn errors_on_non_exhaustive_match_on_enum() {
+    let src = r#"
+    enum Direction {
+        North,
+        South,
+        East,
+        West,
+    }
+
+    fn main() {
+        let _ = match Direction::North {
+            Direction::North => 1,
+            Direction::East => 2,
+            Direction::South => 3,
+        };
+    }
+    "#;
+
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    let CompilationError::TypeError(TypeCheckError::NonExhaustiveMatch { missing, .. }) =
+        &errors[0].0
+    else {
+        panic!("Expected a non-exhaustive match error, got {:?}", errors[0].0);
+    };
+
+    assert_eq!(missing, "Direction::West");
+}
+
+#[test]
+fn errors_on_non_exhaustive_match_on_tuple() {
+    let src = r#"
+    fn main() {
+        let a = true;
+        let b = false;
+        let _ = match (a, b) {
+            (true, _) => 1,
+            (false, true) => 2,
+        };
+    }
+    "#;
+
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    let CompilationError::TypeError(TypeCheckError::NonExhaustiveMatch { missing, .. }) =
+        &errors[0].0
+    else {
+        panic!("Expected a non-exhaustive match error, got {:?}", errors[0].0);
+    };
+
+    assert_eq!(missing, "(false, false)");
+}
+
+#[test]
+fn warns_on_unreachable_match_rule() {
+    let src = r#"
+    fn main() {
+        let x = true;
+        let _ = match x {
+            true => 1,
+            false => 2,
+            _ => 3,
+        };
+    }
+    "#;
+
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::TypeError(TypeCheckError::UnreachableMatchRule { .. })
+    ));
+}
+
+#[test]
+fn errors_on_struct_syntax_on_enum() {
+    let src = r#"
+    enum Direction {
+        North,
+        South,
+    }
+
+    fn main() {
+        let _ = Direction {};
+    }
+    "#;
+
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::TypeError(TypeCheckError::StructSyntaxOnEnum { .. })
+    ));
+}
+
+#[test]
+fn errors_on_match_pattern_which_is_not_a_variant() {
+    let src = r#"
+    enum Direction {
+        North,
+        South,
+    }
+
+    fn main() {
+        let _ = match Direction::North {
+            Direction::Up => 1,
+        };
+    }
+    "#;
+
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    let CompilationError::TypeError(TypeCheckError::ExpectedEnumVariant { path, .. }) =
+        &errors[0].0
+    else {
+        panic!("Expected an enum variant error, got {:?}", errors[0].0);
+    };
+
+    assert_eq!(path, "Direction::Up");
+}
+
+#[test]
+fn errors_on_duplicate_enum_variant() {
+    let src = r#"
+    enum Direction {
+        North,
+        North,
+    }
+
+    fn main() {}
+    "#;
+
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    assert!(matches!(
+        errors[0].0,
+        CompilationError::DefinitionError(DefCollectorErrorKind::DuplicateEnumVariant { .. })
+    ));
+}
+
+#[test]
+fn errors_on_enum_in_entry_point() {
+    let src = r#"
+    enum Direction {
+        North,
+        South,
+    }
+
+    struct Wrapper {
+        direction: Direction,
+    }
+
+    fn main(_direction: Direction, _wrapper: [Wrapper; 2]) -> pub Direction {
+        Direction::North
+    }
+    "#;
+
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 3);
+
+    for (error, _) in errors {
+        assert!(
+            matches!(error, CompilationError::TypeError(TypeCheckError::EnumInEntryPoint { .. })),
+            "Expected an enum in entry point error, got {error:?}"
+        );
+    }
+}
+
+#[test]
+fn enum_printable_type_hides_lowered_fields() {
+    let src = r#"
+    enum Shape {
+        Circle(Field),
+        Empty,
+    }
+
+    fn area(_shape: Shape) {}
+
+    fn main() {
+        area(Shape::Empty);
+    }
+    "#;
+
+    let (_, context, errors) = get_program(src);
+    assert_eq!(errors.len(), 0, "Expected no errors, got {errors:?}");
+
+    let area = context.def_interner.find_function("area").unwrap();
+    let (_, typ, _) = &context.def_interner.function_meta(&area).parameters.0[0];
+
+    let expected = PrintableType::Enum {
+        name: "Shape".to_string(),
+        variants: vec![
+            ("Circle".to_string(), PrintableType::Tuple { types: vec![PrintableType::Field] }),
+            ("Empty".to_string(), PrintableType::Unit),
+        ],
+    };
+    assert_eq!(PrintableType::from(typ), expected);
+}
+
+#[test]
+fn errors_on_private_struct_field_access() {
+    let src = r#"
+    mod foo {
+        struct Foo {
+            x: Field,
+            pub y: Field,
+        }
+
+        pub fn new() -> Foo {
+            Foo { x: 1, y: 2 }
+        }
+    }
+
+    fn main() {
+        let value = foo::new();
+        let _ = value.y;
+        let _ = value.x;
+    }
+    "#;
+
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 1);
+
+    let CompilationError::ResolverError(ResolverError::PrivateStructField {
+        field,
+        struct_definition,
+    }) = &errors[0].0
+    else {
+        panic!("Expected a private field error, got {:?}", errors[0].0);
+    };
+
+    assert_eq!(field.to_string(), "x");
+    assert_eq!(struct_definition.to_string(), "Foo");
+    assert!(noirc_errors::CustomDiagnostic::from(&errors[0].0).is_error());
+}
+
+#[test]
+fn errors_on_private_struct_field_in_constructor_and_pattern() {
+    let src = r#"
+    mod foo {
+        struct Foo {
+            pub x: Field,
+            y: Field,
+        }
+    }
+
+    fn main() {
+        let value = foo::Foo { x: 1, y: 2 };
+        let foo::Foo { x: _, y: _ } = value;
+    }
+    "#;
+
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 2);
+
+    for (error, _) in errors {
+        let CompilationError::ResolverError(ResolverError::PrivateStructField { field, .. }) =
+            &error
+        else {
+            panic!("Expected a private field error, got {:?}", error);
+        };
+        assert_eq!(field.to_string(), "y");
+    }
+}
+
+#[test]
+fn allows_struct_field_access_from_defining_and_child_modules() {
+    let src = r#"
+    struct Foo {
+        x: Field,
+        pub(crate) y: Field,
+    }
+
+    mod bar {
+        fn baz(foo: crate::Foo) -> Field {
+            let crate::Foo { x, y } = foo;
+            x + y + foo.x
+        }
+    }
+
+    mod qux {
+        fn quux(foo: crate::Foo) -> Field {
+            foo.y
+        }
+    }
+
+    fn main() {
+        let foo = Foo { x: 1, y: 2 };
+        let _ = foo.x;
+    }
+    "#;
+    assert_no_errors(src);
+}
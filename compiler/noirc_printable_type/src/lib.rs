@@ -33,6 +33,13 @@ pub enum PrintableType {
         name: String,
         fields: Vec<(String, PrintableType)>,
     },
+    /// An enum is laid out as the index of its active variant followed by the arguments of
+    /// each variant in turn. The type of each variant is a tuple of its arguments, or unit
+    /// for variants declared without parentheses.
+    Enum {
+        name: String,
+        variants: Vec<(String, PrintableType)>,
+    },
     String {
         length: u32,
     },
@@ -228,6 +235,20 @@ fn to_string<F: AcirField>(value: &PrintableValue<F>, typ: &PrintableType) -> Op
             output.push_str(" }");
         }
 
+        (PrintableValue::Vec { array_elements, .. }, PrintableType::Enum { name, variants }) => {
+            let PrintableValue::Field(tag) = &array_elements[0] else {
+                return None;
+            };
+            let index = tag.to_u128() as usize;
+            let (variant, typ) = variants.get(index)?;
+
+            output.push_str(&format!("{name}::{variant}"));
+            if *typ != PrintableType::Unit {
+                let arguments = array_elements[index + 1].clone();
+                output.push_str(&PrintableValueDisplay::Plain(arguments, typ.clone()).to_string());
+            }
+        }
+
         (PrintableValue::Vec { array_elements, .. }, PrintableType::Tuple { types }) => {
             output.push('(');
             let mut elems = array_elements.iter().zip(types).peekable();
@@ -360,6 +381,15 @@ pub fn decode_value<F: AcirField>(
 
             PrintableValue::Struct(struct_map)
         }
+        PrintableType::Enum { variants, .. } => {
+            let tag = PrintableValue::Field(field_iterator.next().unwrap());
+            let mut array_elements = vec![tag];
+            for (_, typ) in variants {
+                array_elements.push(decode_value(field_iterator, typ));
+            }
+
+            PrintableValue::Vec { array_elements, is_slice: false }
+        }
         PrintableType::Function { env, .. } => {
             let field_element = field_iterator.next().unwrap();
             let func_ref = PrintableValue::Field(field_element);
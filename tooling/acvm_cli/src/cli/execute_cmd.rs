@@ -3,12 +3,14 @@ use std::io::{self, Write};
 use acir::circuit::Program;
 use acir::native_types::{WitnessMap, WitnessStack};
 use acir::FieldElement;
+use acvm::pwg::ExecutionStats;
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
 
 use crate::cli::fs::inputs::{read_bytecode_from_file, read_inputs_from_file};
 use crate::errors::CliError;
-use nargo::ops::{execute_program, DefaultForeignCallExecutor};
+use nargo::ops::{execute_program, execute_program_with_stats, DefaultForeignCallExecutor};
+use nargo::witness::WitnessFormat;
 
 use super::fs::witness::{create_output_witness_string, save_witness_to_dir};
 
@@ -19,6 +21,10 @@ pub(crate) struct ExecuteCommand {
     #[clap(long, short)]
     output_witness: Option<String>,
 
+    /// The format to write the output witness in: bincode, json or toml
+    #[clap(long, default_value = "bincode")]
+    witness_format: WitnessFormat,
+
     /// The name of the toml file which contains the input witness map
     #[clap(long, short)]
     input_witness: String,
@@ -34,12 +40,20 @@ pub(crate) struct ExecuteCommand {
     /// Set to print output witness to stdout
     #[clap(long, short, action)]
     print: bool,
+
+    /// Set to print statistics about the opcodes, black box functions and foreign calls executed to stderr
+    #[clap(long, action)]
+    stats: bool,
 }
 
 fn run_command(args: ExecuteCommand) -> Result<String, CliError> {
     let bytecode = read_bytecode_from_file(&args.working_directory, &args.bytecode)?;
     let circuit_inputs = read_inputs_from_file(&args.working_directory, &args.input_witness)?;
-    let output_witness = execute_program_from_witness(circuit_inputs, &bytecode)?;
+    let (output_witness, stats) =
+        execute_program_from_witness(circuit_inputs, &bytecode, args.stats)?;
+    if let Some(stats) = stats {
+        eprintln!("{stats}");
+    }
     assert_eq!(output_witness.length(), 1, "ACVM CLI only supports a witness stack of size 1");
     let output_witness_string = create_output_witness_string(
         &output_witness.peek().expect("Should have a witness stack item").witness,
@@ -49,6 +63,7 @@ fn run_command(args: ExecuteCommand) -> Result<String, CliError> {
             output_witness,
             &args.output_witness.unwrap(),
             &args.working_directory,
+            args.witness_format,
         )?;
     }
     Ok(output_witness_string)
@@ -66,14 +81,22 @@ pub(crate) fn run(args: ExecuteCommand) -> Result<String, CliError> {
 pub(crate) fn execute_program_from_witness(
     inputs_map: WitnessMap<FieldElement>,
     bytecode: &[u8],
-) -> Result<WitnessStack<FieldElement>, CliError> {
+    collect_stats: bool,
+) -> Result<(WitnessStack<FieldElement>, Option<ExecutionStats>), CliError> {
     let program: Program<FieldElement> = Program::deserialize_program(bytecode)
         .map_err(|_| CliError::CircuitDeserializationError())?;
-    execute_program(
-        &program,
-        inputs_map,
-        &Bn254BlackBoxSolver,
-        &mut DefaultForeignCallExecutor::new(true, None, None, None),
-    )
-    .map_err(CliError::CircuitExecutionError)
+    let mut foreign_call_executor = DefaultForeignCallExecutor::new(true, None, None, None);
+    let result = if collect_stats {
+        execute_program_with_stats(
+            &program,
+            inputs_map,
+            &Bn254BlackBoxSolver,
+            &mut foreign_call_executor,
+        )
+        .map(|(witness_stack, stats)| (witness_stack, Some(stats)))
+    } else {
+        execute_program(&program, inputs_map, &Bn254BlackBoxSolver, &mut foreign_call_executor)
+            .map(|witness_stack| (witness_stack, None))
+    };
+    result.map_err(CliError::CircuitExecutionError)
 }
@@ -0,0 +1,19 @@
+use std::path::{Path, PathBuf};
+
+use crate::errors::FilesystemError;
+
+/// Writes the serialized bytecode to the file with the given name in `bytecode_dir`
+pub(crate) fn save_bytecode_to_dir<P: AsRef<Path>>(
+    bytecode: &[u8],
+    bytecode_name: &str,
+    bytecode_dir: P,
+) -> Result<PathBuf, FilesystemError> {
+    std::fs::create_dir_all(bytecode_dir.as_ref())
+        .map_err(|_| FilesystemError::OutputBytecodeCreationFailed(bytecode_name.to_string()))?;
+    let bytecode_path = bytecode_dir.as_ref().join(bytecode_name);
+
+    std::fs::write(&bytecode_path, bytecode)
+        .map_err(|_| FilesystemError::OutputBytecodeCreationFailed(bytecode_name.to_string()))?;
+
+    Ok(bytecode_path)
+}
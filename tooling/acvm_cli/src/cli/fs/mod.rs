@@ -1,2 +1,3 @@
+pub(super) mod bytecode;
 pub(super) mod inputs;
 pub(super) mod witness;
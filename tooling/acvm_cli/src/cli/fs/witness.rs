@@ -10,6 +10,7 @@ use acvm::acir::{
     native_types::{WitnessMap, WitnessStack},
     AcirField,
 };
+use nargo::witness::{serialize_witness_stack, WitnessFormat};
 
 use crate::errors::{CliError, FilesystemError};
 
@@ -50,12 +51,12 @@ pub(crate) fn save_witness_to_dir<P: AsRef<Path>>(
     witnesses: WitnessStack<FieldElement>,
     witness_name: &str,
     witness_dir: P,
+    format: WitnessFormat,
 ) -> Result<PathBuf, FilesystemError> {
     create_named_dir(witness_dir.as_ref(), "witness");
-    let witness_path = witness_dir.as_ref().join(witness_name).with_extension("gz");
+    let witness_path = witness_dir.as_ref().join(witness_name).with_extension(format.extension());
 
-    let buf: Vec<u8> = witnesses
-        .try_into()
+    let buf = serialize_witness_stack(witnesses, format, None)
         .map_err(|_op| FilesystemError::OutputWitnessCreationFailed(witness_name.to_string()))?;
     write_to_file(buf.as_slice(), &witness_path);
 
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use acir::circuit::{Circuit, Opcode, Program};
+use acir::{AcirField, FieldElement};
+use clap::Args;
+
+use crate::cli::fs::inputs::read_bytecode_from_file;
+use crate::errors::CliError;
+
+/// Prints a summary of the circuits and Brillig functions in a bytecode file
+#[derive(Debug, Clone, Args)]
+pub(crate) struct InspectCommand {
+    /// The name of the binary file containing circuit bytecode
+    #[clap(long, short)]
+    bytecode: String,
+
+    /// The working directory
+    #[clap(long, short)]
+    working_directory: String,
+
+    /// Set to also print every opcode
+    #[clap(long, action)]
+    print_opcodes: bool,
+}
+
+pub(crate) fn run(args: InspectCommand) -> Result<String, CliError> {
+    let bytecode = read_bytecode_from_file(&args.working_directory, &args.bytecode)?;
+    let program: Program<FieldElement> = Program::deserialize_program(&bytecode)
+        .map_err(|_| CliError::CircuitDeserializationError())?;
+
+    let output = inspect_program(&program, args.print_opcodes);
+    print!("{output}");
+    Ok(output)
+}
+
+fn inspect_program<F: AcirField>(program: &Program<F>, print_opcodes: bool) -> String {
+    let mut output = String::new();
+    for (func_index, circuit) in program.functions.iter().enumerate() {
+        writeln!(output, "func {func_index}").unwrap();
+        write_circuit_summary(&mut output, circuit);
+        if print_opcodes {
+            writeln!(output, "  opcodes:").unwrap();
+            for opcode in &circuit.opcodes {
+                writeln!(output, "    {opcode}").unwrap();
+            }
+        }
+    }
+    for (func_index, function) in program.unconstrained_functions.iter().enumerate() {
+        writeln!(output, "unconstrained func {func_index}").unwrap();
+        writeln!(output, "  brillig opcodes: {}", function.bytecode.len()).unwrap();
+        if print_opcodes {
+            for (index, opcode) in function.bytecode.iter().enumerate() {
+                writeln!(output, "    {index}: {opcode:?}").unwrap();
+            }
+        }
+    }
+    output
+}
+
+fn write_circuit_summary<F: AcirField>(output: &mut String, circuit: &Circuit<F>) {
+    let format_witnesses = |indices: Vec<u32>| {
+        indices.iter().map(|index| format!("w{index}")).collect::<Vec<_>>().join(", ")
+    };
+
+    writeln!(output, "  current witness index: {}", circuit.current_witness_index).unwrap();
+    writeln!(output, "  expression width: {:?}", circuit.expression_width).unwrap();
+    writeln!(
+        output,
+        "  private parameters: [{}]",
+        format_witnesses(circuit.private_parameters.iter().map(|w| w.witness_index()).collect())
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "  public parameters: [{}]",
+        format_witnesses(circuit.public_parameters.indices())
+    )
+    .unwrap();
+    writeln!(output, "  return values: [{}]", format_witnesses(circuit.return_values.indices()))
+        .unwrap();
+    writeln!(output, "  opcodes: {}", circuit.opcodes.len()).unwrap();
+    for (kind, count) in opcode_histogram(&circuit.opcodes) {
+        writeln!(output, "    {kind:<32} {count:>8}").unwrap();
+    }
+}
+
+/// Counts the opcodes of each kind, breaking black box function calls down by function.
+fn opcode_histogram<F>(opcodes: &[Opcode<F>]) -> BTreeMap<String, usize> {
+    let mut histogram = BTreeMap::new();
+    for opcode in opcodes {
+        let kind = match opcode {
+            Opcode::AssertZero(_) => "assert_zero".to_string(),
+            Opcode::BlackBoxFuncCall(call) => {
+                format!("black_box::{}", call.get_black_box_func().name())
+            }
+            Opcode::Directive(_) => "directive".to_string(),
+            Opcode::MemoryInit { .. } => "memory_init".to_string(),
+            Opcode::MemoryOp { .. } => "memory_op".to_string(),
+            Opcode::BrilligCall { .. } => "brillig_call".to_string(),
+            Opcode::Call { .. } => "call".to_string(),
+        };
+        *histogram.entry(kind).or_default() += 1;
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acir::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            Opcode,
+        },
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+
+    use super::opcode_histogram;
+
+    #[test]
+    fn histogram_splits_black_box_functions() {
+        let range = |witness| {
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                input: FunctionInput::witness(Witness(witness), 8),
+            })
+        };
+        let opcodes: Vec<Opcode<FieldElement>> =
+            vec![Opcode::AssertZero(Expression::default()), range(0), range(1)];
+
+        assert_eq!(
+            opcode_histogram(&opcodes),
+            BTreeMap::from([("assert_zero".to_string(), 1), ("black_box::range".to_string(), 2)])
+        );
+    }
+}
@@ -4,6 +4,9 @@ use const_format::formatcp;
 
 mod execute_cmd;
 mod fs;
+mod inspect_cmd;
+mod optimize_cmd;
+mod validate_cmd;
 
 const ACVM_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -20,6 +23,9 @@ struct ACVMCli {
 #[derive(Subcommand, Clone, Debug)]
 enum ACVMCommand {
     Execute(execute_cmd::ExecuteCommand),
+    Inspect(inspect_cmd::InspectCommand),
+    Optimize(optimize_cmd::OptimizeCommand),
+    Validate(validate_cmd::ValidateCommand),
 }
 
 #[cfg(not(feature = "codegen-docs"))]
@@ -28,6 +34,9 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
 
     match command {
         ACVMCommand::Execute(args) => execute_cmd::run(args),
+        ACVMCommand::Inspect(args) => inspect_cmd::run(args),
+        ACVMCommand::Optimize(args) => optimize_cmd::run(args),
+        ACVMCommand::Validate(args) => validate_cmd::run(args),
     }?;
 
     Ok(())
@@ -0,0 +1,141 @@
+use acir::circuit::serialization::SerializationFormat;
+use acir::circuit::{ExpressionWidth, Program};
+use acir::FieldElement;
+use acvm::compiler::{compile_to_gate_shape, compile_with_brillig, BoundedGateShape, CostReport};
+use clap::Args;
+
+use crate::cli::fs::bytecode::save_bytecode_to_dir;
+use crate::cli::fs::inputs::read_bytecode_from_file;
+use crate::errors::CliError;
+
+/// Runs the ACVM optimizations and transformations on a bytecode file
+#[derive(Debug, Clone, Args)]
+#[clap(visible_alias = "transform")]
+pub(crate) struct OptimizeCommand {
+    /// The name of the binary file containing circuit bytecode
+    #[clap(long, short)]
+    bytecode: String,
+
+    /// The working directory
+    #[clap(long, short)]
+    working_directory: String,
+
+    /// The name of the file to write the optimized bytecode to, relative to the working directory
+    #[clap(long, short)]
+    output_bytecode: String,
+
+    /// The expression width to transform the circuits to. A width of 0 leaves expressions unbounded
+    #[clap(long, value_parser = parse_expression_width, default_value = "4")]
+    expression_width: ExpressionWidth,
+
+    /// The gate shape of the target proving system, as `<linear terms>:<mul terms>`.
+    /// Takes precedence over the expression width and reports the estimated proving cost of each function
+    #[clap(long, value_parser = parse_gate_shape)]
+    gate_shape: Option<BoundedGateShape>,
+
+    /// The format to serialize the optimized bytecode in with a versioned envelope: bincode, msgpack or json.
+    /// If not set the bytecode is written as bincode without an envelope, as read by backends
+    #[clap(long)]
+    serialization_format: Option<SerializationFormat>,
+}
+
+fn parse_expression_width(input: &str) -> Result<ExpressionWidth, std::io::Error> {
+    use std::io::{Error, ErrorKind};
+    let width = input
+        .parse::<usize>()
+        .map_err(|err| Error::new(ErrorKind::InvalidInput, err.to_string()))?;
+
+    match width {
+        0 => Ok(ExpressionWidth::Unbounded),
+        _ => Ok(ExpressionWidth::Bounded { width }),
+    }
+}
+
+fn parse_gate_shape(input: &str) -> Result<BoundedGateShape, std::io::Error> {
+    use std::io::{Error, ErrorKind};
+    let invalid = || {
+        let message = format!("Invalid gate shape `{input}`, expected e.g. `4:1`");
+        Error::new(ErrorKind::InvalidInput, message)
+    };
+
+    let Some((linear_terms, mul_terms)) = input.split_once(':') else {
+        return Err(invalid());
+    };
+    let (Ok(linear_terms), Ok(mul_terms)) = (linear_terms.parse(), mul_terms.parse()) else {
+        return Err(invalid());
+    };
+
+    // The CSAT transformer needs room for at least two witnesses and an intermediate variable
+    if linear_terms < 3 || mul_terms == 0 {
+        return Err(invalid());
+    }
+    Ok(BoundedGateShape { linear_terms, mul_terms })
+}
+
+pub(crate) fn run(args: OptimizeCommand) -> Result<String, CliError> {
+    let bytecode = read_bytecode_from_file(&args.working_directory, &args.bytecode)?;
+    let program: Program<FieldElement> = Program::deserialize_program(&bytecode)
+        .map_err(|_| CliError::CircuitDeserializationError())?;
+
+    let (optimized_program, cost_reports) = match &args.gate_shape {
+        Some(gate_shape) => optimize_program_to_gate_shape(program, gate_shape),
+        None => (optimize_program(program, args.expression_width), Vec::new()),
+    };
+
+    let mut summary = String::new();
+    for (func_index, circuit) in optimized_program.functions.iter().enumerate() {
+        summary += &format!(
+            "func {func_index}: {} opcodes, current witness index {}\n",
+            circuit.opcodes.len(),
+            circuit.current_witness_index
+        );
+        if let Some(cost_report) = cost_reports.get(func_index) {
+            summary += &format!("{cost_report}\n");
+        }
+    }
+
+    let optimized_bytecode = match args.serialization_format {
+        Some(format) => Program::serialize_program_with_format(&optimized_program, format)
+            .map_err(CliError::CircuitSerializationError)?,
+        None => Program::serialize_program(&optimized_program),
+    };
+    let bytecode_path =
+        save_bytecode_to_dir(&optimized_bytecode, &args.output_bytecode, &args.working_directory)?;
+    summary += &format!("Optimized bytecode saved to {}\n", bytecode_path.display());
+
+    print!("{summary}");
+    Ok(summary)
+}
+
+fn optimize_program(
+    mut program: Program<FieldElement>,
+    expression_width: ExpressionWidth,
+) -> Program<FieldElement> {
+    let functions = std::mem::take(&mut program.functions);
+    program.functions = functions
+        .into_iter()
+        .map(|circuit| {
+            let (circuit, _) =
+                compile_with_brillig(circuit, expression_width, &program.unconstrained_functions);
+            circuit
+        })
+        .collect();
+    program
+}
+
+fn optimize_program_to_gate_shape(
+    mut program: Program<FieldElement>,
+    gate_shape: &BoundedGateShape,
+) -> (Program<FieldElement>, Vec<CostReport>) {
+    let functions = std::mem::take(&mut program.functions);
+    let (functions, cost_reports) = functions
+        .into_iter()
+        .map(|circuit| {
+            let (circuit, _, cost_report) =
+                compile_to_gate_shape(circuit, gate_shape, &program.unconstrained_functions);
+            (circuit, cost_report)
+        })
+        .unzip();
+    program.functions = functions;
+    (program, cost_reports)
+}
@@ -0,0 +1,328 @@
+use std::collections::BTreeSet;
+
+use acir::circuit::brillig::{BrilligInputs, BrilligOutputs};
+use acir::circuit::directives::Directive;
+use acir::circuit::opcodes::{BlockId, ConstantOrWitnessEnum};
+use acir::circuit::{AssertionPayload, Circuit, ExpressionOrMemory, Opcode, Program};
+use acir::native_types::{Expression, Witness};
+use acir::{AcirField, FieldElement};
+use clap::Args;
+
+use crate::cli::fs::inputs::read_bytecode_from_file;
+use crate::errors::CliError;
+
+/// Checks the structural invariants of the circuits in a bytecode file
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ValidateCommand {
+    /// The name of the binary file containing circuit bytecode
+    #[clap(long, short)]
+    bytecode: String,
+
+    /// The working directory
+    #[clap(long, short)]
+    working_directory: String,
+}
+
+pub(crate) fn run(args: ValidateCommand) -> Result<String, CliError> {
+    let bytecode = read_bytecode_from_file(&args.working_directory, &args.bytecode)?;
+    let program: Program<FieldElement> = Program::deserialize_program(&bytecode)
+        .map_err(|_| CliError::CircuitDeserializationError())?;
+
+    let errors = validate_program(&program);
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{error}");
+        }
+        return Err(CliError::InvalidBytecode(errors.len()));
+    }
+
+    let output = "Bytecode is valid".to_string();
+    println!("{output}");
+    Ok(output)
+}
+
+/// Returns a description of every structural invariant which `program` violates.
+fn validate_program<F: AcirField>(program: &Program<F>) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (func_index, circuit) in program.functions.iter().enumerate() {
+        let mut report = |location: String, message: String| {
+            errors.push(format!("func {func_index}: {location}: {message}"));
+        };
+        validate_circuit(circuit, program, &mut report);
+    }
+    errors
+}
+
+fn validate_circuit<F: AcirField>(
+    circuit: &Circuit<F>,
+    program: &Program<F>,
+    report: &mut impl FnMut(String, String),
+) {
+    let check_witness =
+        |report: &mut dyn FnMut(String, String), location: &str, witness: Witness| {
+            if witness.0 > circuit.current_witness_index {
+                report(
+                    location.to_string(),
+                    format!(
+                        "witness w{} exceeds the current witness index {}",
+                        witness.0, circuit.current_witness_index
+                    ),
+                );
+            }
+        };
+
+    let parameters = circuit
+        .private_parameters
+        .iter()
+        .chain(&circuit.public_parameters.0)
+        .chain(&circuit.return_values.0);
+    for witness in parameters {
+        check_witness(&mut *report, "parameters", *witness);
+    }
+
+    let mut initialized_blocks = BTreeSet::new();
+    for (index, opcode) in circuit.opcodes.iter().enumerate() {
+        let location = format!("opcode {index}");
+
+        for witness in opcode_witnesses(opcode) {
+            check_witness(&mut *report, &location, witness);
+        }
+
+        let mut check_block = |block_id: &BlockId| {
+            if !initialized_blocks.contains(block_id) {
+                report(location.clone(), format!("memory block {} is not initialized", block_id.0));
+            }
+        };
+        match opcode {
+            Opcode::MemoryOp { block_id, .. } => check_block(block_id),
+            Opcode::BrilligCall { inputs, .. } => {
+                for input in inputs {
+                    if let BrilligInputs::MemoryArray(block_id) = input {
+                        check_block(block_id);
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        match opcode {
+            Opcode::MemoryInit { block_id, .. } => {
+                if !initialized_blocks.insert(*block_id) {
+                    report(
+                        location.clone(),
+                        format!("memory block {} is initialized more than once", block_id.0),
+                    );
+                }
+            }
+            Opcode::BrilligCall { id, .. } => {
+                if id.as_usize() >= program.unconstrained_functions.len() {
+                    report(location.clone(), format!("call to unknown Brillig function {id}"));
+                }
+            }
+            Opcode::Call { id, inputs, outputs, .. } => {
+                if id.as_usize() == 0 {
+                    report(location.clone(), "call to the main function".to_string());
+                }
+                match program.functions.get(id.as_usize()) {
+                    Some(callee) => {
+                        let num_parameters =
+                            callee.private_parameters.len() + callee.public_parameters.0.len();
+                        if inputs.len() != num_parameters {
+                            report(
+                                location.clone(),
+                                format!(
+                                    "call to func {id} has {} inputs but the function has {num_parameters} parameters",
+                                    inputs.len()
+                                ),
+                            );
+                        }
+                        if outputs.len() != callee.return_values.0.len() {
+                            report(
+                                location.clone(),
+                                format!(
+                                    "call to func {id} has {} outputs but the function has {} return values",
+                                    outputs.len(),
+                                    callee.return_values.0.len()
+                                ),
+                            );
+                        }
+                    }
+                    None => report(location.clone(), format!("call to unknown function {id}")),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    for (opcode_location, payload) in &circuit.assert_messages {
+        if let AssertionPayload::Dynamic(_, values) = payload {
+            for value in values {
+                if let ExpressionOrMemory::Memory(block_id) = value {
+                    if !initialized_blocks.contains(block_id) {
+                        report(
+                            format!("assertion payload at {opcode_location}"),
+                            format!("memory block {} is not initialized", block_id.0),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns every witness referenced by `opcode`.
+fn opcode_witnesses<F: AcirField>(opcode: &Opcode<F>) -> Vec<Witness> {
+    fn expression_witnesses<F>(expr: &Expression<F>) -> impl Iterator<Item = Witness> + '_ {
+        let mul_witnesses = expr.mul_terms.iter().flat_map(|(_, lhs, rhs)| [*lhs, *rhs]);
+        let linear_witnesses = expr.linear_combinations.iter().map(|(_, witness)| *witness);
+        mul_witnesses.chain(linear_witnesses)
+    }
+
+    let mut witnesses = Vec::new();
+    match opcode {
+        Opcode::AssertZero(expr) => witnesses.extend(expression_witnesses(expr)),
+        Opcode::BlackBoxFuncCall(call) => {
+            for input in call.get_inputs_vec() {
+                if let ConstantOrWitnessEnum::Witness(witness) = input.input {
+                    witnesses.push(witness);
+                }
+            }
+            witnesses.extend(call.get_outputs_vec());
+        }
+        Opcode::Directive(Directive::ToLeRadix { a, b, .. }) => {
+            witnesses.extend(expression_witnesses(a));
+            witnesses.extend(b);
+        }
+        Opcode::MemoryInit { init, .. } => witnesses.extend(init),
+        Opcode::MemoryOp { op, predicate, .. } => {
+            witnesses.extend(expression_witnesses(&op.operation));
+            witnesses.extend(expression_witnesses(&op.index));
+            witnesses.extend(expression_witnesses(&op.value));
+            if let Some(predicate) = predicate {
+                witnesses.extend(expression_witnesses(predicate));
+            }
+        }
+        Opcode::BrilligCall { inputs, outputs, predicate, .. } => {
+            for input in inputs {
+                match input {
+                    BrilligInputs::Single(expr) => witnesses.extend(expression_witnesses(expr)),
+                    BrilligInputs::Array(exprs) => {
+                        witnesses.extend(exprs.iter().flat_map(expression_witnesses));
+                    }
+                    BrilligInputs::MemoryArray(_) => (),
+                }
+            }
+            for output in outputs {
+                match output {
+                    BrilligOutputs::Simple(witness) => witnesses.push(*witness),
+                    BrilligOutputs::Array(outputs) => witnesses.extend(outputs),
+                }
+            }
+            if let Some(predicate) = predicate {
+                witnesses.extend(expression_witnesses(predicate));
+            }
+        }
+        Opcode::Call { inputs, outputs, predicate, .. } => {
+            witnesses.extend(inputs);
+            witnesses.extend(outputs);
+            if let Some(predicate) = predicate {
+                witnesses.extend(expression_witnesses(predicate));
+            }
+        }
+    }
+    witnesses
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir::{
+        circuit::{
+            brillig::BrilligFunctionId,
+            opcodes::{AcirFunctionId, BlockId, BlockType, MemOp},
+            Circuit, Opcode, Program,
+        },
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+
+    use super::validate_program;
+
+    #[test]
+    fn accepts_valid_program() {
+        let main = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![
+                Opcode::MemoryInit {
+                    block_id: BlockId(0),
+                    init: vec![Witness(0)],
+                    block_type: BlockType::Memory,
+                },
+                Opcode::MemoryOp {
+                    block_id: BlockId(0),
+                    op: MemOp::read_at_mem_index(Expression::zero(), Witness(1)),
+                    predicate: None,
+                },
+                Opcode::Call {
+                    id: AcirFunctionId(1),
+                    inputs: vec![Witness(1)],
+                    outputs: vec![Witness(2)],
+                    predicate: None,
+                },
+            ],
+            private_parameters: BTreeSet::from([Witness(0)]),
+            ..Circuit::default()
+        };
+        let callee = Circuit {
+            current_witness_index: 1,
+            private_parameters: BTreeSet::from([Witness(0)]),
+            return_values: acir::circuit::PublicInputs(BTreeSet::from([Witness(1)])),
+            ..Circuit::default()
+        };
+        let program: Program<FieldElement> =
+            Program { functions: vec![main, callee], unconstrained_functions: Vec::new() };
+
+        assert!(validate_program(&program).is_empty());
+    }
+
+    #[test]
+    fn reports_structural_errors() {
+        let main = Circuit {
+            current_witness_index: 1,
+            opcodes: vec![
+                Opcode::MemoryOp {
+                    block_id: BlockId(0),
+                    op: MemOp::read_at_mem_index(Expression::zero(), Witness(1)),
+                    predicate: None,
+                },
+                Opcode::AssertZero(Witness(5).into()),
+                Opcode::BrilligCall {
+                    id: BrilligFunctionId(0),
+                    inputs: Vec::new(),
+                    outputs: Vec::new(),
+                    predicate: None,
+                },
+                Opcode::Call {
+                    id: AcirFunctionId(3),
+                    inputs: Vec::new(),
+                    outputs: Vec::new(),
+                    predicate: None,
+                },
+            ],
+            ..Circuit::default()
+        };
+        let program: Program<FieldElement> =
+            Program { functions: vec![main], unconstrained_functions: Vec::new() };
+
+        assert_eq!(
+            validate_program(&program),
+            vec![
+                "func 0: opcode 0: memory block 0 is not initialized",
+                "func 0: opcode 1: witness w5 exceeds the current witness index 1",
+                "func 0: opcode 2: call to unknown Brillig function 0",
+                "func 0: opcode 3: call to unknown function 3",
+            ]
+        );
+    }
+}
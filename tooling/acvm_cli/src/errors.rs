@@ -1,4 +1,4 @@
-use acir::FieldElement;
+use acir::{circuit::serialization::SerializationError, FieldElement};
 use nargo::NargoError;
 use std::path::PathBuf;
 use thiserror::Error;
@@ -21,6 +21,9 @@ pub(crate) enum FilesystemError {
 
     #[error(" Error: failed to create output witness file {0}.")]
     OutputWitnessCreationFailed(String),
+
+    #[error(" Error: failed to create output bytecode file {0}.")]
+    OutputBytecodeCreationFailed(String),
 }
 
 #[derive(Debug, Error)]
@@ -33,6 +36,10 @@ pub(crate) enum CliError {
     #[error("Error: failed to deserialize circuit in ACVM CLI")]
     CircuitDeserializationError(),
 
+    /// Error related to circuit serialization
+    #[error("Error: failed to serialize circuit in ACVM CLI: {0}")]
+    CircuitSerializationError(SerializationError),
+
     /// Error related to circuit execution
     #[error(transparent)]
     CircuitExecutionError(#[from] NargoError<FieldElement>),
@@ -47,4 +54,8 @@ pub(crate) enum CliError {
 
     #[error(" Error: failed to serialize output witness.")]
     OutputWitnessSerializationFailed(),
+
+    /// Bytecode structural validation errors
+    #[error("Error: bytecode failed validation with {0} error(s)")]
+    InvalidBytecode(usize),
 }
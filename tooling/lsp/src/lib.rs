@@ -28,7 +28,7 @@ use lsp_types::{
     CodeLens,
 };
 use nargo::{
-    package::{Package, PackageType},
+    package::{ComptimeAllowlist, Package, PackageType},
     parse_all,
     workspace::Workspace,
 };
@@ -47,15 +47,17 @@ use noirc_frontend::{
 use rayon::prelude::*;
 
 use notifications::{
-    on_did_change_configuration, on_did_change_text_document, on_did_close_text_document,
-    on_did_open_text_document, on_did_save_text_document, on_exit, on_initialized,
+    on_did_change_configuration, on_did_change_text_document, on_did_change_watched_files,
+    on_did_close_text_document, on_did_open_text_document, on_did_save_text_document, on_exit,
+    on_initialized,
 };
 use requests::{
     on_code_lens_request, on_completion_request, on_document_symbol_request, on_formatting,
-    on_goto_declaration_request, on_goto_definition_request, on_goto_type_definition_request,
-    on_hover_request, on_initialize, on_inlay_hint_request, on_prepare_rename_request,
-    on_profile_run_request, on_references_request, on_rename_request, on_shutdown,
-    on_signature_help_request, on_test_run_request, on_tests_request, LspInitializationOptions,
+    on_function_sizes_computed, on_goto_declaration_request, on_goto_definition_request,
+    on_goto_type_definition_request, on_hover_request, on_initialize, on_inlay_hint_request,
+    on_prepare_rename_request, on_profile_run_request, on_references_request, on_rename_request,
+    on_shutdown, on_signature_help_request, on_test_run_request, on_tests_request,
+    FunctionSizeGeneration, FunctionSizesComputed, LspInitializationOptions,
 };
 use serde_json::Value as JsonValue;
 use thiserror::Error;
@@ -88,9 +90,14 @@ pub struct LspState {
     open_documents_count: usize,
     input_files: HashMap<String, String>,
     cached_lenses: HashMap<String, Vec<CodeLens>>,
+    cached_function_size_lenses: HashMap<String, Vec<CodeLens>>,
+    function_size_generations: HashMap<String, FunctionSizeGeneration>,
     cached_definitions: HashMap<String, NodeInterner>,
     cached_parsed_files: HashMap<PathBuf, (usize, (ParsedModule, Vec<ParserError>))>,
     cached_def_maps: HashMap<String, BTreeMap<CrateId, CrateDefMap>>,
+    /// Maps each file allowlisted for comptime code to the document whose package allowlists it,
+    /// so that the package can be rechecked when the file changes.
+    comptime_input_files: HashMap<PathBuf, Url>,
     options: LspInitializationOptions,
 }
 
@@ -105,10 +112,13 @@ impl LspState {
             solver: WrapperSolver(Box::new(solver)),
             input_files: HashMap::new(),
             cached_lenses: HashMap::new(),
+            cached_function_size_lenses: HashMap::new(),
+            function_size_generations: HashMap::new(),
             cached_definitions: HashMap::new(),
             open_documents_count: 0,
             cached_parsed_files: HashMap::new(),
             cached_def_maps: HashMap::new(),
+            comptime_input_files: HashMap::new(),
             options: Default::default(),
         }
     }
@@ -150,7 +160,9 @@ impl NargoLspService {
             .notification::<notification::DidChangeTextDocument>(on_did_change_text_document)
             .notification::<notification::DidCloseTextDocument>(on_did_close_text_document)
             .notification::<notification::DidSaveTextDocument>(on_did_save_text_document)
-            .notification::<notification::Exit>(on_exit);
+            .notification::<notification::DidChangeWatchedFiles>(on_did_change_watched_files)
+            .notification::<notification::Exit>(on_exit)
+            .event::<FunctionSizesComputed>(on_function_sizes_computed);
         Self { router }
     }
 }
@@ -274,6 +286,7 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
             .map_err(|err| LspError::WorkspaceResolutionError(err.to_string()))?,
         dependencies: BTreeMap::new(),
         expression_width: None,
+        comptime_allowlist: ComptimeAllowlist::default(),
     };
     let workspace = Workspace {
         root_dir: PathBuf::from(parent_folder),
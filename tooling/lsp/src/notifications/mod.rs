@@ -1,19 +1,26 @@
+use std::collections::HashSet;
 use std::ops::ControlFlow;
 
 use crate::insert_all_files_for_workspace_into_file_manager;
 use async_lsp::{ErrorCode, LanguageClient, ResponseError};
+use lsp_types::notification::Notification;
+use nargo::package::Package;
 use noirc_driver::{check_crate, file_manager_with_stdlib, CheckOptions};
 use noirc_errors::{DiagnosticKind, FileDiagnostic};
+use noirc_frontend::hir::comptime::normalize_path;
 
 use crate::types::{
     notification, Diagnostic, DiagnosticSeverity, DidChangeConfigurationParams,
-    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, InitializedParams, NargoPackageTests, PublishDiagnosticsParams,
+    DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, FileSystemWatcher, GlobPattern,
+    InitializedParams, NargoPackageTests, PublishDiagnosticsParams, Registration,
+    RegistrationParams, Url,
 };
 
 use crate::{
-    byte_span_to_range, get_package_tests_in_crate, parse_diff, resolve_workspace_for_source_path,
-    LspState,
+    byte_span_to_range, get_package_tests_in_crate, parse_diff,
+    requests::compute_function_sizes_in_background, resolve_workspace_for_source_path, LspState,
 };
 
 pub(super) fn on_initialized(
@@ -70,6 +77,13 @@ pub(super) fn on_did_close_text_document(
 ) -> ControlFlow<Result<(), async_lsp::Error>> {
     state.input_files.remove(&params.text_document.uri.to_string());
     state.cached_lenses.remove(&params.text_document.uri.to_string());
+    state.cached_function_size_lenses.remove(&params.text_document.uri.to_string());
+    // Drop the result of any function size computation which is still running for this document.
+    if let Some(generation) =
+        state.function_size_generations.get(&params.text_document.uri.to_string())
+    {
+        generation.next();
+    }
 
     state.open_documents_count -= 1;
 
@@ -93,9 +107,67 @@ pub(super) fn on_did_save_text_document(
     let document_uri = params.text_document.uri;
     let output_diagnostics = true;
 
-    match process_workspace_for_noir_document(state, document_uri, output_diagnostics) {
-        Ok(_) => ControlFlow::Continue(()),
-        Err(err) => ControlFlow::Break(Err(err)),
+    if let Err(err) =
+        process_workspace_for_noir_document(state, document_uri.clone(), output_diagnostics)
+    {
+        return ControlFlow::Break(Err(err));
+    }
+
+    if state.options.enable_code_lens && state.options.enable_function_size_lens {
+        compute_function_sizes_in_background(state, &document_uri);
+    }
+
+    ControlFlow::Continue(())
+}
+
+// Files read by comptime code aren't Noir documents, so when one of them changes we recheck
+// the package of the document whose package allowlists it.
+pub(super) fn on_did_change_watched_files(
+    state: &mut LspState,
+    params: DidChangeWatchedFilesParams,
+) -> ControlFlow<Result<(), async_lsp::Error>> {
+    let document_uris: HashSet<_> = params
+        .changes
+        .into_iter()
+        .filter_map(|change| {
+            let file_path = change.uri.to_file_path().ok()?;
+            state.comptime_input_files.get(&file_path).cloned()
+        })
+        .collect();
+
+    for document_uri in document_uris {
+        let output_diagnostics = true;
+        if let Err(err) =
+            process_workspace_for_noir_document(state, document_uri, output_diagnostics)
+        {
+            return ControlFlow::Break(Err(err));
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// Asks the client to notify us of changes to the files `package` allowlists for comptime code,
+/// so that the package can be rechecked when one of them changes.
+fn watch_comptime_input_files(state: &mut LspState, package: &Package, document_uri: &Url) {
+    for path in &package.comptime_allowlist.files {
+        let path = package.root_dir.join(normalize_path(path));
+        if state.comptime_input_files.insert(path.clone(), document_uri.clone()).is_some() {
+            continue;
+        }
+
+        let pattern = path.to_string_lossy().to_string();
+        let watcher = FileSystemWatcher { glob_pattern: GlobPattern::String(pattern), kind: None };
+        let options = DidChangeWatchedFilesRegistrationOptions { watchers: vec![watcher] };
+        let registration = Registration {
+            id: format!("nargo/comptime-input/{}", path.display()),
+            method: notification::DidChangeWatchedFiles::METHOD.to_string(),
+            register_options: serde_json::to_value(options).ok(),
+        };
+        // The response carries no information we need, so it isn't awaited
+        let _ = state
+            .client
+            .register_capability(RegistrationParams { registrations: vec![registration] });
     }
 }
 
@@ -157,6 +229,9 @@ pub(crate) fn process_workspace_for_noir_document(
                 Some(&file_path),
             );
             state.cached_lenses.insert(document_uri.to_string(), collected_lenses);
+
+            watch_comptime_input_files(state, package, &document_uri);
+
             state.cached_definitions.insert(package_root_dir.clone(), context.def_interner);
             state.cached_def_maps.insert(package_root_dir.clone(), context.def_maps);
 
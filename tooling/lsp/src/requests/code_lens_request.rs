@@ -17,7 +17,7 @@ const TEST_COMMAND: &str = "nargo.test";
 const TEST_CODELENS_TITLE: &str = "Run Test";
 const COMPILE_COMMAND: &str = "nargo.compile";
 const COMPILE_CODELENS_TITLE: &str = "Compile";
-const INFO_COMMAND: &str = "nargo.info";
+pub(super) const INFO_COMMAND: &str = "nargo.info";
 const INFO_CODELENS_TITLE: &str = "Info";
 const EXECUTE_COMMAND: &str = "nargo.execute";
 const EXECUTE_CODELENS_TITLE: &str = "Execute";
@@ -31,7 +31,10 @@ fn with_arrow(title: &str) -> String {
     format!("{ARROW} {title}")
 }
 
-fn package_selection_args(workspace: &Workspace, package: &Package) -> Vec<serde_json::Value> {
+pub(super) fn package_selection_args(
+    workspace: &Workspace,
+    package: &Package,
+) -> Vec<serde_json::Value> {
     vec![
         "--program-dir".into(),
         workspace.root_dir.display().to_string().into(),
@@ -55,8 +58,14 @@ fn on_code_lens_request_inner(
         ResponseError::new(ErrorCode::REQUEST_FAILED, "URI is not a valid file path")
     })?;
 
+    let function_size_lenses = state
+        .cached_function_size_lenses
+        .get(&params.text_document.uri.to_string())
+        .cloned()
+        .unwrap_or_default();
+
     if let Some(collected_lenses) = state.cached_lenses.get(&params.text_document.uri.to_string()) {
-        return Ok(Some(collected_lenses.clone()));
+        return Ok(Some([collected_lenses.clone(), function_size_lenses].concat()));
     }
 
     let source_string = std::fs::read_to_string(&file_path).map_err(|_| {
@@ -79,12 +88,17 @@ fn on_code_lens_request_inner(
 
     if collected_lenses.is_empty() {
         state.cached_lenses.remove(&params.text_document.uri.to_string());
-        Ok(None)
     } else {
         state
             .cached_lenses
             .insert(params.text_document.uri.to_string().clone(), collected_lenses.clone());
-        Ok(Some(collected_lenses))
+    }
+
+    let lenses = [collected_lenses, function_size_lenses].concat();
+    if lenses.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(lenses))
     }
 }
 
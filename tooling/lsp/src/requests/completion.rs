@@ -17,16 +17,16 @@ use noirc_frontend::{
     ast::{
         AsTraitPath, BlockExpression, CallExpression, ConstructorExpression, Expression,
         ExpressionKind, ForLoopStatement, Ident, IfExpression, ItemVisibility, LValue, Lambda,
-        LetStatement, MemberAccessExpression, MethodCallExpression, NoirFunction, NoirStruct,
-        NoirTraitImpl, Path, PathKind, PathSegment, Pattern, Statement, StatementKind, TraitItem,
-        TypeImpl, UnresolvedGeneric, UnresolvedGenerics, UnresolvedType, UnresolvedTypeData,
-        UseTree, UseTreeKind,
+        LetStatement, MatchExpression, MatchPattern, MemberAccessExpression, MethodCallExpression,
+        NoirEnumeration, NoirFunction, NoirStruct, NoirTraitImpl, Path, PathKind, PathSegment,
+        Pattern, Statement, StatementKind, TraitItem, TypeImpl, UnresolvedGeneric,
+        UnresolvedGenerics, UnresolvedType, UnresolvedTypeData, UseTree, UseTreeKind,
     },
     graph::{CrateId, Dependency},
     hir::{
         def_map::{CrateDefMap, LocalModuleId, ModuleId},
         resolution::{
-            import::can_reference_module_id,
+            import::{can_reference_module_id, struct_member_is_visible},
             path_resolver::{PathResolver, StandardPathResolver},
         },
     },
@@ -230,6 +230,7 @@ impl<'a> NodeFinder<'a> {
             ItemKind::Global(let_statement) => self.find_in_let_statement(let_statement, false),
             ItemKind::TypeAlias(noir_type_alias) => self.find_in_noir_type_alias(noir_type_alias),
             ItemKind::Struct(noir_struct) => self.find_in_noir_struct(noir_struct),
+            ItemKind::Enum(noir_enum) => self.find_in_noir_enum(noir_enum),
             ItemKind::Trait(noir_trait) => self.find_in_noir_trait(noir_trait),
             ItemKind::ModuleDecl(_) => (),
         }
@@ -291,8 +292,21 @@ impl<'a> NodeFinder<'a> {
         self.type_parameters.clear();
         self.collect_type_parameters_in_generics(&noir_struct.generics);
 
-        for (_name, unresolved_type) in &noir_struct.fields {
-            self.find_in_unresolved_type(unresolved_type);
+        for field in &noir_struct.fields {
+            self.find_in_unresolved_type(&field.typ);
+        }
+
+        self.type_parameters.clear();
+    }
+
+    fn find_in_noir_enum(&mut self, noir_enum: &NoirEnumeration) {
+        self.type_parameters.clear();
+        self.collect_type_parameters_in_generics(&noir_enum.generics);
+
+        for variant in &noir_enum.variants {
+            for unresolved_type in variant.parameters.iter().flatten() {
+                self.find_in_unresolved_type(unresolved_type);
+            }
         }
 
         self.type_parameters.clear();
@@ -437,6 +451,18 @@ impl<'a> NodeFinder<'a> {
             StatementKind::For(for_loop_statement) => {
                 self.find_in_for_loop_statement(for_loop_statement);
             }
+            StatementKind::While(while_statement) => {
+                self.find_in_expression(&while_statement.condition);
+                self.find_in_expression(&while_statement.block);
+            }
+            StatementKind::Loop(block) => {
+                self.find_in_expression(block);
+            }
+            StatementKind::Return(value) => {
+                if let Some(value) = value {
+                    self.find_in_expression(value);
+                }
+            }
             StatementKind::Comptime(statement) => {
                 // When entering a comptime block, regular local variables shouldn't be offered anymore
                 let old_local_variables = self.local_variables.clone();
@@ -541,6 +567,12 @@ impl<'a> NodeFinder<'a> {
             ExpressionKind::If(if_expression) => {
                 self.find_in_if_expression(if_expression);
             }
+            ExpressionKind::Match(match_expression) => {
+                self.find_in_match_expression(match_expression);
+            }
+            ExpressionKind::Try(expression) => {
+                self.find_in_expression(expression);
+            }
             ExpressionKind::Variable(path) => {
                 self.find_in_path(path, RequestedItems::AnyItems);
             }
@@ -625,11 +657,14 @@ impl<'a> NodeFinder<'a> {
         let struct_type = self.interner.get_struct(struct_id);
         let struct_type = struct_type.borrow();
 
-        // First get all of the struct's fields
+        // First get all of the struct's fields that are visible from the current module
         let mut fields = HashMap::new();
         let fields_as_written = struct_type.get_fields_as_written();
-        for (field, typ) in &fields_as_written {
-            fields.insert(field, typ);
+        for field in &fields_as_written {
+            let visibility = field.visibility;
+            if struct_member_is_visible(struct_id, visibility, self.module_id, self.def_maps) {
+                fields.insert(&field.name.0.contents, &field.typ);
+            }
         }
 
         // Remove the ones that already exists in the constructor
@@ -680,6 +715,17 @@ impl<'a> NodeFinder<'a> {
         }
     }
 
+    fn find_in_match_expression(&mut self, match_expression: &MatchExpression) {
+        self.find_in_expression(&match_expression.expression);
+
+        for (pattern, body) in &match_expression.rules {
+            let old_local_variables = self.local_variables.clone();
+            self.collect_local_variables_in_match_pattern(pattern);
+            self.find_in_expression(body);
+            self.local_variables = old_local_variables;
+        }
+    }
+
     fn find_in_lambda(&mut self, lambda: &Lambda) {
         for (_, unresolved_type) in &lambda.parameters {
             self.find_in_unresolved_type(unresolved_type);
@@ -1061,6 +1107,30 @@ impl<'a> NodeFinder<'a> {
         }
     }
 
+    fn collect_local_variables_in_match_pattern(&mut self, pattern: &MatchPattern) {
+        match pattern {
+            MatchPattern::Identifier(ident) => {
+                self.local_variables.insert(ident.to_string(), ident.span());
+            }
+            MatchPattern::Tuple(patterns, _) => {
+                for pattern in patterns {
+                    self.collect_local_variables_in_match_pattern(pattern);
+                }
+            }
+            MatchPattern::Struct(_, fields, _) => {
+                for (_, pattern) in fields {
+                    self.collect_local_variables_in_match_pattern(pattern);
+                }
+            }
+            MatchPattern::Variant(_, arguments, _) => {
+                for pattern in arguments.iter().flatten() {
+                    self.collect_local_variables_in_match_pattern(pattern);
+                }
+            }
+            MatchPattern::Wildcard(_) | MatchPattern::Literal(..) => (),
+        }
+    }
+
     fn collect_type_parameters_in_generics(&mut self, generics: &UnresolvedGenerics) {
         for generic in generics {
             self.collect_type_parameters_in_generic(generic);
@@ -1186,8 +1256,18 @@ impl<'a> NodeFinder<'a> {
         generics: &[Type],
         prefix: &str,
     ) {
+        // The fields of an enum are an implementation detail of how it's lowered to a struct
+        if struct_type.is_enum() {
+            return;
+        }
+
         for (name, typ) in &struct_type.get_fields(generics) {
-            if name_matches(name, prefix) {
+            if !name_matches(name, prefix) {
+                continue;
+            }
+
+            let visibility = struct_type.field_visibility(name).unwrap_or(ItemVisibility::Public);
+            if struct_member_is_visible(struct_type.id, visibility, self.module_id, self.def_maps) {
                 self.completion_items.push(struct_field_completion_item(name, typ));
             }
         }
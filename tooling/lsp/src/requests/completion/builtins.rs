@@ -96,6 +96,7 @@ pub(super) fn keyword_builtin_type(keyword: &Keyword) -> Option<&'static str> {
         Keyword::TraitDefinition => Some("TraitDefinition"),
         Keyword::TraitImpl => Some("TraitImpl"),
         Keyword::TypeType => Some("Type"),
+        Keyword::TypedExpr => Some("TypedExpr"),
         Keyword::UnresolvedType => Some("UnresolvedType"),
 
         Keyword::As
@@ -111,6 +112,7 @@ pub(super) fn keyword_builtin_type(keyword: &Keyword) -> Option<&'static str> {
         | Keyword::Crate
         | Keyword::Dep
         | Keyword::Else
+        | Keyword::Enum
         | Keyword::Fn
         | Keyword::For
         | Keyword::FormatString
@@ -119,6 +121,8 @@ pub(super) fn keyword_builtin_type(keyword: &Keyword) -> Option<&'static str> {
         | Keyword::Impl
         | Keyword::In
         | Keyword::Let
+        | Keyword::Loop
+        | Keyword::Match
         | Keyword::Mod
         | Keyword::Module
         | Keyword::Mut
@@ -172,6 +176,7 @@ pub(super) fn keyword_builtin_function(keyword: &Keyword) -> Option<BuiltInFunct
         | Keyword::Crate
         | Keyword::Dep
         | Keyword::Else
+        | Keyword::Enum
         | Keyword::Expr
         | Keyword::Field
         | Keyword::Fn
@@ -183,6 +188,8 @@ pub(super) fn keyword_builtin_function(keyword: &Keyword) -> Option<BuiltInFunct
         | Keyword::Impl
         | Keyword::In
         | Keyword::Let
+        | Keyword::Loop
+        | Keyword::Match
         | Keyword::Mod
         | Keyword::Module
         | Keyword::Mut
@@ -201,6 +208,7 @@ pub(super) fn keyword_builtin_function(keyword: &Keyword) -> Option<BuiltInFunct
         | Keyword::TraitImpl
         | Keyword::Type
         | Keyword::TypeType
+        | Keyword::TypedExpr
         | Keyword::Unchecked
         | Keyword::Unconstrained
         | Keyword::UnresolvedType
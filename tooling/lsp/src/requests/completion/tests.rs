@@ -1305,10 +1305,10 @@ mod completion_tests {
         let src = r#"
             mod foobar {
                 struct Foo {
-                    bb: i32,
-                    bbb: Field,
-                    bbbb: bool,
-                    bbbbb: str<6>,
+                    pub bb: i32,
+                    pub bbb: Field,
+                    pub bbbb: bool,
+                    pub bbbbb: str<6>,
                 }
             }
 
@@ -1323,6 +1323,68 @@ mod completion_tests {
         .await;
     }
 
+    #[test]
+    async fn test_does_not_complete_private_constructor_fields() {
+        let src = r#"
+            mod foobar {
+                struct Foo {
+                    pub bb: i32,
+                    bbb: Field,
+                    pub(crate) bbbb: bool,
+                }
+            }
+
+            fn main() {
+                foobar::Foo { b>|< }
+            }
+        "#;
+        assert_completion(
+            src,
+            vec![field_completion_item("bb", "i32"), field_completion_item("bbbb", "bool")],
+        )
+        .await;
+    }
+
+    #[test]
+    async fn test_does_not_complete_private_fields() {
+        let src = r#"
+            mod foobar {
+                struct Foo {
+                    pub bar: i32,
+                    baz: Field,
+                }
+            }
+
+            fn foo(f: foobar::Foo) {
+                f.>|<
+            }
+        "#;
+        assert_completion(src, vec![field_completion_item("bar", "i32")]).await;
+    }
+
+    #[test]
+    async fn test_does_not_complete_enum_fields() {
+        let src = r#"
+            enum Direction {
+                North,
+                South(Field),
+            }
+
+            impl Direction {
+                fn flip(self) -> Direction { self }
+            }
+
+            fn foo(d: Direction) {
+                d.>|<
+            }
+        "#;
+        assert_completion(
+            src,
+            vec![function_completion_item("flip()", "flip()", "fn(self) -> Direction")],
+        )
+        .await;
+    }
+
     #[test]
     async fn test_completes_trait_methods() {
         let src = r#"
@@ -9,8 +9,9 @@ use lsp_types::{
 use noirc_errors::Span;
 use noirc_frontend::{
     ast::{
-        Expression, FunctionReturnType, Ident, LetStatement, NoirFunction, NoirStruct, NoirTrait,
-        NoirTraitImpl, TraitImplItem, TraitItem, TypeImpl, UnresolvedType, UnresolvedTypeData,
+        Expression, FunctionReturnType, Ident, LetStatement, NoirEnumeration, NoirFunction,
+        NoirStruct, NoirTrait, NoirTraitImpl, TraitImplItem, TraitItem, TypeImpl, UnresolvedType,
+        UnresolvedTypeData,
     },
     parser::{Item, ItemKind, ParsedSubModule},
     ParsedModule,
@@ -77,6 +78,9 @@ impl<'a> DocumentSymbolCollector<'a> {
             ItemKind::Struct(noir_struct) => {
                 self.collect_in_noir_struct(noir_struct, item.span, symbols);
             }
+            ItemKind::Enum(noir_enum) => {
+                self.collect_in_noir_enum(noir_enum, item.span, symbols);
+            }
             ItemKind::Trait(noir_trait) => {
                 self.collect_in_noir_trait(noir_trait, item.span, symbols);
             }
@@ -139,8 +143,9 @@ impl<'a> DocumentSymbolCollector<'a> {
         };
 
         let mut children = Vec::new();
-        for (field_name, typ) in &noir_struct.fields {
-            let span = Span::from(field_name.span().start()..typ.span.end());
+        for field in &noir_struct.fields {
+            let field_name = &field.name;
+            let span = Span::from(field_name.span().start()..field.typ.span.end());
 
             let Some(field_location) = self.to_lsp_location(span) else {
                 continue;
@@ -176,6 +181,52 @@ impl<'a> DocumentSymbolCollector<'a> {
         });
     }
 
+    fn collect_in_noir_enum(
+        &mut self,
+        noir_enum: &NoirEnumeration,
+        span: Span,
+        symbols: &mut Vec<DocumentSymbol>,
+    ) {
+        let Some(location) = self.to_lsp_location(span) else {
+            return;
+        };
+
+        let Some(selection_location) = self.to_lsp_location(noir_enum.name.span()) else {
+            return;
+        };
+
+        let mut children = Vec::new();
+        for variant in &noir_enum.variants {
+            let Some(variant_location) = self.to_lsp_location(variant.name.span()) else {
+                continue;
+            };
+
+            #[allow(deprecated)]
+            children.push(DocumentSymbol {
+                name: variant.name.to_string(),
+                detail: None,
+                kind: SymbolKind::ENUM_MEMBER,
+                tags: None,
+                deprecated: None,
+                range: variant_location.range,
+                selection_range: variant_location.range,
+                children: None,
+            });
+        }
+
+        #[allow(deprecated)]
+        symbols.push(DocumentSymbol {
+            name: noir_enum.name.to_string(),
+            detail: None,
+            kind: SymbolKind::ENUM,
+            tags: None,
+            deprecated: None,
+            range: location.range,
+            selection_range: selection_location.range,
+            children: Some(children),
+        });
+    }
+
     fn collect_in_noir_trait(
         &mut self,
         noir_trait: &NoirTrait,
@@ -0,0 +1,282 @@
+use std::{
+    collections::HashSet,
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use acvm::acir::circuit::{ExpressionWidth, OpcodeLocation};
+use async_lsp::LanguageClient;
+use fm::{FileManager, PathString};
+use lsp_types::Url;
+use nargo::{functions::collect_functions, package::Package, workspace::Workspace};
+use noirc_driver::{
+    file_manager_with_stdlib, CompileOptions, CompiledProgram, DEFAULT_EXPRESSION_WIDTH,
+};
+use noirc_errors::Location;
+use noirc_frontend::hir::ParsedFiles;
+
+use crate::{
+    byte_span_to_range, insert_all_files_for_workspace_into_file_manager, parse_diff,
+    resolve_workspace_for_source_path,
+    types::{CodeLens, Command},
+    LspState,
+};
+
+use super::code_lens_request::{package_selection_args, INFO_COMMAND};
+
+/// Loopback event emitted once the sizes of the functions in a saved document
+/// have been computed in the background.
+pub(crate) struct FunctionSizesComputed {
+    uri: Url,
+    /// The generation of the computation which produced these lenses, see [FunctionSizeGeneration].
+    generation: usize,
+    lenses: Vec<CodeLens>,
+}
+
+/// Counts the function size computations started for a document.
+///
+/// Save notifications don't carry a document version, so each computation is tagged with the
+/// generation it was started in instead. A computation whose generation is no longer the latest one
+/// has been superseded by a later save (or by the document being closed) and its result is dropped.
+#[derive(Clone, Default)]
+pub(crate) struct FunctionSizeGeneration(Arc<AtomicUsize>);
+
+impl FunctionSizeGeneration {
+    /// Starts a new generation, superseding any computation which is still running.
+    pub(crate) fn next(&self) -> usize {
+        self.0.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub(crate) fn is_current(&self, generation: usize) -> bool {
+        self.0.load(Ordering::SeqCst) == generation
+    }
+}
+
+pub(crate) fn on_function_sizes_computed(
+    state: &mut LspState,
+    event: FunctionSizesComputed,
+) -> ControlFlow<Result<(), async_lsp::Error>> {
+    let uri = event.uri.to_string();
+    let is_current = state
+        .function_size_generations
+        .get(&uri)
+        .is_some_and(|generation| generation.is_current(event.generation));
+    if !is_current {
+        return ControlFlow::Continue(());
+    }
+
+    state.cached_function_size_lenses.insert(uri, event.lenses);
+
+    // Ask the client to request the code lenses again so that the new sizes are shown right away.
+    // The request is sent immediately and its response is empty, so there's no need to await it.
+    drop(state.client.code_lens_refresh(()));
+    ControlFlow::Continue(())
+}
+
+/// Compiles the package containing `document_uri` on the rayon thread pool and reports the ACIR and
+/// Brillig sizes of every function in the document back to the server through [FunctionSizesComputed].
+///
+/// Only binary packages are compiled, as these are the only ones which `nargo info` reports on.
+pub(crate) fn compute_function_sizes_in_background(state: &mut LspState, document_uri: &Url) {
+    let Ok(file_path) = document_uri.to_file_path() else {
+        return;
+    };
+    let Ok(workspace) = resolve_workspace_for_source_path(&file_path) else {
+        return;
+    };
+    let Some(package) = crate::workspace_package_for_file(&workspace, &file_path).cloned() else {
+        return;
+    };
+    if !package.is_binary() {
+        return;
+    }
+
+    let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
+    insert_all_files_for_workspace_into_file_manager(
+        state,
+        &workspace,
+        &mut workspace_file_manager,
+    );
+    let parsed_files = parse_diff(&workspace_file_manager, state);
+
+    let generations =
+        state.function_size_generations.entry(document_uri.to_string()).or_default().clone();
+    let generation = generations.next();
+
+    let client = state.client.clone();
+    let uri = document_uri.clone();
+    rayon::spawn(move || {
+        // Don't bother compiling if the document was saved again while this task was queued.
+        if !generations.is_current(generation) {
+            return;
+        }
+
+        // If compilation fails we keep showing the sizes from the last successful compilation.
+        if let Some(lenses) = collect_function_size_lenses(
+            &workspace_file_manager,
+            &parsed_files,
+            &workspace,
+            &package,
+            &file_path,
+        ) {
+            let _ = client.emit(FunctionSizesComputed { uri, generation, lenses });
+        }
+    });
+}
+
+/// Compiles `package` and returns a code lens above each function in `file_path` showing the number
+/// of ACIR and Brillig opcodes which are attributed to it.
+///
+/// Returns `None` if the package fails to compile.
+pub(crate) fn collect_function_size_lenses(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    workspace: &Workspace,
+    package: &Package,
+    file_path: &Path,
+) -> Option<Vec<CodeLens>> {
+    let file_id =
+        file_manager.as_file_map().get_file_id(&PathString::from_path(PathBuf::from(file_path)))?;
+    let (parsed_module, _errors) = parsed_files.get(&file_id)?;
+
+    let (compiled_program, _warnings) = nargo::ops::compile_program(
+        file_manager,
+        parsed_files,
+        workspace,
+        package,
+        &CompileOptions::default(),
+        None,
+    )
+    .ok()?;
+    let expression_width = package.expression_width.unwrap_or(DEFAULT_EXPRESSION_WIDTH);
+    let compiled_program = nargo::ops::transform_program(compiled_program, expression_width);
+
+    let files = file_manager.as_file_map();
+    let lenses = collect_functions(parsed_module)
+        .into_iter()
+        .filter_map(|function| {
+            let size = FunctionSize::new(&compiled_program, Location::new(function.span, file_id));
+            if size.is_empty() {
+                return None;
+            }
+
+            let range = byte_span_to_range(files, file_id, function.name_span.into())?;
+            let command = Command {
+                title: size.title(expression_width),
+                command: INFO_COMMAND.into(),
+                arguments: Some(package_selection_args(workspace, package)),
+            };
+            Some(CodeLens { range, command: Some(command), data: None })
+        })
+        .collect();
+
+    Some(lenses)
+}
+
+/// The number of opcodes generated for a single source function.
+///
+/// An opcode is attributed to a function if any location in its call stack lies within the function,
+/// so the size of a function includes the code of every function inlined into it.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct FunctionSize {
+    acir_opcodes: usize,
+    brillig_opcodes: usize,
+}
+
+impl FunctionSize {
+    fn new(program: &CompiledProgram, function_location: Location) -> Self {
+        let is_within_function = |call_stack: &[Location]| {
+            call_stack.iter().any(|location| function_location.contains(location))
+        };
+
+        let acir_opcodes = program
+            .debug
+            .iter()
+            .map(|debug_info| {
+                debug_info
+                    .locations
+                    .iter()
+                    .filter(|(opcode_location, call_stack)| {
+                        matches!(opcode_location, OpcodeLocation::Acir(_))
+                            && is_within_function(call_stack.as_slice())
+                    })
+                    .count()
+            })
+            .sum();
+
+        // Brillig functions may be called from several ACIR functions so we deduplicate their opcodes.
+        let mut brillig_opcodes = HashSet::new();
+        for debug_info in &program.debug {
+            for (brillig_function_id, locations) in &debug_info.brillig_locations {
+                for (opcode_location, call_stack) in locations {
+                    if is_within_function(call_stack.as_slice()) {
+                        brillig_opcodes.insert((*brillig_function_id, *opcode_location));
+                    }
+                }
+            }
+        }
+
+        FunctionSize { acir_opcodes, brillig_opcodes: brillig_opcodes.len() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.acir_opcodes == 0 && self.brillig_opcodes == 0
+    }
+
+    fn title(&self, expression_width: ExpressionWidth) -> String {
+        let expression_width = match expression_width {
+            ExpressionWidth::Unbounded => "unbounded".to_string(),
+            ExpressionWidth::Bounded { width } => width.to_string(),
+        };
+        format!(
+            "ACIR opcodes: {} (width {}) | Brillig opcodes: {}",
+            self.acir_opcodes, expression_width, self.brillig_opcodes
+        )
+    }
+}
+
+#[cfg(test)]
+mod function_size_tests {
+    use crate::test_utils;
+
+    use super::*;
+    use tokio::test;
+
+    #[test]
+    async fn test_function_size_lenses() {
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("function_size").await;
+        let file_path = noir_text_document.to_file_path().unwrap();
+
+        let workspace = resolve_workspace_for_source_path(&file_path).unwrap();
+        let package = crate::workspace_package_for_file(&workspace, &file_path).unwrap();
+        let mut workspace_file_manager = file_manager_with_stdlib(&workspace.root_dir);
+        insert_all_files_for_workspace_into_file_manager(
+            &state,
+            &workspace,
+            &mut workspace_file_manager,
+        );
+        let parsed_files = parse_diff(&workspace_file_manager, &mut state);
+
+        let lenses = collect_function_size_lenses(
+            &workspace_file_manager,
+            &parsed_files,
+            &workspace,
+            package,
+            &file_path,
+        )
+        .expect("Expected program to compile");
+
+        let titles: Vec<_> =
+            lenses.iter().map(|lens| lens.command.as_ref().unwrap().title.clone()).collect();
+
+        // `main`, `square` and `unconstrained_square` each get a lens, the unused function doesn't.
+        assert_eq!(lenses.len(), 3, "{titles:?}");
+        assert!(titles[0].starts_with("ACIR opcodes: "));
+        assert!(titles[0].contains("(width 4)"));
+        assert!(!titles[2].ends_with("Brillig opcodes: 0"));
+    }
+}
@@ -4,7 +4,7 @@ use async_lsp::ResponseError;
 use fm::FileMap;
 use lsp_types::{Hover, HoverContents, HoverParams, MarkupContent, MarkupKind};
 use noirc_frontend::{
-    ast::Visibility,
+    ast::{ItemVisibility, Visibility},
     graph::CrateId,
     hir::def_map::ModuleId,
     hir_def::{stmt::HirPattern, traits::Trait},
@@ -96,21 +96,48 @@ fn format_struct(id: StructId, args: &ProcessRequestCallbackArgs) -> String {
         string.push('\n');
     }
     string.push_str("    ");
+    if struct_type.is_enum() {
+        format_enum(&struct_type, &mut string);
+        return string;
+    }
     string.push_str("struct ");
     string.push_str(&struct_type.name.0.contents);
     format_generics(&struct_type.generics, &mut string);
     string.push_str(" {\n");
-    for (field_name, field_type) in struct_type.get_fields_as_written() {
+    for field in struct_type.get_fields_as_written() {
         string.push_str("        ");
-        string.push_str(&field_name);
+        format_field_visibility(field.visibility, &mut string);
+        string.push_str(&field.name.0.contents);
         string.push_str(": ");
-        string.push_str(&format!("{}", field_type));
+        string.push_str(&format!("{}", field.typ));
         string.push_str(",\n");
     }
     string.push_str("    }");
     string
 }
 
+/// Formats an enum as it was declared rather than as the struct it was lowered to.
+fn format_enum(struct_type: &StructType, string: &mut String) {
+    string.push_str("enum ");
+    string.push_str(&struct_type.name.0.contents);
+    format_generics(&struct_type.generics, string);
+    string.push_str(" {\n");
+
+    let variants = struct_type.variants.iter().flatten();
+    // The first field is the tag, the remaining ones hold the arguments of each variant
+    let fields = struct_type.get_fields_as_written().into_iter().skip(1);
+    for (variant, field) in variants.zip(fields) {
+        string.push_str("        ");
+        string.push_str(&variant.name.0.contents);
+        if let Type::Tuple(arguments) = &field.typ {
+            let arguments: Vec<_> = arguments.iter().map(ToString::to_string).collect();
+            string.push_str(&format!("({})", arguments.join(", ")));
+        }
+        string.push_str(",\n");
+    }
+    string.push_str("    }");
+}
+
 fn format_struct_member(
     id: StructId,
     field_index: usize,
@@ -118,7 +145,7 @@ fn format_struct_member(
 ) -> String {
     let struct_type = args.interner.get_struct(id);
     let struct_type = struct_type.borrow();
-    let (field_name, field_type) = struct_type.field_at(field_index);
+    let field = struct_type.field_at(field_index);
 
     let mut string = String::new();
     if format_parent_module(ReferenceId::Struct(id), args, &mut string) {
@@ -127,13 +154,21 @@ fn format_struct_member(
     string.push_str(&struct_type.name.0.contents);
     string.push('\n');
     string.push_str("    ");
-    string.push_str(&field_name.0.contents);
+    format_field_visibility(field.visibility, &mut string);
+    string.push_str(&field.name.0.contents);
     string.push_str(": ");
-    string.push_str(&format!("{}", field_type));
-    string.push_str(&go_to_type_links(field_type, args.interner, args.files));
+    string.push_str(&format!("{}", field.typ));
+    string.push_str(&go_to_type_links(&field.typ, args.interner, args.files));
     string
 }
 
+fn format_field_visibility(visibility: ItemVisibility, string: &mut String) {
+    if visibility != ItemVisibility::Private {
+        string.push_str(&visibility.to_string());
+        string.push(' ');
+    }
+}
+
 fn format_trait(id: TraitId, args: &ProcessRequestCallbackArgs) -> String {
     let a_trait = args.interner.get_trait(id);
 
@@ -573,8 +608,8 @@ mod hover_tests {
             Position { line: 9, character: 20 },
             r#"    one::subone
     struct SubOneStruct {
-        some_field: i32,
-        some_other_field: Field,
+        pub some_field: i32,
+        pub some_other_field: Field,
     }"#,
         )
         .await;
@@ -600,7 +635,7 @@ mod hover_tests {
             "two/src/lib.nr",
             Position { line: 9, character: 35 },
             r#"    one::subone::SubOneStruct
-    some_field: i32"#,
+    pub some_field: i32"#,
         )
         .await;
     }
@@ -808,8 +843,8 @@ mod hover_tests {
             Position { line: 51, character: 30 },
             r#"    one::subone
     struct SubOneStruct {
-        some_field: i32,
-        some_other_field: Field,
+        pub some_field: i32,
+        pub some_other_field: Field,
     }"#,
         )
         .await;
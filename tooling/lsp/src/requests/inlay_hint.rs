@@ -10,8 +10,9 @@ use noirc_errors::{Location, Span};
 use noirc_frontend::{
     self,
     ast::{
-        BlockExpression, Expression, ExpressionKind, Ident, LetStatement, NoirFunction, Pattern,
-        Statement, StatementKind, TraitImplItem, TraitItem, UnresolvedTypeData,
+        BlockExpression, Expression, ExpressionKind, Ident, LetStatement, MatchPattern,
+        NoirFunction, Pattern, Statement, StatementKind, TraitImplItem, TraitItem,
+        UnresolvedTypeData,
     },
     hir_def::stmt::HirPattern,
     macros_api::NodeInterner,
@@ -129,6 +130,7 @@ impl<'a> InlayHintCollector<'a> {
             ItemKind::ModuleDecl(_) => (),
             ItemKind::Import(_) => (),
             ItemKind::Struct(_) => (),
+            ItemKind::Enum(_) => (),
             ItemKind::TypeAlias(_) => (),
         }
     }
@@ -200,6 +202,16 @@ impl<'a> InlayHintCollector<'a> {
                 self.collect_in_ident(&for_loop_statement.identifier, false);
                 self.collect_in_expression(&for_loop_statement.block);
             }
+            StatementKind::While(while_statement) => {
+                self.collect_in_expression(&while_statement.condition);
+                self.collect_in_expression(&while_statement.block);
+            }
+            StatementKind::Loop(block) => self.collect_in_expression(block),
+            StatementKind::Return(value) => {
+                if let Some(value) = value {
+                    self.collect_in_expression(value);
+                }
+            }
             StatementKind::Comptime(statement) => self.collect_in_statement(statement),
             StatementKind::Semi(expression) => self.collect_in_expression(expression),
             StatementKind::Break
@@ -271,6 +283,16 @@ impl<'a> InlayHintCollector<'a> {
                     self.collect_in_expression(alternative);
                 }
             }
+            ExpressionKind::Match(match_expression) => {
+                self.collect_in_expression(&match_expression.expression);
+                for (pattern, body) in &match_expression.rules {
+                    self.collect_in_match_pattern(pattern);
+                    self.collect_in_expression(body);
+                }
+            }
+            ExpressionKind::Try(expression) => {
+                self.collect_in_expression(expression);
+            }
             ExpressionKind::Tuple(expressions) => {
                 for expression in expressions {
                     self.collect_in_expression(expression);
@@ -334,6 +356,34 @@ impl<'a> InlayHintCollector<'a> {
         }
     }
 
+    fn collect_in_match_pattern(&mut self, pattern: &MatchPattern) {
+        if !self.options.type_hints.enabled {
+            return;
+        }
+
+        match pattern {
+            MatchPattern::Identifier(ident) => {
+                self.collect_in_ident(ident, false);
+            }
+            MatchPattern::Tuple(patterns, _span) => {
+                for pattern in patterns {
+                    self.collect_in_match_pattern(pattern);
+                }
+            }
+            MatchPattern::Struct(_path, fields, _span) => {
+                for (_ident, pattern) in fields {
+                    self.collect_in_match_pattern(pattern);
+                }
+            }
+            MatchPattern::Variant(_path, arguments, _span) => {
+                for pattern in arguments.iter().flatten() {
+                    self.collect_in_match_pattern(pattern);
+                }
+            }
+            MatchPattern::Wildcard(_) | MatchPattern::Literal(..) => (),
+        }
+    }
+
     fn collect_in_ident(&mut self, ident: &Ident, editable: bool) {
         if !self.options.type_hints.enabled {
             return;
@@ -357,8 +407,8 @@ impl<'a> InlayHintCollector<'a> {
                     ReferenceId::StructMember(struct_id, field_index) => {
                         let struct_type = self.interner.get_struct(struct_id);
                         let struct_type = struct_type.borrow();
-                        let (_field_name, field_type) = struct_type.field_at(field_index);
-                        self.push_type_hint(lsp_location, field_type, false);
+                        let field = struct_type.field_at(field_index);
+                        self.push_type_hint(lsp_location, &field.typ, false);
                     }
                     ReferenceId::Module(_)
                     | ReferenceId::Struct(_)
@@ -682,12 +732,14 @@ fn get_expression_name(expression: &Expression) -> Option<String> {
         ExpressionKind::MethodCall(method_call) => Some(method_call.method_name.to_string()),
         ExpressionKind::Cast(cast) => get_expression_name(&cast.lhs),
         ExpressionKind::Parenthesized(expr) => get_expression_name(expr),
+        ExpressionKind::Try(expr) => get_expression_name(expr),
         ExpressionKind::AsTraitPath(path) => Some(path.impl_item.to_string()),
         ExpressionKind::Constructor(..)
         | ExpressionKind::Infix(..)
         | ExpressionKind::Index(..)
         | ExpressionKind::Block(..)
         | ExpressionKind::If(..)
+        | ExpressionKind::Match(..)
         | ExpressionKind::Lambda(..)
         | ExpressionKind::Tuple(..)
         | ExpressionKind::Quote(..)
@@ -39,6 +39,7 @@ use crate::{
 mod code_lens_request;
 mod completion;
 mod document_symbol;
+mod function_size;
 mod goto_declaration;
 mod goto_definition;
 mod hover;
@@ -53,6 +54,8 @@ mod tests;
 pub(crate) use {
     code_lens_request::collect_lenses_for_package, code_lens_request::on_code_lens_request,
     completion::on_completion_request, document_symbol::on_document_symbol_request,
+    function_size::compute_function_sizes_in_background, function_size::on_function_sizes_computed,
+    function_size::FunctionSizeGeneration, function_size::FunctionSizesComputed,
     goto_declaration::on_goto_declaration_request, goto_definition::on_goto_definition_request,
     goto_definition::on_goto_type_definition_request, hover::on_hover_request,
     inlay_hint::on_inlay_hint_request, profile_run::on_profile_run_request,
@@ -70,6 +73,12 @@ pub(crate) struct LspInitializationOptions {
     #[serde(rename = "enableCodeLens", default = "default_enable_code_lens")]
     pub(crate) enable_code_lens: bool,
 
+    /// Controls whether the ACIR and Brillig sizes of each function are computed after saving a file
+    /// and shown as code lenses. This has no effect if code lens is disabled.
+    /// By default this will be set to true (enabled).
+    #[serde(rename = "enableFunctionSizeLens", default = "default_enable_function_size_lens")]
+    pub(crate) enable_function_size_lens: bool,
+
     #[serde(rename = "enableParsingCache", default = "default_enable_parsing_cache")]
     pub(crate) enable_parsing_cache: bool,
 
@@ -114,6 +123,10 @@ fn default_enable_code_lens() -> bool {
     true
 }
 
+fn default_enable_function_size_lens() -> bool {
+    true
+}
+
 fn default_enable_parsing_cache() -> bool {
     true
 }
@@ -161,6 +174,7 @@ impl Default for LspInitializationOptions {
     fn default() -> Self {
         Self {
             enable_code_lens: default_enable_code_lens(),
+            enable_function_size_lens: default_enable_function_size_lens(),
             enable_parsing_cache: default_enable_parsing_cache(),
             inlay_hints: default_inlay_hints(),
         }
@@ -38,6 +38,7 @@ impl<'a> SignatureFinder<'a> {
             ItemKind::Import(..)
             | ItemKind::TypeAlias(_)
             | ItemKind::Struct(_)
+            | ItemKind::Enum(_)
             | ItemKind::ModuleDecl(_) => (),
         }
     }
@@ -119,6 +120,18 @@ impl<'a> SignatureFinder<'a> {
             StatementKind::For(for_loop_statement) => {
                 self.find_in_for_loop_statement(for_loop_statement);
             }
+            StatementKind::While(while_statement) => {
+                self.find_in_expression(&while_statement.condition);
+                self.find_in_expression(&while_statement.block);
+            }
+            StatementKind::Loop(block) => {
+                self.find_in_expression(block);
+            }
+            StatementKind::Return(value) => {
+                if let Some(value) = value {
+                    self.find_in_expression(value);
+                }
+            }
             StatementKind::Comptime(statement) => {
                 self.find_in_statement(statement);
             }
@@ -216,6 +229,15 @@ impl<'a> SignatureFinder<'a> {
             ExpressionKind::If(if_expression) => {
                 self.find_in_if_expression(if_expression);
             }
+            ExpressionKind::Match(match_expression) => {
+                self.find_in_expression(&match_expression.expression);
+                for (_, body) in &match_expression.rules {
+                    self.find_in_expression(body);
+                }
+            }
+            ExpressionKind::Try(expression) => {
+                self.find_in_expression(expression);
+            }
             ExpressionKind::Tuple(expressions) => {
                 self.find_in_expressions(expressions);
             }
@@ -14,9 +14,11 @@ use std::collections::{BTreeMap, HashMap};
 // Re-providing lsp_types that we don't need to override
 pub(crate) use lsp_types::{
     CodeLens, CodeLensOptions, CodeLensParams, Command, Diagnostic, DiagnosticSeverity,
-    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
-    DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, InitializedParams,
-    Position, PublishDiagnosticsParams, Range, ServerInfo, TextDocumentSyncCapability, Url,
+    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, FileSystemWatcher, GlobPattern,
+    InitializeParams, InitializedParams, Position, PublishDiagnosticsParams, Range, Registration,
+    RegistrationParams, ServerInfo, TextDocumentSyncCapability, Url,
 };
 
 pub(crate) mod request {
@@ -73,8 +75,8 @@ pub(crate) mod notification {
 
     // Re-providing lsp_types that we don't need to override
     pub(crate) use lsp_types::notification::{
-        DidChangeConfiguration, DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
-        DidSaveTextDocument, Exit, Initialized,
+        DidChangeConfiguration, DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument,
+        DidOpenTextDocument, DidSaveTextDocument, Exit, Initialized,
     };
 
     pub(crate) struct NargoUpdateTests;
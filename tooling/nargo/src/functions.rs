@@ -0,0 +1,93 @@
+//! Finds the functions defined in Noir source code, e.g. to attribute compiled opcodes back to them.
+
+use noirc_errors::Span;
+use noirc_frontend::{ast::TraitImplItem, parse_program, parser::ItemKind, ParsedModule};
+
+/// A function defined in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFunction {
+    /// The function's name. Methods are named after the type they're implemented on, e.g. `Foo::bar`.
+    pub name: String,
+    /// The span of the function's name.
+    pub name_span: Span,
+    /// The span of the function's whole definition.
+    pub span: Span,
+}
+
+/// Parses `source` and returns every function defined in it.
+///
+/// Parse errors are ignored, so functions are still found in files which don't fully parse.
+pub fn parse_functions(source: &str) -> Vec<SourceFunction> {
+    let (parsed_module, _errors) = parse_program(source);
+    collect_functions(&parsed_module)
+}
+
+/// Returns every function defined in `module`, including methods and functions in submodules declared inline.
+pub fn collect_functions(module: &ParsedModule) -> Vec<SourceFunction> {
+    let mut functions = Vec::new();
+    collect_functions_in_module(module, &mut functions);
+    functions
+}
+
+fn collect_functions_in_module(module: &ParsedModule, functions: &mut Vec<SourceFunction>) {
+    for item in &module.items {
+        match &item.kind {
+            ItemKind::Function(noir_function) => functions.push(SourceFunction {
+                name: noir_function.name().to_string(),
+                name_span: noir_function.name_ident().span(),
+                span: item.span,
+            }),
+            ItemKind::Impl(type_impl) => {
+                for (method, span) in &type_impl.methods {
+                    functions.push(SourceFunction {
+                        name: format!("{}::{}", type_impl.object_type, method.name()),
+                        name_span: method.name_ident().span(),
+                        span: *span,
+                    });
+                }
+            }
+            ItemKind::TraitImpl(trait_impl) => {
+                for trait_impl_item in &trait_impl.items {
+                    if let TraitImplItem::Function(method) = trait_impl_item {
+                        functions.push(SourceFunction {
+                            name: format!("{}::{}", trait_impl.object_type, method.name()),
+                            name_span: method.name_ident().span(),
+                            span: method.span(),
+                        });
+                    }
+                }
+            }
+            ItemKind::Submodules(parsed_sub_module) => {
+                collect_functions_in_module(&parsed_sub_module.contents, functions);
+            }
+            ItemKind::Import(..)
+            | ItemKind::Struct(..)
+            | ItemKind::Enum(..)
+            | ItemKind::Trait(..)
+            | ItemKind::TypeAlias(..)
+            | ItemKind::Global(..)
+            | ItemKind::ModuleDecl(..) => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_functions;
+
+    #[test]
+    fn finds_functions_and_methods() {
+        let source = "
+            fn main() {}
+            struct Foo {}
+            impl Foo { fn bar(self) {} }
+            trait Baz { fn baz(self); }
+            impl Baz for Foo { fn baz(self) {} }
+            mod inner { fn helper() {} }
+        ";
+
+        let names: Vec<_> =
+            parse_functions(source).into_iter().map(|function| function.name).collect();
+        assert_eq!(names, vec!["main", "Foo::bar", "Foo::baz", "helper"]);
+    }
+}
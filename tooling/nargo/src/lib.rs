@@ -9,8 +9,10 @@
 
 pub mod constants;
 pub mod errors;
+pub mod functions;
 pub mod ops;
 pub mod package;
+pub mod witness;
 pub mod workspace;
 
 use std::collections::{BTreeMap, HashMap};
@@ -35,6 +37,7 @@ pub fn prepare_dependencies(
         match dep {
             Dependency::Remote { package } | Dependency::Local { package } => {
                 let crate_id = prepare_dependency(context, &package.entry_path);
+                context.def_interner.set_comptime_inputs(crate_id, package.comptime_inputs());
                 add_dep(context, parent_crate, crate_id, dep_name.clone());
                 prepare_dependencies(context, crate_id, &package.dependencies);
             }
@@ -138,6 +141,7 @@ pub fn prepare_package<'file_manager, 'parsed_files>(
     let mut context = Context::from_ref_file_manager(file_manager, parsed_files);
 
     let crate_id = prepare_crate(&mut context, &package.entry_path);
+    context.def_interner.set_comptime_inputs(crate_id, package.comptime_inputs());
 
     prepare_dependencies(&mut context, crate_id, &package.dependencies);
 
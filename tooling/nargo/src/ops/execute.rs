@@ -1,17 +1,65 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use acvm::acir::brillig::{ForeignCallResult, Opcode as BrilligOpcode};
 use acvm::acir::circuit::brillig::BrilligBytecode;
+use acvm::acir::circuit::opcodes::AcirFunctionId;
 use acvm::acir::circuit::{
-    OpcodeLocation, Program, ResolvedAssertionPayload, ResolvedOpcodeLocation,
+    Opcode, OpcodeLocation, Program, ResolvedAssertionPayload, ResolvedOpcodeLocation,
+};
+use acvm::acir::native_types::{Witness, WitnessStack};
+use acvm::pwg::{
+    get_value, ACVMStatus, AcirCallWaitInfo, ErrorLocation, ExecutionStats, ForeignCallWaitInfo,
+    OpcodeNotSolvable, OpcodeResolutionError, ProfilingSample, ACVM,
 };
-use acvm::acir::native_types::WitnessStack;
-use acvm::pwg::{ACVMStatus, ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError, ACVM};
 use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
 use acvm::{AcirField, BlackBoxFunctionSolver};
+use noirc_printable_type::ForeignCallError;
+use rayon::prelude::*;
 
 use crate::errors::ExecutionError;
 use crate::NargoError;
 
 use super::foreign_calls::ForeignCallExecutor;
 
+/// An ACIR call whose inputs are known, so which is ready to be solved.
+struct PendingCall<F> {
+    /// The index of the `Call` opcode in the calling function.
+    opcode_index: usize,
+    id: AcirFunctionId,
+    initial_witness: WitnessMap<F>,
+}
+
+/// The solved witness of an ACIR call along with the witness stack built up by the calls it made in turn.
+struct SolvedCall<F> {
+    witness: WitnessMap<F>,
+    witness_stack: WitnessStack<F>,
+}
+
+/// Independent ACIR calls made by a single function, to be solved together.
+struct CallBatch<'a, F, B> {
+    functions: &'a [Circuit<F>],
+    unconstrained_functions: &'a [BrilligBytecode<F>],
+    blackbox_solver: &'a B,
+    parallel_calls: &'a ParallelCalls<F, B>,
+    // The call stack of the calling function, which each call's own call stack is built on top of.
+    call_stack: &'a [ResolvedOpcodeLocation],
+    caller_function_index: usize,
+    calls: Vec<PendingCall<F>>,
+}
+
+/// Allows independent ACIR calls to be solved concurrently.
+struct ParallelCalls<F, B> {
+    /// Functions which can be solved away from the foreign call executor, as neither they nor any of the
+    /// functions they call make foreign calls.
+    isolated_functions: BTreeSet<u32>,
+
+    /// Solves each call in a batch, returning the results in the same order as the calls.
+    ///
+    /// This is a function pointer so that only [`execute_program_in_parallel`] requires the program
+    /// and blackbox solver to be shareable across threads.
+    solve_calls: for<'b> fn(CallBatch<'b, F, B>) -> Vec<Result<SolvedCall<F>, NargoError<F>>>,
+}
+
 struct ProgramExecutor<'a, F, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>> {
     functions: &'a [Circuit<F>],
 
@@ -32,6 +80,15 @@ struct ProgramExecutor<'a, F, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecut
     // This is used to fetch the function we want to execute
     // and to resolve call stack locations across many function calls.
     current_function_index: usize,
+
+    // Statistics combined across all executed circuits, if they are being collected.
+    stats: Option<ExecutionStats>,
+
+    // Brillig profiling samples keyed by the index of the ACIR function which made the Brillig call, if they are being collected.
+    profiling_samples: Option<BTreeMap<usize, Vec<ProfilingSample>>>,
+
+    // Set if independent ACIR calls should be solved concurrently rather than one after another.
+    parallel_calls: Option<&'a ParallelCalls<F, B>>,
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
@@ -51,6 +108,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
             foreign_call_executor,
             call_stack: Vec::default(),
             current_function_index: 0,
+            stats: None,
+            profiling_samples: None,
+            parallel_calls: None,
         }
     }
 
@@ -58,6 +118,17 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
         self.witness_stack
     }
 
+    /// Pushes the items of a witness stack built up by another executor, preserving the order in which they were pushed.
+    fn append_witness_stack(&mut self, mut witness_stack: WitnessStack<F>) {
+        let mut items = Vec::with_capacity(witness_stack.length());
+        while let Some(item) = witness_stack.pop() {
+            items.push(item);
+        }
+        for item in items.into_iter().rev() {
+            self.witness_stack.push(item.index, item.witness);
+        }
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     fn execute_circuit(
         &mut self,
@@ -71,6 +142,15 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
             self.unconstrained_functions,
             &circuit.assert_messages,
         );
+        if self.stats.is_some() {
+            acvm.enable_stats();
+        }
+        if self.profiling_samples.is_some() {
+            acvm.enable_profiling();
+        }
+
+        // Calls which have been solved ahead of the ACVM reaching them, keyed by the index of their `Call` opcode.
+        let mut solved_calls = BTreeMap::new();
 
         loop {
             let solver_status = acvm.solve();
@@ -130,23 +210,60 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
                     acvm.resolve_pending_foreign_call(foreign_call_result);
                 }
                 ACVMStatus::RequiresAcirCall(call_info) => {
-                    // Store the parent function index whose context we are currently executing
-                    let acir_function_caller = self.current_function_index;
-                    // Add call opcode to the call stack with a reference to the parent function index
-                    self.call_stack.push(ResolvedOpcodeLocation {
-                        acir_function_index: acir_function_caller,
-                        opcode_location: OpcodeLocation::Acir(acvm.instruction_pointer()),
-                    });
-
-                    // Set current function to the circuit we are about to execute
-                    self.current_function_index = call_info.id.as_usize();
-                    // Execute the ACIR call
+                    let opcode_index = acvm.instruction_pointer();
+                    if let Some(parallel_calls) = self.parallel_calls {
+                        if !solved_calls.contains_key(&opcode_index) {
+                            let calls = independent_calls(
+                                acvm.opcodes(),
+                                acvm.witness_map(),
+                                opcode_index,
+                                &call_info,
+                                &parallel_calls.isolated_functions,
+                            );
+                            if calls.len() > 1 {
+                                let opcode_indices: Vec<usize> =
+                                    calls.iter().map(|call| call.opcode_index).collect();
+                                let results = (parallel_calls.solve_calls)(CallBatch {
+                                    functions: self.functions,
+                                    unconstrained_functions: self.unconstrained_functions,
+                                    blackbox_solver: self.blackbox_solver,
+                                    parallel_calls,
+                                    call_stack: &self.call_stack,
+                                    caller_function_index: self.current_function_index,
+                                    calls,
+                                });
+                                solved_calls.extend(opcode_indices.into_iter().zip(results));
+                            }
+                        }
+                    }
+
                     let acir_to_call = &self.functions[call_info.id.as_usize()];
-                    let initial_witness = call_info.initial_witness;
-                    let call_solved_witness = self.execute_circuit(initial_witness)?;
+                    let call_solved_witness = match solved_calls.remove(&opcode_index) {
+                        Some(solved_call) => {
+                            let SolvedCall { witness, witness_stack } = solved_call?;
+                            self.append_witness_stack(witness_stack);
+                            witness
+                        }
+                        None => {
+                            // Store the parent function index whose context we are currently executing
+                            let acir_function_caller = self.current_function_index;
+                            // Add call opcode to the call stack with a reference to the parent function index
+                            self.call_stack.push(ResolvedOpcodeLocation {
+                                acir_function_index: acir_function_caller,
+                                opcode_location: OpcodeLocation::Acir(opcode_index),
+                            });
 
-                    // Set tracking index back to the parent function after ACIR call execution
-                    self.current_function_index = acir_function_caller;
+                            // Set current function to the circuit we are about to execute
+                            self.current_function_index = call_info.id.as_usize();
+                            // Execute the ACIR call
+                            let initial_witness = call_info.initial_witness;
+                            let call_solved_witness = self.execute_circuit(initial_witness)?;
+
+                            // Set tracking index back to the parent function after ACIR call execution
+                            self.current_function_index = acir_function_caller;
+                            call_solved_witness
+                        }
+                    };
 
                     let mut call_resolved_outputs = Vec::new();
                     for return_witness_index in acir_to_call.return_values.indices() {
@@ -172,10 +289,181 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
         // included in a failure case.
         self.call_stack.clear();
 
+        if let (Some(stats), Some(circuit_stats)) = (&mut self.stats, acvm.stats()) {
+            stats.merge(circuit_stats);
+        }
+        if let Some(profiling_samples) = &mut self.profiling_samples {
+            profiling_samples
+                .entry(self.current_function_index)
+                .or_default()
+                .extend(acvm.take_profiling_samples());
+        }
+
         Ok(acvm.finalize())
     }
 }
 
+/// Collects the ACIR call which the ACVM is waiting on at `opcode_index`, along with the calls directly following it
+/// which can be solved alongside it.
+///
+/// A following call is independent of the calls before it if its predicate and inputs are already known,
+/// so cannot depend on their outputs. Calls are only collected to functions which make no foreign calls,
+/// as the foreign call executor cannot be shared between threads.
+fn independent_calls<F: AcirField>(
+    opcodes: &[Opcode<F>],
+    witness_map: &WitnessMap<F>,
+    opcode_index: usize,
+    call_info: &AcirCallWaitInfo<F>,
+    isolated_functions: &BTreeSet<u32>,
+) -> Vec<PendingCall<F>> {
+    if !isolated_functions.contains(&call_info.id.0) {
+        return Vec::new();
+    }
+    let mut calls = vec![PendingCall {
+        opcode_index,
+        id: call_info.id,
+        initial_witness: call_info.initial_witness.clone(),
+    }];
+
+    let mut pending_outputs: BTreeSet<Witness> = BTreeSet::new();
+    if let Opcode::Call { outputs, .. } = &opcodes[opcode_index] {
+        pending_outputs.extend(outputs);
+    }
+
+    for (opcode_index, opcode) in opcodes.iter().enumerate().skip(opcode_index + 1) {
+        let Opcode::Call { id, inputs, outputs, predicate } = opcode else {
+            break;
+        };
+        // The main function cannot be called, so leave the ACVM to report the error.
+        if *id == AcirFunctionId(0) || !isolated_functions.contains(&id.0) {
+            break;
+        }
+        let predicate_is_true = match predicate {
+            Some(predicate) => {
+                get_value(predicate, witness_map).is_ok_and(|value| !value.is_zero())
+            }
+            None => true,
+        };
+        if !predicate_is_true || inputs.iter().any(|input| pending_outputs.contains(input)) {
+            break;
+        }
+
+        let initial_witness: Option<BTreeMap<Witness, F>> = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| witness_map.get(input).map(|value| (Witness(i as u32), *value)))
+            .collect();
+        let Some(initial_witness) = initial_witness else {
+            break;
+        };
+
+        pending_outputs.extend(outputs);
+        calls.push(PendingCall { opcode_index, id: *id, initial_witness: initial_witness.into() });
+    }
+    calls
+}
+
+/// Returns the indices of the functions in `program` which never make foreign calls,
+/// either from their own Brillig calls or from those of the functions they call.
+fn functions_without_foreign_calls<F>(program: &Program<F>) -> BTreeSet<u32> {
+    let unconstrained_foreign_calls: Vec<bool> = program
+        .unconstrained_functions
+        .iter()
+        .map(|function| {
+            function
+                .bytecode
+                .iter()
+                .any(|opcode| matches!(opcode, BrilligOpcode::ForeignCall { .. }))
+        })
+        .collect();
+    let mut makes_foreign_calls: Vec<bool> = program
+        .functions
+        .iter()
+        .map(|circuit| {
+            circuit.opcodes.iter().any(|opcode| {
+                matches!(opcode, Opcode::BrilligCall { id, .. }
+                    if unconstrained_foreign_calls.get(id.as_usize()).copied().unwrap_or(true))
+            })
+        })
+        .collect();
+
+    // Functions which call a function making foreign calls make them too, so propagate to callers until nothing changes.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (index, circuit) in program.functions.iter().enumerate() {
+            if makes_foreign_calls[index] {
+                continue;
+            }
+            let calls_foreign_function = circuit.opcodes.iter().any(|opcode| {
+                matches!(opcode, Opcode::Call { id, .. }
+                    if makes_foreign_calls.get(id.as_usize()).copied().unwrap_or(true))
+            });
+            if calls_foreign_function {
+                makes_foreign_calls[index] = true;
+                changed = true;
+            }
+        }
+    }
+
+    (0..).zip(makes_foreign_calls).filter(|(_, foreign)| !foreign).map(|(index, _)| index).collect()
+}
+
+/// Solves the calls in `batch` on the rayon thread pool.
+fn solve_calls_in_parallel<F, B>(
+    batch: CallBatch<'_, F, B>,
+) -> Vec<Result<SolvedCall<F>, NargoError<F>>>
+where
+    F: AcirField + Send + Sync,
+    B: BlackBoxFunctionSolver<F> + Sync,
+{
+    let CallBatch {
+        functions,
+        unconstrained_functions,
+        blackbox_solver,
+        parallel_calls,
+        call_stack,
+        caller_function_index,
+        calls,
+    } = batch;
+
+    calls
+        .into_par_iter()
+        .map(|call| {
+            let mut foreign_call_executor = NoForeignCalls;
+            let mut executor = ProgramExecutor::new(
+                functions,
+                unconstrained_functions,
+                blackbox_solver,
+                &mut foreign_call_executor,
+            );
+            executor.parallel_calls = Some(parallel_calls);
+            executor.current_function_index = call.id.as_usize();
+            executor.call_stack = call_stack.to_vec();
+            executor.call_stack.push(ResolvedOpcodeLocation {
+                acir_function_index: caller_function_index,
+                opcode_location: OpcodeLocation::Acir(call.opcode_index),
+            });
+
+            let witness = executor.execute_circuit(call.initial_witness)?;
+            Ok(SolvedCall { witness, witness_stack: executor.finalize() })
+        })
+        .collect()
+}
+
+/// The foreign call executor for calls solved on other threads,
+/// which are only made to functions which never make foreign calls.
+struct NoForeignCalls;
+
+impl<F> ForeignCallExecutor<F> for NoForeignCalls {
+    fn execute(
+        &mut self,
+        _foreign_call: &ForeignCallWaitInfo<F>,
+    ) -> Result<ForeignCallResult<F>, ForeignCallError> {
+        unreachable!("Calls solved in parallel should not make foreign calls")
+    }
+}
+
 #[tracing::instrument(level = "trace", skip_all)]
 pub fn execute_program<F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>(
     program: &Program<F>,
@@ -194,3 +482,222 @@ pub fn execute_program<F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCal
 
     Ok(executor.finalize())
 }
+
+/// Executes `program` as in [`execute_program`], additionally collecting [`ExecutionStats`]
+/// across all of the circuits which are executed.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_program_with_stats<
+    F: AcirField,
+    B: BlackBoxFunctionSolver<F>,
+    E: ForeignCallExecutor<F>,
+>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+) -> Result<(WitnessStack<F>, ExecutionStats), NargoError<F>> {
+    let mut executor = ProgramExecutor::new(
+        &program.functions,
+        &program.unconstrained_functions,
+        blackbox_solver,
+        foreign_call_executor,
+    );
+    executor.stats = Some(ExecutionStats::default());
+    let main_witness = executor.execute_circuit(initial_witness)?;
+    executor.witness_stack.push(0, main_witness);
+
+    let stats = executor.stats.take().unwrap_or_default();
+    Ok((executor.finalize(), stats))
+}
+
+/// Executes `program` as in [`execute_program`], additionally counting the Brillig opcodes executed at each call stack.
+///
+/// The returned samples are keyed by the index of the ACIR function which made the Brillig call.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_program_with_profiling<
+    F: AcirField,
+    B: BlackBoxFunctionSolver<F>,
+    E: ForeignCallExecutor<F>,
+>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+) -> Result<(WitnessStack<F>, BTreeMap<usize, Vec<ProfilingSample>>), NargoError<F>> {
+    let mut executor = ProgramExecutor::new(
+        &program.functions,
+        &program.unconstrained_functions,
+        blackbox_solver,
+        foreign_call_executor,
+    );
+    executor.profiling_samples = Some(BTreeMap::new());
+    let main_witness = executor.execute_circuit(initial_witness)?;
+    executor.witness_stack.push(0, main_witness);
+
+    let profiling_samples = executor.profiling_samples.take().unwrap_or_default();
+    Ok((executor.finalize(), profiling_samples))
+}
+
+/// Executes `program` as in [`execute_program`], solving independent ACIR calls concurrently.
+///
+/// Successive `Call` opcodes whose inputs are already known are solved together on the rayon thread pool,
+/// as long as the functions being called make no foreign calls. The resulting witness stack is identical
+/// to the one returned by [`execute_program`].
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_program_in_parallel<F, B, E>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+) -> Result<WitnessStack<F>, NargoError<F>>
+where
+    F: AcirField + Send + Sync,
+    B: BlackBoxFunctionSolver<F> + Sync,
+    E: ForeignCallExecutor<F>,
+{
+    let parallel_calls = ParallelCalls {
+        isolated_functions: functions_without_foreign_calls(program),
+        solve_calls: solve_calls_in_parallel::<F, B>,
+    };
+    let mut executor = ProgramExecutor::new(
+        &program.functions,
+        &program.unconstrained_functions,
+        blackbox_solver,
+        foreign_call_executor,
+    );
+    executor.parallel_calls = Some(&parallel_calls);
+    let main_witness = executor.execute_circuit(initial_witness)?;
+    executor.witness_stack.push(0, main_witness);
+
+    Ok(executor.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use acvm::{
+        acir::{
+            circuit::{opcodes::AcirFunctionId, Circuit, Opcode, Program, PublicInputs},
+            native_types::{Expression, Witness, WitnessMap},
+        },
+        blackbox_solver::StubbedBlackBoxSolver,
+        pwg::AcirCallWaitInfo,
+        FieldElement,
+    };
+
+    use super::{
+        execute_program, execute_program_in_parallel, functions_without_foreign_calls,
+        independent_calls, NoForeignCalls,
+    };
+
+    fn linear_combination(terms: &[(i128, u32)], q_c: i128) -> Expression<FieldElement> {
+        Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: terms
+                .iter()
+                .map(|(coefficient, witness)| (FieldElement::from(*coefficient), Witness(*witness)))
+                .collect(),
+            q_c: FieldElement::from(q_c),
+        }
+    }
+
+    fn call(id: u32, inputs: &[u32], outputs: &[u32]) -> Opcode<FieldElement> {
+        Opcode::Call {
+            id: AcirFunctionId(id),
+            inputs: inputs.iter().copied().map(Witness).collect(),
+            outputs: outputs.iter().copied().map(Witness).collect(),
+            predicate: None,
+        }
+    }
+
+    /// A program whose main function makes two independent calls followed by one depending on the first,
+    /// where the function being called makes a call of its own.
+    fn program() -> Program<FieldElement> {
+        let main = Circuit {
+            current_witness_index: 4,
+            opcodes: vec![
+                call(1, &[0], &[1]),
+                call(1, &[0], &[2]),
+                call(1, &[1], &[3]),
+                Opcode::AssertZero(linear_combination(&[(1, 1), (1, 2), (1, 3), (-1, 4)], 0)),
+            ],
+            private_parameters: BTreeSet::from([Witness(0)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(4)])),
+            ..Circuit::default()
+        };
+        // Returns `2 * x + 1`
+        let double_plus_one = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![
+                call(2, &[0], &[1]),
+                Opcode::AssertZero(linear_combination(&[(1, 1), (-1, 2)], 1)),
+            ],
+            private_parameters: BTreeSet::from([Witness(0)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(2)])),
+            ..Circuit::default()
+        };
+        // Returns `2 * x`
+        let double = Circuit {
+            current_witness_index: 1,
+            opcodes: vec![Opcode::AssertZero(linear_combination(&[(2, 0), (-1, 1)], 0))],
+            private_parameters: BTreeSet::from([Witness(0)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(1)])),
+            ..Circuit::default()
+        };
+        Program {
+            functions: vec![main, double_plus_one, double],
+            unconstrained_functions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn collects_calls_with_known_inputs() {
+        let program = program();
+        let witness_map =
+            WitnessMap::from(BTreeMap::from([(Witness(0), FieldElement::from(3u128))]));
+        let call_info =
+            AcirCallWaitInfo { id: AcirFunctionId(1), initial_witness: witness_map.clone() };
+
+        let isolated_functions = functions_without_foreign_calls(&program);
+        assert_eq!(isolated_functions, BTreeSet::from([0, 1, 2]));
+
+        let calls = independent_calls(
+            &program.functions[0].opcodes,
+            &witness_map,
+            0,
+            &call_info,
+            &isolated_functions,
+        );
+        let opcode_indices: Vec<usize> = calls.iter().map(|call| call.opcode_index).collect();
+        assert_eq!(opcode_indices, vec![0, 1]);
+    }
+
+    #[test]
+    fn parallel_execution_matches_serial_execution() {
+        let program = program();
+        let initial_witness =
+            WitnessMap::from(BTreeMap::from([(Witness(0), FieldElement::from(3u128))]));
+
+        let serial = execute_program(
+            &program,
+            initial_witness.clone(),
+            &StubbedBlackBoxSolver,
+            &mut NoForeignCalls,
+        )
+        .expect("should execute");
+        let parallel = execute_program_in_parallel(
+            &program,
+            initial_witness,
+            &StubbedBlackBoxSolver,
+            &mut NoForeignCalls,
+        )
+        .expect("should execute");
+
+        assert_eq!(parallel, serial);
+        assert_eq!(parallel.length(), 7);
+        let main_witness = &parallel.peek().expect("should have main witness").witness;
+        // 7 + 7 + 15
+        assert_eq!(main_witness[&Witness(4)], FieldElement::from(29u128));
+    }
+}
@@ -28,7 +28,8 @@ fn optimize_program_internal(
         .into_iter()
         .enumerate()
         .map(|(i, function)| {
-            let (optimized_circuit, location_map) = acvm::compiler::optimize(function);
+            let (optimized_circuit, location_map) =
+                acvm::compiler::optimize_with_brillig(function, &program.unconstrained_functions);
             debug[i].update_acir(location_map);
             optimized_circuit
         })
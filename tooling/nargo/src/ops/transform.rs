@@ -43,8 +43,11 @@ fn transform_program_internal(
         .into_iter()
         .enumerate()
         .map(|(i, function)| {
-            let (optimized_circuit, location_map) =
-                acvm::compiler::compile(function, expression_width);
+            let (optimized_circuit, location_map) = acvm::compiler::compile_with_brillig(
+                function,
+                expression_width,
+                &program.unconstrained_functions,
+            );
             debug[i].update_acir(location_map);
             optimized_circuit
         })
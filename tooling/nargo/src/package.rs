@@ -1,7 +1,10 @@
 use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
 
 use acvm::acir::circuit::ExpressionWidth;
-use noirc_frontend::graph::CrateName;
+use noirc_frontend::{
+    graph::CrateName,
+    hir::comptime::{normalize_path, ComptimeInputs},
+};
 
 use crate::constants::PROVER_INPUT_FILE;
 
@@ -42,6 +45,16 @@ impl Dependency {
     }
 }
 
+/// Inputs from outside of the program which a package's comptime code may read,
+/// as listed in the `[comptime]` section of its `Nargo.toml`.
+#[derive(Debug, Default, Clone)]
+pub struct ComptimeAllowlist {
+    /// Paths of files, relative to the package root
+    pub files: Vec<String>,
+    /// Names of environment variables
+    pub env: Vec<String>,
+}
+
 #[derive(Clone)]
 pub struct Package {
     pub version: Option<String>,
@@ -53,6 +66,7 @@ pub struct Package {
     pub name: CrateName,
     pub dependencies: BTreeMap<CrateName, Dependency>,
     pub expression_width: Option<ExpressionWidth>,
+    pub comptime_allowlist: ComptimeAllowlist,
 }
 
 impl Package {
@@ -62,6 +76,22 @@ impl Package {
         self.root_dir.join(format!("{PROVER_INPUT_FILE}.toml"))
     }
 
+    /// Reads each of the package's allowlisted comptime inputs so they can be handed to the compiler
+    pub fn comptime_inputs(&self) -> ComptimeInputs {
+        let allowlist = &self.comptime_allowlist;
+        let files = allowlist.files.iter().map(|path| {
+            let contents = std::fs::read(self.root_dir.join(path)).ok();
+            (normalize_path(path), contents)
+        });
+        let env = allowlist.env.iter().map(|name| (name.clone(), std::env::var(name).ok()));
+
+        ComptimeInputs {
+            root_dir: self.root_dir.clone(),
+            files: files.collect(),
+            env: env.collect(),
+        }
+    }
+
     pub fn is_binary(&self) -> bool {
         self.package_type == PackageType::Binary
     }
@@ -0,0 +1,240 @@
+use std::{fmt::Display, str::FromStr};
+
+use acvm::{
+    acir::native_types::{WitnessStack, WitnessStackError},
+    AcirField, FieldElement,
+};
+use noirc_abi::Abi;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::constants::WITNESS_EXT;
+
+/// The formats which a witness stack can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WitnessFormat {
+    /// Gzipped bincode, as read by backends.
+    #[default]
+    Bincode,
+    Json,
+    Toml,
+}
+
+impl WitnessFormat {
+    /// The extension of files containing witnesses in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            WitnessFormat::Bincode => WITNESS_EXT,
+            WitnessFormat::Json => "json",
+            WitnessFormat::Toml => "toml",
+        }
+    }
+}
+
+impl Display for WitnessFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WitnessFormat::Bincode => write!(f, "bincode"),
+            WitnessFormat::Json => write!(f, "json"),
+            WitnessFormat::Toml => write!(f, "toml"),
+        }
+    }
+}
+
+impl FromStr for WitnessFormat {
+    type Err = WitnessFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bincode" => Ok(WitnessFormat::Bincode),
+            "json" => Ok(WitnessFormat::Json),
+            "toml" => Ok(WitnessFormat::Toml),
+            _ => Err(WitnessFormatError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WitnessFormatError {
+    #[error("Unknown witness format `{0}`, expected one of bincode, json or toml")]
+    UnknownFormat(String),
+
+    #[error(transparent)]
+    Bincode(#[from] WitnessStackError),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Toml(#[from] toml::ser::Error),
+}
+
+#[derive(Serialize)]
+struct AnnotatedWitnessStack {
+    stack: Vec<AnnotatedStackItem>,
+}
+
+#[derive(Serialize)]
+struct AnnotatedStackItem {
+    /// The index of the ACIR function which the witnesses belong to.
+    function: u32,
+    witnesses: Vec<AnnotatedWitness>,
+}
+
+#[derive(Serialize)]
+struct AnnotatedWitness {
+    witness: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    value: String,
+}
+
+/// Pairs each witness in the stack with its value as a hex string and, for the witnesses of the
+/// main function, the name of the ABI parameter or return value it encodes.
+///
+/// Stack items are listed from the top of the stack, starting with the main function.
+fn annotate_witness_stack(
+    witness_stack: &WitnessStack<FieldElement>,
+    abi: Option<&Abi>,
+) -> AnnotatedWitnessStack {
+    let witness_names = abi.map(Abi::witness_names).unwrap_or_default();
+
+    let mut witness_stack = witness_stack.clone();
+    let mut stack = Vec::with_capacity(witness_stack.length());
+    while let Some(item) = witness_stack.pop() {
+        let witnesses = item
+            .witness
+            .into_iter()
+            .map(|(witness, value)| AnnotatedWitness {
+                witness: witness.witness_index(),
+                name: (item.index == 0).then(|| witness_names.get(&witness).cloned()).flatten(),
+                value: format!("0x{}", value.to_hex()),
+            })
+            .collect();
+        stack.push(AnnotatedStackItem { function: item.index, witnesses });
+    }
+    AnnotatedWitnessStack { stack }
+}
+
+/// Serializes the witness stack in the given `format`.
+///
+/// The JSON and TOML formats name the witnesses of the main function after the parameters in `abi`, if one is provided.
+pub fn serialize_witness_stack(
+    witness_stack: WitnessStack<FieldElement>,
+    format: WitnessFormat,
+    abi: Option<&Abi>,
+) -> Result<Vec<u8>, WitnessFormatError> {
+    match format {
+        WitnessFormat::Bincode => Ok(witness_stack.try_into()?),
+        WitnessFormat::Json => {
+            let annotated = annotate_witness_stack(&witness_stack, abi);
+            Ok(serde_json::to_vec_pretty(&annotated)?)
+        }
+        WitnessFormat::Toml => {
+            let annotated = annotate_witness_stack(&witness_stack, abi);
+            Ok(toml::to_string(&annotated)?.into_bytes())
+        }
+    }
+}
+
+/// Formats the witness stack as a table, naming the witnesses of the main function after the parameters in `abi`.
+pub fn format_witness_stack(
+    witness_stack: &WitnessStack<FieldElement>,
+    abi: Option<&Abi>,
+) -> String {
+    let annotated = annotate_witness_stack(witness_stack, abi);
+
+    let mut output = String::new();
+    for item in annotated.stack {
+        output += &format!("func {}: {} witnesses\n", item.function, item.witnesses.len());
+
+        let witness_width = item
+            .witnesses
+            .iter()
+            .map(|witness| witness.witness.to_string().len() + 1)
+            .max()
+            .unwrap_or(0);
+        let name_width = item
+            .witnesses
+            .iter()
+            .filter_map(|witness| witness.name.as_ref().map(String::len))
+            .max()
+            .unwrap_or(0);
+        for witness in item.witnesses {
+            let label = format!("w{}", witness.witness);
+            if name_width == 0 {
+                output += &format!("  {label:<witness_width$}  {}\n", witness.value);
+            } else {
+                let name = witness.name.unwrap_or_default();
+                output +=
+                    &format!("  {label:<witness_width$}  {name:<name_width$}  {}\n", witness.value);
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::native_types::{Witness, WitnessMap, WitnessStack},
+        FieldElement,
+    };
+    use noirc_abi::{Abi, AbiParameter, AbiType, AbiVisibility};
+
+    use super::{format_witness_stack, serialize_witness_stack, WitnessFormat};
+
+    fn witness_stack() -> WitnessStack<FieldElement> {
+        let callee_witness = WitnessMap::from(std::collections::BTreeMap::from([(
+            Witness(0),
+            FieldElement::from(7u128),
+        )]));
+        let main_witness = WitnessMap::from(std::collections::BTreeMap::from([
+            (Witness(0), FieldElement::from(1u128)),
+            (Witness(1), FieldElement::from(2u128)),
+            (Witness(2), FieldElement::from(3u128)),
+        ]));
+
+        let mut witness_stack = WitnessStack::default();
+        witness_stack.push(1, callee_witness);
+        witness_stack.push(0, main_witness);
+        witness_stack
+    }
+
+    fn abi() -> Abi {
+        Abi {
+            parameters: vec![AbiParameter {
+                name: "xs".to_string(),
+                typ: AbiType::Array { length: 2, typ: Box::new(AbiType::Field) },
+                visibility: AbiVisibility::Private,
+            }],
+            ..Abi::default()
+        }
+    }
+
+    #[test]
+    fn names_main_witnesses() {
+        let one = format!("0x{}", "0".repeat(63) + "1");
+        let two = format!("0x{}", "0".repeat(63) + "2");
+        let three = format!("0x{}", "0".repeat(63) + "3");
+        let seven = format!("0x{}", "0".repeat(63) + "7");
+
+        let expected = format!(
+            "func 0: 3 witnesses\n  w0  xs[0]  {one}\n  w1  xs[1]  {two}\n  w2         {three}\nfunc 1: 1 witnesses\n  w0  {seven}\n"
+        );
+        assert_eq!(format_witness_stack(&witness_stack(), Some(&abi())), expected);
+    }
+
+    #[test]
+    fn serializes_annotated_json() {
+        let json = serialize_witness_stack(witness_stack(), WitnessFormat::Json, Some(&abi()))
+            .expect("should serialize");
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+
+        assert_eq!(json["stack"][0]["function"], 0);
+        assert_eq!(json["stack"][0]["witnesses"][1]["name"], "xs[1]");
+        assert!(json["stack"][0]["witnesses"][2].get("name").is_none());
+        assert_eq!(json["stack"][1]["function"], 1);
+        assert!(json["stack"][1]["witnesses"][0].get("name").is_none());
+    }
+}
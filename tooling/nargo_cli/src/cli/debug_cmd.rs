@@ -10,6 +10,7 @@ use nargo::constants::PROVER_INPUT_FILE;
 use nargo::errors::CompileError;
 use nargo::ops::{compile_program, compile_program_with_debug_instrumenter, report_errors};
 use nargo::package::Package;
+use nargo::witness::WitnessFormat;
 use nargo::workspace::Workspace;
 use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
@@ -190,8 +191,13 @@ fn run_async(
             }
 
             if let Some(witness_name) = witness_name {
-                let witness_path =
-                    save_witness_to_dir(solved_witness_stack, witness_name, target_dir)?;
+                let witness_path = save_witness_to_dir(
+                    solved_witness_stack,
+                    witness_name,
+                    target_dir,
+                    WitnessFormat::Bincode,
+                    None,
+                )?;
 
                 println!("[{}] Witness saved to {}", package.name, witness_path.display());
             }
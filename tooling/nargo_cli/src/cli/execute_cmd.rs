@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use acvm::acir::native_types::WitnessStack;
+use acvm::pwg::ExecutionStats;
 use acvm::FieldElement;
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
@@ -9,6 +10,7 @@ use nargo::constants::PROVER_INPUT_FILE;
 use nargo::errors::try_to_diagnose_runtime_error;
 use nargo::ops::DefaultForeignCallExecutor;
 use nargo::package::Package;
+use nargo::witness::WitnessFormat;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::input_parser::{Format, InputValue};
 use noirc_abi::InputMap;
@@ -47,6 +49,19 @@ pub(crate) struct ExecuteCommand {
     /// JSON RPC url to solve oracle calls
     #[clap(long)]
     oracle_resolver: Option<String>,
+
+    /// Print statistics about the opcodes, black box functions and foreign calls executed
+    #[clap(long)]
+    stats: bool,
+
+    /// Solve independent calls to non-entrypoint functions concurrently
+    #[clap(long, conflicts_with = "stats")]
+    parallel_calls: bool,
+
+    /// The format to write the witness in: bincode, json or toml.
+    /// The json and toml formats name witnesses after the program's parameters
+    #[clap(long, default_value = "bincode")]
+    witness_format: WitnessFormat,
 }
 
 pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliError> {
@@ -69,22 +84,34 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
         let program_artifact_path = workspace.package_build_path(package);
         let program: CompiledProgram =
             read_program_from_file(program_artifact_path.clone())?.into();
+        let abi = program.abi.clone();
 
-        let (return_value, witness_stack) = execute_program_and_decode(
+        let (return_value, witness_stack, stats) = execute_program_and_decode(
             program,
             package,
             &args.prover_name,
             args.oracle_resolver.as_deref(),
             Some(workspace.root_dir.clone()),
             Some(package.name.to_string()),
+            args.stats,
+            args.parallel_calls,
         )?;
 
         println!("[{}] Circuit witness successfully solved", package.name);
+        if let Some(stats) = stats {
+            println!("[{}] Execution statistics:\n{stats}", package.name);
+        }
         if let Some(return_value) = return_value {
             println!("[{}] Circuit output: {return_value:?}", package.name);
         }
         if let Some(witness_name) = &args.witness_name {
-            let witness_path = save_witness_to_dir(witness_stack, witness_name, target_dir)?;
+            let witness_path = save_witness_to_dir(
+                witness_stack,
+                witness_name,
+                target_dir,
+                args.witness_format,
+                Some(&abi),
+            )?;
 
             println!("[{}] Witness saved to {}", package.name, witness_path.display());
         }
@@ -92,6 +119,7 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_program_and_decode(
     program: CompiledProgram,
     package: &Package,
@@ -99,18 +127,27 @@ fn execute_program_and_decode(
     foreign_call_resolver_url: Option<&str>,
     root_path: Option<PathBuf>,
     package_name: Option<String>,
-) -> Result<(Option<InputValue>, WitnessStack<FieldElement>), CliError> {
+    collect_stats: bool,
+    parallel_calls: bool,
+) -> Result<(Option<InputValue>, WitnessStack<FieldElement>, Option<ExecutionStats>), CliError> {
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
-    let witness_stack =
-        execute_program(&program, &inputs_map, foreign_call_resolver_url, root_path, package_name)?;
+    let (witness_stack, stats) = execute_program(
+        &program,
+        &inputs_map,
+        foreign_call_resolver_url,
+        root_path,
+        package_name,
+        collect_stats,
+        parallel_calls,
+    )?;
     // Get the entry point witness for the ABI
     let main_witness =
         &witness_stack.peek().expect("Should have at least one witness on the stack").witness;
     let (_, return_value) = program.abi.decode(main_witness)?;
 
-    Ok((return_value, witness_stack))
+    Ok((return_value, witness_stack, stats))
 }
 
 pub(crate) fn execute_program(
@@ -119,20 +156,38 @@ pub(crate) fn execute_program(
     foreign_call_resolver_url: Option<&str>,
     root_path: Option<PathBuf>,
     package_name: Option<String>,
-) -> Result<WitnessStack<FieldElement>, CliError> {
+    collect_stats: bool,
+    parallel_calls: bool,
+) -> Result<(WitnessStack<FieldElement>, Option<ExecutionStats>), CliError> {
     let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
 
-    let solved_witness_stack_err = nargo::ops::execute_program(
-        &compiled_program.program,
-        initial_witness,
-        &Bn254BlackBoxSolver,
-        &mut DefaultForeignCallExecutor::new(
-            true,
-            foreign_call_resolver_url,
-            root_path,
-            package_name,
-        ),
-    );
+    let mut foreign_call_executor =
+        DefaultForeignCallExecutor::new(true, foreign_call_resolver_url, root_path, package_name);
+    let solved_witness_stack_err = if collect_stats {
+        nargo::ops::execute_program_with_stats(
+            &compiled_program.program,
+            initial_witness,
+            &Bn254BlackBoxSolver,
+            &mut foreign_call_executor,
+        )
+        .map(|(witness_stack, stats)| (witness_stack, Some(stats)))
+    } else if parallel_calls {
+        nargo::ops::execute_program_in_parallel(
+            &compiled_program.program,
+            initial_witness,
+            &Bn254BlackBoxSolver,
+            &mut foreign_call_executor,
+        )
+        .map(|witness_stack| (witness_stack, None))
+    } else {
+        nargo::ops::execute_program(
+            &compiled_program.program,
+            initial_witness,
+            &Bn254BlackBoxSolver,
+            &mut foreign_call_executor,
+        )
+        .map(|witness_stack| (witness_stack, None))
+    };
     match solved_witness_stack_err {
         Ok(solved_witness_stack) => Ok(solved_witness_stack),
         Err(err) => {
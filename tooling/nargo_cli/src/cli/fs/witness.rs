@@ -1,22 +1,35 @@
 use std::path::{Path, PathBuf};
 
 use acvm::{acir::native_types::WitnessStack, FieldElement};
-use nargo::constants::WITNESS_EXT;
+use nargo::witness::{serialize_witness_stack, WitnessFormat};
+use noirc_abi::Abi;
 
 use super::{create_named_dir, write_to_file};
 use crate::errors::FilesystemError;
 
+/// Writes the witness stack in the given `format`, naming witnesses after the parameters in `abi` where the format allows.
 pub(crate) fn save_witness_to_dir<P: AsRef<Path>>(
     witness_stack: WitnessStack<FieldElement>,
     witness_name: &str,
     witness_dir: P,
+    format: WitnessFormat,
+    abi: Option<&Abi>,
 ) -> Result<PathBuf, FilesystemError> {
     create_named_dir(witness_dir.as_ref(), "witness");
-    let witness_path = witness_dir.as_ref().join(witness_name).with_extension(WITNESS_EXT);
+    let witness_path = witness_dir.as_ref().join(witness_name).with_extension(format.extension());
 
-    let buf: Vec<u8> = witness_stack.try_into()?;
+    let buf = serialize_witness_stack(witness_stack, format, abi)?;
 
     write_to_file(buf.as_slice(), &witness_path);
 
     Ok(witness_path)
 }
+
+pub(crate) fn read_witness_from_file(
+    witness_path: &Path,
+) -> Result<WitnessStack<FieldElement>, FilesystemError> {
+    let buf = std::fs::read(witness_path)
+        .map_err(|_| FilesystemError::PathNotValid(witness_path.to_path_buf()))?;
+
+    Ok(WitnessStack::try_from(buf.as_slice())?)
+}
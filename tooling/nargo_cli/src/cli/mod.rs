@@ -20,6 +20,7 @@ mod init_cmd;
 mod lsp_cmd;
 mod new_cmd;
 mod test_cmd;
+mod witness_cmd;
 
 const GIT_HASH: &str = env!("GIT_COMMIT");
 const IS_DIRTY: &str = env!("GIT_DIRTY");
@@ -69,6 +70,7 @@ enum NargoCommand {
     Lsp(lsp_cmd::LspCommand),
     #[command(hide = true)]
     Dap(dap_cmd::DapCommand),
+    Witness(witness_cmd::WitnessCommand),
 }
 
 #[cfg(not(feature = "codegen-docs"))]
@@ -83,7 +85,11 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
     // Search through parent directories to find package root if necessary.
     if !matches!(
         command,
-        NargoCommand::New(_) | NargoCommand::Init(_) | NargoCommand::Lsp(_) | NargoCommand::Dap(_)
+        NargoCommand::New(_)
+            | NargoCommand::Init(_)
+            | NargoCommand::Lsp(_)
+            | NargoCommand::Dap(_)
+            | NargoCommand::Witness(_)
     ) {
         config.program_dir = find_package_root(&config.program_dir)?;
     }
@@ -101,6 +107,7 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::Lsp(args) => lsp_cmd::run(args, config),
         NargoCommand::Dap(args) => dap_cmd::run(args, config),
         NargoCommand::Fmt(args) => fmt_cmd::run(args, config),
+        NargoCommand::Witness(args) => witness_cmd::run(args, config),
     }?;
 
     Ok(())
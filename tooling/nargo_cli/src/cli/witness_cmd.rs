@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use nargo::witness::{format_witness_stack, serialize_witness_stack, WitnessFormat};
+
+use super::fs::{program::read_program_from_file, witness::read_witness_from_file};
+use super::NargoConfig;
+use crate::errors::{CliError, FilesystemError};
+
+/// Work with witness files written by `nargo execute`
+#[derive(Debug, Clone, Args)]
+pub(crate) struct WitnessCommand {
+    #[command(subcommand)]
+    command: WitnessSubcommand,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum WitnessSubcommand {
+    Inspect(InspectCommand),
+}
+
+/// Decodes a gzipped witness file and prints the value of each witness
+#[derive(Debug, Clone, Args)]
+struct InspectCommand {
+    /// The path to the witness file
+    witness_path: PathBuf,
+
+    /// The path to the program artifact JSON file, used to name witnesses after the program's parameters
+    #[clap(long)]
+    artifact_path: Option<PathBuf>,
+
+    /// Print the witnesses as json or toml instead of as a table
+    #[clap(long)]
+    format: Option<WitnessFormat>,
+}
+
+pub(crate) fn run(args: WitnessCommand, _config: NargoConfig) -> Result<(), CliError> {
+    match args.command {
+        WitnessSubcommand::Inspect(args) => run_inspect(args),
+    }
+}
+
+fn run_inspect(args: InspectCommand) -> Result<(), CliError> {
+    let witness_stack = read_witness_from_file(&args.witness_path)?;
+    let abi = match &args.artifact_path {
+        Some(artifact_path) => Some(read_program_from_file(artifact_path)?.abi),
+        None => None,
+    };
+
+    match args.format {
+        None => print!("{}", format_witness_stack(&witness_stack, abi.as_ref())),
+        Some(WitnessFormat::Bincode) => {
+            return Err(CliError::Generic(
+                "Witnesses can only be printed as json or toml".to_string(),
+            ));
+        }
+        Some(format) => {
+            let buf = serialize_witness_stack(witness_stack, format, abi.as_ref())
+                .map_err(FilesystemError::from)?;
+            println!("{}", String::from_utf8(buf).expect("json and toml should be valid utf-8"));
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use acvm::{acir::native_types::WitnessStackError, FieldElement};
-use nargo::{errors::CompileError, NargoError};
+use nargo::{errors::CompileError, witness::WitnessFormatError, NargoError};
 use nargo_toml::ManifestError;
 use noir_debugger::errors::DapError;
 use noirc_abi::errors::{AbiError, InputParserError};
@@ -24,6 +24,10 @@ pub(crate) enum FilesystemError {
     #[error(transparent)]
     WitnessStackSerialization(#[from] WitnessStackError),
 
+    /// Error writing a witness in a human-readable format
+    #[error(transparent)]
+    WitnessFormat(#[from] WitnessFormatError),
+
     #[error("Error: could not deserialize build program: {0}")]
     ProgramSerializationError(String),
 }
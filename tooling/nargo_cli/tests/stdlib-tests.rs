@@ -7,7 +7,7 @@ use noirc_frontend::hir::FunctionNameMatch;
 
 use nargo::{
     ops::{report_errors, run_test, TestStatus},
-    package::{Package, PackageType},
+    package::{ComptimeAllowlist, Package, PackageType},
     parse_all, prepare_package,
 };
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
@@ -28,6 +28,7 @@ fn run_stdlib_tests() {
         name: "stdlib".parse().unwrap(),
         dependencies: BTreeMap::new(),
         expression_width: None,
+        comptime_allowlist: ComptimeAllowlist::default(),
     };
 
     let (mut context, dummy_crate_id) =
@@ -42,6 +42,7 @@ pub(crate) fn rewrite(
 
             format!("{op}{}", rewrite_sub_expr(visitor, shape, prefix.rhs))
         }
+        ExpressionKind::Try(expr) => format!("{}?", rewrite_sub_expr(visitor, shape, *expr)),
         ExpressionKind::Cast(cast) => {
             format!("{} as {}", rewrite_sub_expr(visitor, shape, cast.lhs), cast.r#type)
         }
@@ -164,6 +165,7 @@ pub(crate) fn rewrite(
         }
         ExpressionKind::Variable(path) => rewrite_path(visitor, shape, path),
         ExpressionKind::Lambda(_) => visitor.slice(span).to_string(),
+        ExpressionKind::Match(_) => visitor.slice(span).to_string(),
         ExpressionKind::Quote(_) => visitor.slice(span).to_string(),
         ExpressionKind::Comptime(block, block_span) => {
             format!("comptime {}", rewrite_block(visitor, block, block_span))
@@ -169,8 +169,11 @@ impl super::FmtVisitor<'_> {
                     let after_brace = self.span_after(span, Token::LeftBrace).start();
                     self.last_position = after_brace;
 
-                    let keyword = if module.is_contract { "contract" } else { "mod" };
+                    let keyword = if module.is_contract { Keyword::Contract } else { Keyword::Mod };
 
+                    // Keep any attributes on the module as they were written
+                    let keyword_start = self.span_before(span, Token::Keyword(keyword)).start();
+                    self.push_str(self.slice(span.start()..keyword_start));
                     self.push_str(&format!("{keyword} {name} "));
 
                     if module.contents.items.is_empty() {
@@ -223,6 +226,7 @@ impl super::FmtVisitor<'_> {
                     self.last_position = span.end();
                 }
                 ItemKind::Struct(_)
+                | ItemKind::Enum(_)
                 | ItemKind::Trait(_)
                 | ItemKind::TraitImpl(_)
                 | ItemKind::TypeAlias(_)
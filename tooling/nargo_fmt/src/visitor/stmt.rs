@@ -97,12 +97,28 @@ impl super::FmtVisitor<'_> {
                 let result = format!("for {identifier} in {range} {block}");
                 self.push_rewrite(result, span);
             }
+            StatementKind::While(while_stmt) => {
+                let condition = rewrite::sub_expr(self, self.shape(), while_stmt.condition);
+                let block = rewrite::sub_expr(self, self.shape(), while_stmt.block);
+
+                let result = format!("while {condition} {block}");
+                self.push_rewrite(result, span);
+            }
+            StatementKind::Loop(block) => {
+                let block = rewrite::sub_expr(self, self.shape(), block);
+                self.push_rewrite(format!("loop {block}"), span);
+            }
             StatementKind::Assign(_) => {
                 self.push_rewrite(self.slice(span).to_string(), span);
             }
             StatementKind::Error => unreachable!(),
             StatementKind::Break => self.push_rewrite("break;".into(), span),
             StatementKind::Continue => self.push_rewrite("continue;".into(), span),
+            StatementKind::Return(None) => self.push_rewrite("return;".into(), span),
+            StatementKind::Return(Some(value)) => {
+                let value = rewrite::sub_expr(self, self.shape(), value);
+                self.push_rewrite(format!("return {value};"), span);
+            }
             StatementKind::Comptime(statement) => self.visit_stmt(statement.kind, span, is_last),
             StatementKind::Interned(_) => unreachable!(
                 "StatementKind::Resolved should only emitted by the comptime interpreter"
@@ -48,6 +48,11 @@ pub enum ManifestError {
     #[error("Invalid directory path {directory} in {toml}: It must point to a subdirectory")]
     InvalidDirectory { toml: PathBuf, directory: PathBuf },
 
+    #[error(
+        "Invalid comptime file path {path} in {toml}: It must point to a file within the package"
+    )]
+    InvalidComptimeFile { toml: PathBuf, path: PathBuf },
+
     /// Encountered error while downloading git repository.
     #[error("{0}")]
     GitError(String),
@@ -11,7 +11,7 @@ use std::{
 use errors::SemverError;
 use fm::{NormalizePath, FILE_EXTENSION};
 use nargo::{
-    package::{Dependency, Package, PackageType},
+    package::{ComptimeAllowlist, Dependency, Package, PackageType},
     workspace::Workspace,
 };
 use noirc_driver::parse_expression_width;
@@ -118,6 +118,8 @@ struct PackageConfig {
     package: PackageMetadata,
     #[serde(default)]
     dependencies: BTreeMap<String, DependencyConfig>,
+    #[serde(default)]
+    comptime: ComptimeConfig,
 }
 
 impl PackageConfig {
@@ -210,6 +212,8 @@ impl PackageConfig {
             })
             .map_or(Ok(None), |res| res.map(Some))?;
 
+        let comptime_allowlist = self.comptime.resolve_to_allowlist(root_dir)?;
+
         Ok(Package {
             version: self.package.version.clone(),
             compiler_required_version: self.package.compiler_version.clone(),
@@ -219,6 +223,7 @@ impl PackageConfig {
             name,
             dependencies,
             expression_width,
+            comptime_allowlist,
         })
     }
 }
@@ -290,6 +295,51 @@ struct PackageMetadata {
     expression_width: Option<String>,
 }
 
+/// The inputs from outside of the program which comptime code may read
+#[derive(Default, Debug, Deserialize, Clone)]
+struct ComptimeConfig {
+    /// Files which may be read with `std::meta::read_file`, relative to the package root
+    #[serde(default)]
+    files: Vec<String>,
+    /// Environment variables which may be read with `std::meta::env_var`
+    #[serde(default)]
+    env: Vec<String>,
+}
+
+impl ComptimeConfig {
+    fn resolve_to_allowlist(&self, root_dir: &Path) -> Result<ComptimeAllowlist, ManifestError> {
+        let canonical_root_dir = resolve_symlinks(root_dir);
+        for file in &self.files {
+            // Symlinks are resolved so that a link within the package can't be used to read a file outside of it.
+            let path = resolve_symlinks(&root_dir.join(file));
+            if Path::new(file).is_absolute() || !path.starts_with(&canonical_root_dir) {
+                return Err(ManifestError::InvalidComptimeFile {
+                    toml: root_dir.join("Nargo.toml"),
+                    path: file.into(),
+                });
+            }
+        }
+
+        Ok(ComptimeAllowlist { files: self.files.clone(), env: self.env.clone() })
+    }
+}
+
+/// Resolves any symlinks in `path`.
+///
+/// Allowlisted files don't need to exist yet, so any components after the last existing ancestor of `path`
+/// are appended to that ancestor's canonical path.
+fn resolve_symlinks(path: &Path) -> PathBuf {
+    let path = path.normalize();
+    for ancestor in path.ancestors() {
+        if let Ok(canonical_ancestor) = ancestor.canonicalize() {
+            let remainder =
+                path.strip_prefix(ancestor).expect("ancestor should be a prefix of path");
+            return canonical_ancestor.join(remainder);
+        }
+    }
+    path
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(untagged)]
 /// Enum representing the different types of ways to
@@ -559,3 +609,58 @@ fn parse_package_expression_width_toml() {
     assert!(Config::try_from(String::from(src)).is_ok());
     assert!(Config::try_from(src).is_ok());
 }
+
+#[test]
+fn parse_package_comptime_toml() {
+    let src = r#"
+    [package]
+    name = "test"
+    type = "bin"
+    authors = [""]
+
+    [comptime]
+    files = ["keys/vk.bin"]
+    env = ["TABLE_SIZE"]
+    "#;
+
+    let Config::Package { package_config } = Config::try_from(src).unwrap() else {
+        panic!("Expected a package config");
+    };
+    assert_eq!(package_config.comptime.files, vec!["keys/vk.bin".to_string()]);
+    assert_eq!(package_config.comptime.env, vec!["TABLE_SIZE".to_string()]);
+}
+
+#[test]
+fn comptime_files_must_be_within_the_package() {
+    let root_dir = Path::new("/package");
+    let config = ComptimeConfig { files: vec!["../secret.txt".to_string()], env: Vec::new() };
+    assert!(matches!(
+        config.resolve_to_allowlist(root_dir),
+        Err(ManifestError::InvalidComptimeFile { .. })
+    ));
+
+    let config = ComptimeConfig { files: vec!["keys/./vk.bin".to_string()], env: Vec::new() };
+    assert!(config.resolve_to_allowlist(root_dir).is_ok());
+}
+
+#[cfg(unix)]
+#[test]
+fn comptime_files_must_not_escape_the_package_through_symlinks() {
+    let package_dir = tempfile::tempdir().unwrap();
+    let outside_dir = tempfile::tempdir().unwrap();
+    std::fs::write(outside_dir.path().join("secret.txt"), "secret").unwrap();
+    std::os::unix::fs::symlink(outside_dir.path(), package_dir.path().join("link")).unwrap();
+
+    let config = ComptimeConfig { files: vec!["link/secret.txt".to_string()], env: Vec::new() };
+    assert!(matches!(
+        config.resolve_to_allowlist(package_dir.path()),
+        Err(ManifestError::InvalidComptimeFile { .. })
+    ));
+
+    let config =
+        ComptimeConfig { files: vec!["link/not_created_yet.txt".to_string()], env: Vec::new() };
+    assert!(matches!(
+        config.resolve_to_allowlist(package_dir.path()),
+        Err(ManifestError::InvalidComptimeFile { .. })
+    ));
+}
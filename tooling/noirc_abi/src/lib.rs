@@ -367,6 +367,44 @@ impl Abi {
 
         Ok((public_inputs_map, return_value))
     }
+
+    /// Names each witness which the parameters and return value are encoded into,
+    /// e.g. `x`, `array[1]`, `point.y` or `return.0`.
+    pub fn witness_names(&self) -> BTreeMap<Witness, String> {
+        fn flatten(name: String, typ: &AbiType, names: &mut Vec<String>) {
+            match typ {
+                AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => names.push(name),
+                AbiType::Array { length, typ } => {
+                    for index in 0..*length {
+                        flatten(format!("{name}[{index}]"), typ, names);
+                    }
+                }
+                AbiType::String { length } => {
+                    names.extend((0..*length).map(|index| format!("{name}[{index}]")));
+                }
+                AbiType::Struct { fields, .. } => {
+                    for (field_name, typ) in fields {
+                        flatten(format!("{name}.{field_name}"), typ, names);
+                    }
+                }
+                AbiType::Tuple { fields } => {
+                    for (index, typ) in fields.iter().enumerate() {
+                        flatten(format!("{name}.{index}"), typ, names);
+                    }
+                }
+            }
+        }
+
+        let mut names = Vec::new();
+        for AbiParameter { name, typ, .. } in &self.parameters {
+            flatten(name.clone(), typ, &mut names);
+        }
+        if let Some(return_type) = &self.return_type {
+            flatten(MAIN_RETURN_NAME.to_string(), &return_type.abi_type, &mut names);
+        }
+
+        names.into_iter().enumerate().map(|(index, name)| (Witness(index as u32), name)).collect()
+    }
 }
 
 pub fn decode_value(
@@ -499,9 +537,13 @@ pub fn display_abi_error<F: AcirField>(
 
 #[cfg(test)]
 mod test {
+    use std::collections::BTreeMap;
+
+    use acvm::acir::native_types::Witness;
     use proptest::prelude::*;
 
     use crate::arbitrary::arb_abi_and_input_map;
+    use crate::{Abi, AbiParameter, AbiReturnType, AbiType, AbiVisibility, Sign};
 
     proptest! {
         #[test]
@@ -513,4 +555,50 @@ mod test {
             prop_assert_eq!(return_value, None);
         }
     }
+    #[test]
+    fn names_witnesses_after_parameters() {
+        let point = AbiType::Struct {
+            path: "Point".to_string(),
+            fields: vec![("x".to_string(), AbiType::Field), ("y".to_string(), AbiType::Field)],
+        };
+        let abi = Abi {
+            parameters: vec![
+                AbiParameter {
+                    name: "points".to_string(),
+                    typ: AbiType::Array { length: 2, typ: Box::new(point) },
+                    visibility: AbiVisibility::Private,
+                },
+                AbiParameter {
+                    name: "flag".to_string(),
+                    typ: AbiType::Boolean,
+                    visibility: AbiVisibility::Public,
+                },
+            ],
+            return_type: Some(AbiReturnType {
+                abi_type: AbiType::Tuple {
+                    fields: vec![
+                        AbiType::Integer { sign: Sign::Unsigned, width: 8 },
+                        AbiType::String { length: 1 },
+                    ],
+                },
+                visibility: AbiVisibility::Public,
+            }),
+            error_types: BTreeMap::new(),
+        };
+
+        let names: Vec<(Witness, &str)> =
+            abi.witness_names().iter().map(|(witness, name)| (*witness, name.as_str())).collect();
+        assert_eq!(
+            names,
+            vec![
+                (Witness(0), "points[0].x"),
+                (Witness(1), "points[0].y"),
+                (Witness(2), "points[1].x"),
+                (Witness(3), "points[1].y"),
+                (Witness(4), "flag"),
+                (Witness(5), "return.0"),
+                (Witness(6), "return.1[0]"),
+            ]
+        );
+    }
 }
@@ -0,0 +1,446 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use acir::circuit::OpcodeLocation;
+use clap::Args;
+use color_eyre::eyre::{self, Context};
+
+use fm::FileId;
+use nargo::functions::{parse_functions, SourceFunction};
+use noirc_artifacts::debug::DebugArtifact;
+use noirc_errors::Location;
+
+use crate::flamegraph::{
+    generate_folded_sorted_lines, DifferentialFlamegraphGenerator, InfernoFlamegraphGenerator,
+    Sample,
+};
+use crate::fs::read_program_from_file;
+use crate::gates_provider::{BackendGatesProvider, GatesProvider};
+use crate::opcode_formatter::AcirOrBrilligOpcode;
+
+/// Compares the opcodes (or gates) attributed to each source location in two artifacts
+#[derive(Debug, Clone, Args)]
+pub(crate) struct DiffCommand {
+    /// The path to the artifact JSON file to compare against
+    #[clap(long)]
+    before: String,
+
+    /// The path to the artifact JSON file to compare
+    #[clap(long)]
+    after: String,
+
+    /// Path to the noir backend binary. If provided, gates are compared instead of ACIR opcodes
+    #[clap(long, short)]
+    backend_path: Option<String>,
+
+    /// Command to get a gates report from the backend. Defaults to "gates"
+    #[clap(long, short = 'g', default_value = "gates")]
+    backend_gates_command: String,
+
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    backend_extra_args: Vec<String>,
+
+    /// The output folder for the differential flamegraph svg files
+    #[clap(long, short)]
+    output: String,
+}
+
+pub(crate) fn run(args: DiffCommand) -> eyre::Result<()> {
+    let gates_provider = args.backend_path.map(|backend_path| BackendGatesProvider {
+        backend_path: PathBuf::from(backend_path),
+        gates_command: args.backend_gates_command,
+        extra_args: args.backend_extra_args,
+    });
+    let count_name = if gates_provider.is_some() { "gates" } else { "opcodes" };
+
+    let table = run_with_generator(
+        &PathBuf::from(args.before),
+        &PathBuf::from(args.after),
+        gates_provider.as_ref().map(|provider| provider as &dyn GatesProvider),
+        &InfernoFlamegraphGenerator { count_name: count_name.to_string() },
+        &PathBuf::from(args.output),
+    )?;
+    print!("{table}");
+
+    Ok(())
+}
+
+/// The opcodes, or gates, of an artifact.
+#[derive(Default)]
+struct Profile {
+    /// The folded stack lines of each ACIR function
+    folded_lines: BTreeMap<String, Vec<String>>,
+    /// The total count attributed to each source function
+    totals: BTreeMap<String, usize>,
+}
+
+/// A row of the table summarizing how much each source function changed.
+#[derive(Debug, PartialEq, Eq)]
+struct FunctionDiff {
+    name: String,
+    before: usize,
+    after: usize,
+}
+
+impl FunctionDiff {
+    fn change(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+}
+
+/// Writes a differential flamegraph for each ACIR function in either artifact,
+/// returning a table of the source functions which grew or shrank.
+fn run_with_generator<Generator: DifferentialFlamegraphGenerator>(
+    before_path: &Path,
+    after_path: &Path,
+    gates_provider: Option<&dyn GatesProvider>,
+    flamegraph_generator: &Generator,
+    output_path: &Path,
+) -> eyre::Result<String> {
+    let mut before = read_profile(before_path, gates_provider)?;
+    let mut after = read_profile(after_path, gates_provider)?;
+
+    let function_names: BTreeSet<String> =
+        before.folded_lines.keys().chain(after.folded_lines.keys()).cloned().collect();
+    for function_name in function_names {
+        // Functions which only exist in one of the artifacts are compared against an empty profile.
+        let before = before.folded_lines.remove(&function_name).unwrap_or_default();
+        let after = after.folded_lines.remove(&function_name).unwrap_or_default();
+
+        flamegraph_generator.generate_differential_flamegraph(
+            &before,
+            &after,
+            &format!("{}-diff", function_name),
+            &output_path.join(Path::new(&format!("{}_diff.svg", function_name))),
+        )?;
+    }
+
+    let function_names: BTreeSet<&String> =
+        before.totals.keys().chain(after.totals.keys()).collect();
+    let diffs = function_names
+        .into_iter()
+        .map(|name| FunctionDiff {
+            name: name.clone(),
+            before: before.totals.get(name).copied().unwrap_or_default(),
+            after: after.totals.get(name).copied().unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(format_diff_table(diffs))
+}
+
+/// Attributes the opcodes, or gates if a `gates_provider` is given, of each ACIR function in the artifact
+/// to their source locations.
+///
+/// Each opcode is also attributed to the source function containing the innermost location of its call stack,
+/// so that code inlined into an ACIR function is counted towards the function it was written in.
+/// Opcodes without a known source function are attributed to their ACIR function.
+fn read_profile(
+    artifact_path: &Path,
+    gates_provider: Option<&dyn GatesProvider>,
+) -> eyre::Result<Profile> {
+    let mut program =
+        read_program_from_file(artifact_path).context("Error reading program from file")?;
+
+    let gates_per_function: Option<Vec<Vec<usize>>> = match gates_provider {
+        Some(gates_provider) => {
+            let response = gates_provider
+                .get_gates(artifact_path)
+                .context("Error querying backend for gates")?;
+            Some(response.functions.into_iter().map(|report| report.gates_per_opcode).collect())
+        }
+        None => None,
+    };
+
+    let function_names = program.names.clone();
+
+    let bytecode = std::mem::take(&mut program.bytecode);
+
+    let debug_artifact: DebugArtifact = program.into();
+
+    let mut source_functions = SourceFunctions::new(&debug_artifact);
+    let mut profile = Profile::default();
+    for (func_idx, (func_name, function)) in
+        function_names.into_iter().zip(bytecode.functions).enumerate()
+    {
+        let gates_per_opcode = gates_per_function.as_ref().map(|gates| &gates[func_idx]);
+        let debug_symbols = &debug_artifact.debug_symbols[func_idx];
+
+        let mut samples = Vec::new();
+        for (index, opcode) in function.opcodes.into_iter().enumerate() {
+            let opcode_location = OpcodeLocation::Acir(index);
+            let count = gates_per_opcode.map_or(1, |gates| gates[index]);
+
+            let source_function = debug_symbols
+                .opcode_location(&opcode_location)
+                .and_then(|call_stack| call_stack.last().copied())
+                .and_then(|location| source_functions.name_at(location))
+                .unwrap_or_else(|| func_name.clone());
+            *profile.totals.entry(source_function).or_default() += count;
+
+            samples.push(Sample {
+                opcode: AcirOrBrilligOpcode::Acir(opcode),
+                call_stack: vec![opcode_location],
+                count,
+                brillig_function_id: None,
+            });
+        }
+
+        let folded_lines = generate_folded_sorted_lines(samples, debug_symbols, &debug_artifact);
+        profile.folded_lines.insert(func_name, folded_lines);
+    }
+
+    Ok(profile)
+}
+
+/// Finds the source function containing a location, parsing each of the artifact's files when first needed.
+struct SourceFunctions<'a> {
+    debug_artifact: &'a DebugArtifact,
+    /// Every function defined in each parsed file
+    functions: HashMap<FileId, Vec<SourceFunction>>,
+}
+
+impl<'a> SourceFunctions<'a> {
+    fn new(debug_artifact: &'a DebugArtifact) -> Self {
+        Self { debug_artifact, functions: HashMap::new() }
+    }
+
+    /// Returns the name of the innermost function whose definition contains `location`.
+    fn name_at(&mut self, location: Location) -> Option<String> {
+        let debug_artifact = self.debug_artifact;
+        let functions = self.functions.entry(location.file).or_insert_with(|| {
+            let file = debug_artifact.file_map.get(&location.file);
+            file.map(|file| parse_functions(&file.source)).unwrap_or_default()
+        });
+
+        functions
+            .iter()
+            .filter(|function| function.span.contains(&location.span))
+            .min_by_key(|function| function.span.end() - function.span.start())
+            .map(|function| function.name.clone())
+    }
+}
+
+/// Formats the source functions which grew or shrank, largest growth first.
+fn format_diff_table(mut diffs: Vec<FunctionDiff>) -> String {
+    diffs.retain(|diff| diff.before != diff.after);
+    if diffs.is_empty() {
+        return "No functions changed\n".to_string();
+    }
+    diffs.sort_by_key(|diff| (-diff.change(), diff.name.clone()));
+
+    let name_width = diffs.iter().map(|diff| diff.name.len()).max().unwrap_or(0).max(8);
+    let mut table =
+        format!("{:<name_width$} {:>10} {:>10} {:>10}\n", "Function", "Before", "After", "Change");
+    for diff in diffs {
+        table += &format!(
+            "{:<name_width$} {:>10} {:>10} {:>+10}\n",
+            diff.name,
+            diff.before,
+            diff.after,
+            diff.change()
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{
+        circuit::{Circuit, Opcode, OpcodeLocation, Program},
+        native_types::Expression,
+        FieldElement,
+    };
+    use color_eyre::eyre::{self};
+    use fm::FileId;
+    use noirc_artifacts::program::ProgramArtifact;
+    use noirc_driver::DebugFile;
+    use noirc_errors::{
+        debug_info::{DebugInfo, ProgramDebugInfo},
+        Location, Span,
+    };
+    use std::{
+        collections::BTreeMap,
+        path::{Path, PathBuf},
+    };
+
+    use super::{format_diff_table, FunctionDiff};
+
+    #[derive(Default)]
+    struct TestFlamegraphGenerator {}
+
+    impl super::DifferentialFlamegraphGenerator for TestFlamegraphGenerator {
+        fn generate_differential_flamegraph(
+            &self,
+            _before_lines: &[String],
+            _after_lines: &[String],
+            _title: &str,
+            output_path: &Path,
+        ) -> eyre::Result<()> {
+            let output_file = std::fs::File::create(output_path).unwrap();
+            std::io::Write::write_all(&mut std::io::BufWriter::new(output_file), b"success")
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    fn write_artifact(path: &Path, functions: Vec<(&str, usize)>) {
+        let (names, circuits): (Vec<_>, Vec<_>) = functions
+            .into_iter()
+            .map(|(name, num_opcodes)| {
+                let opcodes: Vec<Opcode<FieldElement>> =
+                    vec![Opcode::AssertZero(Expression::default()); num_opcodes];
+                (name.to_string(), Circuit { opcodes, ..Circuit::default() })
+            })
+            .unzip();
+
+        let artifact = ProgramArtifact {
+            noir_version: "0.0.0".to_string(),
+            hash: 27,
+            abi: noirc_abi::Abi::default(),
+            bytecode: Program { functions: circuits, ..Program::default() },
+            debug_symbols: ProgramDebugInfo {
+                debug_infos: vec![DebugInfo::default(); names.len()],
+            },
+            file_map: BTreeMap::default(),
+            names,
+            brillig_names: Vec::new(),
+        };
+
+        let artifact_file = std::fs::File::create(path).unwrap();
+        serde_json::to_writer(artifact_file, &artifact).unwrap();
+    }
+
+    #[test]
+    fn reports_changed_functions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let before_path = temp_dir.path().join("before.json");
+        let after_path = temp_dir.path().join("after.json");
+        write_artifact(&before_path, vec![("main", 1), ("foo", 4), ("bar", 2)]);
+        write_artifact(&after_path, vec![("main", 3), ("foo", 4), ("baz", 1)]);
+
+        let flamegraph_generator = TestFlamegraphGenerator::default();
+
+        let table = super::run_with_generator(
+            &before_path,
+            &after_path,
+            None,
+            &flamegraph_generator,
+            temp_dir.path(),
+        )
+        .expect("should run without errors");
+
+        let expected = format_diff_table(vec![
+            FunctionDiff { name: "main".to_string(), before: 1, after: 3 },
+            FunctionDiff { name: "bar".to_string(), before: 2, after: 0 },
+            FunctionDiff { name: "baz".to_string(), before: 0, after: 1 },
+        ]);
+        assert_eq!(table, expected);
+        assert!(!table.contains("foo"));
+
+        // Functions in either artifact should have a flamegraph
+        for function in ["main", "foo", "bar", "baz"] {
+            assert!(temp_dir.path().join(format!("{function}_diff.svg")).exists());
+        }
+    }
+
+    /// Writes an artifact with a single `main` function whose opcodes have the given call stacks,
+    /// each given as the code snippets in `source` which the call stack is made of.
+    fn write_artifact_with_call_stacks(path: &Path, source: &str, call_stacks: Vec<Vec<&str>>) {
+        let file_id = FileId::dummy();
+        let locations = call_stacks
+            .iter()
+            .enumerate()
+            .map(|(index, call_stack)| {
+                let call_stack = call_stack
+                    .iter()
+                    .map(|snippet| {
+                        let start = source.find(snippet).expect("snippet should be in the source");
+                        let span = Span::from(start as u32..(start + snippet.len()) as u32);
+                        Location::new(span, file_id)
+                    })
+                    .collect();
+                (OpcodeLocation::Acir(index), call_stack)
+            })
+            .collect();
+        let debug_info = DebugInfo { locations, ..DebugInfo::default() };
+
+        let opcodes: Vec<Opcode<FieldElement>> =
+            vec![Opcode::AssertZero(Expression::default()); call_stacks.len()];
+        let source = DebugFile { source: source.to_string(), path: PathBuf::from("main.nr") };
+
+        let artifact = ProgramArtifact {
+            noir_version: "0.0.0".to_string(),
+            hash: 27,
+            abi: noirc_abi::Abi::default(),
+            bytecode: Program {
+                functions: vec![Circuit { opcodes, ..Circuit::default() }],
+                ..Program::default()
+            },
+            debug_symbols: ProgramDebugInfo { debug_infos: vec![debug_info] },
+            file_map: BTreeMap::from([(file_id, source)]),
+            names: vec!["main".to_string()],
+            brillig_names: Vec::new(),
+        };
+
+        let artifact_file = std::fs::File::create(path).unwrap();
+        serde_json::to_writer(artifact_file, &artifact).unwrap();
+    }
+
+    #[test]
+    fn reports_changed_source_functions() {
+        let source = "
+            fn main(x: Field) {
+                helper(x);
+                assert(x != 0);
+            }
+
+            fn helper(x: Field) {
+                assert(x == 1);
+                assert(x * x == 1);
+            }
+        ";
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let before_path = temp_dir.path().join("before.json");
+        let after_path = temp_dir.path().join("after.json");
+
+        // `helper` is inlined into `main`, so its opcodes are in the same ACIR function.
+        let before = vec![vec!["helper(x)", "x == 1"], vec!["x != 0"]];
+        let after =
+            vec![vec!["helper(x)", "x == 1"], vec!["helper(x)", "x * x == 1"], vec!["x != 0"]];
+        write_artifact_with_call_stacks(&before_path, source, before);
+        write_artifact_with_call_stacks(&after_path, source, after);
+
+        let table = super::run_with_generator(
+            &before_path,
+            &after_path,
+            None,
+            &TestFlamegraphGenerator::default(),
+            temp_dir.path(),
+        )
+        .expect("should run without errors");
+
+        let expected = format_diff_table(vec![FunctionDiff {
+            name: "helper".to_string(),
+            before: 1,
+            after: 2,
+        }]);
+        assert_eq!(table, expected);
+    }
+
+    #[test]
+    fn sorts_by_largest_growth() {
+        let table = format_diff_table(vec![
+            FunctionDiff { name: "shrunk".to_string(), before: 10, after: 5 },
+            FunctionDiff { name: "grew".to_string(), before: 1, after: 6 },
+            FunctionDiff { name: "unchanged".to_string(), before: 3, after: 3 },
+        ]);
+        let rows: Vec<&str> = table.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[1].starts_with("grew") && rows[1].ends_with("+5"));
+        assert!(rows[2].starts_with("shrunk") && rows[2].ends_with("-5"));
+    }
+}
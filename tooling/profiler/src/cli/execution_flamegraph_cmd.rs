@@ -0,0 +1,203 @@
+use std::path::{Path, PathBuf};
+
+use acir::circuit::OpcodeLocation;
+use acir::FieldElement;
+use bn254_blackbox_solver::Bn254BlackBoxSolver;
+use clap::Args;
+use color_eyre::eyre::{self, Context};
+
+use nargo::ops::{execute_program_with_profiling, DefaultForeignCallExecutor};
+use noirc_abi::input_parser::Format;
+use noirc_artifacts::debug::DebugArtifact;
+
+use crate::flamegraph::{FlamegraphGenerator, InfernoFlamegraphGenerator, Sample};
+use crate::fs::read_program_from_file;
+use crate::opcode_formatter::AcirOrBrilligOpcode;
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExecutionFlamegraphCommand {
+    /// The path to the artifact JSON file
+    #[clap(long, short)]
+    artifact_path: String,
+
+    /// The path to the Prover.toml file which contains the inputs to execute the program with
+    #[clap(long, short)]
+    prover_toml_path: String,
+
+    /// The output folder for the flamegraph svg files
+    #[clap(long, short)]
+    output: String,
+}
+
+pub(crate) fn run(args: ExecutionFlamegraphCommand) -> eyre::Result<()> {
+    run_with_generator(
+        &PathBuf::from(args.artifact_path),
+        &PathBuf::from(args.prover_toml_path),
+        &InfernoFlamegraphGenerator { count_name: "samples".to_string() },
+        &PathBuf::from(args.output),
+    )
+}
+
+fn run_with_generator<Generator: FlamegraphGenerator>(
+    artifact_path: &Path,
+    prover_toml_path: &Path,
+    flamegraph_generator: &Generator,
+    output_path: &Path,
+) -> eyre::Result<()> {
+    let mut program =
+        read_program_from_file(artifact_path).context("Error reading program from file")?;
+
+    let inputs = std::fs::read_to_string(prover_toml_path).context("Error reading inputs file")?;
+    let inputs_map =
+        Format::Toml.parse(&inputs, &program.abi).context("Error parsing inputs file")?;
+    let initial_witness = program.abi.encode(&inputs_map, None)?;
+
+    let (_, profiling_samples) = execute_program_with_profiling(
+        &program.bytecode,
+        initial_witness,
+        &Bn254BlackBoxSolver,
+        &mut DefaultForeignCallExecutor::new(true, None, None, None),
+    )
+    .map_err(|err| eyre::eyre!("Error executing program: {err}"))?;
+
+    let function_names = program.names.clone();
+
+    let bytecode = std::mem::take(&mut program.bytecode);
+
+    let debug_artifact: DebugArtifact = program.into();
+
+    for (acir_fn_index, samples) in profiling_samples {
+        let func_name = &function_names[acir_fn_index];
+
+        let samples: Vec<Sample<FieldElement>> = samples
+            .into_iter()
+            .filter_map(|sample| {
+                let brillig_function_id = sample.brillig_function_id?;
+                let Some(OpcodeLocation::Brillig { brillig_index, .. }) = sample.call_stack.last()
+                else {
+                    return None;
+                };
+                let opcode = bytecode.unconstrained_functions[brillig_function_id.as_usize()]
+                    .bytecode[*brillig_index]
+                    .clone();
+
+                Some(Sample {
+                    opcode: AcirOrBrilligOpcode::Brillig(opcode),
+                    call_stack: sample.call_stack,
+                    count: sample.count,
+                    brillig_function_id: Some(brillig_function_id),
+                })
+            })
+            .collect();
+
+        println!(
+            "Brillig opcodes executed from {}: {}",
+            func_name,
+            samples.iter().map(|sample| sample.count).sum::<usize>()
+        );
+
+        flamegraph_generator.generate_flamegraph(
+            samples,
+            &debug_artifact.debug_symbols[acir_fn_index],
+            &debug_artifact,
+            artifact_path.to_str().unwrap(),
+            func_name,
+            &Path::new(&output_path).join(Path::new(&format!("{}_brillig_trace.svg", func_name))),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{
+        brillig::Opcode as BrilligOpcode,
+        circuit::{
+            brillig::{BrilligBytecode, BrilligFunctionId},
+            Circuit, Opcode, Program,
+        },
+        AcirField, FieldElement,
+    };
+    use color_eyre::eyre::{self};
+    use fm::codespan_files::Files;
+    use noirc_artifacts::program::ProgramArtifact;
+    use noirc_errors::debug_info::{DebugInfo, ProgramDebugInfo};
+    use std::{collections::BTreeMap, path::Path};
+
+    use crate::flamegraph::Sample;
+
+    #[derive(Default)]
+    struct TestFlamegraphGenerator {}
+
+    impl super::FlamegraphGenerator for TestFlamegraphGenerator {
+        fn generate_flamegraph<'files, F: AcirField>(
+            &self,
+            samples: Vec<Sample<F>>,
+            _debug_symbols: &DebugInfo,
+            _files: &'files impl Files<'files, FileId = fm::FileId>,
+            _artifact_name: &str,
+            _function_name: &str,
+            output_path: &Path,
+        ) -> eyre::Result<()> {
+            let total_samples: usize = samples.iter().map(|sample| sample.count).sum();
+            std::fs::write(output_path, total_samples.to_string()).unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn counts_executed_brillig_opcodes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let artifact_path = temp_dir.path().join("test.json");
+        let prover_toml_path = temp_dir.path().join("Prover.toml");
+
+        let acir: Vec<Opcode<FieldElement>> = vec![Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![],
+            outputs: vec![],
+            predicate: None,
+        }];
+        let brillig_bytecode = BrilligBytecode {
+            bytecode: vec![
+                BrilligOpcode::Jump { location: 1 },
+                BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 0 },
+            ],
+        };
+
+        let artifact = ProgramArtifact {
+            noir_version: "0.0.0".to_string(),
+            hash: 27,
+            abi: noirc_abi::Abi::default(),
+            bytecode: Program {
+                functions: vec![Circuit { opcodes: acir, ..Circuit::default() }],
+                unconstrained_functions: vec![brillig_bytecode],
+            },
+            debug_symbols: ProgramDebugInfo { debug_infos: vec![DebugInfo::default()] },
+            file_map: BTreeMap::default(),
+            names: vec!["main".to_string()],
+            brillig_names: vec!["main".to_string()],
+        };
+
+        // Write the artifact and inputs to files
+        let artifact_file = std::fs::File::create(&artifact_path).unwrap();
+        serde_json::to_writer(artifact_file, &artifact).unwrap();
+        std::fs::write(&prover_toml_path, "").unwrap();
+
+        let flamegraph_generator = TestFlamegraphGenerator::default();
+
+        super::run_with_generator(
+            &artifact_path,
+            &prover_toml_path,
+            &flamegraph_generator,
+            temp_dir.path(),
+        )
+        .expect("should run without errors");
+
+        // Both Brillig opcodes should have been executed once
+        let output_file = temp_dir.path().join("main_brillig_trace.svg");
+        assert_eq!(std::fs::read_to_string(output_file).unwrap(), "2");
+    }
+}
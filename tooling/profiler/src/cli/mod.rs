@@ -2,6 +2,8 @@ use clap::{Parser, Subcommand};
 use color_eyre::eyre;
 use const_format::formatcp;
 
+mod diff_cmd;
+mod execution_flamegraph_cmd;
 mod gates_flamegraph_cmd;
 mod opcodes_flamegraph_cmd;
 
@@ -21,6 +23,8 @@ struct ProfilerCli {
 enum ProfilerCommand {
     GatesFlamegraph(gates_flamegraph_cmd::GatesFlamegraphCommand),
     OpcodesFlamegraph(opcodes_flamegraph_cmd::OpcodesFlamegraphCommand),
+    ExecutionFlamegraph(execution_flamegraph_cmd::ExecutionFlamegraphCommand),
+    Diff(diff_cmd::DiffCommand),
 }
 
 pub(crate) fn start_cli() -> eyre::Result<()> {
@@ -29,5 +33,7 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
     match command {
         ProfilerCommand::GatesFlamegraph(args) => gates_flamegraph_cmd::run(args),
         ProfilerCommand::OpcodesFlamegraph(args) => opcodes_flamegraph_cmd::run(args),
+        ProfilerCommand::ExecutionFlamegraph(args) => execution_flamegraph_cmd::run(args),
+        ProfilerCommand::Diff(args) => diff_cmd::run(args),
     }
 }
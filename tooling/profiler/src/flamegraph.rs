@@ -6,6 +6,7 @@ use acir::circuit::OpcodeLocation;
 use acir::AcirField;
 use color_eyre::eyre::{self};
 use fm::codespan_files::Files;
+use inferno::differential;
 use inferno::flamegraph::{from_lines, Options, TextTruncateDirection};
 use noirc_errors::debug_info::DebugInfo;
 use noirc_errors::reporter::line_and_column_from_span;
@@ -42,10 +43,37 @@ pub(crate) trait FlamegraphGenerator {
     ) -> eyre::Result<()>;
 }
 
+pub(crate) trait DifferentialFlamegraphGenerator {
+    /// Generates a flamegraph showing the difference between two sets of folded stack lines,
+    /// as produced by [`generate_folded_sorted_lines`].
+    fn generate_differential_flamegraph(
+        &self,
+        before_lines: &[String],
+        after_lines: &[String],
+        title: &str,
+        output_path: &Path,
+    ) -> eyre::Result<()>;
+}
+
 pub(crate) struct InfernoFlamegraphGenerator {
     pub(crate) count_name: String,
 }
 
+impl InfernoFlamegraphGenerator {
+    fn options(&self, title: String) -> Options<'_> {
+        let mut options = Options::default();
+        options.hash = true;
+        options.deterministic = true;
+        options.title = title;
+        options.frame_height = 24;
+        options.color_diffusion = true;
+        options.min_width = 0.0;
+        options.count_name = self.count_name.clone();
+        options.text_truncate_direction = TextTruncateDirection::Right;
+        options
+    }
+}
+
 impl FlamegraphGenerator for InfernoFlamegraphGenerator {
     fn generate_flamegraph<'files, F: AcirField>(
         &self,
@@ -60,15 +88,7 @@ impl FlamegraphGenerator for InfernoFlamegraphGenerator {
         let flamegraph_file = std::fs::File::create(output_path)?;
         let flamegraph_writer = BufWriter::new(flamegraph_file);
 
-        let mut options = Options::default();
-        options.hash = true;
-        options.deterministic = true;
-        options.title = format!("{}-{}", artifact_name, function_name);
-        options.frame_height = 24;
-        options.color_diffusion = true;
-        options.min_width = 0.0;
-        options.count_name = self.count_name.clone();
-        options.text_truncate_direction = TextTruncateDirection::Right;
+        let mut options = self.options(format!("{}-{}", artifact_name, function_name));
 
         from_lines(
             &mut options,
@@ -80,7 +100,35 @@ impl FlamegraphGenerator for InfernoFlamegraphGenerator {
     }
 }
 
-fn generate_folded_sorted_lines<'files, F: AcirField>(
+impl DifferentialFlamegraphGenerator for InfernoFlamegraphGenerator {
+    fn generate_differential_flamegraph(
+        &self,
+        before_lines: &[String],
+        after_lines: &[String],
+        title: &str,
+        output_path: &Path,
+    ) -> eyre::Result<()> {
+        // Each differential line contains the stack followed by its count before and after.
+        let mut differential_lines = Vec::new();
+        differential::from_readers(
+            differential::Options::default(),
+            before_lines.join("\n").as_bytes(),
+            after_lines.join("\n").as_bytes(),
+            &mut differential_lines,
+        )?;
+        let differential_lines = String::from_utf8(differential_lines)?;
+
+        let flamegraph_file = std::fs::File::create(output_path)?;
+        let flamegraph_writer = BufWriter::new(flamegraph_file);
+
+        let mut options = self.options(title.to_string());
+        from_lines(&mut options, differential_lines.lines(), flamegraph_writer)?;
+
+        Ok(())
+    }
+}
+
+pub(crate) fn generate_folded_sorted_lines<'files, F: AcirField>(
     samples: Vec<Sample<F>>,
     debug_symbols: &DebugInfo,
     files: &'files impl Files<'files, FileId = fm::FileId>,